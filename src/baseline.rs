@@ -0,0 +1,232 @@
+//! Baselines of known license findings
+//!
+//! `--write-baseline` records the current restrictive and incompatible findings in a JSON
+//! file; later runs with `--baseline` still report them but they no longer count toward
+//! `--fail-on-restrictive` and `--fail-on-incompatible`, so CI only fails on new problems.
+//! A finding is identified by package name, version and license, so upgrading a package
+//! or a change of its license makes it new again.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::licenses::{LicenseCompatibility, LicenseInfo};
+use crate::reporter::ReportOutcome;
+
+/// A known finding, keyed by `name@version` and license
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+}
+
+impl BaselineEntry {
+    fn from_info(info: &LicenseInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            version: info.version.clone(),
+            license: info.get_license(),
+        }
+    }
+}
+
+/// Findings accepted as known, as stored in the baseline file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub findings: BTreeSet<BaselineEntry>,
+}
+
+fn is_incompatible(info: &LicenseInfo) -> bool {
+    info.compatibility == LicenseCompatibility::Incompatible
+}
+
+impl Baseline {
+    /// Baseline of every restrictive or incompatible dependency in `data`
+    pub fn from_findings(data: &[LicenseInfo]) -> Self {
+        Self {
+            findings: data
+                .iter()
+                .filter(|info| info.is_restrictive || is_incompatible(info))
+                .map(BaselineEntry::from_info)
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> FeludaResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            FeludaError::Config(format!(
+                "Failed to read baseline {}: {e} (create it with --write-baseline)",
+                path.display()
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            FeludaError::InvalidData(format!("Invalid baseline {}: {e}", path.display()))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> FeludaResult<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            FeludaError::Serialization(format!("Failed to serialize baseline: {e}"))
+        })?;
+        fs::write(path, content + "\n").map_err(|e| {
+            FeludaError::FileWrite(format!("Failed to write baseline {}: {e}", path.display()))
+        })?;
+
+        log(
+            LogLevel::Info,
+            &format!(
+                "Wrote {} findings to baseline {}",
+                self.findings.len(),
+                path.display()
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn contains(&self, info: &LicenseInfo) -> bool {
+        self.findings.contains(&BaselineEntry::from_info(info))
+    }
+
+    /// Findings in `data` that are not in the baseline
+    pub fn check(&self, data: &[LicenseInfo]) -> BaselineCheck {
+        let new = |is_finding: fn(&LicenseInfo) -> bool| {
+            data.iter()
+                .any(|info| is_finding(info) && !self.contains(info))
+        };
+
+        let check = BaselineCheck {
+            has_new_restrictive: new(|info| info.is_restrictive),
+            has_new_incompatible: new(is_incompatible),
+            suppressed: data
+                .iter()
+                .filter(|info| {
+                    (info.is_restrictive || is_incompatible(info)) && self.contains(info)
+                })
+                .count(),
+        };
+
+        log(
+            LogLevel::Info,
+            &format!("Baseline suppresses {} known findings", check.suppressed),
+        );
+        check
+    }
+}
+
+/// Result of comparing a scan against a baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaselineCheck {
+    pub has_new_restrictive: bool,
+    pub has_new_incompatible: bool,
+    /// Number of dependencies whose findings are in the baseline
+    pub suppressed: usize,
+}
+
+impl BaselineCheck {
+    /// Only count findings that are not in the baseline toward the exit code
+    ///
+    /// A flag the report already cleared, e.g. with `--restrictive`, stays cleared.
+    pub fn apply(&self, outcome: ReportOutcome) -> ReportOutcome {
+        ReportOutcome {
+            has_restrictive: outcome.has_restrictive && self.has_new_restrictive,
+            has_incompatible: outcome.has_incompatible && self.has_new_incompatible,
+            ..outcome
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::{test_dependency, OsiStatus};
+    use crate::reporter::FailOn;
+
+    fn dependency(name: &str, version: &str, license: &str, restrictive: bool) -> LicenseInfo {
+        LicenseInfo {
+            is_restrictive: restrictive,
+            osi_status: OsiStatus::Approved,
+            ..test_dependency(name, version, license)
+        }
+    }
+
+    fn fail_on_findings() -> FailOn {
+        FailOn {
+            restrictive: true,
+            incompatible: true,
+            ..FailOn::default()
+        }
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let data = vec![
+            dependency("serde", "1.0.0", "MIT", false),
+            dependency("gpl-lib", "2.0.0", "GPL-3.0", true),
+            LicenseInfo {
+                compatibility: LicenseCompatibility::Incompatible,
+                ..dependency("mpl-lib", "0.1.0", "MPL-2.0", false)
+            },
+        ];
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("feluda-baseline.json");
+        let baseline = Baseline::from_findings(&data);
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.findings.len(), 2);
+        assert!(loaded.contains(&data[1]));
+        assert!(!loaded.contains(&data[0]));
+    }
+
+    #[test]
+    fn test_baselined_finding_does_not_fail() {
+        let known = vec![
+            dependency("serde", "1.0.0", "MIT", false),
+            dependency("gpl-lib", "2.0.0", "GPL-3.0", true),
+        ];
+        let baseline = Baseline::from_findings(&known);
+        let outcome = ReportOutcome {
+            has_restrictive: true,
+            ..ReportOutcome::default()
+        };
+
+        let check = baseline.check(&known);
+        assert_eq!(check.suppressed, 1);
+        assert!(!check.apply(outcome).fails(&fail_on_findings()));
+
+        // A new restrictive dependency fails the run again
+        let mut data = known.clone();
+        data.push(dependency("agpl-lib", "1.0.0", "AGPL-3.0", true));
+        assert!(baseline
+            .check(&data)
+            .apply(outcome)
+            .fails(&fail_on_findings()));
+
+        // So does a baselined package at a new version
+        let upgraded = vec![dependency("gpl-lib", "3.0.0", "GPL-3.0", true)];
+        assert!(baseline
+            .check(&upgraded)
+            .apply(outcome)
+            .fails(&fail_on_findings()));
+
+        // A new finding does not bring back a flag the report filters cleared
+        assert!(!baseline
+            .check(&upgraded)
+            .apply(ReportOutcome::default())
+            .fails(&fail_on_findings()));
+    }
+
+    #[test]
+    fn test_load_missing_baseline() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let err = Baseline::load(&temp_dir.path().join("missing.json")).unwrap_err();
+        assert!(err.to_string().contains("--write-baseline"));
+    }
+}
@@ -1,20 +1,47 @@
 //! Caching functionality for license data
 //!
-//! Future considerations:
-//! - Per-package license cache (language:package:version keys)
-//! - Dependency manifest cache with mtime tracking for incremental analysis
+//! The GitHub license list is cached under the OS cache directory and reused while it is
+//! younger than the cache TTL (24 hours by default, `--cache-ttl <HOURS>`). Use
+//! `--refresh-cache` to ignore the cached copy and fetch a fresh one.
+//!
+//! Resolved dependency licenses are cached per package, keyed by `ecosystem:name:version`,
+//! so repeated runs skip registry and filesystem lookups. Use `--no-cache` to bypass it.
+//!
+//! Whole project analyses are cached too, keyed by the project path and stored with a hash
+//! of its manifests and lockfiles. While the hash matches and the entry is within the TTL,
+//! the previous dependencies are reused without parsing or resolving anything. Use
+//! `--no-incremental` to bypass it.
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
 use crate::debug::{log, log_error, FeludaResult, LogLevel};
-use crate::licenses::License;
+use crate::licenses::{License, LicenseInfo};
 
 const CACHE_SUBDIR: &str = "feluda";
 const GITHUB_LICENSES_CACHE_FILE: &str = "github_licenses.json";
-const CACHE_TTL_SECS: u64 = 30 * 24 * 60 * 60; // 30 days
+const PACKAGE_LICENSES_CACHE_FILE: &str = "package_licenses.json";
+const ANALYSIS_CACHE_SUBDIR: &str = "analysis";
+pub const DEFAULT_CACHE_TTL_HOURS: u64 = 24;
+
+// Cache freshness window, overridable with --cache-ttl
+static CACHE_TTL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_CACHE_TTL_HOURS * 60 * 60);
+
+// Static atomic flag for --refresh-cache
+static REFRESH_CACHE: AtomicBool = AtomicBool::new(false);
+
+// Static atomic flag for --no-cache; tests opt in so the user's cache is never touched
+static PACKAGE_CACHE_DISABLED: AtomicBool = AtomicBool::new(cfg!(test));
+
+// Static atomic flag for --no-incremental; tests opt in so analyses are never reused
+static INCREMENTAL_DISABLED: AtomicBool = AtomicBool::new(cfg!(test));
+
+/// Process-wide per-package license cache, loaded on first use
+static PACKAGE_CACHE: OnceLock<PackageLicenseCache> = OnceLock::new();
 
 const CACHE_VERSION: u32 = 1;
 
@@ -26,7 +53,39 @@ struct CacheEntry {
     timestamp: u64,
 }
 
+#[cfg(test)]
+thread_local! {
+    static TEST_CACHE_DIR: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Set how long cached license data is considered fresh
+pub fn set_cache_ttl_hours(hours: u64) {
+    CACHE_TTL_SECS.store(hours.saturating_mul(60 * 60), Ordering::Relaxed);
+    log(LogLevel::Info, &format!("Cache TTL set to {hours} hours"));
+}
+
+fn cache_ttl_secs() -> u64 {
+    CACHE_TTL_SECS.load(Ordering::Relaxed)
+}
+
+/// Force cached license data to be ignored and re-fetched
+pub fn set_refresh_cache(refresh: bool) {
+    REFRESH_CACHE.store(refresh, Ordering::Relaxed);
+    if refresh {
+        log(LogLevel::Info, "Cache refresh requested");
+    }
+}
+
+fn is_refresh_requested() -> bool {
+    REFRESH_CACHE.load(Ordering::Relaxed)
+}
+
 fn cache_dir_path() -> FeludaResult<PathBuf> {
+    #[cfg(test)]
+    if let Some(dir) = TEST_CACHE_DIR.with(|dir| dir.borrow().clone()) {
+        return Ok(dir);
+    }
+
     let base = dirs::cache_dir().ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
@@ -55,7 +114,7 @@ fn is_entry_fresh(timestamp: u64) -> bool {
         .map(|d| d.as_secs())
         .unwrap_or(0);
     let age = now.saturating_sub(timestamp);
-    let is_fresh = age < CACHE_TTL_SECS;
+    let is_fresh = age < cache_ttl_secs();
     log(
         LogLevel::Info,
         &format!("Cache age: {age} seconds (fresh: {is_fresh})"),
@@ -79,6 +138,14 @@ pub fn load_github_licenses_from_cache() -> FeludaResult<Option<HashMap<String,
         return Ok(None);
     }
 
+    if is_refresh_requested() {
+        log(
+            LogLevel::Info,
+            "Ignoring GitHub licenses cache, refresh requested",
+        );
+        return Ok(None);
+    }
+
     log(LogLevel::Info, "Loading GitHub licenses from cache");
 
     match fs::read_to_string(&cache_path) {
@@ -163,6 +230,228 @@ pub fn save_github_licenses_to_cache(licenses: &HashMap<String, License>) -> Fel
     Ok(())
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+struct PackageCacheEntry {
+    license: String,
+    timestamp: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
+struct PackageCacheFile {
+    #[serde(default)]
+    version: u32,
+    entries: HashMap<String, PackageCacheEntry>,
+}
+
+/// Per-package license cache keyed by `ecosystem:name:version`
+///
+/// Safe to share across rayon workers; the lock is never held while a license is resolved.
+#[derive(Debug, Default)]
+pub struct PackageLicenseCache {
+    entries: Mutex<HashMap<String, PackageCacheEntry>>,
+    dirty: AtomicBool,
+}
+
+impl PackageLicenseCache {
+    /// Load the cache from `path`, starting empty if it is missing or corrupt
+    pub fn load(path: &Path) -> Self {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => match serde_json::from_str::<PackageCacheFile>(&content) {
+                Ok(file) if file.version == CACHE_VERSION => file.entries,
+                Ok(_) => {
+                    log(
+                        LogLevel::Info,
+                        "Package license cache version mismatch, starting fresh",
+                    );
+                    HashMap::new()
+                }
+                Err(e) => {
+                    log(
+                        LogLevel::Warn,
+                        &format!("Corrupt package license cache, starting fresh: {e}"),
+                    );
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        log(
+            LogLevel::Info,
+            &format!("Loaded {} cached package licenses", entries.len()),
+        );
+
+        Self {
+            entries: Mutex::new(entries),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn key(ecosystem: &str, name: &str, version: &str) -> String {
+        format!("{ecosystem}:{name}:{version}")
+    }
+
+    /// Return a fresh cached license, if any
+    pub fn get(&self, ecosystem: &str, name: &str, version: &str) -> Option<String> {
+        if is_refresh_requested() {
+            return None;
+        }
+
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&Self::key(ecosystem, name, version))
+            .filter(|entry| is_entry_fresh(entry.timestamp))
+            .map(|entry| entry.license.clone())
+    }
+
+    /// Record a resolved license; unresolved placeholders are never cached
+    pub fn insert(&self, ecosystem: &str, name: &str, version: &str, license: &str) {
+        if !is_cacheable_license(license) {
+            return;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                Self::key(ecosystem, name, version),
+                PackageCacheEntry {
+                    license: license.to_string(),
+                    timestamp,
+                },
+            );
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Return the cached license or resolve it with `resolve` and remember the result
+    pub fn get_or_resolve(
+        &self,
+        ecosystem: &str,
+        name: &str,
+        version: &str,
+        resolve: impl FnOnce() -> String,
+    ) -> String {
+        if let Some(license) = self.get(ecosystem, name, version) {
+            log(
+                LogLevel::Info,
+                &format!("Using cached license for {ecosystem}:{name}:{version}: {license}"),
+            );
+            return license;
+        }
+
+        let license = resolve();
+        self.insert(ecosystem, name, version, &license);
+        license
+    }
+
+    /// Write the cache to `path` if it changed since it was loaded
+    pub fn save(&self, path: &Path) -> FeludaResult<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries.clone(),
+            Err(_) => return Ok(()),
+        };
+        let count = entries.len();
+        let file = PackageCacheFile {
+            version: CACHE_VERSION,
+            entries,
+        };
+
+        let json = serde_json::to_string(&file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        fs::write(path, json)
+            .inspect_err(|e| log_error("Failed to write package license cache", e))?;
+
+        log(
+            LogLevel::Info,
+            &format!(
+                "Saved {count} package licenses to cache at {}",
+                path.display()
+            ),
+        );
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+}
+
+fn is_cacheable_license(license: &str) -> bool {
+    let license = license.trim();
+    !license.is_empty() && !license.starts_with("Unknown") && license != "No License"
+}
+
+fn package_cache_path() -> FeludaResult<PathBuf> {
+    Ok(cache_dir_path()?.join(PACKAGE_LICENSES_CACHE_FILE))
+}
+
+fn package_cache() -> Option<&'static PackageLicenseCache> {
+    if PACKAGE_CACHE_DISABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let path = package_cache_path().ok()?;
+    Some(PACKAGE_CACHE.get_or_init(|| PackageLicenseCache::load(&path)))
+}
+
+/// Disable the per-package license cache (`--no-cache`)
+pub fn set_package_cache_disabled(disabled: bool) {
+    PACKAGE_CACHE_DISABLED.store(disabled, Ordering::Relaxed);
+    if disabled {
+        log(LogLevel::Info, "Package license cache disabled");
+    }
+}
+
+/// Resolve a dependency license through the per-package cache
+pub fn cached_package_license(
+    ecosystem: &str,
+    name: &str,
+    version: &str,
+    resolve: impl FnOnce() -> String,
+) -> String {
+    match package_cache() {
+        Some(cache) => cache.get_or_resolve(ecosystem, name, version, resolve),
+        None => resolve(),
+    }
+}
+
+/// Persist newly resolved package licenses to disk
+pub fn save_package_license_cache() -> FeludaResult<()> {
+    if PACKAGE_CACHE_DISABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    match PACKAGE_CACHE.get() {
+        Some(cache) => {
+            let cache_dir = ensure_cache_dir()?;
+            cache.save(&cache_dir.join(PACKAGE_LICENSES_CACHE_FILE))
+        }
+        None => Ok(()),
+    }
+}
+
+pub fn clear_package_license_cache() -> FeludaResult<()> {
+    let cache_path = package_cache_path()?;
+
+    if cache_path.exists() {
+        fs::remove_file(&cache_path)
+            .inspect_err(|e| log_error("Failed to clear package license cache", e))?;
+        log(LogLevel::Info, "Cleared package license cache");
+    }
+
+    Ok(())
+}
+
 pub fn clear_github_licenses_cache() -> FeludaResult<()> {
     let cache_path = github_cache_path()?;
 
@@ -176,6 +465,126 @@ pub fn clear_github_licenses_cache() -> FeludaResult<()> {
     Ok(())
 }
 
+/// Cached analysis of one project
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct AnalysisCacheEntry {
+    #[serde(default)]
+    version: u32,
+    project: String,
+    manifest_hash: String,
+    timestamp: u64,
+    licenses: Vec<LicenseInfo>,
+}
+
+/// Bypass the cache of whole project analyses (`--no-incremental`)
+pub fn set_incremental_disabled(disabled: bool) {
+    INCREMENTAL_DISABLED.store(disabled, Ordering::Relaxed);
+    if disabled {
+        log(LogLevel::Info, "Incremental analysis disabled");
+    }
+}
+
+pub fn is_incremental_enabled() -> bool {
+    !INCREMENTAL_DISABLED.load(Ordering::Relaxed)
+}
+
+fn analysis_cache_path(project_key: &str) -> FeludaResult<PathBuf> {
+    Ok(cache_dir_path()?
+        .join(ANALYSIS_CACHE_SUBDIR)
+        .join(format!("{project_key}.json")))
+}
+
+/// Dependencies from the previous analysis of a project, if its manifests still hash to
+/// `manifest_hash` and the entry is fresh
+pub fn load_analysis(project_key: &str, manifest_hash: &str) -> Option<Vec<LicenseInfo>> {
+    if is_refresh_requested() {
+        return None;
+    }
+
+    let content = fs::read_to_string(analysis_cache_path(project_key).ok()?).ok()?;
+    let entry = match serde_json::from_str::<AnalysisCacheEntry>(&content) {
+        Ok(entry) => entry,
+        Err(e) => {
+            log(
+                LogLevel::Warn,
+                &format!("Corrupt analysis cache for {project_key}, re-analyzing: {e}"),
+            );
+            return None;
+        }
+    };
+
+    if entry.version != CACHE_VERSION || entry.manifest_hash != manifest_hash {
+        log(
+            LogLevel::Info,
+            &format!("Manifests of {} changed, re-analyzing", entry.project),
+        );
+        return None;
+    }
+    if !is_entry_fresh(entry.timestamp) {
+        return None;
+    }
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Reusing cached analysis of {} ({} dependencies)",
+            entry.project,
+            entry.licenses.len()
+        ),
+    );
+    Some(entry.licenses)
+}
+
+/// Remember the analysis of `project` for runs with the same manifests
+pub fn save_analysis(
+    project_key: &str,
+    project: &Path,
+    manifest_hash: &str,
+    licenses: &[LicenseInfo],
+) -> FeludaResult<()> {
+    let cache_path = analysis_cache_path(project_key)?;
+    if let Some(dir) = cache_path.parent() {
+        fs::create_dir_all(dir)
+            .inspect_err(|e| log_error("Failed to create analysis cache directory", e))?;
+    }
+
+    let entry = AnalysisCacheEntry {
+        version: CACHE_VERSION,
+        project: project.display().to_string(),
+        manifest_hash: manifest_hash.to_string(),
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        licenses: licenses.to_vec(),
+    };
+    let json = serde_json::to_string(&entry)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+    fs::write(&cache_path, json).inspect_err(|e| log_error("Failed to write analysis cache", e))?;
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Cached analysis of {} at {}",
+            project.display(),
+            cache_path.display()
+        ),
+    );
+    Ok(())
+}
+
+pub fn clear_analysis_cache() -> FeludaResult<()> {
+    let cache_dir = cache_dir_path()?.join(ANALYSIS_CACHE_SUBDIR);
+
+    if cache_dir.exists() {
+        fs::remove_dir_all(&cache_dir)
+            .inspect_err(|e| log_error("Failed to clear analysis cache", e))?;
+        log(LogLevel::Info, "Cleared analysis cache");
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct CacheStatus {
     pub exists: bool,
@@ -249,6 +658,15 @@ fn load_from_content(content: &str) -> Option<HashMap<String, License>> {
     }
 }
 
+/// Create the cache directory if needed and check that files can be written to it
+pub fn check_cache_dir_writable() -> FeludaResult<PathBuf> {
+    let cache_dir = ensure_cache_dir()?;
+    let probe = cache_dir.join(".feluda-write-check");
+    fs::write(&probe, b"ok")?;
+    fs::remove_file(&probe)?;
+    Ok(cache_dir)
+}
+
 pub fn get_cache_status() -> FeludaResult<CacheStatus> {
     let cache_path = github_cache_path()?;
 
@@ -287,9 +705,30 @@ pub fn get_cache_status() -> FeludaResult<CacheStatus> {
     })
 }
 
+/// Run `f` with the cache directory redirected to `dir` on the current thread
+#[cfg(test)]
+pub fn with_test_cache_dir<T>(dir: &std::path::Path, f: impl FnOnce() -> T) -> T {
+    TEST_CACHE_DIR.with(|d| *d.borrow_mut() = Some(dir.to_path_buf()));
+    let result = f();
+    TEST_CACHE_DIR.with(|d| *d.borrow_mut() = None);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn test_check_cache_dir_writable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("nested");
+        let checked = with_test_cache_dir(&cache_dir, check_cache_dir_writable).unwrap();
+
+        assert_eq!(checked, cache_dir);
+        assert!(cache_dir.is_dir());
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 0);
+    }
 
     fn make_license(id: &str) -> License {
         License {
@@ -315,7 +754,7 @@ mod tests {
 
     #[test]
     fn stale_entry_is_not_fresh() {
-        let old = now_secs() - CACHE_TTL_SECS - 1;
+        let old = now_secs() - cache_ttl_secs() - 1;
         assert!(!is_entry_fresh(old));
     }
 
@@ -365,7 +804,7 @@ mod tests {
         let entry = CacheEntry {
             version: CACHE_VERSION,
             data,
-            timestamp: now_secs() - CACHE_TTL_SECS - 1,
+            timestamp: now_secs() - cache_ttl_secs() - 1,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(load_from_content(&json).is_none());
@@ -378,6 +817,119 @@ mod tests {
         assert!(load_from_content("{}").is_none());
     }
 
+    #[test]
+    #[serial]
+    fn custom_ttl_changes_freshness() {
+        let ts = now_secs() - 2 * 60 * 60;
+
+        set_cache_ttl_hours(1);
+        assert!(!is_entry_fresh(ts));
+
+        set_cache_ttl_hours(3);
+        assert!(is_entry_fresh(ts));
+
+        set_cache_ttl_hours(DEFAULT_CACHE_TTL_HOURS);
+    }
+
+    #[test]
+    #[serial]
+    fn save_and_load_with_temp_cache_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        with_test_cache_dir(temp_dir.path(), || {
+            assert!(load_github_licenses_from_cache().unwrap().is_none());
+
+            let mut data = HashMap::new();
+            data.insert("MIT".to_string(), make_license("MIT"));
+            save_github_licenses_to_cache(&data).unwrap();
+
+            let loaded = load_github_licenses_from_cache().unwrap().unwrap();
+            assert_eq!(loaded["MIT"].spdx_id, "MIT");
+
+            set_refresh_cache(true);
+            assert!(load_github_licenses_from_cache().unwrap().is_none());
+            set_refresh_cache(false);
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn corrupt_cache_file_falls_back_to_fetch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        with_test_cache_dir(temp_dir.path(), || {
+            fs::write(
+                temp_dir.path().join(GITHUB_LICENSES_CACHE_FILE),
+                "not valid json {{{",
+            )
+            .unwrap();
+            assert!(load_github_licenses_from_cache().unwrap().is_none());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn package_cache_warm_avoids_resolution() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(PACKAGE_LICENSES_CACHE_FILE);
+        let mut calls = 0;
+
+        let cache = PackageLicenseCache::load(&path);
+        let license = cache.get_or_resolve("node", "left-pad", "1.3.0", || {
+            calls += 1;
+            "MIT".to_string()
+        });
+        assert_eq!(license, "MIT");
+        cache.save(&path).unwrap();
+
+        // A fresh process reading the persisted cache does not resolve again
+        let warm = PackageLicenseCache::load(&path);
+        let license = warm.get_or_resolve("node", "left-pad", "1.3.0", || {
+            calls += 1;
+            "Apache-2.0".to_string()
+        });
+        assert_eq!(license, "MIT");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    #[serial]
+    fn package_cache_skips_unresolved_licenses() {
+        let cache = PackageLicenseCache::default();
+        cache.insert("python", "foo", "1.0", "Unknown license for foo: 1.0");
+        cache.insert("go", "bar", "v1", "Unknown");
+        cache.insert("rust", "baz", "0.1", "");
+        assert_eq!(cache.len(), 0);
+
+        cache.insert("python", "foo", "1.0", "BSD-3-Clause");
+        assert_eq!(
+            cache.get("python", "foo", "1.0").as_deref(),
+            Some("BSD-3-Clause")
+        );
+        assert!(cache.get("python", "foo", "2.0").is_none());
+    }
+
+    #[test]
+    fn package_cache_corrupt_file_starts_empty() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(PACKAGE_LICENSES_CACHE_FILE);
+        fs::write(&path, "not valid json").unwrap();
+
+        let cache = PackageLicenseCache::load(&path);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn package_cache_parallel_access() {
+        use rayon::prelude::*;
+
+        let cache = PackageLicenseCache::default();
+        (0..100).into_par_iter().for_each(|i| {
+            let name = format!("pkg-{}", i % 10);
+            cache.get_or_resolve("node", &name, "1.0.0", || "MIT".to_string());
+        });
+        assert_eq!(cache.len(), 10);
+    }
+
     #[test]
     fn format_size_bytes() {
         assert_eq!(CacheStatus::format_size(500), "500 B");
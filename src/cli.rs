@@ -1,14 +1,17 @@
 use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use colored::*;
 use std::env;
-use std::io::{self, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
 // Import from the debug module instead of defining here
-use crate::debug::{is_debug_mode, log, LogLevel};
+use crate::debug::{is_debug_mode, is_quiet_mode, log, LogLevel};
+
+// Value enums shared with the configuration file
+pub use crate::config::{MaxDepsAction, NodeLicenseSource};
 
 /// CI output format options
 #[derive(ValueEnum, Clone, Debug)]
@@ -17,6 +20,8 @@ pub enum CiFormat {
     Github,
     /// Jenkins compatible format (JUnit XML)
     Jenkins,
+    /// JUnit XML test suite with one test case per dependency
+    Junit,
 }
 
 /// SBOM format options
@@ -24,12 +29,37 @@ pub enum CiFormat {
 pub enum SbomFormat {
     /// SPDX format
     Spdx,
+    /// SPDX tag-value format
+    SpdxTagValue,
     /// CycloneDX format
     Cyclonedx,
+    /// CycloneDX XML format
+    CyclonedxXml,
+    /// CycloneDX VEX skeleton with an empty vulnerability list
+    Vex,
     /// Generate all supported formats
     All,
 }
 
+/// Target format of `sbom convert`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ConvertFormat {
+    /// SPDX JSON
+    Spdx,
+    /// CycloneDX JSON
+    Cyclonedx,
+}
+
+/// Output format for files written by the generate command
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum GenerateFormat {
+    /// Human-readable Markdown/plain text
+    #[default]
+    Markdown,
+    /// Machine-readable JSON (THIRD_PARTY_LICENSES only)
+    Json,
+}
+
 /// OSI filter options
 #[derive(ValueEnum, Clone, Debug)]
 pub enum OsiFilter {
@@ -41,6 +71,38 @@ pub enum OsiFilter {
     Unknown,
 }
 
+/// Severity of a dependency's license findings, from least to most severe
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// No license findings
+    Info,
+    /// Restrictive but not copyleft, or not OSI approved
+    Warn,
+    /// Incompatible with the project license, or restrictive copyleft or proprietary
+    Error,
+}
+
+/// Sort keys for the report output
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportSort {
+    /// Sort by package name
+    Name,
+    /// Sort by license, then package name
+    License,
+    /// Restrictive licenses first, then package name
+    Restrictive,
+}
+
+/// Format of debug log lines
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Colored `[LEVEL] message` lines
+    #[default]
+    Human,
+    /// One JSON object per line with `level`, `ts` and `msg` fields
+    Json,
+}
+
 /// SBOM Subcommands
 #[derive(Subcommand, Debug, Clone)]
 pub enum SbomCommand {
@@ -53,6 +115,10 @@ pub enum SbomCommand {
         /// Path to write the SBOM file
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Emit the classic SPDX tag-value format instead of JSON
+        #[arg(long)]
+        tag_value: bool,
     },
     /// Generate CycloneDX format SBOM
     Cyclonedx {
@@ -63,6 +129,20 @@ pub enum SbomCommand {
         /// Path to write the SBOM file
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Emit CycloneDX XML instead of JSON
+        #[arg(long)]
+        xml: bool,
+    },
+    /// Generate a CycloneDX VEX skeleton listing the SBOM components, for vulnerability tools to fill in
+    Vex {
+        /// Path to the local project directory
+        #[arg(short, long, default_value = "./")]
+        path: String,
+
+        /// Path to write the VEX file
+        #[arg(short, long)]
+        output: Option<String>,
     },
     /// Validate SBOM file (JSON format)
     Validate {
@@ -78,6 +158,48 @@ pub enum SbomCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Compare two SBOM files (SPDX or CycloneDX JSON)
+    Diff {
+        /// Path to the older SBOM file
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// Path to the newer SBOM file
+        #[arg(value_name = "NEW")]
+        new: String,
+
+        /// Path to write the diff report
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output the diff in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Merge several SPDX JSON documents into one
+    Merge {
+        /// SPDX JSON files to merge
+        #[arg(value_name = "FILES", required = true, num_args = 2..)]
+        files: Vec<String>,
+
+        /// Path to write the merged SBOM file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Convert an SPDX or CycloneDX JSON SBOM to the other format
+    Convert {
+        /// SBOM file to convert; its format is detected
+        #[arg(value_name = "INPUT")]
+        input: String,
+
+        /// Format to convert to
+        #[arg(long, value_enum)]
+        to: ConvertFormat,
+
+        /// Path to write the converted SBOM file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 /// CLI Commands
@@ -96,6 +218,23 @@ pub enum Commands {
         /// Specify the project license explicitly
         #[arg(long)]
         project_license: Option<String>,
+
+        /// Output format for the generated file
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Markdown)]
+        format: GenerateFormat,
+
+        /// Generate the NOTICE file without the interactive menu
+        #[arg(long)]
+        notice: bool,
+
+        /// Generate the THIRD_PARTY_LICENSES file without the interactive menu
+        #[arg(long)]
+        third_party_licenses: bool,
+
+        /// Template for the NOTICE file with {{license_sections}}, {{dependency_count}},
+        /// {{license_count}} and {{date}} placeholders
+        #[arg(long, value_name = "FILE")]
+        notice_template: Option<String>,
     },
     /// Generate Software Bill of Materials (SBOM)
     Sbom {
@@ -107,16 +246,52 @@ pub enum Commands {
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Directory to write the SBOM files to, created if missing
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<String>,
+
         /// SBOM format subcommand
         #[command(subcommand)]
         format: Option<SbomCommand>,
     },
     /// Manage cache
     Cache {
-        /// Clear the GitHub licenses cache
+        /// Clear the GitHub licenses and per-package license caches
         #[arg(long)]
         clear: bool,
     },
+    /// List the distinct licenses in use and how many packages use each
+    ListLicenses {
+        /// Path to the local project directory
+        #[arg(short, long, default_value = "./")]
+        path: String,
+
+        /// Output the inventory as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Summarize license health metrics for dashboards
+    Stats {
+        /// Path to the local project directory
+        #[arg(short, long, default_value = "./")]
+        path: String,
+
+        /// Output the metrics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the dependency tree with each package's license
+    Tree {
+        /// Path to the local project directory
+        #[arg(short, long, default_value = "./")]
+        path: String,
+
+        /// Specify the language to scan
+        #[arg(long, short)]
+        language: Option<String>,
+    },
+    /// Check package managers, registry access, GitHub token and cache directory
+    Doctor,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -133,6 +308,28 @@ pub struct Cli {
     #[arg(long, short, global = true)]
     pub debug: bool,
 
+    /// Print only the report itself, or nothing when all dependencies pass
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Format of debug log lines, e.g. json for log aggregation systems
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LogFormat::Human,
+        env = "FELUDA_LOG_FORMAT",
+        global = true
+    )]
+    pub log_format: LogFormat,
+
+    /// Disable colored output (also disabled by NO_COLOR or when stdout is not a terminal)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Write JSON reports and SBOMs on a single line instead of indented
+    #[arg(long, global = true)]
+    pub compact: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 
@@ -141,9 +338,13 @@ pub struct Cli {
     pub path: String,
 
     /// URL of the Git repository to analyze (HTTPS or SSH)
-    #[arg(long)]
+    #[arg(long, conflicts_with = "offline")]
     pub repo: Option<String>,
 
+    /// Analyze a single manifest or lockfile instead of discovering project files under --path
+    #[arg(long, value_name = "FILE", conflicts_with = "repo")]
+    pub manifest: Option<String>,
+
     // For HTTPS authentication
     #[arg(long, requires = "repo")]
     pub token: Option<String>,
@@ -157,7 +358,7 @@ pub struct Cli {
     pub ssh_passphrase: Option<String>,
 
     /// GitHub personal access token for API authentication (increases rate limits)
-    #[arg(long, env = "GITHUB_TOKEN", global = true)]
+    #[arg(long, env = "FELUDA_GITHUB_TOKEN", global = true)]
     pub github_token: Option<String>,
 
     /// Output in JSON format
@@ -174,6 +375,14 @@ pub struct Cli {
     /// This is useful for CI/CD pipelines.
     pub yaml: bool,
 
+    /// Output in TOML format, as a `[[dependency]]` array of tables
+    #[arg(long, group = "output")]
+    pub toml: bool,
+
+    /// Output one JSON object per dependency per line (JSON Lines), for streaming into tools like jq
+    #[arg(long, group = "output")]
+    pub ndjson: bool,
+
     /// Enable verbose output
     #[arg(long)]
     pub verbose: bool,
@@ -194,10 +403,15 @@ pub struct Cli {
     #[arg(long, value_enum)]
     pub ci_format: Option<CiFormat>,
 
-    /// Path to write the CI report file
+    /// Path to write the report (table, JSON, YAML, TOML, NDJSON, CI or --summary-json) to
+    /// instead of stdout
     #[arg(long)]
     pub output_file: Option<String>,
 
+    /// Print a single JSON object with dependency counts instead of the report table
+    #[arg(long, conflicts_with_all = ["json", "yaml", "toml", "ndjson", "gist", "gui", "ci_format"])]
+    pub summary_json: bool,
+
     /// Fail with non-zero exit code when restrictive licenses are found
     #[arg(long)]
     pub fail_on_restrictive: bool,
@@ -210,6 +424,42 @@ pub struct Cli {
     #[arg(long)]
     pub fail_on_incompatible: bool,
 
+    /// Fail with non-zero exit code when any dependency license could not be resolved
+    #[arg(long)]
+    pub fail_on_unknown: bool,
+
+    /// Fail with non-zero exit code when more than PERCENT of dependency licenses are unresolved
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub fail_on_unknown_ratio: Option<u8>,
+
+    /// Fail with non-zero exit code when any dependency license is not OSI approved
+    #[arg(long, alias = "strict-osi")]
+    pub fail_on_non_osi: bool,
+
+    /// With --fail-on-non-osi, also fail when a license's OSI status is unknown
+    #[arg(long, requires = "fail_on_non_osi")]
+    pub fail_on_unknown_osi: bool,
+
+    /// Fail with non-zero exit code when a dependency is under this SPDX license (repeatable)
+    #[arg(long, value_name = "SPDX", value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    pub deny_license: Vec<String>,
+
+    /// Warn when a dependency is under this SPDX license (repeatable)
+    #[arg(long, value_name = "SPDX", value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    pub warn_license: Vec<String>,
+
+    /// Baseline of known findings that still show but do not trigger --fail-on-* gates
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<String>,
+
+    /// Write the current restrictive and incompatible findings to the --baseline file
+    #[arg(long, requires = "baseline")]
+    pub write_baseline: bool,
+
+    /// Report license changes against a git revision; --fail-on-* only counts findings new since then
+    #[arg(long, value_name = "GIT_REF", conflicts_with_all = ["baseline", "gui", "why"])]
+    pub diff: Option<String>,
+
     /// Specify the project license (overrides auto-detection)
     #[arg(long)]
     pub project_license: Option<String>,
@@ -218,17 +468,143 @@ pub struct Cli {
     #[arg(long, group = "output")]
     pub gist: bool,
 
+    /// Print only the license counts and the flagged packages, without the per-package table
+    #[arg(long, group = "output")]
+    pub summary_only: bool,
+
     /// Filter by OSI license approval status
     #[arg(long, value_enum)]
     pub osi: Option<OsiFilter>,
 
+    /// Show only dependencies at or above this severity, combining --restrictive, --incompatible and --osi
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    pub min_severity: Option<Severity>,
+
+    /// Sort the report instead of keeping the order dependencies were found in
+    #[arg(long, value_enum)]
+    pub sort: Option<ReportSort>,
+
     /// Enable strict mode for license parser
     #[arg(long)]
     pub strict: bool,
 
     /// Skip local license detection, force network lookup only
-    #[arg(long)]
+    #[arg(long, conflicts_with = "offline")]
     pub no_local: bool,
+
+    /// Disable all network requests and resolve licenses from local data only
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Limit Node transitive dependency resolution depth (0 = direct dependencies only)
+    #[arg(long, value_name = "N")]
+    pub depth: Option<usize>,
+
+    /// Only analyze production dependencies; this is the default unless --include-dev is given
+    #[arg(long, conflicts_with = "include_dev")]
+    pub production_only: bool,
+
+    /// Also analyze dev, test, build and optional dependencies
+    #[arg(long)]
+    pub include_dev: bool,
+
+    /// Only report dependencies declared directly in the manifests, without transitive ones
+    #[arg(long, conflicts_with = "depth")]
+    pub direct_only: bool,
+
+    /// Print the dependency paths that pull in PACKAGE instead of the report
+    #[arg(long, value_name = "PACKAGE", conflicts_with_all = ["direct_only", "gui"])]
+    pub why: Option<String>,
+
+    /// Map common license aliases (e.g. "Apache 2.0", "The MIT License") to SPDX identifiers
+    #[arg(long)]
+    pub normalize_licenses: bool,
+
+    /// Abort the scan once more than N dependencies are found
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub max_deps: Option<usize>,
+
+    /// Whether exceeding --max-deps is an error or truncates the results
+    #[arg(long, value_enum, default_value_t = MaxDepsAction::Error, requires = "max_deps")]
+    pub max_deps_action: MaxDepsAction,
+
+    /// Configuration file to use instead of .feluda.toml in the working directory
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<String>,
+
+    /// Ignore cached license data and fetch a fresh copy
+    #[arg(long, global = true)]
+    pub refresh_cache: bool,
+
+    /// Hours before cached license data is re-fetched (default: 24)
+    #[arg(long, global = true, value_name = "HOURS")]
+    pub cache_ttl: Option<u64>,
+
+    /// Bypass the per-package license cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Re-analyze projects even when their manifests and lockfiles are unchanged
+    #[arg(long, global = true)]
+    pub no_incremental: bool,
+
+    /// Maximum number of HTTP requests in flight while resolving licenses
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = crate::network::DEFAULT_MAX_CONCURRENCY,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub max_concurrency: usize,
+
+    /// Seconds before a registry or API request is abandoned (default: 10)
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        value_parser = clap::builder::RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub timeout: Option<u64>,
+
+    /// Worker threads for parallel dependency analysis (0 = one per CPU core)
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(0..=crate::utils::MAX_THREADS as u64)
+    )]
+    pub threads: Option<usize>,
+
+    /// npm registry mirror to query instead of registry.npmjs.org
+    #[arg(long, global = true, value_name = "URL", value_parser = parse_registry_url)]
+    pub npm_registry: Option<String>,
+
+    /// PyPI index mirror to query instead of pypi.org
+    #[arg(long, global = true, value_name = "URL", value_parser = parse_registry_url)]
+    pub pypi_index: Option<String>,
+
+    /// crates.io-compatible registry to query instead of crates.io
+    #[arg(long, global = true, value_name = "URL", value_parser = parse_registry_url)]
+    pub crates_registry: Option<String>,
+
+    /// Bearer token for a private registry host (HOST=TOKEN, repeatable); .npmrc and .netrc are read too
+    #[arg(long, global = true, value_name = "HOST=TOKEN")]
+    pub registry_token: Vec<crate::registry_auth::RegistryToken>,
+
+    /// Comma-separated Node.js license sources to try, in order (e.g. package_json,registry_api)
+    #[arg(long, global = true, value_name = "SOURCES", value_delimiter = ',')]
+    pub node_license_sources: Option<Vec<NodeLicenseSource>>,
+}
+
+/// Accept only absolute http(s) registry URLs
+fn parse_registry_url(url: &str) -> Result<String, String> {
+    crate::config::validate_registry_url(url)?;
+    Ok(url.trim().to_string())
 }
 
 impl Cli {
@@ -242,6 +618,10 @@ impl Cli {
                     path: "".to_string(),
                     language: None,
                     project_license: None,
+                    format: GenerateFormat::Markdown,
+                    notice: false,
+                    third_party_licenses: false,
+                    notice_template: None,
                 }
             }
         }
@@ -298,6 +678,72 @@ pub fn print_version_info() {
     );
 }
 
+// Spinners write to stdout, so they are turned off when it carries machine-readable output
+static PROGRESS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+// License resolution counters shared by the analyzers, shown as `resolved n/total`
+static DEPENDENCIES_TOTAL: AtomicUsize = AtomicUsize::new(0);
+static DEPENDENCIES_RESOLVED: AtomicUsize = AtomicUsize::new(0);
+
+/// Enable or disable spinners and progress output
+pub fn set_progress_enabled(enabled: bool) {
+    PROGRESS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if spinners and progress output are enabled
+pub fn is_progress_enabled() -> bool {
+    PROGRESS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Colors are used on a terminal unless `--no-color` is passed or `NO_COLOR` is set to a non-empty value
+pub fn should_use_color(
+    no_color_flag: bool,
+    no_color_env: Option<std::ffi::OsString>,
+    stdout_is_terminal: bool,
+) -> bool {
+    let no_color_env = no_color_env.is_some_and(|value| !value.is_empty());
+    stdout_is_terminal && !no_color_flag && !no_color_env
+}
+
+/// Progress is only shown on an interactive terminal that isn't receiving JSON, YAML or CI output
+pub fn should_show_progress(stdout_is_terminal: bool, machine_output: bool) -> bool {
+    stdout_is_terminal && !machine_output
+}
+
+/// Spinners only animate on an interactive terminal, with progress enabled and outside
+/// `--quiet`, so CI logs and redirected output get no control characters
+fn should_animate(stdout_is_terminal: bool) -> bool {
+    stdout_is_terminal && is_progress_enabled() && !is_quiet_mode()
+}
+
+/// Register `count` more dependencies whose licenses are about to be resolved
+pub fn add_dependencies_to_resolve(count: usize) {
+    DEPENDENCIES_TOTAL.fetch_add(count, Ordering::Relaxed);
+}
+
+/// Record that the license of one dependency has been resolved
+pub fn mark_dependency_resolved() {
+    DEPENDENCIES_RESOLVED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Clear the resolution counters before a new run
+pub fn reset_resolution_progress() {
+    DEPENDENCIES_TOTAL.store(0, Ordering::Relaxed);
+    DEPENDENCIES_RESOLVED.store(0, Ordering::Relaxed);
+}
+
+fn resolution_progress() -> Option<String> {
+    format_resolution_progress(
+        DEPENDENCIES_RESOLVED.load(Ordering::Relaxed),
+        DEPENDENCIES_TOTAL.load(Ordering::Relaxed),
+    )
+}
+
+/// `resolved n/total` while dependencies are being resolved, `None` before any are registered
+fn format_resolution_progress(resolved: usize, total: usize) -> Option<String> {
+    (total > 0).then(|| format!("resolved {}/{total}", resolved.min(total)))
+}
+
 /// A loading indicator that displays a spinner and progress updates
 /// without deleting the previous line
 pub struct LoadingIndicator {
@@ -326,6 +772,10 @@ impl LoadingIndicator {
             return;
         }
 
+        if !should_animate(io::stdout().is_terminal()) {
+            return;
+        }
+
         let message = self.message.clone();
         let running = self.running.clone();
         let spinner_frames = self.spinner_frames.clone();
@@ -350,8 +800,10 @@ impl LoadingIndicator {
                 let spinner_char = spinner_frames[frame_idx];
                 print!("{} {} ", spinner_char.cyan(), message);
 
-                // Print progress info if available
-                if let Some(ref progress_text) = *progress.lock().unwrap() {
+                // Print progress info if available, preferring the resolution count
+                if let Some(resolved) = resolution_progress() {
+                    print!("({resolved})");
+                } else if let Some(ref progress_text) = *progress.lock().unwrap() {
                     print!("({progress_text})");
                 }
 
@@ -362,7 +814,9 @@ impl LoadingIndicator {
             // Clear line and print completion message
             print!("\x1B[2K\r");
             print!("{} {} ", "✓".green().bold(), message);
-            if let Some(ref progress_text) = *progress.lock().unwrap() {
+            if let Some(resolved) = resolution_progress() {
+                print!("({resolved})");
+            } else if let Some(ref progress_text) = *progress.lock().unwrap() {
                 print!("({progress_text})");
             }
             println!(" ✅");
@@ -408,6 +862,15 @@ impl LoadingIndicator {
 /// });
 /// ```
 pub fn with_spinner<F, T>(message: &str, f: F) -> T
+where
+    F: FnOnce(&LoadingIndicator) -> T,
+{
+    run_with_spinner(message, io::stdout().is_terminal(), f)
+}
+
+/// [`with_spinner`] with the terminal check supplied by the caller; without an interactive
+/// terminal the closure runs with an indicator that never prints
+fn run_with_spinner<F, T>(message: &str, stdout_is_terminal: bool, f: F) -> T
 where
     F: FnOnce(&LoadingIndicator) -> T,
 {
@@ -419,6 +882,8 @@ where
         let duration = start.elapsed();
         log(LogLevel::Info, &format!("Completed in {duration:?}"));
         result
+    } else if !should_animate(stdout_is_terminal) {
+        f(&LoadingIndicator::new(message))
     } else {
         let mut indicator = LoadingIndicator::new(message);
         indicator.start();
@@ -459,29 +924,71 @@ mod tests {
     fn test_cli_default_values() {
         let cli = Cli {
             debug: false,
+            quiet: false,
+            log_format: LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: None,
             path: "./".to_string(),
             repo: None,
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         assert_eq!(cli.path, "./");
@@ -498,33 +1005,79 @@ mod tests {
     fn test_get_command_args_with_command() {
         let cli = Cli {
             debug: false,
+            quiet: false,
+            log_format: LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: Some(Commands::Generate {
                 path: "/test/path".to_string(),
                 language: Some("rust".to_string()),
                 project_license: Some("MIT".to_string()),
+                format: GenerateFormat::Json,
+                notice: false,
+                third_party_licenses: true,
+                notice_template: None,
             }),
             path: "./".to_string(),
             repo: None,
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         let cmd = cli.get_command_args();
@@ -533,10 +1086,18 @@ mod tests {
                 path,
                 language,
                 project_license,
+                format,
+                notice,
+                third_party_licenses,
+                notice_template,
             } => {
                 assert_eq!(path, "/test/path");
                 assert_eq!(language, Some("rust".to_string()));
                 assert_eq!(project_license, Some("MIT".to_string()));
+                assert_eq!(format, GenerateFormat::Json);
+                assert!(!notice);
+                assert!(third_party_licenses);
+                assert_eq!(notice_template, None);
             }
             Commands::Sbom { .. } => {
                 panic!("Expected Generate command");
@@ -544,6 +1105,18 @@ mod tests {
             Commands::Cache { .. } => {
                 panic!("Expected Generate command");
             }
+            Commands::ListLicenses { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Stats { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Tree { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Doctor => {
+                panic!("Expected Generate command");
+            }
         }
         assert!(!cli.is_default_command());
     }
@@ -552,29 +1125,71 @@ mod tests {
     fn test_get_command_args_default() {
         let cli = Cli {
             debug: false,
+            quiet: false,
+            log_format: LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: None,
             path: "./test".to_string(),
             repo: None,
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         let cmd = cli.get_command_args();
@@ -583,6 +1198,7 @@ mod tests {
                 path,
                 language,
                 project_license,
+                ..
             } => {
                 assert_eq!(path, "");
                 assert_eq!(language, None);
@@ -594,6 +1210,18 @@ mod tests {
             Commands::Cache { .. } => {
                 panic!("Expected Generate command");
             }
+            Commands::ListLicenses { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Stats { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Tree { .. } => {
+                panic!("Expected Generate command");
+            }
+            Commands::Doctor => {
+                panic!("Expected Generate command");
+            }
         }
     }
 
@@ -640,6 +1268,78 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_should_use_color() {
+        assert!(should_use_color(false, None, true));
+        assert!(should_use_color(false, Some("".into()), true));
+        assert!(!should_use_color(true, None, true));
+        assert!(!should_use_color(false, Some("1".into()), true));
+        assert!(!should_use_color(false, None, false));
+    }
+
+    #[test]
+    fn test_should_show_progress() {
+        assert!(should_show_progress(true, false));
+        assert!(!should_show_progress(false, false));
+        assert!(!should_show_progress(true, true));
+        assert!(!should_show_progress(false, true));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_progress_not_emitted_when_disabled() {
+        set_progress_enabled(should_show_progress(false, false));
+
+        let mut indicator = LoadingIndicator::new("Test operation");
+        indicator.start();
+        assert!(indicator.handle.is_none());
+        indicator.stop();
+
+        let result = with_spinner("Test operation", |indicator| {
+            indicator.update_progress("working");
+            7
+        });
+        assert_eq!(result, 7);
+
+        set_progress_enabled(true);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_spinner_skipped_without_terminal_or_in_quiet_mode() {
+        set_progress_enabled(true);
+        let result = run_with_spinner("Test operation", false, |indicator| {
+            indicator.update_progress("working");
+            assert!(indicator.handle.is_none());
+            "done"
+        });
+        assert_eq!(result, "done");
+        assert!(!should_animate(false));
+        assert!(should_animate(true));
+
+        crate::debug::set_quiet_mode(true);
+        assert!(!should_animate(true));
+        let result = run_with_spinner("Test operation", true, |indicator| {
+            assert!(indicator.handle.is_none());
+            vec![1, 2, 3]
+        });
+        crate::debug::set_quiet_mode(false);
+        assert_eq!(result, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_format_resolution_progress() {
+        assert_eq!(format_resolution_progress(0, 0), None);
+        assert_eq!(
+            format_resolution_progress(120, 4000).as_deref(),
+            Some("resolved 120/4000")
+        );
+        assert_eq!(
+            format_resolution_progress(5, 4).as_deref(),
+            Some("resolved 4/4")
+        );
+    }
+
     #[test]
     fn test_format_before_help() {
         let help_text = format_before_help();
@@ -671,6 +1371,10 @@ mod tests {
             path: "./".to_string(),
             language: None,
             project_license: None,
+            format: GenerateFormat::Markdown,
+            notice: false,
+            third_party_licenses: false,
+            notice_template: None,
         };
 
         let cloned_cmd = generate_cmd.clone();
@@ -681,11 +1385,13 @@ mod tests {
                     path: p1,
                     language: l1,
                     project_license: pl1,
+                    ..
                 },
                 Commands::Generate {
                     path: p2,
                     language: l2,
                     project_license: pl2,
+                    ..
                 },
             ) => {
                 assert_eq!(p1, p2);
@@ -716,6 +1422,7 @@ mod tests {
             path: "./".to_string(),
             format: None,
             output: None,
+            output_dir: None,
         };
 
         match sbom_cmd {
@@ -723,6 +1430,7 @@ mod tests {
                 path,
                 format,
                 output,
+                ..
             } => {
                 assert_eq!(path, "./");
                 assert!(format.is_none());
@@ -739,8 +1447,10 @@ mod tests {
             format: Some(SbomCommand::Spdx {
                 path: "/project".to_string(),
                 output: Some("sbom.json".to_string()),
+                tag_value: false,
             }),
             output: None,
+            output_dir: None,
         };
 
         match sbom_cmd {
@@ -748,14 +1458,20 @@ mod tests {
                 path,
                 format,
                 output,
+                ..
             } => {
                 assert_eq!(path, "/project");
                 assert!(format.is_some());
                 assert!(output.is_none());
                 match format.unwrap() {
-                    SbomCommand::Spdx { path: p, output: o } => {
+                    SbomCommand::Spdx {
+                        path: p,
+                        output: o,
+                        tag_value,
+                    } => {
                         assert_eq!(p, "/project");
                         assert_eq!(o, Some("sbom.json".to_string()));
+                        assert!(!tag_value);
                     }
                     _ => panic!("Expected Spdx subcommand"),
                 }
@@ -771,8 +1487,10 @@ mod tests {
             format: Some(SbomCommand::Cyclonedx {
                 path: "/project".to_string(),
                 output: Some("sbom.xml".to_string()),
+                xml: true,
             }),
             output: None,
+            output_dir: None,
         };
 
         match sbom_cmd {
@@ -780,14 +1498,20 @@ mod tests {
                 path,
                 format,
                 output,
+                ..
             } => {
                 assert_eq!(path, "/project");
                 assert!(format.is_some());
                 assert!(output.is_none());
                 match format.unwrap() {
-                    SbomCommand::Cyclonedx { path: p, output: o } => {
+                    SbomCommand::Cyclonedx {
+                        path: p,
+                        output: o,
+                        xml,
+                    } => {
                         assert_eq!(p, "/project");
                         assert_eq!(o, Some("sbom.xml".to_string()));
+                        assert!(xml);
                     }
                     _ => panic!("Expected Cyclonedx subcommand"),
                 }
@@ -795,4 +1519,326 @@ mod tests {
             _ => panic!("Expected Sbom command"),
         }
     }
+
+    #[test]
+    fn test_fail_on_non_osi_flags() {
+        let cli = Cli::try_parse_from(["feluda", "--strict-osi", "--fail-on-unknown-osi"]).unwrap();
+        assert!(cli.fail_on_non_osi);
+        assert!(cli.fail_on_unknown_osi);
+
+        assert!(Cli::try_parse_from(["feluda", "--fail-on-unknown-osi"]).is_err());
+    }
+
+    #[test]
+    fn test_summary_only_flag() {
+        let cli = Cli::try_parse_from(["feluda", "--summary-only", "--verbose"]).unwrap();
+        assert!(cli.summary_only);
+        assert!(Cli::try_parse_from(["feluda", "--summary-only", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_license_flags_repeat() {
+        let cli = Cli::try_parse_from([
+            "feluda",
+            "--deny-license",
+            "AGPL-3.0",
+            "--deny-license",
+            "SSPL-1.0",
+            "--warn-license",
+            "MPL-2.0",
+        ])
+        .unwrap();
+        assert_eq!(cli.deny_license, ["AGPL-3.0", "SSPL-1.0"]);
+        assert_eq!(cli.warn_license, ["MPL-2.0"]);
+
+        assert!(Cli::try_parse_from(["feluda", "--deny-license", ""]).is_err());
+    }
+
+    #[test]
+    fn test_sbom_vex_subcommand() {
+        let cli =
+            Cli::try_parse_from(["feluda", "sbom", "vex", "--output", "app.vex.json"]).unwrap();
+        match cli.command {
+            Some(Commands::Sbom {
+                format: Some(SbomCommand::Vex { path, output }),
+                ..
+            }) => {
+                assert_eq!(path, "./");
+                assert_eq!(output.as_deref(), Some("app.vex.json"));
+            }
+            _ => panic!("Expected sbom vex subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_offline_parses_with_subcommands() {
+        let cli =
+            Cli::try_parse_from(["feluda", "--offline", "sbom", "spdx", "--tag-value"]).unwrap();
+        assert!(cli.offline);
+        match cli.command {
+            Some(Commands::Sbom {
+                format: Some(SbomCommand::Spdx { tag_value, .. }),
+                ..
+            }) => assert!(tag_value),
+            _ => panic!("Expected sbom spdx subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["feluda", "--offline", "--no-local"]).is_err());
+    }
+
+    #[test]
+    fn test_sbom_convert_parses() {
+        let cli = Cli::try_parse_from([
+            "feluda", "sbom", "convert", "bom.json", "--to", "spdx", "-o", "out.json",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Sbom {
+                format: Some(SbomCommand::Convert { input, to, output }),
+                ..
+            }) => {
+                assert_eq!(input, "bom.json");
+                assert_eq!(to, ConvertFormat::Spdx);
+                assert_eq!(output.as_deref(), Some("out.json"));
+            }
+            _ => panic!("Expected sbom convert subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["feluda", "sbom", "convert", "bom.json"]).is_err());
+    }
+
+    #[test]
+    fn test_max_concurrency_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.max_concurrency, crate::network::DEFAULT_MAX_CONCURRENCY);
+
+        let cli = Cli::try_parse_from(["feluda", "generate", "--max-concurrency", "1"]).unwrap();
+        assert_eq!(cli.max_concurrency, 1);
+
+        assert!(Cli::try_parse_from(["feluda", "--max-concurrency", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_include_dev_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert!(!cli.include_dev);
+
+        let cli = Cli::try_parse_from(["feluda", "--include-dev"]).unwrap();
+        assert!(cli.include_dev);
+
+        assert!(Cli::try_parse_from(["feluda", "--include-dev", "--production-only"]).is_err());
+    }
+
+    #[test]
+    fn test_fail_on_unknown_ratio_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.fail_on_unknown_ratio, None);
+
+        let cli = Cli::try_parse_from(["feluda", "--fail-on-unknown-ratio", "25"]).unwrap();
+        assert_eq!(cli.fail_on_unknown_ratio, Some(25));
+
+        assert!(Cli::try_parse_from(["feluda", "--fail-on-unknown-ratio", "101"]).is_err());
+    }
+
+    #[test]
+    fn test_node_license_sources_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.node_license_sources, None);
+
+        let cli = Cli::try_parse_from([
+            "feluda",
+            "--node-license-sources",
+            "package_json,registry_api",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.node_license_sources,
+            Some(vec![
+                NodeLicenseSource::PackageJson,
+                NodeLicenseSource::RegistryApi
+            ])
+        );
+
+        assert!(Cli::try_parse_from(["feluda", "--node-license-sources", "npm"]).is_err());
+    }
+
+    #[test]
+    fn test_diff_flag() {
+        let cli = Cli::try_parse_from(["feluda", "--diff", "origin/main"]).unwrap();
+        assert_eq!(cli.diff.as_deref(), Some("origin/main"));
+        assert!(Cli::try_parse_from(["feluda", "--diff", "main", "--baseline", "b.json"]).is_err());
+    }
+
+    #[test]
+    fn test_min_severity_flag() {
+        let cli = Cli::try_parse_from(["feluda", "--min-severity", "warn"]).unwrap();
+        assert_eq!(cli.min_severity, Some(Severity::Warn));
+        assert!(Severity::Info < Severity::Warn && Severity::Warn < Severity::Error);
+        assert!(Cli::try_parse_from(["feluda", "--min-severity", "critical"]).is_err());
+    }
+
+    #[test]
+    fn test_why_flag() {
+        let cli = Cli::try_parse_from(["feluda", "--why", "gpl-lib"]).unwrap();
+        assert_eq!(cli.why.as_deref(), Some("gpl-lib"));
+        assert!(Cli::try_parse_from(["feluda", "--why", "gpl-lib", "--direct-only"]).is_err());
+    }
+
+    #[test]
+    fn test_direct_only_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert!(!cli.direct_only);
+
+        let cli = Cli::try_parse_from(["feluda", "--direct-only", "--include-dev"]).unwrap();
+        assert!(cli.direct_only);
+        assert!(cli.include_dev);
+
+        assert!(Cli::try_parse_from(["feluda", "--direct-only", "--depth", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_max_deps_flags() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.max_deps, None);
+        assert_eq!(cli.max_deps_action, MaxDepsAction::Error);
+
+        let cli = Cli::try_parse_from(["feluda", "--max-deps", "5000"]).unwrap();
+        assert_eq!(cli.max_deps, Some(5000));
+        assert_eq!(cli.max_deps_action, MaxDepsAction::Error);
+
+        let cli = Cli::try_parse_from([
+            "feluda",
+            "--max-deps",
+            "5000",
+            "--max-deps-action",
+            "truncate",
+        ])
+        .unwrap();
+        assert_eq!(cli.max_deps_action, MaxDepsAction::Truncate);
+
+        assert!(Cli::try_parse_from(["feluda", "--max-deps", "0"]).is_err());
+        assert!(Cli::try_parse_from(["feluda", "--max-deps-action", "truncate"]).is_err());
+    }
+
+    #[test]
+    fn test_timeout_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.timeout, None);
+
+        let cli = Cli::try_parse_from(["feluda", "generate", "--timeout", "3"]).unwrap();
+        assert_eq!(cli.timeout, Some(3));
+
+        assert!(Cli::try_parse_from(["feluda", "--timeout", "0"]).is_err());
+        assert!(Cli::try_parse_from(["feluda", "--timeout", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_config_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.config, None);
+
+        let cli = Cli::try_parse_from(["feluda", "sbom", "--config", "ci/feluda.toml"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("ci/feluda.toml"));
+    }
+
+    #[test]
+    fn test_threads_flag() {
+        let cli = Cli::try_parse_from(["feluda"]).unwrap();
+        assert_eq!(cli.threads, None);
+
+        let cli = Cli::try_parse_from(["feluda", "sbom", "--threads", "2"]).unwrap();
+        assert_eq!(cli.threads, Some(2));
+
+        let cli = Cli::try_parse_from(["feluda", "--threads", "0"]).unwrap();
+        assert_eq!(cli.threads, Some(0));
+
+        assert!(Cli::try_parse_from(["feluda", "--threads", "100000"]).is_err());
+    }
+
+    #[test]
+    fn test_registry_flags() {
+        let cli = Cli::try_parse_from([
+            "feluda",
+            "--npm-registry",
+            "https://npm.example.com",
+            "generate",
+            "--crates-registry",
+            "https://crates.example.com",
+        ])
+        .unwrap();
+        assert_eq!(cli.npm_registry.as_deref(), Some("https://npm.example.com"));
+        assert_eq!(cli.pypi_index, None);
+        assert_eq!(
+            cli.crates_registry.as_deref(),
+            Some("https://crates.example.com")
+        );
+
+        assert!(Cli::try_parse_from(["feluda", "--pypi-index", "not a url"]).is_err());
+    }
+
+    #[test]
+    fn test_registry_token_flag() {
+        let cli = Cli::try_parse_from([
+            "feluda",
+            "--registry-token",
+            "npm.example.com=abc",
+            "--registry-token",
+            "pypi.example.com:8443=def",
+        ])
+        .unwrap();
+        let hosts: Vec<_> = cli.registry_token.iter().map(|t| t.host.as_str()).collect();
+        assert_eq!(hosts, ["npm.example.com", "pypi.example.com:8443"]);
+        assert!(!format!("{cli:?}").contains("abc"));
+
+        assert!(Cli::try_parse_from(["feluda", "--registry-token", "abc"]).is_err());
+    }
+
+    #[test]
+    fn test_list_licenses_command_parses() {
+        let cli =
+            Cli::try_parse_from(["feluda", "list-licenses", "--path", "/tmp/x", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::ListLicenses { path, json }) => {
+                assert_eq!(path, "/tmp/x");
+                assert!(json);
+            }
+            _ => panic!("Expected list-licenses command"),
+        }
+    }
+
+    #[test]
+    fn test_stats_command_parses() {
+        let cli = Cli::try_parse_from(["feluda", "stats", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Stats { path, json }) => {
+                assert_eq!(path, "./");
+                assert!(json);
+            }
+            _ => panic!("Expected stats command"),
+        }
+    }
+
+    #[test]
+    fn test_tree_command_parses() {
+        let cli = Cli::try_parse_from(["feluda", "tree", "--language", "node"]).unwrap();
+        match cli.command {
+            Some(Commands::Tree { path, language }) => {
+                assert_eq!(path, "./");
+                assert_eq!(language.as_deref(), Some("node"));
+            }
+            _ => panic!("Expected tree command"),
+        }
+    }
+
+    #[test]
+    fn test_doctor_command_parses() {
+        let cli = Cli::try_parse_from(["feluda", "doctor"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Doctor)));
+    }
 }
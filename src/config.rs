@@ -6,10 +6,14 @@
 //! 1. Default values (built into the binary)
 //! 2. `.feluda.toml` file in the project root
 //! 3. Environment variables prefixed with `FELUDA_`
+//! 4. An explicit file passed with `--config`, which replaces `.feluda.toml` discovery
 //!
 //! # Configuration File Example
 //!
 //! ```toml
+//! # Seconds before a registry or API request is abandoned (default: 10)
+//! timeout = 30
+//!
 //! [licenses]
 //! # Override the default list of restrictive licenses
 //! restrictive = [
@@ -33,6 +37,40 @@
 //! name = "something-else"
 //! version = ""  # Empty version means ignore all versions of this dependency
 //! reason = "We have a written acknowledgment from the author that we may use their code under our license."
+//!
+//! # Explicit compatibility allowlists per project license.
+//! # When the project license has an entry here, it is used instead of the built-in matrix:
+//! # dependency licenses in the list are compatible, known licenses outside it are
+//! # incompatible, and unrecognized licenses are unknown (incompatible in strict mode).
+//! [compatibility]
+//! "Apache-2.0" = ["MIT", "BSD-3-Clause", "Apache-2.0"]
+//!
+//! # Tighten or relax the restrictive determination without replacing the defaults
+//! [restrictive]
+//! additional = ["BUSL-1.1", "SSPL-1.0"]
+//! allow = ["LGPL-3.0"]
+//!
+//! # Package registry mirrors used instead of the public registries
+//! [registries]
+//! npm = "https://npm.example.com"
+//! pypi = "https://pypi.example.com"
+//! crates = "https://crates.example.com"
+//!
+//! # Where Node.js package licenses are looked up, in order; leave out `npm_view` to skip
+//! # spawning npm
+//! [node]
+//! license_sources = ["package_json", "license_file", "registry_api"]
+//!
+//! # Correct wrong registry metadata; `name@version` beats a bare `name`
+//! [overrides]
+//! "left-pad" = "MIT"
+//! "some-crate@0.3.1" = "Apache-2.0"
+//!
+//! # Fail the run on denied licenses, warn on others; see the `policy` module
+//! [policy]
+//! default = "allow"
+//! warn = ["LGPL-*", "unknown"]
+//! deny = ["GPL-*", "AGPL-*"]
 //! ```
 //!
 //! # Environment Variables
@@ -46,14 +84,18 @@
 //! export FELUDA_LICENSES_IGNORE='["MIT","Apache-2.0"]'
 //! ```
 
+use clap::ValueEnum;
 use figment::{
     providers::{Env, Format, Serialized, Toml},
     Figment,
 };
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 use crate::debug::{log, log_debug, log_error, FeludaError, FeludaResult, LogLevel};
+use crate::policy::Policy;
 
 /// Main configuration structure for Feluda
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
@@ -64,6 +106,129 @@ pub struct FeludaConfig {
     pub dependencies: DependencyConfig,
     #[serde(default)]
     pub strict: bool,
+    /// Project license -> allowlist of compatible dependency licenses.
+    /// Takes precedence over the built-in compatibility matrix.
+    #[serde(default)]
+    pub compatibility: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub restrictive: RestrictiveConfig,
+    #[serde(default)]
+    pub registries: RegistryConfig,
+    #[serde(default)]
+    pub node: NodeConfig,
+    /// Seconds before a registry or API request is abandoned; `--timeout` takes precedence
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// License patterns to allow, warn about or deny after analysis
+    #[serde(default)]
+    pub policy: Option<Policy>,
+    /// `name` or `name@version` -> license that replaces the detected one
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+    /// Node transitive resolution depth, set from `--depth`
+    #[serde(skip)]
+    pub node_depth: Option<usize>,
+    /// Also scan dev, test and build-only dependencies, set from `--include-dev`
+    #[serde(skip)]
+    pub include_dev: bool,
+    /// Only report dependencies the manifests declare directly, set from `--direct-only`
+    #[serde(skip)]
+    pub direct_only: bool,
+    /// Rewrite recognized license aliases to SPDX identifiers, set from `--normalize-licenses`
+    #[serde(skip)]
+    pub normalize_licenses: bool,
+    /// Single manifest to analyze instead of discovering project files, set from `--manifest`
+    #[serde(skip)]
+    pub manifest: Option<PathBuf>,
+    /// Cap on the number of collected dependencies, set from `--max-deps`
+    #[serde(skip)]
+    pub max_deps: DependencyLimit,
+}
+
+/// Split an `[overrides]` key into name and optional version
+///
+/// The leading `@` of a scoped npm package such as `@types/node@20.1.0` is part of the name.
+fn split_override_key(key: &str) -> (&str, Option<&str>) {
+    match key.rfind('@') {
+        Some(at) if at > 0 => (&key[..at], Some(&key[at + 1..])),
+        _ => (key, None),
+    }
+}
+
+/// What to do when a scan finds more dependencies than `--max-deps` allows
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MaxDepsAction {
+    /// Stop the scan with an error
+    #[default]
+    Error,
+    /// Warn and keep only the first N dependencies
+    Truncate,
+}
+
+/// Safety limit on how many dependencies a scan may collect
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DependencyLimit {
+    /// Maximum number of dependencies; `None` means unlimited
+    pub max: Option<usize>,
+    /// Whether exceeding `max` fails the scan or truncates the results
+    pub action: MaxDepsAction,
+}
+
+impl DependencyLimit {
+    pub fn new(max: Option<usize>, action: MaxDepsAction) -> Self {
+        Self { max, action }
+    }
+
+    /// Check a running dependency count against the limit
+    ///
+    /// Returns `Ok(Some(max))` when the count exceeds the limit in truncate mode: the caller
+    /// keeps `max` entries and stops collecting. In error mode exceeding it is a parser error.
+    pub fn check(&self, count: usize) -> FeludaResult<Option<usize>> {
+        match self.max {
+            Some(max) if count > max => match self.action {
+                MaxDepsAction::Error => Err(FeludaError::Parser(format!(
+                    "Found more than {max} dependencies (--max-deps); narrow the scan with \
+                     --manifest or .feludaignore, or use --max-deps-action truncate"
+                ))),
+                MaxDepsAction::Truncate => {
+                    log(
+                        LogLevel::Warn,
+                        &format!("Found more than {max} dependencies, keeping the first {max}"),
+                    );
+                    Ok(Some(max))
+                }
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Where the Node.js analyzer looks for a package's license, tried in the configured order
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[value(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NodeLicenseSource {
+    /// `license` field of the installed package.json
+    PackageJson,
+    /// LICENSE file of the installed package (skipped with --no-local)
+    LicenseFile,
+    /// package.json in the pnpm virtual store
+    PnpmMetadata,
+    /// `npm view` subprocess
+    NpmView,
+    /// npm registry HTTP API
+    RegistryApi,
+}
+
+impl NodeLicenseSource {
+    /// Local sources first, then the `npm view` subprocess, then the registry API
+    pub const DEFAULT_ORDER: [Self; 5] = [
+        Self::PackageJson,
+        Self::LicenseFile,
+        Self::PnpmMetadata,
+        Self::NpmView,
+        Self::RegistryApi,
+    ];
 }
 
 impl FeludaConfig {
@@ -71,6 +236,76 @@ impl FeludaConfig {
     pub fn validate(&self) -> FeludaResult<()> {
         self.licenses.validate()?;
         self.dependencies.validate()?;
+        self.restrictive.validate()?;
+        self.registries.validate()?;
+        self.node.validate()?;
+        self.validate_compatibility()?;
+        if let Some(policy) = &self.policy {
+            policy.validate()?;
+        }
+        self.validate_overrides()?;
+        if self.timeout == Some(0) {
+            return Err(FeludaError::Config(
+                "timeout must be at least 1 second".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the `[overrides]` entries
+    fn validate_overrides(&self) -> FeludaResult<()> {
+        for (key, license) in &self.overrides {
+            let (name, version) = split_override_key(key);
+            if name.trim().is_empty() || version.is_some_and(|v| v.trim().is_empty()) {
+                return Err(FeludaError::Config(format!(
+                    "Invalid override '{key}': expected `name` or `name@version`"
+                )));
+            }
+            if license.trim().is_empty() {
+                return Err(FeludaError::Config(format!(
+                    "Empty license for override '{key}'"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// License forced by `[overrides]`; an exact `name@version` entry beats a bare `name`
+    pub fn license_override(&self, name: &str, version: &str) -> Option<&str> {
+        self.overrides
+            .get(&format!("{name}@{version}"))
+            .or_else(|| self.overrides.get(name))
+            .map(String::as_str)
+    }
+
+    /// Validates the `[compatibility]` allowlists
+    fn validate_compatibility(&self) -> FeludaResult<()> {
+        for (project_license, allowed) in &self.compatibility {
+            if project_license.trim().is_empty() {
+                return Err(FeludaError::Config(
+                    "Empty project license found in compatibility section".to_string(),
+                ));
+            }
+
+            if allowed.iter().any(|license| license.trim().is_empty()) {
+                return Err(FeludaError::Config(format!(
+                    "Empty license string found in compatibility list for {project_license}"
+                )));
+            }
+
+            if allowed.is_empty() {
+                log(
+                    LogLevel::Warn,
+                    &format!(
+                        "Compatibility list for {project_license} is empty - all dependencies will be considered incompatible"
+                    ),
+                );
+            }
+        }
+
+        if !self.compatibility.is_empty() {
+            log_debug("Compatibility overrides", &self.compatibility);
+        }
         Ok(())
     }
 }
@@ -228,6 +463,132 @@ impl LicenseConfig {
     }
 }
 
+/// Additional restrictive licenses and allowlisted exceptions
+///
+/// Applied after the normal restrictive determination: licenses in `additional`
+/// become restrictive and licenses in `allow` are never restrictive.
+/// Matching is case-insensitive against the normalized license identifier.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct RestrictiveConfig {
+    #[serde(default)]
+    pub additional: Vec<String>,
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl RestrictiveConfig {
+    /// Validates the restrictive overrides
+    pub fn validate(&self) -> FeludaResult<()> {
+        if self
+            .additional
+            .iter()
+            .chain(self.allow.iter())
+            .any(|license| license.trim().is_empty())
+        {
+            return Err(FeludaError::Config(
+                "Empty license string found in restrictive overrides".to_string(),
+            ));
+        }
+
+        let overlap: Vec<_> = self
+            .additional
+            .iter()
+            .filter(|license| self.is_allowed(license))
+            .cloned()
+            .collect();
+
+        if !overlap.is_empty() {
+            log(
+                LogLevel::Warn,
+                &format!(
+                    "Licenses found in both additional and allow lists will be allowed: {}",
+                    overlap.join(", ")
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the license is in the `additional` list
+    pub fn is_additional(&self, license: &str) -> bool {
+        Self::matches_any(&self.additional, license)
+    }
+
+    /// Returns true if the license is in the `allow` list
+    pub fn is_allowed(&self, license: &str) -> bool {
+        Self::matches_any(&self.allow, license)
+    }
+
+    fn matches_any(list: &[String], license: &str) -> bool {
+        let normalized = crate::licenses::normalize_license_id(license).to_lowercase();
+        list.iter()
+            .any(|entry| crate::licenses::normalize_license_id(entry).to_lowercase() == normalized)
+    }
+}
+
+/// Base URLs of package registry mirrors, overriding the public registries
+///
+/// Unset entries fall back to `registry.npmjs.org`, `pypi.org` and `crates.io`.
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct RegistryConfig {
+    /// npm registry serving `<base>/<name>/<version>`
+    #[serde(default)]
+    pub npm: Option<String>,
+    /// PyPI index serving the JSON API at `<base>/pypi/<name>/<version>/json`
+    #[serde(default)]
+    pub pypi: Option<String>,
+    /// crates.io-compatible registry serving `<base>/api/v1/crates/<name>`
+    #[serde(default)]
+    pub crates: Option<String>,
+}
+
+/// Settings of the Node.js analyzer
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct NodeConfig {
+    /// License sources to try in order; `--node-license-sources` takes precedence
+    #[serde(default)]
+    pub license_sources: Option<Vec<NodeLicenseSource>>,
+}
+
+impl NodeConfig {
+    pub fn validate(&self) -> FeludaResult<()> {
+        if self.license_sources.as_ref().is_some_and(Vec::is_empty) {
+            return Err(FeludaError::Config(
+                "node.license_sources must list at least one source".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl RegistryConfig {
+    /// Validates that every configured registry is an absolute http(s) URL
+    pub fn validate(&self) -> FeludaResult<()> {
+        for (name, url) in [
+            ("npm", &self.npm),
+            ("pypi", &self.pypi),
+            ("crates", &self.crates),
+        ] {
+            if let Some(url) = url {
+                validate_registry_url(url)
+                    .map_err(|e| FeludaError::Config(format!("Invalid {name} registry: {e}")))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that a registry base URL parses as an absolute http(s) URL
+pub fn validate_registry_url(url: &str) -> Result<(), String> {
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|e| format!("'{url}' is not a URL: {e}"))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!("'{url}' must use http or https, not {scheme}")),
+    }
+}
+
 /// Configuration for dependency-related settings
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DependencyConfig {
@@ -381,11 +742,33 @@ fn default_restrictive_licenses() -> Vec<String> {
     licenses
 }
 
+/// Configuration file set with `--config`, replacing `.feluda.toml` discovery
+static CONFIG_PATH: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Use an explicit configuration file instead of `.feluda.toml` in the working directory
+pub fn set_config_path(path: Option<PathBuf>) {
+    if let Some(path) = &path {
+        log(
+            LogLevel::Info,
+            &format!("Using configuration file: {}", path.display()),
+        );
+    }
+    if let Ok(mut config_path) = CONFIG_PATH.write() {
+        *config_path = path;
+    }
+}
+
+/// Configuration file given with `--config`, if any
+pub fn config_path() -> Option<PathBuf> {
+    CONFIG_PATH.read().ok().and_then(|path| path.clone())
+}
+
 /// Loads the configuration using the following providers (in order of precedence):
 ///
-/// 1. Environment variables prefixed with `FELUDA_`
-/// 2. `.feluda.toml` file in the project root
-/// 3. Default values
+/// 1. The file passed with `--config`, if any
+/// 2. Environment variables prefixed with `FELUDA_`
+/// 3. `.feluda.toml` file in the project root (skipped when `--config` is given)
+/// 4. Default values
 ///
 /// # Environment Variables
 ///
@@ -397,26 +780,54 @@ fn default_restrictive_licenses() -> Vec<String> {
 /// For example:
 /// - `FELUDA_LICENSES_RESTRICTIVE` -> `licenses.restrictive`
 pub fn load_config() -> FeludaResult<FeludaConfig> {
+    load_config_from(config_path().as_deref())
+}
+
+/// Loads the configuration, reading `explicit_path` instead of `.feluda.toml` when given
+///
+/// An explicit file must exist and takes precedence over environment variables.
+pub fn load_config_from(explicit_path: Option<&Path>) -> FeludaResult<FeludaConfig> {
     log(LogLevel::Info, "Loading Feluda configuration");
 
     // Start with default values
     let mut figment = Figment::new().merge(Serialized::defaults(FeludaConfig::default()));
 
-    // Check if .feluda.toml exists and add it if it does
-    let config_path = Path::new(".feluda.toml");
-    if config_path.exists() {
-        log(
-            LogLevel::Info,
-            &format!("Found configuration file: {}", config_path.display()),
-        );
-        figment = figment.merge(Toml::file(config_path));
-    } else {
-        log(LogLevel::Info, "No .feluda.toml file found, using defaults");
-    }
+    match explicit_path {
+        Some(config_path) => {
+            if !config_path.is_file() {
+                return Err(FeludaError::Config(format!(
+                    "Configuration file not found: {}",
+                    config_path.display()
+                )));
+            }
+
+            figment = figment.merge(Env::prefixed("FELUDA_").split("_"));
+            log(LogLevel::Info, "Checking for FELUDA_ environment variables");
 
-    // Add environment variables
-    figment = figment.merge(Env::prefixed("FELUDA_").split("_"));
-    log(LogLevel::Info, "Checking for FELUDA_ environment variables");
+            log(
+                LogLevel::Info,
+                &format!("Loading configuration file: {}", config_path.display()),
+            );
+            figment = figment.merge(Toml::file_exact(config_path));
+        }
+        None => {
+            // Check if .feluda.toml exists and add it if it does
+            let config_path = Path::new(".feluda.toml");
+            if config_path.exists() {
+                log(
+                    LogLevel::Info,
+                    &format!("Found configuration file: {}", config_path.display()),
+                );
+                figment = figment.merge(Toml::file(config_path));
+            } else {
+                log(LogLevel::Info, "No .feluda.toml file found, using defaults");
+            }
+
+            // Add environment variables
+            figment = figment.merge(Env::prefixed("FELUDA_").split("_"));
+            log(LogLevel::Info, "Checking for FELUDA_ environment variables");
+        }
+    }
 
     // Extract the final configuration
     match figment.extract::<FeludaConfig>() {
@@ -709,6 +1120,72 @@ restrictive = ["TOML-LICENSE-1", "TOML-LICENSE-2"]"#,
         );
     }
 
+    #[test]
+    fn test_load_config_from_explicit_file() {
+        temp_env::with_var(
+            "FELUDA_LICENSES_RESTRICTIVE",
+            Some(r#"["ENV-LICENSE"]"#),
+            || {
+                let dir = setup();
+                // The default location must be ignored when a file is given explicitly
+                fs::write(
+                    ".feluda.toml",
+                    "[licenses]\nrestrictive = [\"DEFAULT-LICENSE\"]\n",
+                )
+                .unwrap();
+                let custom = dir.path().join("ci").join("feluda.toml");
+                fs::create_dir_all(custom.parent().unwrap()).unwrap();
+                fs::write(
+                    &custom,
+                    "[licenses]\nrestrictive = [\"CUSTOM-LICENSE\"]\n\n[dependencies]\nmax_depth = 3\n",
+                )
+                .unwrap();
+
+                let config = load_config_from(Some(&custom)).unwrap();
+                // The explicit file also beats environment variables
+                assert_eq!(config.licenses.restrictive, vec!["CUSTOM-LICENSE"]);
+                assert_eq!(config.dependencies.max_depth, 3);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_config_from_explicit_file_keeps_env_for_unset_keys() {
+        temp_env::with_var("FELUDA_LICENSES_IGNORE", Some(r#"["MIT"]"#), || {
+            let dir = setup();
+            let custom = dir.path().join("custom.toml");
+            fs::write(&custom, "strict = true\n").unwrap();
+
+            let config = load_config_from(Some(&custom)).unwrap();
+            assert!(config.strict);
+            assert_eq!(config.licenses.ignore, vec!["MIT"]);
+        });
+    }
+
+    #[test]
+    fn test_load_config_from_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = load_config_from(Some(&dir.path().join("missing.toml")));
+        match result {
+            Err(FeludaError::Config(message)) => assert!(message.contains("missing.toml")),
+            other => panic!("expected a config error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_load_config_from_malformed_file() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            let custom = dir.path().join("broken.toml");
+            fs::write(&custom, "[licenses\nrestrictive = [\"MIT\"\n").unwrap();
+
+            assert!(matches!(
+                load_config_from(Some(&custom)),
+                Err(FeludaError::Config(_))
+            ));
+        });
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = FeludaConfig {
@@ -721,6 +1198,7 @@ restrictive = ["TOML-LICENSE-1", "TOML-LICENSE-2"]"#,
                 max_depth: 5,
                 ignore: Vec::new(),
             },
+            ..Default::default()
         };
 
         // Test that config can be serialized and deserialized
@@ -975,6 +1453,7 @@ restrictive = [
                 max_depth: 10,
                 ignore: Vec::new(),
             },
+            ..Default::default()
         };
         assert!(config.validate().is_ok());
     }
@@ -991,6 +1470,7 @@ restrictive = [
                 max_depth: 10,
                 ignore: Vec::new(),
             },
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -1012,6 +1492,7 @@ restrictive = [
                 max_depth: 0,
                 ignore: Vec::new(),
             }, // Invalid zero depth
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -1442,6 +1923,7 @@ reason = "All versions ignored"
                     reason: "Test".to_string(),
                 }],
             },
+            ..Default::default()
         };
         assert!(config.validate().is_ok());
         assert!(config
@@ -1498,4 +1980,250 @@ reason = "All versions ignored"
         assert!(config.should_ignore_dependency("package2", Some("1.0.0")));
         assert!(!config.should_ignore_dependency("package2", Some("2.0.0")));
     }
+
+    #[test]
+    fn test_toml_config_with_compatibility() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            fs::write(
+                ".feluda.toml",
+                r#"[compatibility]
+"Apache-2.0" = ["MIT", "BSD-3-Clause", "Apache-2.0"]"#,
+            )
+            .unwrap();
+
+            let config = load_config().unwrap();
+            let allowed = config.compatibility.get("Apache-2.0").unwrap();
+            assert_eq!(allowed.len(), 3);
+            assert!(allowed.contains(&"BSD-3-Clause".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_compatibility_validation_empty_license() {
+        let config = FeludaConfig {
+            compatibility: HashMap::from([(
+                "MIT".to_string(),
+                vec!["MIT".to_string(), " ".to_string()],
+            )]),
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("compatibility list for MIT"));
+    }
+
+    #[test]
+    fn test_toml_config_with_restrictive_overrides() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            fs::write(
+                ".feluda.toml",
+                r#"[restrictive]
+additional = ["BUSL-1.1", "SSPL-1.0"]
+allow = ["GPL-3.0"]"#,
+            )
+            .unwrap();
+
+            let config = load_config().unwrap();
+            assert_eq!(config.restrictive.additional.len(), 2);
+            assert!(config.restrictive.is_additional("busl-1.1"));
+            assert!(config.restrictive.is_allowed("GPL 3.0"));
+            assert!(!config.restrictive.is_allowed("MIT"));
+            // The default restrictive list is left untouched
+            assert_eq!(config.licenses.restrictive.len(), 7);
+        });
+    }
+
+    #[test]
+    fn test_restrictive_config_validation_empty_license() {
+        let config = RestrictiveConfig {
+            additional: vec!["".to_string()],
+            allow: Vec::new(),
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_toml_config_with_registries() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            fs::write(
+                ".feluda.toml",
+                r#"[registries]
+npm = "https://npm.example.com/repository/npm/"
+crates = "https://crates.example.com""#,
+            )
+            .unwrap();
+
+            let config = load_config().unwrap();
+            assert_eq!(
+                config.registries.npm.as_deref(),
+                Some("https://npm.example.com/repository/npm/")
+            );
+            assert_eq!(config.registries.pypi, None);
+            assert_eq!(
+                config.registries.crates.as_deref(),
+                Some("https://crates.example.com")
+            );
+        });
+    }
+
+    #[test]
+    fn test_toml_config_with_node_license_sources() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            assert_eq!(load_config().unwrap().node.license_sources, None);
+
+            fs::write(
+                ".feluda.toml",
+                "[node]\nlicense_sources = [\"package_json\", \"registry_api\"]\n",
+            )
+            .unwrap();
+            assert_eq!(
+                load_config().unwrap().node.license_sources,
+                Some(vec![
+                    NodeLicenseSource::PackageJson,
+                    NodeLicenseSource::RegistryApi
+                ])
+            );
+
+            let empty = NodeConfig {
+                license_sources: Some(Vec::new()),
+            };
+            assert!(empty.validate().is_err());
+        });
+    }
+
+    #[test]
+    fn test_toml_config_with_timeout() {
+        temp_env::with_var("FELUDA_TIMEOUT", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            assert_eq!(load_config().unwrap().timeout, None);
+
+            fs::write(".feluda.toml", "timeout = 30\n").unwrap();
+            assert_eq!(load_config().unwrap().timeout, Some(30));
+
+            fs::write(".feluda.toml", "timeout = 0\n").unwrap();
+            assert!(load_config().is_err());
+        });
+    }
+
+    #[test]
+    fn test_toml_config_with_policy() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            assert_eq!(load_config().unwrap().policy, None);
+
+            fs::write(
+                ".feluda.toml",
+                r#"[policy]
+default = "deny"
+allow = ["MIT", "BSD-*"]
+warn = ["unknown"]"#,
+            )
+            .unwrap();
+            let policy = load_config().unwrap().policy.unwrap();
+            assert_eq!(policy.default, crate::policy::PolicyAction::Deny);
+            assert_eq!(policy.allow, vec!["MIT", "BSD-*"]);
+            assert_eq!(policy.warn, vec!["unknown"]);
+            assert!(policy.deny.is_empty());
+
+            fs::write(".feluda.toml", "[policy]\ndefault = \"block\"\n").unwrap();
+            assert!(load_config().is_err());
+
+            fs::write(".feluda.toml", "[policy]\ndeny = [\"\"]\n").unwrap();
+            assert!(load_config().is_err());
+        });
+    }
+
+    #[test]
+    fn test_toml_config_with_overrides() {
+        temp_env::with_var("FELUDA_LICENSES_RESTRICTIVE", None::<&str>, || {
+            let dir = setup();
+            std::env::set_current_dir(dir.path()).unwrap();
+
+            assert!(load_config().unwrap().overrides.is_empty());
+
+            fs::write(
+                ".feluda.toml",
+                r#"[overrides]
+"left-pad" = "MIT"
+"left-pad@1.3.0" = "Apache-2.0"
+"@types/node@20.1.0" = "ISC""#,
+            )
+            .unwrap();
+            let config = load_config().unwrap();
+            assert_eq!(
+                config.license_override("left-pad", "1.3.0"),
+                Some("Apache-2.0")
+            );
+            assert_eq!(config.license_override("left-pad", "1.1.0"), Some("MIT"));
+            assert_eq!(
+                config.license_override("@types/node", "20.1.0"),
+                Some("ISC")
+            );
+            assert_eq!(config.license_override("@types/node", "18.0.0"), None);
+            assert_eq!(config.license_override("right-pad", "1.0.0"), None);
+
+            fs::write(".feluda.toml", "[overrides]\n\"left-pad\" = \"\"\n").unwrap();
+            assert!(load_config().is_err());
+
+            fs::write(".feluda.toml", "[overrides]\n\"left-pad@\" = \"MIT\"\n").unwrap();
+            assert!(load_config().is_err());
+        });
+    }
+
+    #[test]
+    fn test_split_override_key() {
+        assert_eq!(split_override_key("serde"), ("serde", None));
+        assert_eq!(split_override_key("serde@1.0.0"), ("serde", Some("1.0.0")));
+        assert_eq!(split_override_key("@types/node"), ("@types/node", None));
+        assert_eq!(
+            split_override_key("@types/node@20.1.0"),
+            ("@types/node", Some("20.1.0"))
+        );
+    }
+
+    #[test]
+    fn test_dependency_limit_check() {
+        assert_eq!(DependencyLimit::default().check(usize::MAX).unwrap(), None);
+
+        let limit = DependencyLimit::new(Some(10), MaxDepsAction::Error);
+        assert_eq!(limit.check(10).unwrap(), None);
+        assert!(matches!(limit.check(11), Err(FeludaError::Parser(_))));
+
+        let limit = DependencyLimit::new(Some(10), MaxDepsAction::Truncate);
+        assert_eq!(limit.check(10).unwrap(), None);
+        assert_eq!(limit.check(11).unwrap(), Some(10));
+    }
+
+    #[test]
+    fn test_registry_config_validation() {
+        let config = RegistryConfig {
+            pypi: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("pypi"));
+
+        assert!(validate_registry_url("ftp://mirror.example.com").is_err());
+        assert!(validate_registry_url("https://mirror.example.com/npm").is_ok());
+    }
 }
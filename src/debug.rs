@@ -1,8 +1,16 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::cli::LogFormat;
+
 // Static atomic flag for debug mode
 pub static DEBUG_MODE: AtomicBool = AtomicBool::new(false);
 
+// Static atomic flag for quiet mode
+pub static QUIET_MODE: AtomicBool = AtomicBool::new(false);
+
+// Static atomic flag for JSON log lines instead of the human format
+static JSON_LOG_FORMAT: AtomicBool = AtomicBool::new(false);
+
 // Log levels for different types of debug information
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -13,8 +21,6 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
-    // We're keeping this function since it's needed for the Debug implementation
-    #[allow(dead_code)]
     fn as_str(&self) -> &'static str {
         match self {
             LogLevel::Info => "INFO",
@@ -48,33 +54,73 @@ pub fn is_debug_mode() -> bool {
     DEBUG_MODE.load(Ordering::Relaxed)
 }
 
+/// Set the quiet mode flag
+pub fn set_quiet_mode(quiet: bool) {
+    QUIET_MODE.store(quiet, Ordering::Relaxed);
+}
+
+/// Check if quiet mode is enabled
+pub fn is_quiet_mode() -> bool {
+    QUIET_MODE.load(Ordering::Relaxed)
+}
+
+/// Set the format of log lines
+pub fn set_log_format(format: LogFormat) {
+    JSON_LOG_FORMAT.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+/// Get the format of log lines
+pub fn log_format() -> LogFormat {
+    if JSON_LOG_FORMAT.load(Ordering::Relaxed) {
+        LogFormat::Json
+    } else {
+        LogFormat::Human
+    }
+}
+
+/// Whether a message at `level` is printed; quiet mode keeps only errors
+fn should_log(level: LogLevel) -> bool {
+    is_debug_mode() && (!is_quiet_mode() || level == LogLevel::Error)
+}
+
+/// Render a log line in the given format
+fn format_log_line(format: LogFormat, level: LogLevel, message: &str) -> String {
+    match format {
+        LogFormat::Human => format!("[{}] {}", level.as_colored_str(), message),
+        LogFormat::Json => serde_json::json!({
+            "level": level.as_str().to_lowercase(),
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "msg": message,
+        })
+        .to_string(),
+    }
+}
+
 /// Log a message with the specified level if debug mode is enabled
 pub fn log(level: LogLevel, message: &str) {
-    if is_debug_mode() {
-        println!("[{}] {}", level.as_colored_str(), message);
+    if should_log(level) {
+        println!("{}", format_log_line(log_format(), level, message));
     }
 }
 
 /// Log an error with context information if debug mode is enabled
 pub fn log_error<E: std::fmt::Display>(context: &str, error: &E) {
     if is_debug_mode() {
+        let message = format!("{context}: {error}");
         println!(
-            "[{}] {}: {}",
-            LogLevel::Error.as_colored_str(),
-            context,
-            error
+            "{}",
+            format_log_line(log_format(), LogLevel::Error, &message)
         );
     }
 }
 
 /// Log detailed information about a value if debug mode is enabled
 pub fn log_debug<T: std::fmt::Debug + ?Sized>(context: &str, value: &T) {
-    if is_debug_mode() {
+    if should_log(LogLevel::Trace) {
+        let message = format!("{context}: {value:?}");
         println!(
-            "[{}] {}: {:?}",
-            LogLevel::Trace.as_colored_str(),
-            context,
-            value
+            "{}",
+            format_log_line(log_format(), LogLevel::Trace, &message)
         );
     }
 }
@@ -90,11 +136,9 @@ where
         let start = std::time::Instant::now();
         let result = f();
         let duration = start.elapsed();
-        println!(
-            "[{}] {} completed in {:?}",
-            LogLevel::Info.as_colored_str(),
-            context,
-            duration
+        log(
+            LogLevel::Info,
+            &format!("{context} completed in {duration:?}"),
         );
         log_debug(context, &result);
         result
@@ -116,7 +160,6 @@ pub enum FeludaError {
     Config(String),
 
     #[error("License analysis error: {0}")]
-    #[allow(dead_code)]
     License(String),
 
     #[error("Parser error: {0}")]
@@ -125,6 +168,9 @@ pub enum FeludaError {
     #[error("Repository clone error: {0}")]
     RepositoryClone(String),
 
+    #[error("Git error: {0}")]
+    Git(String),
+
     #[error("Temporary directory error: {0}")]
     TempDir(String),
 
@@ -179,6 +225,49 @@ mod tests {
         assert!(!is_debug_mode());
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_quiet_mode_keeps_only_errors() {
+        set_debug_mode(true);
+        set_quiet_mode(true);
+        assert!(is_quiet_mode());
+        assert!(should_log(LogLevel::Error));
+        assert!(!should_log(LogLevel::Warn));
+        assert!(!should_log(LogLevel::Info));
+        assert!(!should_log(LogLevel::Trace));
+
+        set_quiet_mode(false);
+        assert!(should_log(LogLevel::Info));
+        set_debug_mode(false);
+        assert!(!should_log(LogLevel::Error));
+    }
+
+    #[test]
+    fn test_json_log_line() {
+        let line = format_log_line(LogFormat::Json, LogLevel::Warn, "Restrictive \"GPL\" found");
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "warn");
+        assert_eq!(value["msg"], "Restrictive \"GPL\" found");
+        assert!(chrono::DateTime::parse_from_rfc3339(value["ts"].as_str().unwrap()).is_ok());
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn test_human_log_line() {
+        let line = format_log_line(LogFormat::Human, LogLevel::Info, "Parsing");
+        assert!(line.ends_with("] Parsing"));
+        assert!(serde_json::from_str::<serde_json::Value>(&line).is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_log_format_toggle() {
+        set_log_format(LogFormat::Json);
+        assert_eq!(log_format(), LogFormat::Json);
+        set_log_format(LogFormat::Human);
+        assert_eq!(log_format(), LogFormat::Human);
+    }
+
     #[test]
     fn test_log_level_as_str() {
         assert_eq!(LogLevel::Info.as_str(), "INFO");
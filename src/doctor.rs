@@ -0,0 +1,234 @@
+//! Environment checks for the `doctor` subcommand
+//!
+//! Several analyzers shell out to package managers and query registries; when a tool is
+//! missing or the network is blocked, detection quietly degrades. `feluda doctor` probes
+//! each of these so users can see why.
+
+use std::process::Command;
+
+use colored::Colorize;
+use regex::Regex;
+
+use crate::cache;
+use crate::licenses::has_github_token;
+use crate::network::{
+    crates_api_url, http_client, is_offline_mode, npm_registry_url, pypi_json_url,
+    with_network_permit,
+};
+use crate::registry_auth::registry_get;
+
+/// External tools the analyzers run: (command, version argument, what it is used for)
+const TOOLS: &[(&str, &str, &str)] = &[
+    ("npm", "--version", "Node.js dependency resolution"),
+    ("pnpm", "--version", "pnpm workspaces"),
+    ("yarn", "--version", "Yarn projects"),
+    ("go", "version", "Go module graph"),
+    ("python3", "--version", "Python environment inspection"),
+    ("uv", "--version", "uv-managed Python projects"),
+    ("dotnet", "--version", ".NET projects"),
+    ("pkg-config", "--version", "C/C++ system libraries"),
+    ("bazel", "--version", "Bazel dependency graph"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn symbol(self) -> colored::ColoredString {
+        match self {
+            CheckStatus::Pass => "✓".green(),
+            CheckStatus::Warn => "!".yellow(),
+            CheckStatus::Fail => "✗".red(),
+        }
+    }
+}
+
+/// Result of one environment check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Run every check, in the order they are printed
+pub fn run_checks() -> Vec<Check> {
+    let mut checks: Vec<Check> = TOOLS
+        .iter()
+        .map(|(tool, version_arg, used_for)| check_tool(tool, version_arg, used_for))
+        .collect();
+
+    checks.extend(check_registries());
+    checks.push(check_github_token(has_github_token()));
+    checks.push(check_cache_dir());
+    checks
+}
+
+/// Whether `tool` is installed, and its version; missing tools only degrade detection
+fn check_tool(tool: &str, version_arg: &str, used_for: &str) -> Check {
+    match Command::new(tool).arg(version_arg).output() {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version = parse_tool_version(&stdout).unwrap_or_else(|| "unknown".to_string());
+            Check::new(tool, CheckStatus::Pass, format!("version {version}"))
+        }
+        Ok(output) => Check::new(
+            tool,
+            CheckStatus::Warn,
+            format!(
+                "`{tool} {version_arg}` exited with {}; needed for {used_for}",
+                output.status
+            ),
+        ),
+        Err(_) => Check::new(
+            tool,
+            CheckStatus::Warn,
+            format!("not found; needed for {used_for}"),
+        ),
+    }
+}
+
+/// First version number in a tool's `--version` output, e.g. `1.22.1` in
+/// `go version go1.22.1 linux/amd64`
+fn parse_tool_version(output: &str) -> Option<String> {
+    let version_regex = Regex::new(r"(\d+\.\d+(?:\.\d+)*)").ok()?;
+    version_regex.captures(output).map(|cap| cap[1].to_string())
+}
+
+/// Whether the registries used to resolve licenses answer
+fn check_registries() -> Vec<Check> {
+    let probes = [
+        ("npm registry", npm_registry_url("npm", None)),
+        ("PyPI", pypi_json_url("pip", "24.0")),
+        ("crates.io", crates_api_url("serde")),
+        (
+            "GitHub API",
+            "https://api.github.com/rate_limit".to_string(),
+        ),
+    ];
+
+    probes
+        .into_iter()
+        .map(|(name, url)| {
+            if is_offline_mode() {
+                return Check::new(name, CheckStatus::Warn, "skipped in offline mode");
+            }
+
+            match with_network_permit(|| registry_get(&http_client()?, &url).send()) {
+                Ok(response) if response.status().is_success() => {
+                    Check::new(name, CheckStatus::Pass, format!("reachable ({url})"))
+                }
+                Ok(response) => Check::new(
+                    name,
+                    CheckStatus::Fail,
+                    format!("{url} answered {}", response.status()),
+                ),
+                Err(err) => Check::new(name, CheckStatus::Fail, format!("{url}: {err}")),
+            }
+        })
+        .collect()
+}
+
+fn check_github_token(has_token: bool) -> Check {
+    if has_token {
+        Check::new(
+            "GitHub token",
+            CheckStatus::Pass,
+            "set (5000 requests/hour)",
+        )
+    } else {
+        Check::new(
+            "GitHub token",
+            CheckStatus::Warn,
+            "not set, limited to 60 requests/hour; set FELUDA_GITHUB_TOKEN or GITHUB_TOKEN",
+        )
+    }
+}
+
+fn check_cache_dir() -> Check {
+    match cache::check_cache_dir_writable() {
+        Ok(path) => Check::new(
+            "Cache directory",
+            CheckStatus::Pass,
+            format!("writable ({})", path.display()),
+        ),
+        Err(err) => Check::new("Cache directory", CheckStatus::Fail, err.to_string()),
+    }
+}
+
+/// Print the checklist with a pass/warn/fail mark per check
+pub fn print_checks(checks: &[Check]) {
+    println!("\n{}", "🩺 Feluda Doctor".bold());
+    println!("{}", "─".repeat(50));
+
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in checks {
+        println!(
+            "{} {:width$}  {}",
+            check.status.symbol(),
+            check.name,
+            check.detail
+        );
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    println!(
+        "\n{} passed, {} warnings, {} failed\n",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tool_version() {
+        assert_eq!(parse_tool_version("10.2.4\n").as_deref(), Some("10.2.4"));
+        assert_eq!(
+            parse_tool_version("go version go1.22.1 linux/amd64").as_deref(),
+            Some("1.22.1")
+        );
+        assert_eq!(
+            parse_tool_version("Python 3.12.1").as_deref(),
+            Some("3.12.1")
+        );
+        assert_eq!(parse_tool_version("v20.11.0").as_deref(), Some("20.11.0"));
+        assert_eq!(
+            parse_tool_version("Bazel 7.1.0\nBuild label").as_deref(),
+            Some("7.1.0")
+        );
+        assert_eq!(parse_tool_version("no version here"), None);
+    }
+
+    #[test]
+    fn test_missing_tool_warns() {
+        let check = check_tool("feluda-doctor-missing-tool", "--version", "testing");
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.detail.contains("not found"));
+    }
+
+    #[test]
+    fn test_github_token_check() {
+        assert_eq!(check_github_token(true).status, CheckStatus::Pass);
+        let missing = check_github_token(false);
+        assert_eq!(missing.status, CheckStatus::Warn);
+        assert!(missing.detail.contains("FELUDA_GITHUB_TOKEN"));
+    }
+}
@@ -1,17 +1,28 @@
-use crate::cli::with_spinner;
-use crate::debug::{log, log_debug, LogLevel};
+use crate::cli::{with_spinner, GenerateFormat};
+use crate::debug::{log, log_debug, FeludaError, FeludaResult, LogLevel};
 use crate::licenses::{
-    detect_project_license, is_license_compatible, LicenseCompatibility, LicenseInfo,
+    copyright, detect_project_license, github_auth_header, is_license_compatible_with_config,
+    LicenseCompatibility, LicenseInfo,
 };
-use crate::parser::parse_root;
+use crate::network::{
+    acquire_network_permit, crates_api_url, http_client, npm_registry_url, pypi_json_url,
+    skip_network,
+};
+use crate::parser::parse_root_with_config;
+use crate::registry_auth::registry_get;
 use colored::*;
 use reqwest::blocking::Client;
+use serde::Serialize;
 use std::fs;
+#[cfg(not(windows))]
+use std::io::Read;
 use std::io::{self, Write};
-use std::io::{stdin, Read};
+use std::io::{stdin, IsTerminal};
 #[cfg(unix)]
 use std::os::unix::io::AsRawFd;
 use std::path::Path;
+#[cfg(windows)]
+use std::sync::Mutex;
 use std::time::Duration;
 
 /// Key input handling for cross-platform compatibility
@@ -69,21 +80,126 @@ fn disable_raw_mode() -> std::io::Result<()> {
     Ok(())
 }
 
-/// Windows raw mode
+/// Console mode saved by `enable_raw_mode`, restored by `disable_raw_mode` (For Windows)
+#[cfg(windows)]
+static ORIGINAL_CONSOLE_MODE: Mutex<Option<u32>> = Mutex::new(None);
+
+/// Console input handle, or an error when stdin is not a console (For Windows)
+#[cfg(windows)]
+fn console_input_handle() -> std::io::Result<windows_sys::Win32::Foundation::HANDLE> {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{GetStdHandle, STD_INPUT_HANDLE};
+
+    let handle = unsafe { GetStdHandle(STD_INPUT_HANDLE) };
+    if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(handle)
+}
+
+/// Enable raw console mode (For Windows)
 #[cfg(windows)]
 fn enable_raw_mode() -> std::io::Result<()> {
-    // TODO: Use Windows Console API
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, SetConsoleMode, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT,
+    };
+
+    let handle = console_input_handle()?;
+    let mut mode = 0;
+
+    unsafe {
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        if SetConsoleMode(handle, mode & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT)) == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+
+    let mut original = ORIGINAL_CONSOLE_MODE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    original.get_or_insert(mode);
+
     Ok(())
 }
 
-/// Disable raw mode for Windows
+/// Disable raw console mode, restoring the mode saved by `enable_raw_mode` (For Windows)
 #[cfg(windows)]
 fn disable_raw_mode() -> std::io::Result<()> {
-    // TODO: Pending implementation
+    use windows_sys::Win32::System::Console::SetConsoleMode;
+
+    let mut original = ORIGINAL_CONSOLE_MODE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(mode) = original.take() else {
+        return Ok(());
+    };
+
+    let handle = console_input_handle()?;
+    if unsafe { SetConsoleMode(handle, mode) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
     Ok(())
 }
 
+/// Key for a typed character
+fn char_key(c: char) -> KeyInput {
+    match c {
+        'q' | 'Q' => KeyInput::Escape, // q for quit
+        'k' | 'K' => KeyInput::Up,     // k for up (vim)
+        'j' | 'J' => KeyInput::Down,   // j for down (vim)
+
+        // Printable ASCII
+        c if (' '..='~').contains(&c) => KeyInput::Char(c),
+
+        _ => KeyInput::Unknown,
+    }
+}
+
+/// Read a key press from the console's virtual-key events (For Windows)
+#[cfg(windows)]
+fn read_key() -> std::io::Result<KeyInput> {
+    use windows_sys::Win32::System::Console::{ReadConsoleInputW, INPUT_RECORD, KEY_EVENT};
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{VK_DOWN, VK_ESCAPE, VK_RETURN, VK_UP};
+
+    let handle = console_input_handle()?;
+
+    loop {
+        let mut record = INPUT_RECORD::default();
+        let mut read = 0;
+
+        if unsafe { ReadConsoleInputW(handle, &mut record, 1, &mut read) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if read == 0 || u32::from(record.EventType) != KEY_EVENT {
+            continue;
+        }
+
+        // Only key events reach here, so `KeyEvent` is the active union field
+        let key = unsafe { record.Event.KeyEvent };
+        if key.bKeyDown == 0 {
+            continue;
+        }
+
+        return Ok(match key.wVirtualKeyCode {
+            VK_UP => KeyInput::Up,
+            VK_DOWN => KeyInput::Down,
+            VK_RETURN => KeyInput::Enter,
+            VK_ESCAPE => KeyInput::Escape,
+            _ => match unsafe { key.uChar.UnicodeChar } {
+                // Modifier keys such as Shift carry no character
+                0 => continue,
+                unit => char::from_u32(u32::from(unit)).map_or(KeyInput::Unknown, char_key),
+            },
+        });
+    }
+}
+
 /// Read a key press
+#[cfg(not(windows))]
 fn read_key() -> std::io::Result<KeyInput> {
     let mut buffer = [0; 4];
     let mut stdin = stdin();
@@ -96,32 +212,69 @@ fn read_key() -> std::io::Result<KeyInput> {
 
         // Escape key or escape sequence
         27 => {
-            let mut temp_buffer = [0; 2];
-            match stdin.read(&mut temp_buffer) {
-                Ok(2) if temp_buffer[0] == b'[' => {
-                    // ANSI escape sequence
-                    match temp_buffer[1] {
-                        b'A' => Ok(KeyInput::Up),   // Up arrow
-                        b'B' => Ok(KeyInput::Down), // Down arrow
-                        _ => Ok(KeyInput::Escape),
+            let mut sequence = vec![27];
+            let mut byte = [0; 1];
+
+            if stdin.read(&mut byte)? == 1 {
+                let introducer = byte[0];
+                sequence.push(introducer);
+                match introducer {
+                    // CSI: parameters until a final byte, e.g. `ESC [ 1 ; 5 A`
+                    b'[' => {
+                        while sequence.len() < MAX_ESCAPE_SEQUENCE_LEN
+                            && stdin.read(&mut byte)? == 1
+                        {
+                            sequence.push(byte[0]);
+                            if (0x40..=0x7E).contains(&byte[0]) {
+                                break;
+                            }
+                        }
                     }
+                    // SS3: a single final byte, e.g. `ESC O A` in application cursor mode
+                    b'O' if stdin.read(&mut byte)? == 1 => sequence.push(byte[0]),
+                    _ => {}
                 }
-                _ => Ok(KeyInput::Escape),
             }
-        }
 
-        // Regular characters
-        b'q' | b'Q' => Ok(KeyInput::Escape), // q for quit
-        b'k' | b'K' => Ok(KeyInput::Up),     // k for up (vim)
-        b'j' | b'J' => Ok(KeyInput::Down),   // j for down (vim)
+            Ok(parse_escape_sequence(&sequence))
+        }
 
-        // Printable ASCII
-        c if (32..=126).contains(&c) => Ok(KeyInput::Char(c as char)),
+        c => Ok(char_key(char::from(c))),
+    }
+}
 
-        _ => Ok(KeyInput::Unknown),
+/// Longest escape sequence read before giving up on finding its final byte
+#[cfg(not(windows))]
+const MAX_ESCAPE_SEQUENCE_LEN: usize = 16;
+
+/// Key for an escape sequence starting with `ESC`
+///
+/// Only up and down arrows, with or without modifiers, move the selection; other sequences
+/// such as Home/End/PageUp/PageDown are `Unknown` so they are ignored. `ESC` alone or
+/// followed by a plain character is `Escape`.
+#[cfg(not(windows))]
+fn parse_escape_sequence(sequence: &[u8]) -> KeyInput {
+    match sequence {
+        [27, b'[', rest @ ..] => match rest.last() {
+            Some(b'A') if is_csi_parameters(&rest[..rest.len() - 1]) => KeyInput::Up,
+            Some(b'B') if is_csi_parameters(&rest[..rest.len() - 1]) => KeyInput::Down,
+            _ => KeyInput::Unknown,
+        },
+        [27, b'O', b'A'] => KeyInput::Up,
+        [27, b'O', b'B'] => KeyInput::Down,
+        [27, b'O', ..] => KeyInput::Unknown,
+        _ => KeyInput::Escape,
     }
 }
 
+/// Parameter bytes of a CSI sequence, such as `1;5`
+#[cfg(not(windows))]
+fn is_csi_parameters(parameters: &[u8]) -> bool {
+    parameters
+        .iter()
+        .all(|byte| byte.is_ascii_digit() || *byte == b';')
+}
+
 /// Clear screen and move cursor to top
 fn clear_screen() {
     print!("\x1B[2J\x1B[H");
@@ -176,6 +329,11 @@ impl GenerateOption {
     pub fn full_filename(&self) -> String {
         format!("{}{}", self.filename(), self.extension())
     }
+
+    /// Filename used when writing JSON output
+    pub fn json_filename(&self) -> String {
+        format!("{}.json", self.filename())
+    }
 }
 
 /// Check if a file exists for the given option
@@ -390,8 +548,8 @@ pub fn show_interactive_menu(path: &str) -> Option<GenerateOption> {
     }
 }
 
-/// Generate or update a NOTICE file
-pub fn generate_notice_file(license_data: &[LicenseInfo], path: &str) {
+/// Generate or update a NOTICE file, from `template` when one is given
+pub fn generate_notice_file(license_data: &[LicenseInfo], path: &str, template: Option<&str>) {
     let file_path = Path::new(path).join(GenerateOption::Notice.full_filename());
     let exists = file_exists(GenerateOption::Notice, path);
 
@@ -416,8 +574,26 @@ pub fn generate_notice_file(license_data: &[LicenseInfo], path: &str) {
         file_path.display().to_string().blue()
     );
 
+    let license_data = &resolve_copyrights(license_data);
+
     // Generate NOTICE content
-    let notice_content = generate_notice_content(license_data);
+    let notice_content = match template {
+        Some(template) => match render_notice_template(template, license_data) {
+            Ok(content) => content,
+            Err(err) => {
+                println!(
+                    "{} Failed to render NOTICE template: {err}",
+                    "❌".red().bold()
+                );
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to render NOTICE template: {err}"),
+                );
+                return;
+            }
+        },
+        None => generate_notice_content(license_data),
+    };
 
     // Write to file
     match fs::write(&file_path, notice_content) {
@@ -438,6 +614,36 @@ pub fn generate_notice_file(license_data: &[LicenseInfo], path: &str) {
     }
 }
 
+/// Fill in the copyright statements the analyzers did not find from the published license
+/// texts; dependencies without one are listed without attribution
+fn resolve_copyrights(license_data: &[LicenseInfo]) -> Vec<LicenseInfo> {
+    let missing = license_data
+        .iter()
+        .filter(|info| info.copyright.is_none())
+        .count();
+    if missing == 0 || skip_network("copyright statements") {
+        return license_data.to_vec();
+    }
+
+    with_spinner(
+        &format!("Looking up copyright holders for {missing} dependencies"),
+        |indicator| {
+            license_data
+                .iter()
+                .map(|info| {
+                    let mut info = info.clone();
+                    if info.copyright.is_none() {
+                        indicator.update_progress(&info.name);
+                        info.copyright = fetch_actual_license_content(&info.name, &info.version)
+                            .and_then(|text| copyright::extract_copyright(&text));
+                    }
+                    info
+                })
+                .collect()
+        },
+    )
+}
+
 /// Generate the content for a NOTICE file
 fn generate_notice_content(license_data: &[LicenseInfo]) -> String {
     let mut content = String::new();
@@ -448,31 +654,7 @@ fn generate_notice_content(license_data: &[LicenseInfo]) -> String {
     content.push_str("This project includes third-party software components that are subject to separate copyright notices and license terms.\n");
     content.push_str("Your use of the source code for these components is subject to the terms and conditions of the following licenses.\n\n");
 
-    // Group dependencies by license
-    let mut license_groups: std::collections::HashMap<String, Vec<&LicenseInfo>> =
-        std::collections::HashMap::new();
-
-    for info in license_data {
-        let license_key = info.get_license();
-        license_groups.entry(license_key).or_default().push(info);
-    }
-
-    // Sort license groups
-    let mut sorted_licenses: Vec<_> = license_groups.iter().collect();
-    sorted_licenses.sort_by_key(|(license, _)| license.as_str());
-
-    for (license, dependencies) in sorted_licenses {
-        content.push_str(&format!("## {license} Licensed Components\n\n"));
-
-        // Sort dependencies within each license group
-        let mut sorted_deps = dependencies.clone();
-        sorted_deps.sort_by_key(|dep| &dep.name);
-
-        for dep in sorted_deps {
-            content.push_str(&format!("* {} ({})\n", dep.name, dep.version));
-        }
-        content.push('\n');
-    }
+    content.push_str(&notice_license_sections(license_data));
 
     // Footer
     content.push_str("---\n\n");
@@ -503,6 +685,107 @@ fn generate_notice_content(license_data: &[LicenseInfo]) -> String {
     content
 }
 
+/// One `## <license> Licensed Components` section per license, listing its dependencies
+fn notice_license_sections(license_data: &[LicenseInfo]) -> String {
+    let mut content = String::new();
+
+    // Group dependencies by license
+    let mut license_groups: std::collections::HashMap<String, Vec<&LicenseInfo>> =
+        std::collections::HashMap::new();
+
+    for info in license_data {
+        let license_key = info.get_license();
+        license_groups.entry(license_key).or_default().push(info);
+    }
+
+    // Sort license groups
+    let mut sorted_licenses: Vec<_> = license_groups.iter().collect();
+    sorted_licenses.sort_by_key(|(license, _)| license.as_str());
+
+    for (license, dependencies) in sorted_licenses {
+        content.push_str(&format!("## {license} Licensed Components\n\n"));
+
+        // Sort dependencies within each license group
+        let mut sorted_deps = dependencies.clone();
+        sorted_deps.sort_by_key(|dep| &dep.name);
+
+        for dep in sorted_deps {
+            content.push_str(&format!("* {} ({})\n", dep.name, dep.version));
+            if let Some(copyright) = &dep.copyright {
+                content.push_str(&format!("  {copyright}\n"));
+            }
+        }
+        content.push('\n');
+    }
+
+    content
+}
+
+/// Placeholders a NOTICE template may use, written as `{{name}}`
+const NOTICE_PLACEHOLDERS: &[&str] = &[
+    "license_sections",
+    "dependency_count",
+    "license_count",
+    "date",
+];
+
+/// Replace every `{{name}}` in `template` with `value(name)`
+///
+/// A name `value` does not know is an error, so a typo does not end up in a legal document.
+/// An unterminated `{{` is kept as text.
+fn fill_placeholders(
+    template: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> FeludaResult<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        let replacement = value(name).ok_or_else(|| {
+            FeludaError::Config(format!(
+                "Unknown placeholder {{{{{name}}}}} in NOTICE template (available: {})",
+                NOTICE_PLACEHOLDERS.join(", ")
+            ))
+        })?;
+        output.push_str(&rest[..start]);
+        output.push_str(&replacement);
+        rest = &rest[start + len + 4..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Read a `--notice-template` file, rejecting unknown placeholders before any analysis
+fn load_notice_template(path: &str) -> FeludaResult<String> {
+    let template = fs::read_to_string(path)
+        .map_err(|e| FeludaError::Config(format!("Failed to read NOTICE template {path}: {e}")))?;
+    fill_placeholders(&template, |name| {
+        NOTICE_PLACEHOLDERS.contains(&name).then(String::new)
+    })?;
+    Ok(template)
+}
+
+/// Fill a NOTICE template with the dependencies
+fn render_notice_template(template: &str, license_data: &[LicenseInfo]) -> FeludaResult<String> {
+    fill_placeholders(template, |name| match name {
+        "license_sections" => Some(notice_license_sections(license_data)),
+        "dependency_count" => Some(license_data.len().to_string()),
+        "license_count" => Some(
+            license_data
+                .iter()
+                .map(LicenseInfo::get_license)
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+                .to_string(),
+        ),
+        "date" => Some(chrono::Utc::now().format("%Y-%m-%d").to_string()),
+        _ => None,
+    })
+}
+
 /// Generate or update a THIRD_PARTY_LICENSES file
 pub fn generate_third_party_licenses_file(license_data: &[LicenseInfo], path: &str) {
     let file_path = Path::new(path).join(GenerateOption::ThirdPartyLicenses.full_filename());
@@ -585,18 +868,204 @@ pub fn generate_third_party_licenses_file(license_data: &[LicenseInfo], path: &s
     }
 }
 
-/// HTTP client for API requests
+/// One dependency in `THIRD_PARTY_LICENSES.json`
+#[derive(Debug, Serialize)]
+struct ThirdPartyLicenseEntry {
+    name: String,
+    version: String,
+    license: Option<String>,
+    compatibility: String,
+    restrictive: bool,
+    package_url: Option<String>,
+    license_text: Option<String>,
+}
+
+/// Generate or update a THIRD_PARTY_LICENSES.json file
+pub fn generate_third_party_licenses_json_file(license_data: &[LicenseInfo], path: &str) {
+    let file_path = Path::new(path).join(GenerateOption::ThirdPartyLicenses.json_filename());
+    let action = if file_path.exists() {
+        "Updating"
+    } else {
+        "Generating"
+    };
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "{} THIRD_PARTY_LICENSES.json at {} with {} dependencies",
+            action,
+            file_path.display(),
+            license_data.len()
+        ),
+    );
+
+    println!(
+        "{} {} THIRD_PARTY_LICENSES.json at {}...",
+        "📜".bold(),
+        action.green().bold(),
+        file_path.display().to_string().blue()
+    );
+
+    let (json_content, (successfully_fetched, _)) = with_spinner(
+        &format!(
+            "Fetching license content for {} dependencies",
+            license_data.len()
+        ),
+        |indicator| generate_third_party_licenses_json(license_data, indicator),
+    );
+
+    match fs::write(&file_path, json_content) {
+        Ok(_) => {
+            println!(
+                "{} THIRD_PARTY_LICENSES.json generated successfully!",
+                "✅".green().bold()
+            );
+            println!("   📍 Location: {}", file_path.display().to_string().blue());
+            println!(
+                "   📄 License texts fetched: {}/{}",
+                successfully_fetched.to_string().green(),
+                license_data.len()
+            );
+        }
+        Err(err) => {
+            println!(
+                "{} Failed to write THIRD_PARTY_LICENSES.json: {}",
+                "❌".red().bold(),
+                err
+            );
+            log(
+                LogLevel::Error,
+                &format!("Failed to write THIRD_PARTY_LICENSES.json: {err}"),
+            );
+        }
+    }
+}
+
+/// Build the JSON array for THIRD_PARTY_LICENSES.json, sorted by dependency name
+fn generate_third_party_licenses_json(
+    license_data: &[LicenseInfo],
+    indicator: &crate::cli::LoadingIndicator,
+) -> (String, (usize, usize)) {
+    let mut sorted_deps: Vec<_> = license_data.iter().collect();
+    sorted_deps.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut successfully_fetched = 0;
+    let mut failed_to_fetch = 0;
+
+    let entries: Vec<ThirdPartyLicenseEntry> = sorted_deps
+        .iter()
+        .enumerate()
+        .map(|(index, dep)| {
+            indicator.update_progress(&format!("processing {}/{}", index + 1, sorted_deps.len()));
+
+            let license_text = fetch_actual_license_content(&dep.name, &dep.version);
+            if license_text.is_some() {
+                successfully_fetched += 1;
+            } else {
+                failed_to_fetch += 1;
+            }
+
+            ThirdPartyLicenseEntry {
+                name: dep.name.clone(),
+                version: dep.version.clone(),
+                license: dep.license.clone(),
+                compatibility: dep.compatibility.to_string(),
+                restrictive: dep.is_restrictive,
+                package_url: generate_package_url(&dep.name, &dep.version),
+                license_text,
+            }
+        })
+        .collect();
+
+    let content = match serde_json::to_string_pretty(&entries) {
+        Ok(json) => format!("{json}\n"),
+        Err(err) => {
+            log(
+                LogLevel::Error,
+                &format!("Failed to serialize THIRD_PARTY_LICENSES.json: {err}"),
+            );
+            String::from("[]\n")
+        }
+    };
+
+    (content, (successfully_fetched, failed_to_fetch))
+}
+
+/// HTTP client for API requests, or `None` in offline mode
 fn create_http_client() -> Option<Client> {
-    Client::builder()
-        .user_agent("feluda-license-checker/1.0")
-        .timeout(Duration::from_secs(10))
-        .build()
-        .ok()
+    if skip_network("license content") {
+        return None;
+    }
+
+    http_client().ok()
 }
 
-/// Rate limit delay to avoid hitting API limits
-fn rate_limit_delay() {
-    std::thread::sleep(Duration::from_millis(500));
+/// Add the GitHub `Authorization` header to a request, if a token is configured
+///
+/// Only used for `api.github.com` so the token is never sent to other registries.
+fn with_github_auth(
+    request: reqwest::blocking::RequestBuilder,
+    auth: Option<&reqwest::header::HeaderValue>,
+) -> reqwest::blocking::RequestBuilder {
+    match auth {
+        Some(value) => request.header(reqwest::header::AUTHORIZATION, value.clone()),
+        None => request,
+    }
+}
+
+/// Backoff before each retry of a rate-limited or failing request
+const RETRY_BACKOFF_MS: [u64; 3] = [500, 1000, 2000];
+
+/// Upper bound for a server-provided `Retry-After`, so a bad value cannot stall generation
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+/// Send a GET request, retrying up to three times on 429 and 5xx responses
+///
+/// Waits 500ms, 1s and 2s between attempts unless the response carries a `Retry-After`
+/// header in seconds. The last response is returned even when it is still an error.
+/// Each attempt takes a network permit, so waiting out a backoff does not hold a slot.
+fn fetch_with_retry(
+    request: reqwest::blocking::RequestBuilder,
+) -> reqwest::Result<reqwest::blocking::Response> {
+    let mut attempt = 0;
+    loop {
+        let Some(retry_request) = request.try_clone() else {
+            let _permit = acquire_network_permit();
+            return request.send();
+        };
+        let response = {
+            let _permit = acquire_network_permit();
+            retry_request.send()?
+        };
+        let status = response.status();
+
+        let transient = status.as_u16() == 429 || status.is_server_error();
+        if !transient || attempt >= RETRY_BACKOFF_MS.len() {
+            return Ok(response);
+        }
+
+        let delay = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|secs| Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+            .unwrap_or_else(|| Duration::from_millis(RETRY_BACKOFF_MS[attempt]));
+
+        log(
+            LogLevel::Warn,
+            &format!(
+                "HTTP {status} from {}, retrying in {}ms (attempt {}/{})",
+                response.url(),
+                delay.as_millis(),
+                attempt + 1,
+                RETRY_BACKOFF_MS.len()
+            ),
+        );
+
+        std::thread::sleep(delay);
+        attempt += 1;
+    }
 }
 
 /// Fetch the actual license content for a dependency
@@ -606,6 +1075,10 @@ fn fetch_actual_license_content(name: &str, version: &str) -> Option<String> {
         &format!("Attempting to fetch actual license content for {name} v{version}"),
     );
 
+    if skip_network(&format!("license content of {name}")) {
+        return None;
+    }
+
     // Fetch from crates.io for Rust packages
     if let Some(content) = fetch_license_from_crates_io(name, version) {
         return Some(content);
@@ -646,10 +1119,9 @@ fn fetch_license_from_crates_io(name: &str, version: &str) -> Option<String> {
     );
 
     let client = create_http_client()?;
-    rate_limit_delay();
 
-    let api_url = format!("https://crates.io/api/v1/crates/{name}");
-    let response = client.get(&api_url).send().ok()?;
+    let api_url = crates_api_url(name);
+    let response = fetch_with_retry(registry_get(&client, &api_url)).ok()?;
 
     if !response.status().is_success() {
         log(
@@ -686,10 +1158,9 @@ fn fetch_license_from_npm(name: &str, version: &str) -> Option<String> {
     );
 
     let client = create_http_client()?;
-    rate_limit_delay();
 
-    let api_url = format!("https://registry.npmjs.org/{name}/{version}");
-    let response = client.get(&api_url).send().ok()?;
+    let api_url = npm_registry_url(name, Some(version));
+    let response = fetch_with_retry(registry_get(&client, &api_url)).ok()?;
 
     if !response.status().is_success() {
         log(
@@ -733,10 +1204,9 @@ fn fetch_license_from_pypi(name: &str, version: &str) -> Option<String> {
     );
 
     let client = create_http_client()?;
-    rate_limit_delay();
 
-    let api_url = format!("https://pypi.org/pypi/{name}/{version}/json");
-    let response = client.get(&api_url).send().ok()?;
+    let api_url = pypi_json_url(name, version);
+    let response = fetch_with_retry(registry_get(&client, &api_url)).ok()?;
 
     if !response.status().is_success() {
         log(
@@ -834,7 +1304,7 @@ fn fetch_license_from_github_repo(repo_url: &str) -> Option<String> {
     let repo = parts[parts.len() - 1];
 
     let client = create_http_client()?;
-    rate_limit_delay();
+    let github_auth = github_auth_header();
 
     // Common license file names
     let license_files = [
@@ -856,7 +1326,7 @@ fn fetch_license_from_github_repo(repo_url: &str) -> Option<String> {
 
         log(LogLevel::Info, &format!("Trying to fetch: {api_url}"));
 
-        match client.get(&api_url).send() {
+        match fetch_with_retry(with_github_auth(client.get(&api_url), github_auth.as_ref())) {
             Ok(response) => {
                 if response.status().is_success() {
                     if let Ok(content_info) = response.json::<serde_json::Value>() {
@@ -868,9 +1338,7 @@ fn fetch_license_from_github_repo(repo_url: &str) -> Option<String> {
                                 &format!("Found license file, downloading from: {download_url}"),
                             );
 
-                            rate_limit_delay();
-
-                            match client.get(download_url).send() {
+                            match fetch_with_retry(client.get(download_url)) {
                                 Ok(license_response) => {
                                     if license_response.status().is_success() {
                                         if let Ok(license_content) = license_response.text() {
@@ -977,17 +1445,25 @@ fn generate_third_party_licenses_content(
             content.push_str(&format!("**Package URL:** {url}\n"));
         }
 
-        // Copyright notice placeholder
-        content.push_str(&format!(
-            "**Copyright:** See {} package for copyright information\n",
-            dep.name
-        ));
+        // Try to fetch the actual license content
+        let license_text = fetch_actual_license_content(&dep.name, &dep.version);
+
+        match dep.copyright.clone().or_else(|| {
+            license_text
+                .as_deref()
+                .and_then(copyright::extract_copyright)
+        }) {
+            Some(copyright) => content.push_str(&format!("**Copyright:** {copyright}\n")),
+            None => content.push_str(&format!(
+                "**Copyright:** See {} package for copyright information\n",
+                dep.name
+            )),
+        }
 
         // License text
         content.push_str("\n### License Text\n\n");
 
-        // Try to fetch the actual license content
-        match fetch_actual_license_content(&dep.name, &dep.version) {
+        match license_text {
             Some(actual_license_content) => {
                 successfully_fetched += 1;
                 log(
@@ -1277,14 +1753,42 @@ pub fn handle_generate_command(
     path: String,
     language: Option<String>,
     project_license: Option<String>,
+    format: GenerateFormat,
+    targets: &[GenerateOption],
+    notice_template: Option<String>,
 ) {
     log(
         LogLevel::Info,
         &format!(
-            "Starting generate command with path: {path} language: {language:?} project_license: {project_license:?}"
+            "Starting generate command with path: {path} language: {language:?} project_license: {project_license:?} format: {format:?} targets: {targets:?} notice_template: {notice_template:?}"
         ),
     );
 
+    let notice_template = match notice_template.as_deref().map(load_notice_template) {
+        Some(Ok(template)) => Some(template),
+        Some(Err(err)) => {
+            println!("{} {}", "❌".red().bold(), err.to_string().red());
+            log(LogLevel::Error, &err.to_string());
+            return;
+        }
+        None => None,
+    };
+
+    // The menu reads raw keys from the terminal, so it would block forever in CI
+    if targets.is_empty() && !stdin().is_terminal() {
+        println!(
+            "{} {}",
+            "❌".red().bold(),
+            "No terminal available for the interactive menu. Pass --notice and/or --third-party-licenses to generate files non-interactively."
+                .red()
+        );
+        log(
+            LogLevel::Error,
+            "Generate command needs --notice or --third-party-licenses when stdin is not a TTY",
+        );
+        return;
+    }
+
     // Parse project dependencies first
     log(
         LogLevel::Info,
@@ -1328,8 +1832,21 @@ pub fn handle_generate_command(
         }
     }
 
+    let config = match crate::config::load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{} Failed to load configuration: {}", "❌".red().bold(), e);
+            log(
+                LogLevel::Error,
+                &format!("Failed to load configuration: {e}"),
+            );
+            return;
+        }
+    };
+
     // Parse and analyze dependencies
-    let mut analyzed_data = match parse_root(&path, language.as_deref(), false, false) {
+    let mut analyzed_data = match parse_root_with_config(&path, language.as_deref(), &config, false)
+    {
         Ok(data) => data,
         Err(e) => {
             println!("{} Failed to parse dependencies: {}", "❌".red().bold(), e);
@@ -1352,7 +1869,8 @@ pub fn handle_generate_command(
 
         for info in &mut analyzed_data {
             if let Some(ref dep_license) = info.license {
-                info.compatibility = is_license_compatible(dep_license, proj_license, false);
+                info.compatibility =
+                    is_license_compatible_with_config(dep_license, proj_license, false, &config);
             } else {
                 info.compatibility = LicenseCompatibility::Unknown;
             }
@@ -1385,16 +1903,50 @@ pub fn handle_generate_command(
         format!("Found {} dependencies to process.", analyzed_data.len()).dimmed()
     );
 
-    match show_interactive_menu(&path) {
-        Some(GenerateOption::Notice) => {
-            generate_notice_file(&analyzed_data, &path);
-        }
-        Some(GenerateOption::ThirdPartyLicenses) => {
-            generate_third_party_licenses_file(&analyzed_data, &path);
-        }
-        None => {
-            log(LogLevel::Info, "User cancelled generate operation");
+    let selected: Vec<GenerateOption> = if targets.is_empty() {
+        show_interactive_menu(&path).into_iter().collect()
+    } else {
+        targets.to_vec()
+    };
+
+    if selected.is_empty() {
+        log(LogLevel::Info, "User cancelled generate operation");
+    }
+
+    for option in selected {
+        generate_file(
+            option,
+            format,
+            &analyzed_data,
+            &path,
+            notice_template.as_deref(),
+        );
+    }
+}
+
+/// Write the requested file in the requested format
+fn generate_file(
+    option: GenerateOption,
+    format: GenerateFormat,
+    analyzed_data: &[LicenseInfo],
+    path: &str,
+    notice_template: Option<&str>,
+) {
+    match option {
+        GenerateOption::Notice => {
+            if format == GenerateFormat::Json {
+                println!(
+                    "{}",
+                    "JSON output is only available for THIRD_PARTY_LICENSES; writing a plain NOTICE file."
+                        .yellow()
+                );
+            }
+            generate_notice_file(analyzed_data, path, notice_template);
         }
+        GenerateOption::ThirdPartyLicenses => match format {
+            GenerateFormat::Markdown => generate_third_party_licenses_file(analyzed_data, path),
+            GenerateFormat::Json => generate_third_party_licenses_json_file(analyzed_data, path),
+        },
     }
 }
 
@@ -1402,25 +1954,20 @@ pub fn handle_generate_command(
 mod tests {
     use super::*;
     use crate::licenses::LicenseCompatibility;
+    use serial_test::serial;
     use tempfile::TempDir;
 
     fn get_test_license_data() -> Vec<LicenseInfo> {
         vec![
             LicenseInfo {
-                name: "serde".to_string(),
-                version: "1.0.151".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("serde", "1.0.151", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "tokio".to_string(),
-                version: "1.0.2".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("tokio", "1.0.2", Some("MIT".to_string()))
             },
         ]
     }
@@ -1443,6 +1990,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_option_json_filename() {
+        assert_eq!(
+            GenerateOption::ThirdPartyLicenses.json_filename(),
+            "THIRD_PARTY_LICENSES.json"
+        );
+    }
+
     #[test]
     fn test_generate_option_full_filename() {
         assert_eq!(GenerateOption::Notice.full_filename(), "NOTICE");
@@ -1478,11 +2033,32 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_generate_notice_file() {
         let temp_dir = TempDir::new().unwrap();
         let path = temp_dir.path().to_str().unwrap();
         let license_data = get_test_license_data();
-        generate_notice_file(&license_data, path);
+        crate::network::set_offline_mode(true);
+        generate_notice_file(&license_data, path, None);
+        crate::network::set_offline_mode(false);
+    }
+
+    #[test]
+    #[serial]
+    fn test_generate_notice_file_with_copyright() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+        let mut license_data = get_test_license_data();
+        license_data[0].copyright = Some("Copyright (c) 2014 Erick Tryzelaar".to_string());
+
+        crate::network::set_offline_mode(true);
+        generate_notice_file(&license_data, path, None);
+        crate::network::set_offline_mode(false);
+
+        let notice = fs::read_to_string(temp_dir.path().join("NOTICE")).unwrap();
+        assert!(notice.contains("* serde (1.0.151)\n  Copyright (c) 2014 Erick Tryzelaar\n"));
+        // Without a known statement the dependency is listed on its own
+        assert!(notice.contains("* tokio (1.0.2)\n\n"));
     }
 
     #[test]
@@ -1493,11 +2069,212 @@ mod tests {
         generate_third_party_licenses_file(&license_data, path);
     }
 
+    #[test]
+    #[serial]
+    fn test_generate_third_party_licenses_json_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+        let mut license_data = get_test_license_data();
+        license_data[1].license = Some("GPL-3.0".to_string());
+        license_data[1].is_restrictive = true;
+        license_data[1].compatibility = LicenseCompatibility::Incompatible;
+
+        crate::network::set_offline_mode(true);
+        generate_third_party_licenses_json_file(&license_data, path);
+        crate::network::set_offline_mode(false);
+
+        let content = fs::read_to_string(temp_dir.path().join("THIRD_PARTY_LICENSES.json"))
+            .expect("JSON file should be written");
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0]["name"], "serde");
+        assert_eq!(entries[0]["version"], "1.0.151");
+        assert_eq!(entries[0]["license"], "MIT");
+        assert_eq!(entries[0]["compatibility"], "compatible");
+        assert_eq!(entries[0]["restrictive"], false);
+        assert_eq!(entries[0]["package_url"], "https://crates.io/crates/serde");
+        // License texts cannot be fetched offline
+        assert!(entries[0]["license_text"].is_null());
+
+        assert_eq!(entries[1]["name"], "tokio");
+        assert_eq!(entries[1]["license"], "GPL-3.0");
+        assert_eq!(entries[1]["compatibility"], "incompatible");
+        assert_eq!(entries[1]["restrictive"], true);
+    }
+
+    #[test]
+    #[serial]
+    fn test_handle_generate_command_non_interactive_notice() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+        fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: testpkg\nVersion: 1.0.0\nImports: dplyr\n",
+        )
+        .unwrap();
+
+        crate::network::set_offline_mode(true);
+        handle_generate_command(
+            path.to_string(),
+            None,
+            Some("MIT".to_string()),
+            GenerateFormat::Markdown,
+            &[GenerateOption::Notice],
+            None,
+        );
+        crate::network::set_offline_mode(false);
+
+        let notice = fs::read_to_string(temp_dir.path().join("NOTICE"))
+            .expect("NOTICE should be written without the interactive menu");
+        assert!(notice.contains("dplyr"));
+        assert!(!temp_dir.path().join("THIRD_PARTY_LICENSES.md").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_github_requests_carry_token_from_env() {
+        temp_env::with_vars(
+            [
+                ("FELUDA_GITHUB_TOKEN", Some("feluda-test-token")),
+                ("GITHUB_TOKEN", Some("fallback-token")),
+            ],
+            || {
+                crate::network::set_offline_mode(false);
+                let client = create_http_client().expect("client should build online");
+                let auth = github_auth_header();
+                let request = with_github_auth(
+                    client.get("https://api.github.com/repos/o/r/contents/LICENSE"),
+                    auth.as_ref(),
+                )
+                .build()
+                .unwrap();
+
+                let header = request
+                    .headers()
+                    .get(reqwest::header::AUTHORIZATION)
+                    .expect("Authorization header should be attached");
+                assert_eq!(header.to_str().unwrap(), "Bearer feluda-test-token");
+                assert!(header.is_sensitive());
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("FELUDA_GITHUB_TOKEN", None::<&str>),
+                ("GITHUB_TOKEN", Some("fallback-token")),
+            ],
+            || {
+                let auth = github_auth_header().expect("GITHUB_TOKEN should be used as fallback");
+                assert_eq!(auth.to_str().unwrap(), "Bearer fallback-token");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("FELUDA_GITHUB_TOKEN", None::<&str>),
+                ("GITHUB_TOKEN", None::<&str>),
+            ],
+            || {
+                let client = create_http_client().unwrap();
+                let request = with_github_auth(client.get("https://api.github.com"), None)
+                    .build()
+                    .unwrap();
+                assert!(github_auth_header().is_none());
+                assert!(request
+                    .headers()
+                    .get(reqwest::header::AUTHORIZATION)
+                    .is_none());
+            },
+        );
+    }
+
+    /// Serve the given raw HTTP responses, one per connection, and count the requests
+    fn spawn_stub_server(
+        responses: Vec<&'static str>,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::{BufRead, BufReader};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/license", listener.local_addr().unwrap());
+        let hits = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok() && line != "\r\n" && !line.is_empty() {
+                    line.clear();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (url, hits)
+    }
+
+    const TOO_MANY_REQUESTS: &str = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    #[test]
+    fn test_fetch_with_retry_recovers_after_rate_limit() {
+        let (url, hits) = spawn_stub_server(vec![
+            TOO_MANY_REQUESTS,
+            TOO_MANY_REQUESTS,
+            "HTTP/1.1 200 OK\r\nContent-Length: 11\r\nConnection: close\r\n\r\nMIT License",
+        ]);
+        let client = Client::builder().no_proxy().build().unwrap();
+
+        let response = fetch_with_retry(client.get(&url)).unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(response.text().unwrap(), "MIT License");
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_fetch_with_retry_does_not_retry_client_errors() {
+        let (url, hits) = spawn_stub_server(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = Client::builder().no_proxy().build().unwrap();
+
+        let response = fetch_with_retry(client.get(&url)).unwrap();
+
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_fetch_with_retry_gives_up_after_three_retries() {
+        let (url, hits) = spawn_stub_server(vec![TOO_MANY_REQUESTS; 5]);
+        let client = Client::builder().no_proxy().build().unwrap();
+
+        let response = fetch_with_retry(client.get(&url)).unwrap();
+
+        assert_eq!(response.status().as_u16(), 429);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
     #[test]
     fn test_handle_generate_command_empty_data() {
         let temp_dir = TempDir::new().unwrap();
         let path = temp_dir.path().to_str().unwrap();
-        handle_generate_command(path.to_string(), None, None);
+        handle_generate_command(
+            path.to_string(),
+            None,
+            None,
+            GenerateFormat::Markdown,
+            &[],
+            None,
+        );
     }
 
     #[test]
@@ -1652,28 +2429,19 @@ mod tests {
     fn test_generate_notice_content() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "2.0.0", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "package3".to_string(),
-                version: "1.5.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package3", "1.5.0", Some("MIT".to_string()))
             },
         ];
 
@@ -1714,12 +2482,8 @@ mod tests {
     #[test]
     fn test_generate_notice_content_no_license() {
         let test_data = vec![LicenseInfo {
-            name: "unknown_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: None,
             is_restrictive: true,
-            compatibility: LicenseCompatibility::Unknown,
-            osi_status: crate::licenses::OsiStatus::Unknown,
+            ..LicenseInfo::new("unknown_package", "1.0.0", None)
         }];
 
         let content = generate_notice_content(&test_data);
@@ -1728,6 +2492,84 @@ mod tests {
     }
 
     #[test]
+    fn test_render_notice_template() {
+        let template = "ACME Corp third-party notices\nPackages: {{dependency_count}}, \
+                        licenses: {{ license_count }}\n\n{{license_sections}}Reviewed {{date}}.\n";
+        let content = render_notice_template(template, &get_test_license_data()).unwrap();
+
+        assert!(content.starts_with("ACME Corp third-party notices\nPackages: 2, licenses: 1\n\n"));
+        assert!(content.contains("## MIT Licensed Components\n\n* serde (1.0.151)\n"));
+        assert!(content.contains(&format!(
+            "Reviewed {}.",
+            chrono::Utc::now().format("%Y-%m-%d")
+        )));
+        assert!(!content.contains("{{"));
+        assert!(!content.contains("DISCLAIMER"));
+    }
+
+    #[test]
+    fn test_notice_template_rejects_unknown_placeholders() {
+        let err = render_notice_template("{{project_name}}", &[]).unwrap_err();
+        assert!(err.to_string().contains("{{project_name}}"));
+        assert!(err.to_string().contains("license_sections"));
+
+        // An unterminated placeholder is left as text
+        assert_eq!(
+            render_notice_template("{{dependency_count}} {{date", &[]).unwrap(),
+            "0 {{date"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_handle_generate_command_with_notice_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+        fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: testpkg\nVersion: 1.0.0\nImports: dplyr\n",
+        )
+        .unwrap();
+        let template_path = temp_dir.path().join("notice.tmpl");
+        fs::write(&template_path, "Legal header\n{{license_sections}}").unwrap();
+
+        crate::network::set_offline_mode(true);
+        handle_generate_command(
+            path.to_string(),
+            None,
+            Some("MIT".to_string()),
+            GenerateFormat::Markdown,
+            &[GenerateOption::Notice],
+            Some(template_path.to_string_lossy().to_string()),
+        );
+
+        // A template with an unknown placeholder is rejected before anything is written
+        let bad_dir = TempDir::new().unwrap();
+        fs::write(
+            bad_dir.path().join("DESCRIPTION"),
+            "Package: testpkg\nVersion: 1.0.0\nImports: dplyr\n",
+        )
+        .unwrap();
+        let bad_template = bad_dir.path().join("notice.tmpl");
+        fs::write(&bad_template, "{{unknown}}").unwrap();
+        handle_generate_command(
+            bad_dir.path().to_string_lossy().to_string(),
+            None,
+            Some("MIT".to_string()),
+            GenerateFormat::Markdown,
+            &[GenerateOption::Notice],
+            Some(bad_template.to_string_lossy().to_string()),
+        );
+        crate::network::set_offline_mode(false);
+
+        let notice = fs::read_to_string(temp_dir.path().join("NOTICE")).unwrap();
+        assert!(notice.starts_with("Legal header\n## "));
+        assert!(notice.contains("dplyr"));
+        assert!(!bad_dir.path().join("NOTICE").exists());
+    }
+
+    #[test]
+    #[serial]
     fn test_create_http_client() {
         let client = create_http_client();
         assert!(client.is_some());
@@ -1738,30 +2580,32 @@ mod tests {
     }
 
     #[test]
-    fn test_rate_limit_delay() {
-        let start = std::time::Instant::now();
-        rate_limit_delay();
-        let duration = start.elapsed();
+    #[serial]
+    fn test_offline_mode_skips_http_client() {
+        crate::network::set_offline_mode(true);
+
+        assert!(create_http_client().is_none());
+        assert!(fetch_license_from_crates_io("serde", "1.0.0").is_none());
+        assert!(fetch_actual_license_content("serde", "1.0.0").is_none());
 
-        // Should take at least 500ms
-        assert!(duration >= std::time::Duration::from_millis(500));
+        crate::network::set_offline_mode(false);
     }
 
     #[test]
+    #[serial]
     fn test_generate_notice_file_creation() {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let path = temp_dir.path().to_str().unwrap();
 
         let license_data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
-        generate_notice_file(&license_data, path);
+        crate::network::set_offline_mode(true);
+        generate_notice_file(&license_data, path, None);
+        crate::network::set_offline_mode(false);
 
         // Check that the file was created
         let notice_path = temp_dir.path().join("NOTICE");
@@ -1775,6 +2619,7 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_generate_notice_file_update() {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let path = temp_dir.path().to_str().unwrap();
@@ -1784,15 +2629,14 @@ mod tests {
         std::fs::write(&notice_path, "Old notice content").unwrap();
 
         let license_data = vec![LicenseInfo {
-            name: "new_package".to_string(),
-            version: "2.0.0".to_string(),
-            license: Some("Apache-2.0".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("new_package", "2.0.0", Some("Apache-2.0".to_string()))
         }];
 
-        generate_notice_file(&license_data, path);
+        crate::network::set_offline_mode(true);
+        generate_notice_file(&license_data, path, None);
+        crate::network::set_offline_mode(false);
 
         // Check that the file was updated
         let content = std::fs::read_to_string(notice_path).unwrap();
@@ -1807,12 +2651,9 @@ mod tests {
         let path = temp_dir.path().to_str().unwrap();
 
         let license_data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         generate_third_party_licenses_file(&license_data, path);
@@ -1834,17 +2675,82 @@ mod tests {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let path = temp_dir.path().to_str().unwrap();
 
-        handle_generate_command(path.to_string(), None, None);
+        handle_generate_command(
+            path.to_string(),
+            None,
+            None,
+            GenerateFormat::Markdown,
+            &[],
+            None,
+        );
     }
 
     #[test]
     #[cfg(windows)]
     fn test_enable_disable_raw_mode_windows() {
-        // On Windows, these should return Ok(())
-        assert!(enable_raw_mode().is_ok());
+        use windows_sys::Win32::System::Console::GetConsoleMode;
+
+        let console_mode = || {
+            let handle = console_input_handle().ok()?;
+            let mut mode = 0;
+            (unsafe { GetConsoleMode(handle, &mut mode) } != 0).then_some(mode)
+        };
+
+        // Without a console (e.g. redirected stdin in CI) raw mode is unavailable
+        let before = console_mode();
+        if enable_raw_mode().is_ok() {
+            assert!(disable_raw_mode().is_ok());
+            assert_eq!(console_mode(), before);
+        }
+
+        // Disabling again has nothing left to restore
         assert!(disable_raw_mode().is_ok());
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn test_parse_escape_sequence() {
+        assert_eq!(parse_escape_sequence(b"\x1b"), KeyInput::Escape);
+        assert_eq!(parse_escape_sequence(b"\x1bx"), KeyInput::Escape);
+        assert_eq!(parse_escape_sequence(b"\x1b[A"), KeyInput::Up);
+        assert_eq!(parse_escape_sequence(b"\x1b[B"), KeyInput::Down);
+        assert_eq!(parse_escape_sequence(b"\x1bOA"), KeyInput::Up);
+        assert_eq!(parse_escape_sequence(b"\x1bOB"), KeyInput::Down);
+
+        // Arrows with modifiers, e.g. Ctrl+Up
+        assert_eq!(parse_escape_sequence(b"\x1b[1;5A"), KeyInput::Up);
+        assert_eq!(parse_escape_sequence(b"\x1b[1;2B"), KeyInput::Down);
+
+        // Left/Right, Home/End, PageUp/PageDown and Delete are ignored
+        for sequence in [
+            &b"\x1b[C"[..],
+            b"\x1b[D",
+            b"\x1b[H",
+            b"\x1b[F",
+            b"\x1bOH",
+            b"\x1b[1~",
+            b"\x1b[4~",
+            b"\x1b[5~",
+            b"\x1b[6~",
+            b"\x1b[3;5~",
+        ] {
+            assert_eq!(parse_escape_sequence(sequence), KeyInput::Unknown);
+        }
+
+        // A sequence cut off before its final byte must not move the selection
+        assert_eq!(parse_escape_sequence(b"\x1b[1;5"), KeyInput::Unknown);
+        assert_eq!(parse_escape_sequence(b"\x1b["), KeyInput::Unknown);
+    }
+
+    #[test]
+    fn test_char_key() {
+        assert_eq!(char_key('q'), KeyInput::Escape);
+        assert_eq!(char_key('K'), KeyInput::Up);
+        assert_eq!(char_key('j'), KeyInput::Down);
+        assert_eq!(char_key('1'), KeyInput::Char('1'));
+        assert_eq!(char_key('é'), KeyInput::Unknown);
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_enable_disable_raw_mode_unix() {
@@ -5,10 +5,34 @@ use std::path::Path;
 use std::process::Command;
 
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
-};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+
+/// Ecosystem name for C libraries detected from build files
+const ECOSYSTEM: &str = "c";
+
+/// Parses C projects through the [`LanguageParser`] interface
+pub struct CParser;
+
+impl LanguageParser for CParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_c_licenses(project_file_str(project_file)?, config))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "C"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::C_PATHS
+    }
+}
 
 pub fn analyze_c_licenses(project_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
     log(
@@ -54,6 +78,7 @@ pub fn analyze_c_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Lice
     log_debug("All C dependencies", &all_deps);
 
     let dependencies = all_deps;
+    crate::cli::add_dependencies_to_resolve(dependencies.len());
 
     dependencies
         .into_iter()
@@ -74,16 +99,16 @@ pub fn analyze_c_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Lice
                 );
             }
 
+            crate::cli::mark_dependency_resolved();
+
             LicenseInfo {
-                name,
-                version,
-                license: license.clone(),
                 is_restrictive,
-                compatibility: LicenseCompatibility::Unknown,
                 osi_status: match &license {
                     Some(l) => crate::licenses::get_osi_status(l),
                     None => crate::licenses::OsiStatus::Unknown,
                 },
+                ecosystem: ECOSYSTEM.to_string(),
+                ..LicenseInfo::new(name, version, license)
             }
         })
         .collect()
@@ -5,13 +5,26 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use crate::cache;
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
 use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
+    fetch_github_repository_license, fetch_licenses_from_github, is_license_restrictive,
+    LicenseInfo,
 };
+use crate::network::{http_client, skip_network, with_network_permit};
 
-#[derive(Debug, Clone)]
+/// Ecosystem name shared by vcpkg, Conan and other C++ dependencies
+const ECOSYSTEM: &str = "cpp";
+
+/// Bazel Central Registry, where `bazel_dep` modules are published
+const BAZEL_CENTRAL_REGISTRY: &str = "https://bcr.bazel.build";
+
+/// Git repository of dependencies fetched from source, keyed by dependency name
+type GitSources = HashMap<String, String>;
+
+#[derive(Debug, Clone, Copy)]
 enum CppPackageManager {
     Vcpkg,
     Conan,
@@ -20,6 +33,31 @@ enum CppPackageManager {
     Unknown,
 }
 
+/// Parses C++ projects through the [`LanguageParser`] interface
+pub struct CppParser;
+
+impl LanguageParser for CppParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_cpp_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "C++"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::CPP_PATHS
+    }
+}
+
 pub fn analyze_cpp_licenses(project_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
     log(
         LogLevel::Info,
@@ -40,7 +78,7 @@ pub fn analyze_cpp_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Li
         }
     };
 
-    let (direct_dependencies, package_manager) =
+    let (direct_dependencies, package_manager, git_sources) =
         detect_cpp_dependencies_with_type(project_path, config);
     log(
         LogLevel::Info,
@@ -73,6 +111,7 @@ pub fn analyze_cpp_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Li
     log_debug("All C++ dependencies", &all_deps);
 
     let dependencies = all_deps;
+    crate::cli::add_dependencies_to_resolve(dependencies.len());
 
     dependencies
         .into_iter()
@@ -82,7 +121,10 @@ pub fn analyze_cpp_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Li
                 &format!("Processing dependency: {name} ({version})"),
             );
 
-            let license_result = fetch_license_for_cpp_dependency(&name, &version);
+            let license_result = match git_sources.get(&name) {
+                Some(repository) => fetch_license_for_git_dependency(&name, &version, repository),
+                None => fetch_license_for_cpp_dependency(&name, &version, package_manager),
+            };
             let license = Some(license_result);
             let is_restrictive = is_license_restrictive(&license, &known_licenses, config.strict);
 
@@ -93,25 +135,26 @@ pub fn analyze_cpp_licenses(project_path: &str, config: &FeludaConfig) -> Vec<Li
                 );
             }
 
+            crate::cli::mark_dependency_resolved();
+
             LicenseInfo {
-                name,
-                version,
-                license: license.clone(),
                 is_restrictive,
-                compatibility: LicenseCompatibility::Unknown,
                 osi_status: match &license {
                     Some(l) => crate::licenses::get_osi_status(l),
                     None => crate::licenses::OsiStatus::Unknown,
                 },
+                ecosystem: ECOSYSTEM.to_string(),
+                ..LicenseInfo::new(name, version, license)
             }
         })
         .collect()
 }
 
+/// Direct dependencies, the package manager declaring them and their git sources
 fn detect_cpp_dependencies_with_type(
     project_path: &str,
     config: &FeludaConfig,
-) -> (Vec<(String, String)>, CppPackageManager) {
+) -> (Vec<(String, String)>, CppPackageManager, GitSources) {
     let project_dir = Path::new(project_path).parent().unwrap_or(Path::new("."));
 
     if let Ok(vcpkg_deps) = parse_vcpkg_dependencies(project_dir, config) {
@@ -119,7 +162,7 @@ fn detect_cpp_dependencies_with_type(
             LogLevel::Info,
             &format!("Found {} vcpkg dependencies", vcpkg_deps.len()),
         );
-        return (vcpkg_deps, CppPackageManager::Vcpkg);
+        return (vcpkg_deps, CppPackageManager::Vcpkg, HashMap::new());
     }
 
     if let Ok(conan_deps) = parse_conan_dependencies(project_dir, config) {
@@ -127,15 +170,15 @@ fn detect_cpp_dependencies_with_type(
             LogLevel::Info,
             &format!("Found {} conan dependencies", conan_deps.len()),
         );
-        return (conan_deps, CppPackageManager::Conan);
+        return (conan_deps, CppPackageManager::Conan, HashMap::new());
     }
 
-    if let Ok(cmake_deps) = parse_cmake_dependencies(project_dir, config) {
+    if let Ok((cmake_deps, git_sources)) = parse_cmake_dependencies(project_dir, config) {
         log(
             LogLevel::Info,
             &format!("Found {} cmake dependencies", cmake_deps.len()),
         );
-        return (cmake_deps, CppPackageManager::CMake);
+        return (cmake_deps, CppPackageManager::CMake, git_sources);
     }
 
     if let Ok(bazel_deps) = parse_bazel_dependencies(project_dir, config) {
@@ -143,10 +186,10 @@ fn detect_cpp_dependencies_with_type(
             LogLevel::Info,
             &format!("Found {} bazel dependencies", bazel_deps.len()),
         );
-        return (bazel_deps, CppPackageManager::Bazel);
+        return (bazel_deps, CppPackageManager::Bazel, HashMap::new());
     }
 
-    (Vec::new(), CppPackageManager::Unknown)
+    (Vec::new(), CppPackageManager::Unknown, HashMap::new())
 }
 
 fn resolve_cpp_dependencies(
@@ -254,37 +297,20 @@ fn resolve_vcpkg_transitive(
     package_name: &str,
     _version: &str,
 ) -> Result<Vec<(String, String)>, String> {
+    if skip_network(&format!("vcpkg dependencies of {package_name}")) {
+        return Ok(Vec::new());
+    }
+
     // Try to fetch dependencies from vcpkg registry
     let url = format!(
         "https://raw.githubusercontent.com/microsoft/vcpkg/master/ports/{package_name}/vcpkg.json"
     );
 
-    if let Ok(response) = reqwest::blocking::get(&url) {
+    if let Ok(response) = with_network_permit(|| http_client()?.get(&url).send()) {
         if response.status().is_success() {
             if let Ok(json) = response.json::<Value>() {
-                let mut dependencies = Vec::new();
-
-                if let Some(deps) = json.get("dependencies").and_then(|d| d.as_array()) {
-                    for dep in deps {
-                        match dep {
-                            Value::String(name) => {
-                                dependencies.push((name.clone(), "latest".to_string()));
-                            }
-                            Value::Object(obj) => {
-                                if let Some(name) = obj.get("name").and_then(|n| n.as_str()) {
-                                    let version = obj
-                                        .get("version")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("latest");
-                                    dependencies.push((name.to_string(), version.to_string()));
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-
-                return Ok(dependencies);
+                // Host dependencies of a port are build tools such as vcpkg-cmake
+                return Ok(vcpkg_manifest_dependencies(&json, false));
             }
         }
     }
@@ -296,25 +322,25 @@ fn resolve_conan_transitive(
     package_name: &str,
     version: &str,
 ) -> Result<Vec<(String, String)>, String> {
+    if skip_network(&format!("Conan dependencies of {package_name}")) {
+        return Ok(Vec::new());
+    }
+
     // Try to fetch dependencies from Conan Center
     let url = format!("https://conan.io/center/api/packages/{package_name}/{version}");
 
-    if let Ok(response) = reqwest::blocking::get(&url) {
+    if let Ok(response) = with_network_permit(|| http_client()?.get(&url).send()) {
         if response.status().is_success() {
             if let Ok(json) = response.json::<Value>() {
                 let mut dependencies = Vec::new();
 
                 if let Some(requires) = json.get("requires").and_then(|r| r.as_array()) {
-                    for req in requires {
-                        if let Some(req_str) = req.as_str() {
-                            if let Some(slash_pos) = req_str.find('/') {
-                                let name = &req_str[..slash_pos];
-                                let version = &req_str[slash_pos + 1..];
-                                let clean_version = version.split('@').next().unwrap_or(version);
-                                dependencies.push((name.to_string(), clean_version.to_string()));
-                            }
-                        }
-                    }
+                    dependencies.extend(
+                        requires
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .filter_map(conan_reference),
+                    );
                 }
 
                 return Ok(dependencies);
@@ -404,7 +430,7 @@ fn resolve_bazel_transitive(
 
 fn parse_vcpkg_dependencies(
     project_dir: &Path,
-    _config: &FeludaConfig,
+    config: &FeludaConfig,
 ) -> Result<Vec<(String, String)>, String> {
     let vcpkg_json = project_dir.join("vcpkg.json");
     if !vcpkg_json.exists() {
@@ -417,48 +443,97 @@ fn parse_vcpkg_dependencies(
     let json: Value =
         serde_json::from_str(&content).map_err(|e| format!("Failed to parse vcpkg.json: {e}"))?;
 
-    let mut dependencies = Vec::new();
+    Ok(vcpkg_manifest_dependencies(&json, config.include_dev))
+}
 
-    if let Some(deps) = json.get("dependencies").and_then(|d| d.as_array()) {
-        for dep in deps {
-            match dep {
-                Value::String(name) => {
-                    dependencies.push((name.clone(), "latest".to_string()));
-                }
-                Value::Object(obj) => {
-                    if let Some(name) = obj.get("name").and_then(|n| n.as_str()) {
-                        let version = obj
-                            .get("version")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("latest");
-                        dependencies.push((name.to_string(), version.to_string()));
-                    }
+/// Dependencies of a vcpkg manifest, including those of its default features
+///
+/// Entries are either a port name or an object with `name` and an optional `version>=`
+/// minimum; ports without a version are reported as `latest`. Host dependencies are build
+/// tools and only kept with `include_dev`.
+fn vcpkg_manifest_dependencies(manifest: &Value, include_dev: bool) -> Vec<(String, String)> {
+    let default_features: Vec<&str> = manifest
+        .get("default-features")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|feature| match feature {
+            Value::String(name) => Some(name.as_str()),
+            Value::Object(obj) => obj.get("name").and_then(Value::as_str),
+            _ => None,
+        })
+        .collect();
+
+    let feature_dependencies = default_features.iter().filter_map(|feature| {
+        manifest
+            .get("features")?
+            .get(feature)?
+            .get("dependencies")?
+            .as_array()
+    });
+
+    let mut seen = HashSet::new();
+    manifest
+        .get("dependencies")
+        .and_then(Value::as_array)
+        .into_iter()
+        .chain(feature_dependencies)
+        .flatten()
+        .filter_map(|dep| match dep {
+            Value::String(name) => Some((name.clone(), "latest".to_string())),
+            Value::Object(obj) => {
+                let name = obj.get("name").and_then(Value::as_str)?;
+                if !include_dev && obj.get("host").and_then(Value::as_bool) == Some(true) {
+                    log(
+                        LogLevel::Info,
+                        &format!("Skipping vcpkg host dependency: {name}"),
+                    );
+                    return None;
                 }
-                _ => {}
+                let version = obj
+                    .get("version>=")
+                    .or_else(|| obj.get("version"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("latest");
+                Some((name.to_string(), version.to_string()))
             }
-        }
-    }
-
-    Ok(dependencies)
+            _ => None,
+        })
+        .filter(|(name, _)| seen.insert(name.clone()))
+        .collect()
 }
 
 fn parse_conan_dependencies(
     project_dir: &Path,
-    _config: &FeludaConfig,
+    config: &FeludaConfig,
 ) -> Result<Vec<(String, String)>, String> {
     let conanfile_txt = project_dir.join("conanfile.txt");
     let conanfile_py = project_dir.join("conanfile.py");
 
     if conanfile_txt.exists() {
-        parse_conanfile_txt(&conanfile_txt)
+        parse_conanfile_txt(&conanfile_txt, config.include_dev)
     } else if conanfile_py.exists() {
-        parse_conanfile_py(&conanfile_py)
+        parse_conanfile_py(&conanfile_py, config.include_dev)
     } else {
         Err("No conanfile found".to_string())
     }
 }
 
-fn parse_conanfile_txt(conanfile_path: &Path) -> Result<Vec<(String, String)>, String> {
+/// Split a Conan reference like `pkg/1.0@user/stable#rev` into name and version
+fn conan_reference(reference: &str) -> Option<(String, String)> {
+    let (name, version) = reference.trim().split_once('/')?;
+    let version = version.split(['@', '#']).next().unwrap_or(version).trim();
+    if name.is_empty() || version.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), version.to_string()))
+}
+
+/// Requirements of the `[requires]` section; `[tool_requires]` only with `include_dev`
+fn parse_conanfile_txt(
+    conanfile_path: &Path,
+    include_dev: bool,
+) -> Result<Vec<(String, String)>, String> {
     let content = fs::read_to_string(conanfile_path)
         .map_err(|e| format!("Failed to read conanfile.txt: {e}"))?;
 
@@ -468,66 +543,78 @@ fn parse_conanfile_txt(conanfile_path: &Path) -> Result<Vec<(String, String)>, S
     for line in content.lines() {
         let trimmed = line.trim();
 
-        if trimmed == "[requires]" {
-            in_requires_section = true;
-            continue;
-        }
-
-        if trimmed.starts_with('[') && trimmed.ends_with(']') && trimmed != "[requires]" {
-            in_requires_section = false;
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            in_requires_section = match trimmed {
+                "[requires]" => true,
+                "[tool_requires]" | "[build_requires]" | "[test_requires]" => include_dev,
+                _ => false,
+            };
             continue;
         }
 
         if in_requires_section && !trimmed.is_empty() && !trimmed.starts_with('#') {
-            if let Some(slash_pos) = trimmed.find('/') {
-                let name = &trimmed[..slash_pos];
-                let version = &trimmed[slash_pos + 1..];
-                let clean_version = version.split('@').next().unwrap_or(version);
-                dependencies.push((name.to_string(), clean_version.to_string()));
-            }
+            dependencies.extend(conan_reference(trimmed));
         }
     }
 
     Ok(dependencies)
 }
 
-fn parse_conanfile_py(conanfile_path: &Path) -> Result<Vec<(String, String)>, String> {
+/// Requirements of a `conanfile.py`, extracted with regexes since the recipe is arbitrary Python
+///
+/// Understands `requires = "a/1.0", "b/2.0"` (string, tuple or list) and
+/// `self.requires("a/1.0")`; the tool and test variants are only kept with `include_dev`.
+fn parse_conanfile_py(
+    conanfile_path: &Path,
+    include_dev: bool,
+) -> Result<Vec<(String, String)>, String> {
     let content = fs::read_to_string(conanfile_path)
         .map_err(|e| format!("Failed to read conanfile.py: {e}"))?;
 
+    let attribute_regex = Regex::new(
+        r"(?m)^\s*(requires|tool_requires|build_requires|test_requires)\s*=\s*(\[[^\]]*\]|\([^)]*\)|[^\n]*)",
+    )
+    .map_err(|e| format!("Failed to compile requires regex: {e}"))?;
+    let method_regex = Regex::new(
+        r#"self\.(requires|tool_requires|build_requires|test_requires)\s*\(\s*["']([^"']+)["']"#,
+    )
+    .map_err(|e| format!("Failed to compile self.requires regex: {e}"))?;
+    let string_regex = Regex::new(r#"["']([^"']+)["']"#)
+        .map_err(|e| format!("Failed to compile dependency regex: {e}"))?;
+
+    let wanted = |kind: &str| kind == "requires" || include_dev;
     let mut dependencies = Vec::new();
 
-    let requires_regex = Regex::new(r#"requires\s*=\s*\[(.*?)\]"#)
-        .map_err(|e| format!("Failed to compile requires regex: {e}"))?;
-
-    if let Some(cap) = requires_regex.captures(&content) {
-        if let Some(requires_content) = cap.get(1) {
-            let req_str = requires_content.as_str();
-
-            let dep_regex = Regex::new(r#""([^"]+)""#)
-                .map_err(|e| format!("Failed to compile dependency regex: {e}"))?;
+    for cap in attribute_regex.captures_iter(&content) {
+        if wanted(&cap[1]) {
+            dependencies.extend(
+                string_regex
+                    .captures_iter(&cap[2])
+                    .filter_map(|dep| conan_reference(&dep[1])),
+            );
+        }
+    }
 
-            for dep_cap in dep_regex.captures_iter(req_str) {
-                if let Some(dep_str) = dep_cap.get(1) {
-                    let dep = dep_str.as_str();
-                    if let Some(slash_pos) = dep.find('/') {
-                        let name = &dep[..slash_pos];
-                        let version = &dep[slash_pos + 1..];
-                        let clean_version = version.split('@').next().unwrap_or(version);
-                        dependencies.push((name.to_string(), clean_version.to_string()));
-                    }
-                }
-            }
+    for cap in method_regex.captures_iter(&content) {
+        if wanted(&cap[1]) {
+            dependencies.extend(conan_reference(&cap[2]));
         }
     }
 
+    let mut seen = HashSet::new();
+    dependencies.retain(|(name, _)| seen.insert(name.clone()));
     Ok(dependencies)
 }
 
+/// Best-effort dependencies of a `CMakeLists.txt`, which has no single manifest
+///
+/// `FetchContent_Declare` entries are versioned by their `GIT_TAG` (`git` without one) and
+/// their `GIT_REPOSITORY` or `URL` is returned keyed by name, so licenses can be resolved
+/// from the source. `find_package` names come with no source at all.
 fn parse_cmake_dependencies(
     project_dir: &Path,
     _config: &FeludaConfig,
-) -> Result<Vec<(String, String)>, String> {
+) -> Result<(Vec<(String, String)>, GitSources), String> {
     let cmake_file = project_dir.join("CMakeLists.txt");
     if !cmake_file.exists() {
         return Err("No CMakeLists.txt found".to_string());
@@ -537,14 +624,27 @@ fn parse_cmake_dependencies(
         .map_err(|e| format!("Failed to read CMakeLists.txt: {e}"))?;
 
     let mut dependencies = Vec::new();
+    let mut git_sources = HashMap::new();
 
-    let fetchcontent_regex = Regex::new(r"FetchContent_Declare\s*\(\s*(\w+)")
+    let fetchcontent_regex = Regex::new(r"FetchContent_Declare\s*\(\s*([\w.-]+)([^)]*)\)")
         .map_err(|e| format!("Failed to compile FetchContent regex: {e}"))?;
+    let source_regex = Regex::new(r"\b(?:GIT_REPOSITORY|URL)\s+(\S+)")
+        .map_err(|e| format!("Failed to compile FetchContent source regex: {e}"))?;
+    let tag_regex = Regex::new(r"\bGIT_TAG\s+(\S+)")
+        .map_err(|e| format!("Failed to compile FetchContent tag regex: {e}"))?;
 
     for cap in fetchcontent_regex.captures_iter(&content) {
-        if let Some(dep_name) = cap.get(1) {
-            dependencies.push((dep_name.as_str().to_string(), "git".to_string()));
+        let name = cap[1].to_string();
+        let args = &cap[2];
+
+        let version = tag_regex
+            .captures(args)
+            .map(|tag| tag[1].to_string())
+            .unwrap_or_else(|| "git".to_string());
+        if let Some(source) = source_regex.captures(args) {
+            git_sources.insert(name.clone(), source[1].to_string());
         }
+        dependencies.push((name, version));
     }
 
     let find_package_regex = Regex::new(r"find_package\s*\(\s*(\w+)(?:\s+([^)]+))?\)")
@@ -564,22 +664,25 @@ fn parse_cmake_dependencies(
                 })
                 .unwrap_or("system");
 
-            dependencies.push((pkg_name.as_str().to_string(), version.to_string()));
+            let name = pkg_name.as_str().to_string();
+            if !dependencies.iter().any(|(known, _)| known == &name) {
+                dependencies.push((name, version.to_string()));
+            }
         }
     }
 
-    Ok(dependencies)
+    Ok((dependencies, git_sources))
 }
 
 fn parse_bazel_dependencies(
     project_dir: &Path,
-    _config: &FeludaConfig,
+    config: &FeludaConfig,
 ) -> Result<Vec<(String, String)>, String> {
     let module_bazel = project_dir.join("MODULE.bazel");
     let workspace = project_dir.join("WORKSPACE");
 
     if module_bazel.exists() {
-        parse_module_bazel(&module_bazel)
+        parse_module_bazel(&module_bazel, config.include_dev)
     } else if workspace.exists() {
         parse_workspace_bazel(&workspace)
     } else {
@@ -587,20 +690,58 @@ fn parse_bazel_dependencies(
     }
 }
 
-fn parse_module_bazel(module_path: &Path) -> Result<Vec<(String, String)>, String> {
+/// Parse the `bazel_dep(...)` calls of a `MODULE.bazel` file
+///
+/// Arguments may come in any order and span several lines; a dependency without a version
+/// (pinned by an override) is reported as `latest`. `dev_dependency = True` entries are
+/// only kept with `include_dev`.
+fn parse_module_bazel(
+    module_path: &Path,
+    include_dev: bool,
+) -> Result<Vec<(String, String)>, String> {
     let content =
         fs::read_to_string(module_path).map_err(|e| format!("Failed to read MODULE.bazel: {e}"))?;
 
-    let mut dependencies = Vec::new();
+    // Drop comments so commented out dependencies are not picked up
+    let content = content
+        .lines()
+        .map(|line| match line.find('#') {
+            Some(pos) if line[..pos].matches('"').count() % 2 == 0 => &line[..pos],
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let bazel_dep_regex = Regex::new(r"\bbazel_dep\s*\(([^)]*)\)")
+        .map_err(|e| format!("Failed to compile bazel_dep regex: {e}"))?;
+    let name_regex = Regex::new(r#"\bname\s*=\s*"([^"]+)""#)
+        .map_err(|e| format!("Failed to compile bazel_dep name regex: {e}"))?;
+    let version_regex = Regex::new(r#"\bversion\s*=\s*"([^"]+)""#)
+        .map_err(|e| format!("Failed to compile bazel_dep version regex: {e}"))?;
+    let dev_regex = Regex::new(r"\bdev_dependency\s*=\s*True\b")
+        .map_err(|e| format!("Failed to compile bazel_dep dev_dependency regex: {e}"))?;
 
-    let bazel_dep_regex =
-        Regex::new(r#"bazel_dep\s*\(\s*name\s*=\s*"([^"]+)"\s*,\s*version\s*=\s*"([^"]+)""#)
-            .map_err(|e| format!("Failed to compile bazel_dep regex: {e}"))?;
+    let mut dependencies = Vec::new();
 
     for cap in bazel_dep_regex.captures_iter(&content) {
-        if let (Some(name), Some(version)) = (cap.get(1), cap.get(2)) {
-            dependencies.push((name.as_str().to_string(), version.as_str().to_string()));
+        let args = &cap[1];
+        let Some(name) = name_regex.captures(args).map(|c| c[1].to_string()) else {
+            continue;
+        };
+
+        if !include_dev && dev_regex.is_match(args) {
+            log(
+                LogLevel::Info,
+                &format!("Skipping Bazel dev dependency: {name}"),
+            );
+            continue;
         }
+
+        let version = version_regex
+            .captures(args)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| "latest".to_string());
+        dependencies.push((name, version));
     }
 
     Ok(dependencies)
@@ -624,23 +765,53 @@ fn parse_workspace_bazel(workspace_path: &Path) -> Result<Vec<(String, String)>,
     Ok(dependencies)
 }
 
-fn fetch_license_for_cpp_dependency(name: &str, version: &str) -> String {
-    match version {
+fn fetch_license_for_cpp_dependency(
+    name: &str,
+    version: &str,
+    package_manager: CppPackageManager,
+) -> String {
+    match package_manager {
+        CppPackageManager::Bazel => {
+            return cache::cached_package_license("bazel", name, version, || {
+                fetch_license_from_bazel_registry(name)
+            });
+        }
+        // The registry only has the current port, whose license rarely changes per version
+        CppPackageManager::Vcpkg => {
+            return cache::cached_package_license("vcpkg", name, version, || {
+                fetch_license_from_vcpkg_registry(name)
+            });
+        }
+        CppPackageManager::Conan => {
+            return cache::cached_package_license("conan", name, version, || {
+                fetch_license_from_conan_center(name, version)
+            });
+        }
+        // `find_package` only names a package; the system may know its license
+        CppPackageManager::CMake => return fetch_license_from_system_package(name),
+        _ => {}
+    }
+
+    cache::cached_package_license("cpp", name, version, || match version {
         "latest" | "git" => fetch_license_from_vcpkg_registry(name),
         v if v.chars().next().unwrap_or('0').is_ascii_digit() => {
             fetch_license_from_conan_center(name, version)
         }
         "system" => fetch_license_from_system_package(name),
         _ => format!("Unknown license for {name}: {version}"),
-    }
+    })
 }
 
 fn fetch_license_from_vcpkg_registry(package_name: &str) -> String {
+    if skip_network(&format!("vcpkg license of {package_name}")) {
+        return format!("Unknown license (vcpkg: {package_name})");
+    }
+
     let url = format!(
         "https://raw.githubusercontent.com/microsoft/vcpkg/master/ports/{package_name}/vcpkg.json"
     );
 
-    if let Ok(response) = reqwest::blocking::get(&url) {
+    if let Ok(response) = with_network_permit(|| http_client()?.get(&url).send()) {
         if response.status().is_success() {
             if let Ok(json) = response.json::<Value>() {
                 if let Some(license) = json.get("license").and_then(|l| l.as_str()) {
@@ -654,9 +825,13 @@ fn fetch_license_from_vcpkg_registry(package_name: &str) -> String {
 }
 
 fn fetch_license_from_conan_center(package_name: &str, version: &str) -> String {
+    if skip_network(&format!("Conan license of {package_name}")) {
+        return format!("Unknown license (conan: {package_name})");
+    }
+
     let url = format!("https://conan.io/center/api/packages/{package_name}/{version}");
 
-    if let Ok(response) = reqwest::blocking::get(&url) {
+    if let Ok(response) = with_network_permit(|| http_client()?.get(&url).send()) {
         if response.status().is_success() {
             if let Ok(json) = response.json::<Value>() {
                 if let Some(license) = json.get("license").and_then(|l| l.as_str()) {
@@ -666,7 +841,125 @@ fn fetch_license_from_conan_center(package_name: &str, version: &str) -> String
         }
     }
 
-    format!("Unknown license (conan: {package_name})")
+    fetch_license_from_conan_recipe(package_name, version)
+        .unwrap_or_else(|| format!("Unknown license (conan: {package_name})"))
+}
+
+/// `license` attribute of the ConanCenter recipe building `version` of a package
+fn fetch_license_from_conan_recipe(package_name: &str, version: &str) -> Option<String> {
+    let base = format!(
+        "https://raw.githubusercontent.com/conan-io/conan-center-index/master/recipes/{package_name}"
+    );
+    let fetch = |url: String| {
+        with_network_permit(|| http_client()?.get(&url).send())
+            .ok()
+            .filter(|response| response.status().is_success())
+            .and_then(|response| response.text().ok())
+    };
+
+    let folder = fetch(format!("{base}/config.yml"))
+        .and_then(|config| conan_recipe_folder(&config, version))
+        .unwrap_or_else(|| "all".to_string());
+    let recipe = fetch(format!("{base}/{folder}/conanfile.py"))?;
+    conan_recipe_license(&recipe)
+}
+
+/// Recipe folder of `version` in a ConanCenter `config.yml`
+fn conan_recipe_folder(config_yml: &str, version: &str) -> Option<String> {
+    let config: serde_yaml::Value = serde_yaml::from_str(config_yml).ok()?;
+    config
+        .get("versions")?
+        .get(version)?
+        .get("folder")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Value of the `license` class attribute of a Conan recipe, several licenses joined by `AND`
+fn conan_recipe_license(recipe: &str) -> Option<String> {
+    let license_regex = Regex::new(r#"(?m)^\s*license\s*=\s*([\[(]?[^\n]*)"#).ok()?;
+    let string_regex = Regex::new(r#"["']([^"']+)["']"#).ok()?;
+
+    let value = license_regex.captures(recipe)?;
+    let licenses: Vec<&str> = string_regex
+        .captures_iter(value.get(1)?.as_str())
+        .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+        .collect();
+    (!licenses.is_empty()).then(|| licenses.join(" AND "))
+}
+
+/// License of a Bazel module, from the GitHub repository listed in its BCR metadata
+///
+/// BCR metadata does not carry a license, but nearly every module lists its source
+/// repository as `github:owner/repo`.
+fn fetch_license_from_bazel_registry(module_name: &str) -> String {
+    let unknown = format!("Unknown license (bazel: {module_name})");
+    if skip_network(&format!("Bazel license of {module_name}")) {
+        return unknown;
+    }
+
+    let url = format!("{BAZEL_CENTRAL_REGISTRY}/modules/{module_name}/metadata.json");
+    let metadata = match with_network_permit(|| http_client()?.get(&url).send()) {
+        Ok(response) if response.status().is_success() => response.json::<Value>().ok(),
+        _ => None,
+    };
+    let Some((owner, repo)) = metadata.as_ref().and_then(bcr_github_repository) else {
+        log(
+            LogLevel::Warn,
+            &format!("No GitHub repository in BCR metadata of {module_name}"),
+        );
+        return unknown;
+    };
+
+    fetch_github_repository_license(&owner, &repo).unwrap_or(unknown)
+}
+
+/// License of a dependency built from a git repository, such as a CMake `FetchContent`
+///
+/// Only GitHub repositories can be resolved; anything else is reported as unknown.
+fn fetch_license_for_git_dependency(name: &str, version: &str, repository: &str) -> String {
+    let unknown = format!("Unknown license (git: {name})");
+    let Some((owner, repo)) = github_repository(repository) else {
+        log(
+            LogLevel::Warn,
+            &format!("Cannot resolve license of {name} from non-GitHub source {repository}"),
+        );
+        return unknown;
+    };
+
+    cache::cached_package_license("cpp", name, version, || {
+        if skip_network(&format!("GitHub license of {name}")) {
+            return unknown.clone();
+        }
+        fetch_github_repository_license(&owner, &repo).unwrap_or_else(|| unknown.clone())
+    })
+}
+
+/// Owner and repository of a `https://github.com/owner/repo` URL; release download URLs
+/// under the repository work too
+fn github_repository(url: &str) -> Option<(String, String)> {
+    let path = url
+        .trim_start_matches("git+")
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("git@github.com:"))?;
+    let mut segments = path.split('/');
+    let owner = segments.next().filter(|owner| !owner.is_empty())?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    (!repo.is_empty()).then(|| (owner.to_string(), repo.to_string()))
+}
+
+/// First `github:owner/repo` entry of the `repository` list in BCR `metadata.json`
+fn bcr_github_repository(metadata: &Value) -> Option<(String, String)> {
+    metadata
+        .get("repository")?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .filter_map(|repository| repository.strip_prefix("github:"))
+        .find_map(|repository| {
+            let (owner, repo) = repository.split_once('/')?;
+            Some((owner.to_string(), repo.trim_end_matches(".git").to_string()))
+        })
 }
 
 fn fetch_license_from_system_package(package_name: &str) -> String {
@@ -721,6 +1014,45 @@ mod tests {
             .any(|(name, version)| name == "opencv" && version == "4.5.0"));
     }
 
+    #[test]
+    fn test_parse_vcpkg_manifest_forms() {
+        let manifest = serde_json::json!({
+            "name": "my-app",
+            "version-string": "1.0.0",
+            "dependencies": [
+                "fmt",
+                {"name": "curl", "version>=": "8.4.0", "features": ["ssl"]},
+                {"name": "vcpkg-cmake", "host": true},
+                {"name": "openssl", "platform": "!windows"}
+            ],
+            "default-features": ["compression"],
+            "features": {
+                "compression": {
+                    "description": "Compression support",
+                    "dependencies": ["zlib", {"name": "curl", "version>=": "8.4.0"}]
+                },
+                "tests": {
+                    "description": "Build tests",
+                    "dependencies": ["gtest"]
+                }
+            }
+        });
+
+        assert_eq!(
+            vcpkg_manifest_dependencies(&manifest, false),
+            vec![
+                ("fmt".to_string(), "latest".to_string()),
+                ("curl".to_string(), "8.4.0".to_string()),
+                ("openssl".to_string(), "latest".to_string()),
+                ("zlib".to_string(), "latest".to_string()),
+            ]
+        );
+
+        let with_host = vcpkg_manifest_dependencies(&manifest, true);
+        assert!(with_host.iter().any(|(name, _)| name == "vcpkg-cmake"));
+        assert!(!with_host.iter().any(|(name, _)| name == "gtest"));
+    }
+
     #[test]
     fn test_parse_conanfile_txt() {
         let temp_dir = TempDir::new().unwrap();
@@ -739,7 +1071,7 @@ cmake
         )
         .unwrap();
 
-        let result = parse_conanfile_txt(&conanfile).unwrap();
+        let result = parse_conanfile_txt(&conanfile, false).unwrap();
 
         assert_eq!(result.len(), 3);
         assert!(result
@@ -753,6 +1085,86 @@ cmake
             .any(|(name, version)| name == "zlib" && version == "1.2.11"));
     }
 
+    #[test]
+    fn test_parse_conanfile_txt_user_channel_and_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let conanfile = temp_dir.path().join("conanfile.txt");
+        fs::write(
+            &conanfile,
+            "[requires]\npoco/1.13.3@user/stable\nfmt/10.2.1#5e23b6a4\n\n[tool_requires]\ncmake/3.28.1\n\n[options]\npoco/*:shared=True\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_conanfile_txt(&conanfile, false).unwrap(),
+            vec![
+                ("poco".to_string(), "1.13.3".to_string()),
+                ("fmt".to_string(), "10.2.1".to_string()),
+            ]
+        );
+        assert!(parse_conanfile_txt(&conanfile, true)
+            .unwrap()
+            .contains(&("cmake".to_string(), "3.28.1".to_string())));
+    }
+
+    #[test]
+    fn test_parse_conanfile_py() {
+        let temp_dir = TempDir::new().unwrap();
+        let conanfile = temp_dir.path().join("conanfile.py");
+        fs::write(
+            &conanfile,
+            r#"from conan import ConanFile
+
+class App(ConanFile):
+    settings = "os", "compiler", "build_type", "arch"
+    requires = (
+        "zlib/1.2.13",
+        "openssl/3.2.1@user/stable",
+    )
+    tool_requires = "cmake/3.28.1"
+
+    def requirements(self):
+        self.requires("boost/1.84.0")
+        if self.options.with_json:
+            self.requires('nlohmann_json/3.11.3@demo/testing')
+        self.test_requires("gtest/1.14.0")
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_conanfile_py(&conanfile, false).unwrap(),
+            vec![
+                ("zlib".to_string(), "1.2.13".to_string()),
+                ("openssl".to_string(), "3.2.1".to_string()),
+                ("boost".to_string(), "1.84.0".to_string()),
+                ("nlohmann_json".to_string(), "3.11.3".to_string()),
+            ]
+        );
+
+        let with_dev = parse_conanfile_py(&conanfile, true).unwrap();
+        assert!(with_dev.iter().any(|(name, _)| name == "cmake"));
+        assert!(with_dev.iter().any(|(name, _)| name == "gtest"));
+    }
+
+    #[test]
+    fn test_conan_recipe_license() {
+        let config = "versions:\n  \"1.3.1\":\n    folder: all\n  \"1.2.13\":\n    folder: old\n";
+        assert_eq!(
+            conan_recipe_folder(config, "1.2.13").as_deref(),
+            Some("old")
+        );
+        assert_eq!(conan_recipe_folder(config, "9.9.9"), None);
+
+        let recipe = "class ZlibConan(ConanFile):\n    name = \"zlib\"\n    license = \"Zlib\"\n";
+        assert_eq!(conan_recipe_license(recipe).as_deref(), Some("Zlib"));
+        let recipe = "class Foo(ConanFile):\n    license = (\"MIT\", \"BSL-1.0\")\n";
+        assert_eq!(
+            conan_recipe_license(recipe).as_deref(),
+            Some("MIT AND BSL-1.0")
+        );
+    }
+
     #[test]
     fn test_parse_cmake_dependencies() {
         let temp_dir = TempDir::new().unwrap();
@@ -766,23 +1178,130 @@ project(TestProject)
 include(FetchContent)
 FetchContent_Declare(json
     URL https://github.com/nlohmann/json/releases/download/v3.10.5/json.tar.xz)
-FetchContent_MakeAvailable(json)
+FetchContent_Declare(
+  googletest
+  GIT_REPOSITORY https://github.com/google/googletest.git
+  GIT_TAG        v1.14.0
+)
+FetchContent_Declare(internal GIT_REPOSITORY https://git.example.com/internal.git)
+FetchContent_MakeAvailable(json googletest internal)
 
 find_package(Boost 1.70 REQUIRED COMPONENTS system filesystem)
 find_package(OpenSSL REQUIRED)
+find_package(googletest CONFIG)
 "#,
         )
         .unwrap();
 
         let config = FeludaConfig::default();
-        let result = parse_cmake_dependencies(temp_dir.path(), &config).unwrap();
+        let (result, git_sources) = parse_cmake_dependencies(temp_dir.path(), &config).unwrap();
 
-        assert!(!result.is_empty());
+        assert_eq!(result.len(), 5);
         assert!(result.iter().any(|(name, _)| name == "json"));
+        assert!(result
+            .iter()
+            .any(|(name, version)| name == "googletest" && version == "v1.14.0"));
+        assert!(result
+            .iter()
+            .any(|(name, version)| name == "internal" && version == "git"));
         assert!(result
             .iter()
             .any(|(name, version)| name == "Boost" && version == "1.70"));
         assert!(result.iter().any(|(name, _)| name == "OpenSSL"));
+
+        assert_eq!(git_sources.len(), 3);
+        assert_eq!(
+            git_sources.get("googletest").map(String::as_str),
+            Some("https://github.com/google/googletest.git")
+        );
+        assert!(!git_sources.contains_key("Boost"));
+    }
+
+    #[test]
+    fn test_github_repository() {
+        assert_eq!(
+            github_repository("https://github.com/google/googletest.git"),
+            Some(("google".to_string(), "googletest".to_string()))
+        );
+        assert_eq!(
+            github_repository(
+                "https://github.com/nlohmann/json/releases/download/v3.10.5/json.tar.xz"
+            ),
+            Some(("nlohmann".to_string(), "json".to_string()))
+        );
+        assert_eq!(
+            github_repository("git@github.com:fmtlib/fmt.git"),
+            Some(("fmtlib".to_string(), "fmt".to_string()))
+        );
+        assert_eq!(
+            github_repository("https://git.example.com/internal.git"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_module_bazel() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("MODULE.bazel"),
+            r#"module(name = "my_project", version = "1.0.0")
+
+bazel_dep(name = "abseil-cpp", version = "20240116.2")
+bazel_dep(name = "protobuf", version = "27.0", repo_name = "com_google_protobuf")
+bazel_dep(
+    version = "1.14.0",
+    name = "googletest",
+    dev_dependency = True,
+)
+bazel_dep(name = "rules_cc")  # pinned with single_version_override
+# bazel_dep(name = "zlib", version = "1.3.1")
+"#,
+        )
+        .unwrap();
+
+        let config = FeludaConfig::default();
+        let result = parse_bazel_dependencies(temp_dir.path(), &config).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                ("abseil-cpp".to_string(), "20240116.2".to_string()),
+                ("protobuf".to_string(), "27.0".to_string()),
+                ("rules_cc".to_string(), "latest".to_string()),
+            ]
+        );
+
+        let config = FeludaConfig {
+            include_dev: true,
+            ..FeludaConfig::default()
+        };
+        let result = parse_bazel_dependencies(temp_dir.path(), &config).unwrap();
+        assert_eq!(result.len(), 4);
+        assert!(result
+            .iter()
+            .any(|(name, version)| name == "googletest" && version == "1.14.0"));
+    }
+
+    #[test]
+    fn test_bazel_registry_license_lookup() {
+        let metadata = serde_json::json!({
+            "homepage": "https://abseil.io",
+            "repository": ["https://example.com/mirror", "github:abseil/abseil-cpp"],
+            "versions": ["20240116.2"]
+        });
+        assert_eq!(
+            bcr_github_repository(&metadata),
+            Some(("abseil".to_string(), "abseil-cpp".to_string()))
+        );
+        assert_eq!(bcr_github_repository(&serde_json::json!({})), None);
+
+        let license =
+            serde_json::json!({"license": {"key": "apache-2.0", "spdx_id": "Apache-2.0"}});
+        assert_eq!(
+            crate::licenses::github_license_spdx_id(&license).as_deref(),
+            Some("Apache-2.0")
+        );
+        let other = serde_json::json!({"license": {"key": "other", "spdx_id": "NOASSERTION"}});
+        assert_eq!(crate::licenses::github_license_spdx_id(&other), None);
     }
 
     #[test]
@@ -1,16 +1,19 @@
 use regex::Regex;
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::cache;
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
-};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+use crate::network::{http_client, skip_network, with_network_permit};
+
+/// Ecosystem name for NuGet packages
+const ECOSYSTEM: &str = "nuget";
 
 #[derive(Debug, Clone)]
 pub struct NuGetPackage {
@@ -34,6 +37,31 @@ struct PackageLockInfo {
     dependencies: Option<HashMap<String, String>>,
 }
 
+/// Parses .NET projects through the [`LanguageParser`] interface
+pub struct DotNetParser;
+
+impl LanguageParser for DotNetParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_dotnet_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        ".NET"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::DOTNET_PATHS
+    }
+}
+
 pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
     log(
         LogLevel::Info,
@@ -54,7 +82,7 @@ pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec
         }
     };
 
-    let direct_deps = match detect_and_parse_project(project_path) {
+    let direct_deps = match detect_and_parse_project(project_path, config.direct_only) {
         Ok(deps) => deps,
         Err(err) => {
             log_error("Failed to parse .NET project", &err);
@@ -68,7 +96,12 @@ pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec
     );
     log_debug("Direct .NET dependencies", &direct_deps);
 
-    let max_depth = config.dependencies.max_depth;
+    // A depth of 0 keeps the declared packages without asking dotnet for transitive ones
+    let max_depth = if config.direct_only {
+        0
+    } else {
+        config.dependencies.max_depth
+    };
     log(
         LogLevel::Info,
         &format!("Using max dependency depth: {max_depth}"),
@@ -76,6 +109,8 @@ pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec
 
     let all_deps = resolve_dotnet_dependencies(project_path, &direct_deps, max_depth);
 
+    crate::cli::add_dependencies_to_resolve(all_deps.len());
+
     let mut licenses = Vec::new();
     for (name, version) in all_deps {
         log(
@@ -94,16 +129,16 @@ pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec
             );
         }
 
+        crate::cli::mark_dependency_resolved();
+
         licenses.push(LicenseInfo {
-            name,
-            version,
-            license: license.clone(),
             is_restrictive,
-            compatibility: LicenseCompatibility::Unknown,
             osi_status: match &license {
                 Some(l) => crate::licenses::get_osi_status(l),
                 None => crate::licenses::OsiStatus::Unknown,
             },
+            ecosystem: ECOSYSTEM.to_string(),
+            ..LicenseInfo::new(name, version, license)
         });
     }
 
@@ -114,7 +149,10 @@ pub fn analyze_dotnet_licenses(project_path: &str, config: &FeludaConfig) -> Vec
     licenses
 }
 
-fn detect_and_parse_project(project_path: &str) -> Result<Vec<NuGetPackage>, String> {
+fn detect_and_parse_project(
+    project_path: &str,
+    direct_only: bool,
+) -> Result<Vec<NuGetPackage>, String> {
     let path = Path::new(project_path);
 
     if path.extension().and_then(|s| s.to_str()) == Some("slnx") {
@@ -128,7 +166,7 @@ fn detect_and_parse_project(project_path: &str) -> Result<Vec<NuGetPackage>, Str
     } else {
         let parent_dir = path.parent().unwrap_or(path);
         if let Ok(lock_path) = find_file_in_dir(parent_dir, "packages.lock.json") {
-            parse_packages_lock_json(&lock_path)
+            parse_packages_lock_json(&lock_path, direct_only)
         } else {
             parse_csproj_file(project_path)
         }
@@ -246,7 +284,11 @@ fn parse_project_references(content: &str, base_dir: &Path) -> Result<Vec<String
     Ok(references)
 }
 
-fn parse_packages_lock_json(lock_path: &str) -> Result<Vec<NuGetPackage>, String> {
+/// Packages pinned in `packages.lock.json`; with `direct_only`, only those of type `Direct`
+fn parse_packages_lock_json(
+    lock_path: &str,
+    direct_only: bool,
+) -> Result<Vec<NuGetPackage>, String> {
     log(
         LogLevel::Info,
         &format!("Parsing packages.lock.json: {lock_path}"),
@@ -263,6 +305,9 @@ fn parse_packages_lock_json(lock_path: &str) -> Result<Vec<NuGetPackage>, String
     if let Some(dependencies) = lock_data.dependencies {
         for (_framework, packages_map) in dependencies {
             for (name, info) in packages_map {
+                if direct_only && info.package_type.as_deref() != Some("Direct") {
+                    continue;
+                }
                 if let Some(resolved) = &info.resolved {
                     packages.push(NuGetPackage {
                         name: name.clone(),
@@ -356,19 +401,21 @@ fn parse_dotnet_list_output(output: &str) -> Result<Vec<(String, String)>, Strin
 }
 
 fn fetch_license_for_nuget_package(name: &str, version: &str) -> String {
-    if let Ok(license) = fetch_from_local_nuget_cache(name, version) {
-        return license;
-    }
+    cache::cached_package_license("dotnet", name, version, || {
+        if let Ok(license) = fetch_from_local_nuget_cache(name, version) {
+            return license;
+        }
 
-    if let Ok(license) = fetch_from_nuget_api(name, version) {
-        return license;
-    }
+        if let Ok(license) = fetch_from_nuget_api(name, version) {
+            return license;
+        }
 
-    log(
-        LogLevel::Warn,
-        &format!("Could not find license for {name} {version}"),
-    );
-    "Unknown".to_string()
+        log(
+            LogLevel::Warn,
+            &format!("Could not find license for {name} {version}"),
+        );
+        "Unknown".to_string()
+    })
 }
 
 fn fetch_from_local_nuget_cache(name: &str, version: &str) -> Result<String, String> {
@@ -393,10 +440,11 @@ fn fetch_from_local_nuget_cache(name: &str, version: &str) -> Result<String, Str
 }
 
 fn fetch_from_nuget_api(name: &str, version: &str) -> Result<String, String> {
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    if skip_network(&format!("NuGet license of {name}")) {
+        return Err("NuGet lookup disabled in offline mode".to_string());
+    }
+
+    let client = http_client().map_err(|e| format!("Failed to create HTTP client: {e}"))?;
 
     let nuspec_url = format!(
         "https://api.nuget.org/v3-flatcontainer/{}/{}/{}.nuspec",
@@ -410,9 +458,7 @@ fn fetch_from_nuget_api(name: &str, version: &str) -> Result<String, String> {
         &format!("Fetching from NuGet: {nuspec_url}"),
     );
 
-    let response = client
-        .get(&nuspec_url)
-        .send()
+    let response = with_network_permit(|| client.get(&nuspec_url).send())
         .map_err(|e| format!("Failed to fetch nuspec: {e}"))?;
 
     if !response.status().is_success() {
@@ -463,3 +509,40 @@ fn find_file_in_dir(dir: &Path, filename: &str) -> Result<String, String> {
         Err(format!("{filename} not found in directory"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packages_lock_json_direct_only() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("packages.lock.json");
+        fs::write(
+            &lock_path,
+            r#"{
+  "version": 1,
+  "dependencies": {
+    "net8.0": {
+      "Newtonsoft.Json": {"type": "Direct", "requested": "[13.0.3, )", "resolved": "13.0.3"},
+      "System.Memory": {"type": "Transitive", "resolved": "4.5.5"}
+    }
+  }
+}"#,
+        )
+        .unwrap();
+        let lock_path = lock_path.to_str().unwrap();
+
+        let names = |direct_only| {
+            let mut names: Vec<String> = parse_packages_lock_json(lock_path, direct_only)
+                .unwrap()
+                .into_iter()
+                .map(|package| package.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(true), vec!["Newtonsoft.Json"]);
+        assert_eq!(names(false), vec!["Newtonsoft.Json", "System.Memory"]);
+    }
+}
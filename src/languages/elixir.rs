@@ -0,0 +1,438 @@
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::cache;
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+use crate::network::{http_client, skip_network, with_network_permit};
+
+/// Ecosystem name for `mix.lock` entries
+const ECOSYSTEM: &str = "hex";
+
+/// Where a `mix.lock` entry was fetched from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MixSource {
+    /// Published on Hex, with the Hex package name (which may differ from the lock key)
+    Hex(String),
+    /// Git, path or any other non-Hex source
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MixLockEntry {
+    name: String,
+    version: String,
+    source: MixSource,
+}
+
+/// Parses Elixir projects through the [`LanguageParser`] interface
+pub struct ElixirParser;
+
+impl LanguageParser for ElixirParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_elixir_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Elixir"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &["mix.lock"]
+    }
+}
+
+pub fn analyze_elixir_licenses(mix_lock_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
+    log(
+        LogLevel::Info,
+        &format!("Analyzing Elixir dependencies from: {mix_lock_path}"),
+    );
+
+    let content = match fs::read_to_string(mix_lock_path) {
+        Ok(content) => content,
+        Err(err) => {
+            log_error("Failed to read mix.lock file", &err);
+            return Vec::new();
+        }
+    };
+
+    let mut entries = parse_mix_lock(&content);
+    log(
+        LogLevel::Info,
+        &format!("Found {} packages in mix.lock", entries.len()),
+    );
+    log_debug("mix.lock entries", &entries);
+
+    // mix.lock pins the whole tree; only mix.exs says which packages are direct
+    if config.direct_only {
+        let mix_exs_path = Path::new(mix_lock_path).with_file_name("mix.exs");
+        match fs::read_to_string(&mix_exs_path) {
+            Ok(mix_exs) => {
+                let declared = mix_exs_dependency_names(&mix_exs, config.include_dev);
+                entries.retain(|entry| declared.contains(&entry.name));
+                log(
+                    LogLevel::Info,
+                    &format!("Direct-only: kept {} packages from mix.exs", entries.len()),
+                );
+            }
+            Err(err) => log_error(
+                "Failed to read mix.exs, keeping every mix.lock package",
+                &err,
+            ),
+        }
+    }
+
+    let known_licenses = match fetch_licenses_from_github() {
+        Ok(licenses) => licenses,
+        Err(err) => {
+            log_error("Failed to fetch licenses from GitHub", &err);
+            HashMap::new()
+        }
+    };
+
+    crate::cli::add_dependencies_to_resolve(entries.len());
+
+    let licenses: Vec<LicenseInfo> = entries
+        .into_iter()
+        .map(|entry| {
+            let license = match &entry.source {
+                MixSource::Hex(hex_name) => {
+                    fetch_license_for_hex_dependency(hex_name, &entry.version)
+                }
+                MixSource::Other(kind) => {
+                    log(
+                        LogLevel::Info,
+                        &format!(
+                            "Skipping license lookup for {} ({kind} dependency)",
+                            entry.name
+                        ),
+                    );
+                    String::from("Unknown")
+                }
+            };
+            let license = Some(license);
+            let is_restrictive = is_license_restrictive(&license, &known_licenses, config.strict);
+
+            if is_restrictive {
+                log(
+                    LogLevel::Warn,
+                    &format!("Restrictive license found: {license:?} for {}", entry.name),
+                );
+            }
+
+            crate::cli::mark_dependency_resolved();
+
+            LicenseInfo {
+                is_restrictive,
+                osi_status: match &license {
+                    Some(l) => crate::licenses::get_osi_status(l),
+                    None => crate::licenses::OsiStatus::Unknown,
+                },
+                ecosystem: ECOSYSTEM.to_string(),
+                ..LicenseInfo::new(entry.name, entry.version, license)
+            }
+        })
+        .collect();
+
+    log(
+        LogLevel::Info,
+        &format!("Found {} Elixir dependencies with licenses", licenses.len()),
+    );
+    licenses
+}
+
+/// Names of the dependencies declared in `mix.exs`, e.g. `phoenix` for `{:phoenix, "~> 1.7"}`
+///
+/// Dependencies limited with `only:` to environments other than `:prod` count only
+/// with `include_dev`.
+fn mix_exs_dependency_names(content: &str, include_dev: bool) -> HashSet<String> {
+    let Ok(dep_re) = Regex::new(r"\{\s*:(\w+)\s*,([^{}]*)\}") else {
+        return HashSet::new();
+    };
+    let deps = content
+        .find("defp deps")
+        .map_or(content, |start| &content[start..]);
+
+    dep_re
+        .captures_iter(deps)
+        .filter(|cap| include_dev || !cap[2].contains("only:") || cap[2].contains(":prod"))
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
+/// Parse the entries of a `mix.lock` file
+///
+/// Each entry sits on its own line as `"name": {:source, ...}`, so a line-based scan is
+/// enough and avoids needing a full Erlang term parser.
+fn parse_mix_lock(content: &str) -> Vec<MixLockEntry> {
+    let (Ok(entry_re), Ok(hex_re), Ok(git_re)) = (
+        Regex::new(r#"^"([^"]+)"\s*:\s*\{\s*:(\w+)\s*,\s*(.*)$"#),
+        Regex::new(r#"^:"?([A-Za-z0-9_]+)"?\s*,\s*"([^"]+)""#),
+        Regex::new(r#"^"[^"]*"\s*,\s*"([^"]+)""#),
+    ) else {
+        log(LogLevel::Error, "Failed to compile mix.lock patterns");
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let Some(caps) = entry_re.captures(line.trim()) else {
+            continue;
+        };
+        let name = caps[1].to_string();
+        let kind = &caps[2];
+        let rest = &caps[3];
+
+        let entry = if kind == "hex" {
+            match hex_re.captures(rest) {
+                Some(hex) => MixLockEntry {
+                    name,
+                    version: hex[2].to_string(),
+                    source: MixSource::Hex(hex[1].to_string()),
+                },
+                None => {
+                    log(
+                        LogLevel::Warn,
+                        &format!("Could not parse hex entry for {name} in mix.lock"),
+                    );
+                    continue;
+                }
+            }
+        } else {
+            // Git entries carry the locked commit as their second string
+            let version = git_re
+                .captures(rest)
+                .map(|git| git[1].to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            MixLockEntry {
+                name,
+                version,
+                source: MixSource::Other(kind.to_string()),
+            }
+        };
+        entries.push(entry);
+    }
+
+    entries
+}
+
+pub fn fetch_license_for_hex_dependency(name: &str, version: &str) -> String {
+    cache::cached_package_license("elixir", name, version, || fetch_license_from_hex(name))
+}
+
+fn fetch_license_from_hex(name: &str) -> String {
+    if skip_network(&format!("Hex license of {name}")) {
+        return String::from("Unknown");
+    }
+
+    let api_url = format!("https://hex.pm/api/packages/{name}");
+    log(
+        LogLevel::Info,
+        &format!("Fetching license from Hex: {api_url}"),
+    );
+
+    let client = match http_client() {
+        Ok(client) => client,
+        Err(err) => {
+            log_error("Failed to build HTTP client", &err);
+            return String::from("Unknown");
+        }
+    };
+
+    match with_network_permit(|| client.get(&api_url).send()) {
+        Ok(response) => {
+            let status = response.status();
+            log(
+                LogLevel::Info,
+                &format!("Hex API response status: {status}"),
+            );
+
+            if !status.is_success() {
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to fetch metadata for {name}: HTTP {status}"),
+                );
+                return String::from("Unknown");
+            }
+
+            match response.json::<Value>() {
+                Ok(json) => hex_license_expression(&json).unwrap_or_else(|| {
+                    log(LogLevel::Warn, &format!("No license found for {name}"));
+                    format!("Unknown license for {name}")
+                }),
+                Err(err) => {
+                    log_error(&format!("Failed to parse JSON for {name}"), &err);
+                    String::from("Unknown")
+                }
+            }
+        }
+        Err(err) => {
+            log_error(&format!("Failed to fetch metadata for {name}"), &err);
+            String::from("Unknown")
+        }
+    }
+}
+
+/// Build a license expression from the `meta.licenses` array of a Hex package
+///
+/// Hex does not say how several licenses combine, so assume all of them apply.
+fn hex_license_expression(package: &Value) -> Option<String> {
+    let licenses: Vec<&str> = package["meta"]["licenses"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .map(str::trim)
+        .filter(|license| !license.is_empty())
+        .collect();
+
+    match licenses.len() {
+        0 => None,
+        1 => Some(licenses[0].to_string()),
+        _ => Some(licenses.join(" AND ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::set_offline_mode;
+    use serde_json::json;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    const SAMPLE_MIX_LOCK: &str = r#"%{
+  "cowboy": {:hex, :cowboy, "2.10.0", "ff9ffeff91dae4ae270dd975642997afe2a1179d94b1887863e43f681a203e26", [:make, :rebar3], [{:cowlib, "2.12.1", [hex: :cowlib, repo: "hexpm", optional: false]}], "hexpm", "3afdccb7183cc6f143cb14d3cf51fa00e53db9ec80cdcd525482f5e99bc41d6b"},
+  "jason": {:hex, :jason, "1.4.1", "af1504e35f629ddcdd6addb3513c3853991f694921b1b9368b0bd32beb9f1b63", [:mix], [{:decimal, "~> 1.0 or ~> 2.0", [hex: :decimal, repo: "hexpm", optional: true]}], "hexpm", "fbb01ecdfd565b56261302f7e1fcc27c4fb8f32d56eab74db621fc154604a7a1"},
+  "my_fork": {:git, "https://github.com/example/my_fork.git", "0d5a0b1c2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b", [branch: "main"]},
+  "renamed": {:hex, :plug_crypto, "2.0.0", "77515cc10af06645abbfb5e6ad7a3e9714f805ae118fa1a70205f80d2d70fe73", [:mix], [], "hexpm", "53695bae57cc4e54566d993eb01074e4d894b65a3766f1c43e2c61a1b0f45ea9"},
+}
+"#;
+
+    #[test]
+    fn test_parse_mix_lock_hex_and_git_entries() {
+        let entries = parse_mix_lock(SAMPLE_MIX_LOCK);
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(
+            entries[0],
+            MixLockEntry {
+                name: "cowboy".to_string(),
+                version: "2.10.0".to_string(),
+                source: MixSource::Hex("cowboy".to_string()),
+            }
+        );
+        assert_eq!(
+            entries[2],
+            MixLockEntry {
+                name: "my_fork".to_string(),
+                version: "0d5a0b1c2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b".to_string(),
+                source: MixSource::Other("git".to_string()),
+            }
+        );
+        // The lock key and the Hex package name can differ
+        assert_eq!(entries[3].name, "renamed");
+        assert_eq!(entries[3].source, MixSource::Hex("plug_crypto".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mix_lock_ignores_unrelated_lines() {
+        assert!(parse_mix_lock("%{\n}\n").is_empty());
+        assert!(parse_mix_lock("# not a lock file").is_empty());
+    }
+
+    #[test]
+    fn test_hex_license_expression() {
+        assert_eq!(
+            hex_license_expression(&json!({"meta": {"licenses": ["Apache-2.0"]}})),
+            Some("Apache-2.0".to_string())
+        );
+        assert_eq!(
+            hex_license_expression(&json!({"meta": {"licenses": ["MIT", "Apache-2.0"]}})),
+            Some("MIT AND Apache-2.0".to_string())
+        );
+        assert_eq!(
+            hex_license_expression(&json!({"meta": {"licenses": []}})),
+            None
+        );
+        assert_eq!(hex_license_expression(&json!({"meta": {}})), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_analyze_elixir_licenses_marks_git_dependencies_unknown() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("mix.lock");
+        fs::write(&lock_path, SAMPLE_MIX_LOCK).unwrap();
+
+        set_offline_mode(true);
+        let result = analyze_elixir_licenses(lock_path.to_str().unwrap(), &FeludaConfig::default());
+        set_offline_mode(false);
+
+        assert_eq!(result.len(), 4);
+        let fork = result.iter().find(|info| info.name == "my_fork").unwrap();
+        assert_eq!(fork.license.as_deref(), Some("Unknown"));
+        assert!(result
+            .iter()
+            .any(|info| info.name == "jason" && info.version == "1.4.1"));
+    }
+
+    #[test]
+    fn test_mix_exs_dependency_names() {
+        let mix_exs = r#"defmodule App.MixProject do
+  def project, do: [app: :app, deps: deps()]
+
+  defp deps do
+    [
+      {:cowboy, "~> 2.10"},
+      {:my_fork, git: "https://github.com/example/my_fork.git", branch: "main"},
+      {:credo, "~> 1.7", only: [:dev, :test], runtime: false}
+    ]
+  end
+end
+"#;
+
+        let names = mix_exs_dependency_names(mix_exs, false);
+        assert_eq!(
+            names,
+            HashSet::from(["cowboy".to_string(), "my_fork".to_string()])
+        );
+        assert!(mix_exs_dependency_names(mix_exs, true).contains("credo"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_direct_only_keeps_mix_exs_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("mix.lock");
+        fs::write(&lock_path, SAMPLE_MIX_LOCK).unwrap();
+        fs::write(
+            temp_dir.path().join("mix.exs"),
+            "defp deps do\n  [{:cowboy, \"~> 2.10\"}, {:jason, \"~> 1.4\"}]\nend\n",
+        )
+        .unwrap();
+
+        let config = FeludaConfig {
+            direct_only: true,
+            ..FeludaConfig::default()
+        };
+        set_offline_mode(true);
+        let result = analyze_elixir_licenses(lock_path.to_str().unwrap(), &config);
+        set_offline_mode(false);
+
+        let names: Vec<&str> = result.iter().map(|info| info.name.as_str()).collect();
+        assert_eq!(names, vec!["cowboy", "jason"]);
+    }
+}
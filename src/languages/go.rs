@@ -1,18 +1,24 @@
 use regex::Regex;
 use reqwest::blocking::Client;
 use scraper::{Html, Selector};
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
 use std::thread::sleep;
 use std::time::Duration;
 
+use crate::cache;
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
-};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{analyze_resolved_dependencies, project_file_str, LanguageParser};
+use crate::licenses::{fetch_github_repository_license, fetch_licenses_from_github, LicenseInfo};
+use crate::network::{http_client_builder, skip_network, with_network_permit};
+
+/// Ecosystem name for Go modules
+const ECOSYSTEM: &str = "go";
 
 /// Go module names to exclude from dependency analysis
 /// These are special Go directives and built-in modules, not actual dependencies
@@ -23,6 +29,30 @@ const EXCLUDED_GO_MODULES: &[&str] = &["go", "toolchain"];
 pub struct GoPackages {
     pub name: String,
     pub version: String,
+    /// False when the `require` line is marked `// indirect`
+    pub direct: bool,
+}
+
+/// Parses Go projects through the [`LanguageParser`] interface
+pub struct GoParser;
+
+impl LanguageParser for GoParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_go_licenses(project_file_str(project_file)?, config))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Go"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &["go.mod"]
+    }
 }
 
 /// Analyze the licenses of Go dependencies
@@ -61,44 +91,45 @@ pub fn analyze_go_licenses(go_mod_path: &str, config: &FeludaConfig) -> Vec<Lice
     );
     log_debug("Direct Go dependencies", &direct_dependencies);
 
-    // Try to resolve all dependencies using go mod graph
-    let max_depth = config.dependencies.max_depth;
-    log(
-        LogLevel::Info,
-        &format!("Using max dependency depth: {max_depth}"),
-    );
-    let all_deps = resolve_go_dependencies(go_mod_path, &direct_dependencies, max_depth);
+    let direct_names: HashSet<String> = direct_dependencies
+        .iter()
+        .filter(|dep| dep.direct)
+        .map(|dep| dep.name.clone())
+        .collect();
 
-    // Process all resolved dependencies
-    let mut licenses = Vec::new();
-    for (name, version) in all_deps {
+    let all_deps = if config.direct_only {
+        // Requirements marked `// indirect` are only there to pin transitive versions
         log(
             LogLevel::Info,
-            &format!("Processing dependency: {name} ({version})"),
+            "Direct-only: skipping `// indirect` requires",
         );
+        direct_dependencies
+            .into_iter()
+            .filter(|dep| dep.direct)
+            .map(|dep| (dep.name, dep.version))
+            .collect()
+    } else {
+        // Try to resolve all dependencies using go mod graph
+        let max_depth = config.dependencies.max_depth;
+        log(
+            LogLevel::Info,
+            &format!("Using max dependency depth: {max_depth}"),
+        );
+        resolve_go_dependencies(go_mod_path, &direct_dependencies, max_depth)
+    };
 
-        let license_result = fetch_license_for_go_dependency(name.as_str(), version.as_str());
-        let license = Some(license_result);
-        let is_restrictive = is_license_restrictive(&license, &known_licenses, config.strict);
-
-        if is_restrictive {
-            log(
-                LogLevel::Warn,
-                &format!("Restrictive license found: {license:?} for {name}"),
-            );
-        }
+    // Process all resolved dependencies
+    let mut licenses = analyze_resolved_dependencies(
+        all_deps,
+        &known_licenses,
+        config.strict,
+        ECOSYSTEM,
+        |name, version| fetch_license_for_go_dependency(name, version),
+    );
 
-        licenses.push(LicenseInfo {
-            name,
-            version,
-            license: license.clone(),
-            is_restrictive,
-            compatibility: LicenseCompatibility::Unknown,
-            osi_status: match &license {
-                Some(l) => crate::licenses::get_osi_status(l),
-                None => crate::licenses::OsiStatus::Unknown,
-            },
-        });
+    // Modules only found through resolution are indirect, like `// indirect` requires
+    for info in &mut licenses {
+        info.is_direct = Some(direct_names.contains(&info.name));
     }
 
     log(
@@ -112,6 +143,8 @@ pub fn analyze_go_licenses(go_mod_path: &str, config: &FeludaConfig) -> Vec<Lice
 pub fn get_go_dependencies(content_string: String) -> Vec<GoPackages> {
     log(LogLevel::Info, "Parsing Go dependencies");
 
+    let indirect = indirect_go_modules(&content_string);
+
     let re_comment = match Regex::new(r"(?m)^(.*?)\s*(//|#).*?$") {
         Ok(re) => re,
         Err(err) => {
@@ -163,7 +196,12 @@ pub fn get_go_dependencies(content_string: String) -> Vec<GoPackages> {
                 &format!("Found Go dependency: {name} ({version})"),
             );
 
-            dependency.push(GoPackages { name, version });
+            let direct = !indirect.contains(&name);
+            dependency.push(GoPackages {
+                name,
+                version,
+                direct,
+            });
         }
     }
 
@@ -174,6 +212,19 @@ pub fn get_go_dependencies(content_string: String) -> Vec<GoPackages> {
     dependency
 }
 
+/// Modules whose `require` line in go.mod carries an `// indirect` comment
+fn indirect_go_modules(content: &str) -> HashSet<String> {
+    let Ok(re_indirect) = Regex::new(r"(?m)^\s*(?:require\s+)?([\w./-]+)\s+v\S+\s*//\s*indirect")
+    else {
+        return HashSet::new();
+    };
+
+    re_indirect
+        .captures_iter(content)
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
 /// Resolve all Go dependencies
 fn resolve_go_dependencies(
     go_mod_path: &str,
@@ -185,6 +236,23 @@ fn resolve_go_dependencies(
         &format!("Resolving Go dependencies (including transitive up to depth {max_depth})"),
     );
 
+    // go.sum pins the complete module set without needing the Go toolchain
+    let go_sum_path = Path::new(go_mod_path).with_file_name("go.sum");
+    if let Ok(content) = fs::read_to_string(&go_sum_path) {
+        let go_deps = resolve_with_go_sum(&content, direct_deps);
+        if !go_deps.is_empty() {
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Resolved {} dependencies from {}",
+                    go_deps.len(),
+                    go_sum_path.display()
+                ),
+            );
+            return go_deps;
+        }
+    }
+
     // go mod graph for complete dependency resolution
     if let Ok(go_deps) = resolve_with_go_mod_graph(go_mod_path, max_depth) {
         if !go_deps.is_empty() {
@@ -211,6 +279,58 @@ fn resolve_go_dependencies(
         .collect()
 }
 
+/// Resolve the module set from `go.sum` content, using go.mod versions where listed
+///
+/// Every module version in `go.sum` has a hash for its `go.mod` and, when its code is part
+/// of the build, a second one for the module zip. Versions with only a `/go.mod` line were
+/// considered during version selection but not picked, so they are skipped. If several
+/// versions of a module have zip lines the highest is kept, unless go.mod requires one
+/// explicitly. `go.sum` carries no graph, so the depth limit does not apply.
+fn resolve_with_go_sum(content: &str, direct_deps: &[GoPackages]) -> Vec<(String, String)> {
+    let mut modules: BTreeMap<String, String> = BTreeMap::new();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(version), Some(_hash)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if version.ends_with("/go.mod") || is_excluded_go_module(name) {
+            continue;
+        }
+
+        match modules.get(name) {
+            Some(current) if compare_go_versions(current, version) != Ordering::Less => {}
+            _ => {
+                modules.insert(name.to_string(), version.to_string());
+            }
+        }
+    }
+
+    for dep in direct_deps {
+        modules.insert(dep.name.clone(), dep.version.clone());
+    }
+
+    modules.into_iter().collect()
+}
+
+/// Order Go module versions by semver, including pseudo-versions and `+incompatible`
+fn compare_go_versions(a: &str, b: &str) -> Ordering {
+    let parse = |version: &str| {
+        semver::Version::parse(
+            version
+                .trim_start_matches('v')
+                .trim_end_matches("+incompatible"),
+        )
+        .ok()
+    };
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
 /// Resolve dependencies using go mod graph with depth limit
 fn resolve_with_go_mod_graph(
     go_mod_path: &str,
@@ -417,7 +537,8 @@ fn parse_go_module_version(module_str: &str) -> Option<(String, String)> {
     }
 }
 
-/// Fetch the license for a Go dependency, trying local sources first, then pkg.go.dev
+/// Fetch the license for a Go dependency, trying local sources first, then pkg.go.dev and
+/// finally the GitHub repository inferred from the module path
 pub fn fetch_license_for_go_dependency(
     name: impl Into<String>,
     version: impl Into<String>,
@@ -425,23 +546,74 @@ pub fn fetch_license_for_go_dependency(
     let name = name.into();
     let version = version.into();
 
-    if let Some(license) = get_license_from_local_go_mod(&name) {
-        log(
-            LogLevel::Info,
-            &format!("Found license in local go.mod for {name}: {license}"),
-        );
-        return license;
-    }
+    cache::cached_package_license("go", &name, &version, || {
+        if let Some(license) = get_license_from_local_go_mod(&name) {
+            log(
+                LogLevel::Info,
+                &format!("Found license in local go.mod for {name}: {license}"),
+            );
+            return license;
+        }
 
-    if let Some(license) = get_license_from_go_module_cache(&name, &version) {
-        log(
-            LogLevel::Info,
-            &format!("Found license in Go module cache for {name}: {license}"),
-        );
-        return license;
-    }
+        if let Some(license) = get_license_from_go_module_cache(&name, &version) {
+            log(
+                LogLevel::Info,
+                &format!("Found license in Go module cache for {name}: {license}"),
+            );
+            return license;
+        }
+
+        if skip_network(&name) {
+            return "Unknown".into();
+        }
 
-    fetch_license_from_pkg_go_dev(&name)
+        pkg_go_dev_client()
+            .and_then(|client| {
+                fetch_license_from_pkg_go_dev(client, PKG_GO_DEV_URL, &name, &version)
+            })
+            .or_else(|| {
+                let (owner, repo) = github_repository_for_module(&name)?;
+                log(
+                    LogLevel::Info,
+                    &format!("Falling back to GitHub repository {owner}/{repo} for {name}"),
+                );
+                fetch_github_repository_license(&owner, &repo)
+            })
+            .unwrap_or_else(|| "Unknown".into())
+    })
+}
+
+/// GitHub repository a module is hosted in, inferred from its path
+///
+/// `github.com/owner/repo` paths may carry a subpackage or a `/vN` major version suffix;
+/// `gopkg.in/pkg.vN` and `gopkg.in/owner/pkg.vN` redirect to `go-pkg/pkg` and `owner/pkg`.
+/// Other hosts and vanity import paths are only resolved through pkg.go.dev.
+fn github_repository_for_module(name: &str) -> Option<(String, String)> {
+    let mut segments = name.split('/');
+    let (owner, repo) = match segments.next()? {
+        "github.com" => (segments.next()?.to_string(), segments.next()?.to_string()),
+        "gopkg.in" => {
+            let first = segments.next()?;
+            match segments.next() {
+                Some(package) => (first.to_string(), strip_gopkg_version(package)?),
+                None => {
+                    let package = strip_gopkg_version(first)?;
+                    (format!("go-{package}"), package)
+                }
+            }
+        }
+        _ => return None,
+    };
+    (!owner.is_empty() && !repo.is_empty()).then_some((owner, repo))
+}
+
+/// Package name of a `gopkg.in` path element such as `yaml.v3`
+fn strip_gopkg_version(element: &str) -> Option<String> {
+    let (package, major) = element.rsplit_once(".v")?;
+    major
+        .chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| package.to_string())
 }
 
 fn get_license_from_local_go_mod(package_name: &str) -> Option<String> {
@@ -586,43 +758,73 @@ fn detect_license_from_content(content: &str) -> Option<String> {
     None
 }
 
-fn fetch_license_from_pkg_go_dev(name: &str) -> String {
-    let api_url = format!("https://pkg.go.dev/{name}?tab=licenses");
+/// Go package index that detects licenses for modules on any host
+const PKG_GO_DEV_URL: &str = "https://pkg.go.dev";
+
+/// Shared pkg.go.dev client, reused across parallel lookups
+fn pkg_go_dev_client() -> Option<&'static Client> {
+    static CLIENT: OnceLock<Option<Client>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            match http_client_builder()
+                .user_agent("feluda.anirudha.dev/1")
+                .connect_timeout(Duration::from_secs(60))
+                .build()
+            {
+                Ok(client) => Some(client),
+                Err(err) => {
+                    log_error("Failed to build HTTP client", &err);
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Licenses tab of a module on pkg.go.dev, at `version` when it is known
+///
+/// The `+` of `+incompatible` versions is escaped so it survives as part of the path.
+fn pkg_go_dev_license_url(base_url: &str, name: &str, version: &str) -> String {
+    if version.is_empty() || version == "unknown" {
+        return format!("{base_url}/{name}?tab=licenses");
+    }
+    let version = version.replace('+', "%2B");
+    format!("{base_url}/{name}@{version}?tab=licenses")
+}
+
+/// License pkg.go.dev detected for a module; works for any host, including GitLab,
+/// Bitbucket and vanity import paths
+fn fetch_license_from_pkg_go_dev(
+    client: &Client,
+    base_url: &str,
+    name: &str,
+    version: &str,
+) -> Option<String> {
+    let api_url = pkg_go_dev_license_url(base_url, name, version);
     log(
         LogLevel::Info,
         &format!("Fetching license from Go Package Index: {api_url}"),
     );
 
-    let client = match Client::builder()
-        .user_agent("feluda.anirudha.dev/1")
-        .connect_timeout(Duration::from_secs(60))
-        .timeout(Duration::from_secs(10))
-        .build()
-    {
-        Ok(client) => client,
-        Err(err) => {
-            log_error("Failed to build HTTP client", &err);
-            return "Unknown".into();
-        }
-    };
-
     let mut attempts = 0;
     let max_attempts = 7; // Retry max 7 times. Thala for a reason 🙌
     let wait_time = 12;
 
     while attempts < max_attempts {
-        let response = client
-            .get(&api_url)
-            .header(
-                "User-Agent",
-                "Mozilla/5.0 (compatible; Feluda-Bot/1.0; +https://github.com/anistark/feluda)",
-            )
-            .header(
-                "Accept",
-                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
-            )
-            .header("Referer", "https://pkg.go.dev/")
-            .send();
+        let response = with_network_permit(|| {
+            client
+                .get(&api_url)
+                .header(
+                    "User-Agent",
+                    "Mozilla/5.0 (compatible; Feluda-Bot/1.0; +https://github.com/anistark/feluda)",
+                )
+                .header(
+                    "Accept",
+                    "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+                )
+                .header("Referer", "https://pkg.go.dev/")
+                .send()
+        });
 
         match response {
             Ok(response) => {
@@ -654,7 +856,7 @@ fn fetch_license_from_pkg_go_dev(name: &str) -> String {
                                     LogLevel::Info,
                                     &format!("License found for {name}: {license}"),
                                 );
-                                return license;
+                                return Some(license);
                             } else {
                                 log(
                                     LogLevel::Warn,
@@ -684,9 +886,11 @@ fn fetch_license_from_pkg_go_dev(name: &str) -> String {
 
     log(
         LogLevel::Warn,
-        &format!("Unable to determine license for {name} after {attempts} attempts"),
+        &format!(
+            "Unable to determine license for {name} from pkg.go.dev after {attempts} attempts"
+        ),
     );
-    "Unknown".into()
+    None
 }
 
 /// Extract license information from the HTML content
@@ -770,6 +974,109 @@ mod tests {
         assert_eq!(deps[1].name, "github.com/another/pkg");
     }
 
+    const MIXED_GO_MOD: &str = "module example.com/app
+
+go 1.22
+
+require github.com/direct/single v1.0.0
+
+require (
+\tgithub.com/direct/pkg v1.2.0
+\tgolang.org/x/text v0.14.0 // indirect
+\tgithub.com/commented/pkg v0.5.0 // pinned for a bug fix
+)
+
+require github.com/indirect/single v0.3.0 // indirect
+";
+
+    #[test]
+    fn test_get_go_dependencies_classifies_indirect_requires() {
+        let deps = get_go_dependencies(MIXED_GO_MOD.to_string());
+
+        let classified: Vec<(&str, bool)> = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.direct))
+            .collect();
+        assert_eq!(
+            classified,
+            vec![
+                ("github.com/direct/single", true),
+                ("github.com/direct/pkg", true),
+                ("golang.org/x/text", false),
+                ("github.com/commented/pkg", true),
+                ("github.com/indirect/single", false),
+            ]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_go_license_info_records_is_direct() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let go_mod_path = temp_dir.path().join("go.mod");
+        fs::write(&go_mod_path, MIXED_GO_MOD).unwrap();
+        // go.sum adds a module that go.mod does not list at all
+        fs::write(
+            temp_dir.path().join("go.sum"),
+            "github.com/direct/pkg v1.2.0 h1:a=\n\
+             golang.org/x/text v0.14.0 h1:b=\n\
+             github.com/transitive/only v0.1.0 h1:c=\n",
+        )
+        .unwrap();
+
+        crate::network::set_offline_mode(true);
+        let result = analyze_go_licenses(go_mod_path.to_str().unwrap(), &FeludaConfig::default());
+        crate::network::set_offline_mode(false);
+
+        let is_direct = |name: &str| {
+            result
+                .iter()
+                .find(|info| info.name == name)
+                .and_then(|info| info.is_direct)
+        };
+        assert_eq!(is_direct("github.com/direct/pkg"), Some(true));
+        assert_eq!(is_direct("golang.org/x/text"), Some(false));
+        assert_eq!(is_direct("github.com/transitive/only"), Some(false));
+
+        let json = serde_json::to_value(&result[0]).unwrap();
+        assert!(json["is_direct"].is_boolean());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_direct_only_skips_indirect_requires() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let go_mod_path = temp_dir.path().join("go.mod");
+        fs::write(
+            &go_mod_path,
+            "module example.com/app\n\ngo 1.22\n\nrequire github.com/direct/single v1.0.0\n\n\
+             require (\n\tgithub.com/direct/pkg v1.2.0\n\
+             \tgolang.org/x/text v0.14.0 // indirect\n)\n\n\
+             require github.com/indirect/single v0.3.0 // indirect\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("go.sum"),
+            "github.com/transitive/only v0.1.0 h1:abc=\n",
+        )
+        .unwrap();
+
+        let config = FeludaConfig {
+            direct_only: true,
+            ..FeludaConfig::default()
+        };
+        crate::network::set_offline_mode(true);
+        let result = analyze_go_licenses(go_mod_path.to_str().unwrap(), &config);
+        crate::network::set_offline_mode(false);
+
+        let mut names: Vec<&str> = result.iter().map(|info| info.name.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["github.com/direct/pkg", "github.com/direct/single"]
+        );
+    }
+
     #[test]
     fn test_get_go_dependencies_complex_versions() {
         let content = r#"require (
@@ -811,6 +1118,138 @@ mod tests {
         assert_eq!(license, Some("MIT".to_string()));
     }
 
+    /// pkg.go.dev licenses tab reporting `license`
+    fn licenses_page(license: &str) -> String {
+        let body = format!(
+            r#"<html><body><section class="License"><h2 class="go-textTitle"><div id="lic-0">{license}</div></h2></section></body></html>"#
+        );
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    /// Serve the given raw HTTP responses, one per connection, recording each request line
+    fn spawn_pkg_go_dev_stub(
+        responses: Vec<String>,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = requests.clone();
+
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                seen.lock().unwrap().push(line.trim_end().to_string());
+                while reader.read_line(&mut line).is_ok() && line != "\r\n" && !line.is_empty() {
+                    line.clear();
+                }
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    #[test]
+    fn test_pkg_go_dev_license_url() {
+        let base = "https://pkg.go.dev";
+        assert_eq!(
+            pkg_go_dev_license_url(base, "gitlab.com/gitlab-org/api/client-go", "unknown"),
+            "https://pkg.go.dev/gitlab.com/gitlab-org/api/client-go?tab=licenses"
+        );
+        assert_eq!(
+            pkg_go_dev_license_url(base, "github.com/go-chi/chi/v5", "v5.0.12"),
+            "https://pkg.go.dev/github.com/go-chi/chi/v5@v5.0.12?tab=licenses"
+        );
+        assert_eq!(
+            pkg_go_dev_license_url(base, "github.com/docker/docker", "v24.0.7+incompatible"),
+            "https://pkg.go.dev/github.com/docker/docker@v24.0.7%2Bincompatible?tab=licenses"
+        );
+    }
+
+    #[test]
+    fn test_pkg_go_dev_resolves_non_github_modules() {
+        let (base_url, requests) = spawn_pkg_go_dev_stub(vec![
+            licenses_page("Apache-2.0"),
+            licenses_page("BSD-3-Clause"),
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        ]);
+        let client = Client::builder().no_proxy().build().unwrap();
+
+        // GitLab-hosted module
+        assert_eq!(
+            fetch_license_from_pkg_go_dev(
+                &client,
+                &base_url,
+                "gitlab.com/gitlab-org/api/client-go",
+                "v0.116.0"
+            )
+            .as_deref(),
+            Some("Apache-2.0")
+        );
+        // Vanity import path served from go.googlesource.com
+        assert_eq!(
+            fetch_license_from_pkg_go_dev(&client, &base_url, "golang.org/x/text", "v0.14.0")
+                .as_deref(),
+            Some("BSD-3-Clause")
+        );
+        // A miss leaves the module to the GitHub fallback
+        assert_eq!(
+            fetch_license_from_pkg_go_dev(&client, &base_url, "example.com/missing", "v1.0.0"),
+            None
+        );
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "GET /gitlab.com/gitlab-org/api/client-go@v0.116.0?tab=licenses HTTP/1.1",
+                "GET /golang.org/x/text@v0.14.0?tab=licenses HTTP/1.1",
+                "GET /example.com/missing@v1.0.0?tab=licenses HTTP/1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_github_repository_for_module() {
+        let repo = |owner: &str, repo: &str| Some((owner.to_string(), repo.to_string()));
+
+        assert_eq!(
+            github_repository_for_module("github.com/pkg/errors"),
+            repo("pkg", "errors")
+        );
+        assert_eq!(
+            github_repository_for_module("github.com/go-chi/chi/v5"),
+            repo("go-chi", "chi")
+        );
+        assert_eq!(
+            github_repository_for_module("github.com/aws/aws-sdk-go-v2/service/s3"),
+            repo("aws", "aws-sdk-go-v2")
+        );
+        assert_eq!(
+            github_repository_for_module("gopkg.in/yaml.v3"),
+            repo("go-yaml", "yaml")
+        );
+        assert_eq!(
+            github_repository_for_module("gopkg.in/alecthomas/kingpin.v2"),
+            repo("alecthomas", "kingpin")
+        );
+        assert_eq!(github_repository_for_module("github.com/only-owner"), None);
+        assert_eq!(
+            github_repository_for_module("gitlab.com/gitlab-org/api/client-go"),
+            None
+        );
+        assert_eq!(github_repository_for_module("go.uber.org/zap"), None);
+    }
+
     #[test]
     fn test_extract_license_from_html_no_license() {
         let html_content = r#"
@@ -837,6 +1276,7 @@ mod tests {
         let go_package = GoPackages {
             name: "github.com/test/package".to_string(),
             version: "v1.0.0".to_string(),
+            direct: true,
         };
 
         let debug_str = format!("{go_package:?}");
@@ -912,10 +1352,12 @@ github.com/level2@v1.0.0 github.com/level3@v1.0.0"#;
             GoPackages {
                 name: "github.com/test/pkg1".to_string(),
                 version: "v1.0.0".to_string(),
+                direct: true,
             },
             GoPackages {
                 name: "github.com/test/pkg2".to_string(),
                 version: "v2.0.0".to_string(),
+                direct: true,
             },
         ];
 
@@ -933,6 +1375,109 @@ github.com/level2@v1.0.0 github.com/level3@v1.0.0"#;
         );
     }
 
+    const SAMPLE_GO_SUM: &str = "\
+github.com/davecgh/go-spew v1.1.0/go.mod h1:J7Y8YcW2NihsgmVo/mv3lAwl/skON4iLHjSsI+c5H38=
+github.com/davecgh/go-spew v1.1.1 h1:vj9j/u1bqnvCEfJOwUhtlOARqs3+rkHYY13jYWTU97c=
+github.com/davecgh/go-spew v1.1.1/go.mod h1:J7Y8YcW2NihsgmVo/mv3lAwl/skON4iLHjSsI+c5H38=
+github.com/pmezard/go-difflib v1.0.0 h1:4DBwDE0NGyQoBHbLQYPwSUPoCMWR5BEzIk/f1lZbAQM=
+github.com/pmezard/go-difflib v1.0.0/go.mod h1:iKH77koFhYxTK1pcRnkKkqfTogsbg7gZNVY4sRDYZ/4=
+github.com/stretchr/objx v0.1.0/go.mod h1:HFkY916IF+rwdDfMAkV7OtwuqBVzrE8GR6GFx+wExME=
+github.com/stretchr/testify v1.8.0 h1:pSgiaMZlXftHpm5L7V1+rVB+AZJydKsMxsQBIJw4PKk=
+github.com/stretchr/testify v1.8.0/go.mod h1:yNjHg4UonilssWZ8iaSj1OCr/vHnekPRkoO+kdMU+MU=
+github.com/stretchr/testify v1.8.4 h1:CcVxjf3Q8PM0mHUKJCdn+eZZtm5yQwehR5yeSVQQcUk=
+github.com/stretchr/testify v1.8.4/go.mod h1:sz/lmYIOXD/1dqDmKjjqLyZ2RngseejIcXlSw2iwfAo=
+golang.org/x/sys v0.0.0-20220811171246-fbc7d0a398ab h1:2QkjZIsXupsJbJIdSjjUOgWK3aEtzyuh2mPt3l/CkeU=
+golang.org/x/sys v0.0.0-20220811171246-fbc7d0a398ab/go.mod h1:oPkhp1MJrh7nUepCBck5+mAzfO9JrbApNNgaTdGDITg=
+gopkg.in/yaml.v3 v3.0.0-20200313102051-9f266ea9e77c/go.mod h1:K4uyk7z7BCEPqu6E+C64Yfv1cQ7kz7rIZviUmN+EgEM=
+gopkg.in/yaml.v3 v3.0.1 h1:fxVm/GzAzEWqLHuvctI7KBGREOfePWTqvpVB+0G2l0w=
+gopkg.in/yaml.v3 v3.0.1/go.mod h1:K4uyk7z7BCEPqu6E+C64Yfv1cQ7kz7rIZviUmN+EgEM=
+";
+
+    #[test]
+    fn test_resolve_with_go_sum_includes_transitive_modules() {
+        let direct_deps = vec![GoPackages {
+            name: "github.com/stretchr/testify".to_string(),
+            version: "v1.8.4".to_string(),
+            direct: true,
+        }];
+
+        let deps = resolve_with_go_sum(SAMPLE_GO_SUM, &direct_deps);
+        assert_eq!(
+            deps,
+            vec![
+                (
+                    "github.com/davecgh/go-spew".to_string(),
+                    "v1.1.1".to_string()
+                ),
+                (
+                    "github.com/pmezard/go-difflib".to_string(),
+                    "v1.0.0".to_string()
+                ),
+                (
+                    "github.com/stretchr/testify".to_string(),
+                    "v1.8.4".to_string()
+                ),
+                (
+                    "golang.org/x/sys".to_string(),
+                    "v0.0.0-20220811171246-fbc7d0a398ab".to_string()
+                ),
+                ("gopkg.in/yaml.v3".to_string(), "v3.0.1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_go_sum_skips_go_mod_only_lines() {
+        let deps = resolve_with_go_sum(SAMPLE_GO_SUM, &[]);
+        let names: Vec<&str> = deps.iter().map(|(name, _)| name.as_str()).collect();
+
+        // Only the /go.mod hash was recorded, so objx is not part of the build
+        assert!(!names.contains(&"github.com/stretchr/objx"));
+        assert_eq!(deps.len(), 5);
+        // Without a go.mod requirement the highest zip version wins
+        assert!(deps.contains(&(
+            "github.com/stretchr/testify".to_string(),
+            "v1.8.4".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_compare_go_versions() {
+        assert_eq!(compare_go_versions("v1.8.0", "v1.8.4"), Ordering::Less);
+        assert_eq!(compare_go_versions("v1.10.0", "v1.9.0"), Ordering::Greater);
+        assert_eq!(
+            compare_go_versions(
+                "v0.0.0-20200313102051-9f266ea9e77c",
+                "v0.0.0-20220811171246-fbc7d0a398ab"
+            ),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_go_versions("v2.0.0+incompatible", "v1.9.9"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_resolve_go_dependencies_prefers_go_sum() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let go_mod_path = temp_dir.path().join("go.mod");
+        std::fs::write(
+            &go_mod_path,
+            "module example.com/app\n\ngo 1.21\n\nrequire github.com/stretchr/testify v1.8.4\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("go.sum"), SAMPLE_GO_SUM).unwrap();
+
+        let direct_deps = get_go_dependencies(std::fs::read_to_string(&go_mod_path).unwrap());
+        let deps = resolve_go_dependencies(go_mod_path.to_str().unwrap(), &direct_deps, 5);
+        assert_eq!(deps.len(), 5);
+        assert!(deps.contains(&(
+            "github.com/davecgh/go-spew".to_string(),
+            "v1.1.1".to_string()
+        )));
+    }
+
     #[test]
     fn test_is_excluded_go_module() {
         // Test that standard Go modules are excluded
@@ -0,0 +1,512 @@
+use serde_yaml::Value as YamlValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::cache;
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+use crate::network::{http_client, skip_network, with_network_permit};
+
+/// Ecosystem name for Stack and Cabal dependencies
+const ECOSYSTEM: &str = "hackage";
+
+/// Cabal license identifiers that differ from their SPDX equivalents
+const CABAL_LICENSE_MAP: [(&str, &str); 10] = [
+    ("BSD2", "BSD-2-Clause"),
+    ("BSD3", "BSD-3-Clause"),
+    ("BSD4", "BSD-4-Clause"),
+    ("GPL-2", "GPL-2.0-only"),
+    ("GPL-3", "GPL-3.0-only"),
+    ("LGPL-2.1", "LGPL-2.1-only"),
+    ("LGPL-3", "LGPL-3.0-only"),
+    ("AGPL-3", "AGPL-3.0-only"),
+    ("MPL-2.0", "MPL-2.0"),
+    ("Apache-2", "Apache-2.0"),
+];
+
+/// Map a Cabal license name such as `BSD3` to its SPDX identifier
+pub fn normalize_cabal_license(license: &str) -> String {
+    let license = license.trim();
+    CABAL_LICENSE_MAP
+        .iter()
+        .find(|(cabal, _)| cabal.eq_ignore_ascii_case(license))
+        .map(|(_, spdx)| spdx.to_string())
+        .unwrap_or_else(|| license.to_string())
+}
+
+/// Parses Haskell projects through the [`LanguageParser`] interface
+pub struct HaskellParser;
+
+impl LanguageParser for HaskellParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_haskell_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Haskell"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::HASKELL_PATHS
+    }
+}
+
+pub fn analyze_haskell_licenses(
+    package_file_path: &str,
+    config: &FeludaConfig,
+) -> Vec<LicenseInfo> {
+    log(
+        LogLevel::Info,
+        &format!("Analyzing Haskell dependencies from: {package_file_path}"),
+    );
+
+    let path = Path::new(package_file_path);
+    let project_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    // Cabal files listed here contribute their build-depends
+    let cabal_files: Vec<_> = if package_file_path.ends_with(".cabal") {
+        vec![path.to_path_buf()]
+    } else {
+        fs::read_dir(project_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "cabal"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut dependencies: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for cabal_file in &cabal_files {
+        match fs::read_to_string(cabal_file) {
+            Ok(content) => {
+                for (name, version) in parse_cabal_build_depends(&content) {
+                    let entry = dependencies.entry(name).or_default();
+                    if entry.is_none() {
+                        *entry = version;
+                    }
+                }
+            }
+            Err(err) => log_error(
+                &format!("Failed to read cabal file {}", cabal_file.display()),
+                &err,
+            ),
+        }
+    }
+
+    if package_file_path.ends_with("stack.yaml") {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                for (name, version) in parse_stack_extra_deps(&content) {
+                    dependencies.insert(name, Some(version));
+                }
+            }
+            Err(err) => log_error("Failed to read stack.yaml", &err),
+        }
+    }
+
+    // Pinned versions from a freeze file win over anything else
+    let freeze_path = project_dir.join("cabal.project.freeze");
+    if let Ok(content) = fs::read_to_string(&freeze_path) {
+        let pinned = parse_cabal_freeze(&content);
+        log(
+            LogLevel::Info,
+            &format!(
+                "Found {} pinned versions in cabal.project.freeze",
+                pinned.len()
+            ),
+        );
+        for (name, version) in dependencies.iter_mut() {
+            if let Some(pinned_version) = pinned.get(name) {
+                *version = Some(pinned_version.clone());
+            }
+        }
+    }
+
+    log(
+        LogLevel::Info,
+        &format!("Found {} Haskell dependencies", dependencies.len()),
+    );
+    log_debug("Haskell dependencies", &dependencies);
+
+    let known_licenses = match fetch_licenses_from_github() {
+        Ok(licenses) => licenses,
+        Err(err) => {
+            log_error("Failed to fetch licenses from GitHub", &err);
+            HashMap::new()
+        }
+    };
+
+    crate::cli::add_dependencies_to_resolve(dependencies.len());
+
+    dependencies
+        .into_iter()
+        .map(|(name, version)| {
+            let version = version.unwrap_or_else(|| "latest".to_string());
+            let license = Some(fetch_license_for_haskell_dependency(&name, &version));
+            let is_restrictive = is_license_restrictive(&license, &known_licenses, config.strict);
+
+            if is_restrictive {
+                log(
+                    LogLevel::Warn,
+                    &format!("Restrictive license found: {license:?} for {name}"),
+                );
+            }
+
+            crate::cli::mark_dependency_resolved();
+
+            LicenseInfo {
+                is_restrictive,
+                osi_status: match &license {
+                    Some(l) => crate::licenses::get_osi_status(l),
+                    None => crate::licenses::OsiStatus::Unknown,
+                },
+                ecosystem: ECOSYSTEM.to_string(),
+                ..LicenseInfo::new(name, version, license)
+            }
+        })
+        .collect()
+}
+
+/// Strip a trailing `--` comment from a Cabal line
+fn strip_cabal_comment(line: &str) -> &str {
+    match line.find("--") {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+/// Collect `build-depends` entries from every stanza of a `.cabal` file
+///
+/// Returns each package name with its version when the constraint pins one exactly (`==`).
+/// The package's own library is skipped since test suites and executables depend on it.
+fn parse_cabal_build_depends(content: &str) -> Vec<(String, Option<String>)> {
+    let own_name = content.lines().find_map(|line| {
+        let (field, value) = strip_cabal_comment(line).split_once(':')?;
+        (!line.starts_with(char::is_whitespace) && field.trim().eq_ignore_ascii_case("name"))
+            .then(|| value.trim().to_string())
+    });
+
+    let mut raw_fields: Vec<String> = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for line in content.lines() {
+        let line = strip_cabal_comment(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some((field_indent, value)) = current.as_mut() {
+            if indent > *field_indent {
+                value.push(' ');
+                value.push_str(line.trim());
+                continue;
+            }
+            raw_fields.push(std::mem::take(value));
+            current = None;
+        }
+
+        if let Some((field, value)) = line.trim().split_once(':') {
+            if field.trim().eq_ignore_ascii_case("build-depends") {
+                current = Some((indent, value.trim().to_string()));
+            }
+        }
+    }
+    if let Some((_, value)) = current {
+        raw_fields.push(value);
+    }
+
+    let mut deps: Vec<(String, Option<String>)> = Vec::new();
+    for field in raw_fields {
+        for dep in field.split(',') {
+            let dep = dep.trim();
+            let name_end = dep
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(dep.len());
+            let name = &dep[..name_end];
+            if name.is_empty() || own_name.as_deref() == Some(name) {
+                continue;
+            }
+
+            let constraint = dep[name_end..].trim();
+            let version = constraint
+                .strip_prefix("==")
+                .map(str::trim)
+                .filter(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit() || c == '.'))
+                .map(str::to_string);
+
+            if !deps.iter().any(|(existing, _)| existing == name) {
+                deps.push((name.to_string(), version));
+            }
+        }
+    }
+
+    deps
+}
+
+/// Read pinned versions from `cabal.project.freeze` (`any.aeson ==2.1.2.1,` lines)
+fn parse_cabal_freeze(content: &str) -> HashMap<String, String> {
+    let mut pinned = HashMap::new();
+    let constraints = content
+        .split_once("constraints:")
+        .map(|(_, rest)| rest)
+        .unwrap_or(content);
+
+    for constraint in constraints.split(',') {
+        let constraint = strip_cabal_comment(constraint).trim();
+        let Some((name, version)) = constraint.split_once("==") else {
+            continue;
+        };
+        let name = name.trim();
+        let name = name.strip_prefix("any.").unwrap_or(name);
+        let version = version.split_whitespace().next().unwrap_or_default();
+        if !name.is_empty() && !version.is_empty() {
+            pinned.insert(name.to_string(), version.to_string());
+        }
+    }
+
+    pinned
+}
+
+/// Split Stack `extra-deps` entries like `acme-missiles-0.3@sha256:...` into name and version
+///
+/// Git and archive entries (maps rather than strings) have no Hackage version and are skipped.
+fn parse_stack_extra_deps(content: &str) -> Vec<(String, String)> {
+    let yaml: YamlValue = match serde_yaml::from_str(content) {
+        Ok(yaml) => yaml,
+        Err(err) => {
+            log_error("Failed to parse stack.yaml", &err);
+            return Vec::new();
+        }
+    };
+
+    let Some(extra_deps) = yaml.get("extra-deps").and_then(YamlValue::as_sequence) else {
+        return Vec::new();
+    };
+
+    extra_deps
+        .iter()
+        .filter_map(|dep| {
+            let dep = dep.as_str()?;
+            let dep = dep.split('@').next().unwrap_or(dep);
+            let (name, version) = dep.rsplit_once('-')?;
+            version
+                .starts_with(|c: char| c.is_ascii_digit())
+                .then(|| (name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// Read the `license:` field of a `.cabal` file
+fn parse_cabal_license_field(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let (field, value) = strip_cabal_comment(line).split_once(':')?;
+        let value = value.trim();
+        (field.trim().eq_ignore_ascii_case("license") && !value.is_empty())
+            .then(|| normalize_cabal_license(value))
+    })
+}
+
+pub fn fetch_license_for_haskell_dependency(name: &str, version: &str) -> String {
+    cache::cached_package_license("haskell", name, version, || {
+        fetch_license_from_hackage(name, version)
+    })
+}
+
+fn fetch_license_from_hackage(name: &str, version: &str) -> String {
+    if skip_network(&format!("Hackage license of {name}")) {
+        return String::from("Unknown");
+    }
+
+    let package = if version == "latest" {
+        name.to_string()
+    } else {
+        format!("{name}-{version}")
+    };
+    let cabal_url = format!("https://hackage.haskell.org/package/{package}/{name}.cabal");
+    log(
+        LogLevel::Info,
+        &format!("Fetching license from Hackage: {cabal_url}"),
+    );
+
+    let client = match http_client() {
+        Ok(client) => client,
+        Err(err) => {
+            log_error("Failed to build HTTP client", &err);
+            return String::from("Unknown");
+        }
+    };
+
+    match with_network_permit(|| client.get(&cabal_url).send()) {
+        Ok(response) => {
+            let status = response.status();
+            log(
+                LogLevel::Info,
+                &format!("Hackage response status: {status}"),
+            );
+
+            if !status.is_success() {
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to fetch cabal file for {name}: HTTP {status}"),
+                );
+                return String::from("Unknown");
+            }
+
+            match response.text() {
+                Ok(content) => parse_cabal_license_field(&content).unwrap_or_else(|| {
+                    log(
+                        LogLevel::Warn,
+                        &format!("No license found for {name} ({version})"),
+                    );
+                    format!("Unknown license for {name}: {version}")
+                }),
+                Err(err) => {
+                    log_error(&format!("Failed to read cabal file for {name}"), &err);
+                    String::from("Unknown")
+                }
+            }
+        }
+        Err(err) => {
+            log_error(&format!("Failed to fetch cabal file for {name}"), &err);
+            String::from("Unknown")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::set_offline_mode;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    const SAMPLE_CABAL: &str = r#"cabal-version:      2.4
+name:               my-app
+version:            0.1.0.0
+license:            BSD-3-Clause
+
+library
+    exposed-modules:  MyLib
+    build-depends:    base ^>=4.17.0.0,
+                      aeson >= 2.0 && < 2.3,
+                      text == 2.0.2
+    hs-source-dirs:   src
+
+executable my-app
+    main-is:          Main.hs
+    build-depends:
+        base,
+        my-app,
+        containers -- only for Data.Map
+    if os(windows)
+        build-depends: Win32
+"#;
+
+    #[test]
+    fn test_normalize_cabal_license() {
+        assert_eq!(normalize_cabal_license("BSD3"), "BSD-3-Clause");
+        assert_eq!(normalize_cabal_license("BSD2"), "BSD-2-Clause");
+        assert_eq!(normalize_cabal_license("GPL-3"), "GPL-3.0-only");
+        assert_eq!(normalize_cabal_license(" bsd3 "), "BSD-3-Clause");
+        assert_eq!(normalize_cabal_license("MIT"), "MIT");
+        assert_eq!(normalize_cabal_license("BSD-3-Clause"), "BSD-3-Clause");
+    }
+
+    #[test]
+    fn test_parse_cabal_build_depends() {
+        let deps = parse_cabal_build_depends(SAMPLE_CABAL);
+        let names: Vec<&str> = deps.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["base", "aeson", "text", "containers", "Win32"]);
+
+        let text = deps.iter().find(|(name, _)| name == "text").unwrap();
+        assert_eq!(text.1.as_deref(), Some("2.0.2"));
+        let aeson = deps.iter().find(|(name, _)| name == "aeson").unwrap();
+        assert_eq!(aeson.1, None);
+    }
+
+    #[test]
+    fn test_parse_cabal_freeze() {
+        let content = "active-repositories: hackage.haskell.org:merge\n\
+                       constraints: any.aeson ==2.1.2.1,\n\
+                       \x20            aeson -ordered-keymap,\n\
+                       \x20            any.base ==4.17.2.0\n\
+                       index-state: hackage.haskell.org 2024-01-01T00:00:00Z\n";
+        let pinned = parse_cabal_freeze(content);
+        assert_eq!(pinned.get("aeson").map(String::as_str), Some("2.1.2.1"));
+        assert_eq!(pinned.get("base").map(String::as_str), Some("4.17.2.0"));
+        assert_eq!(pinned.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_stack_extra_deps() {
+        let content = r#"resolver: lts-21.25
+packages:
+  - .
+extra-deps:
+  - acme-missiles-0.3
+  - hspec-discover-2.11.7@sha256:abcdef,1234
+  - git: https://github.com/example/fork.git
+    commit: 0123456
+"#;
+        let deps = parse_stack_extra_deps(content);
+        assert_eq!(
+            deps,
+            vec![
+                ("acme-missiles".to_string(), "0.3".to_string()),
+                ("hspec-discover".to_string(), "2.11.7".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cabal_license_field() {
+        let content = "name: aeson\nversion: 2.2.1.0\nlicense: BSD3\nlicense-file: LICENSE\n";
+        assert_eq!(
+            parse_cabal_license_field(content),
+            Some("BSD-3-Clause".to_string())
+        );
+        assert_eq!(parse_cabal_license_field("name: foo\n"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_analyze_haskell_licenses_applies_freeze_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let cabal_path = temp_dir.path().join("my-app.cabal");
+        fs::write(&cabal_path, SAMPLE_CABAL).unwrap();
+        fs::write(
+            temp_dir.path().join("cabal.project.freeze"),
+            "constraints: any.aeson ==2.1.2.1,\n             any.base ==4.17.2.0\n",
+        )
+        .unwrap();
+
+        set_offline_mode(true);
+        let result =
+            analyze_haskell_licenses(cabal_path.to_str().unwrap(), &FeludaConfig::default());
+        set_offline_mode(false);
+
+        assert_eq!(result.len(), 5);
+        let aeson = result.iter().find(|info| info.name == "aeson").unwrap();
+        assert_eq!(aeson.version, "2.1.2.1");
+        let containers = result
+            .iter()
+            .find(|info| info.name == "containers")
+            .unwrap();
+        assert_eq!(containers.version, "latest");
+    }
+}
@@ -3,40 +3,151 @@
 pub mod c;
 pub mod cpp;
 pub mod dotnet;
+pub mod elixir;
 pub mod go;
+pub mod haskell;
 pub mod node;
+pub mod perl;
 pub mod python;
 pub mod r;
 pub mod rust;
 
-use crate::licenses::LicenseInfo;
+use crate::config::FeludaConfig;
+use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::licenses::{is_license_restrictive, License, LicenseInfo};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::mem::{discriminant, Discriminant};
 use std::path::Path;
 
 /// Common trait for language-specific dependency parsers
-#[allow(dead_code)]
-pub trait LanguageParser {
+pub trait LanguageParser: Send + Sync {
     /// Parse dependencies from a project file and return license information
     fn parse_dependencies(
         &self,
-        project_path: &Path,
-    ) -> crate::debug::FeludaResult<Vec<LicenseInfo>>;
+        project_file: &Path,
+        config: &FeludaConfig,
+        no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>>;
 
     /// Get the name of the language
     fn language_name(&self) -> &'static str;
 
-    /// Get the typical project files
+    /// Get the typical project files; entries starting with `.` are file extensions
     fn supported_files(&self) -> &'static [&'static str];
 }
 
+/// Parsers keyed by the project type they handle
+#[derive(Default)]
+pub struct ParserRegistry {
+    parsers: HashMap<Discriminant<Language>, Box<dyn LanguageParser>>,
+}
+
+impl ParserRegistry {
+    /// Registry with a parser for every supported language
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(Language::C(&C_PATHS), Box::new(c::CParser));
+        registry.register(Language::Cpp(&CPP_PATHS), Box::new(cpp::CppParser));
+        registry.register(
+            Language::DotNet(&DOTNET_PATHS),
+            Box::new(dotnet::DotNetParser),
+        );
+        registry.register(Language::Elixir("mix.lock"), Box::new(elixir::ElixirParser));
+        registry.register(Language::Rust("Cargo.toml"), Box::new(rust::RustParser));
+        registry.register(Language::Node("package.json"), Box::new(node::NodeParser));
+        registry.register(Language::Perl("cpanfile"), Box::new(perl::PerlParser));
+        registry.register(Language::Go("go.mod"), Box::new(go::GoParser));
+        registry.register(
+            Language::Haskell(&HASKELL_PATHS),
+            Box::new(haskell::HaskellParser),
+        );
+        registry.register(
+            Language::Python(&PYTHON_PATHS),
+            Box::new(python::PythonParser),
+        );
+        registry.register(Language::R(&R_PATHS), Box::new(r::RParser));
+        registry
+    }
+
+    /// Use `parser` for projects of `language`, replacing any parser registered before
+    pub fn register(&mut self, language: Language, parser: Box<dyn LanguageParser>) {
+        self.parsers.insert(discriminant(&language), parser);
+    }
+
+    /// Parser for projects of `language`; the variant's file patterns are not compared
+    pub fn get(&self, language: Language) -> Option<&dyn LanguageParser> {
+        self.parsers.get(&discriminant(&language)).map(Box::as_ref)
+    }
+}
+
+/// Project file path as the `&str` the analyzers take
+pub(crate) fn project_file_str(project_file: &Path) -> FeludaResult<&str> {
+    project_file.to_str().ok_or_else(|| {
+        FeludaError::Parser(format!(
+            "Project file path is not valid UTF-8: {}",
+            project_file.display()
+        ))
+    })
+}
+
+/// Licenses of `(name, version)` dependencies resolved in parallel, in input order
+///
+/// `resolve` returns the license of one dependency; restrictiveness and OSI status are derived
+/// from it, and progress is reported as each dependency completes.
+pub(crate) fn analyze_resolved_dependencies(
+    deps: Vec<(String, String)>,
+    known_licenses: &HashMap<String, License>,
+    strict: bool,
+    ecosystem: &str,
+    resolve: impl Fn(&str, &str) -> String + Sync,
+) -> Vec<LicenseInfo> {
+    crate::cli::add_dependencies_to_resolve(deps.len());
+
+    deps.into_par_iter()
+        .map(|(name, version)| {
+            log(
+                LogLevel::Info,
+                &format!("Processing dependency: {name} ({version})"),
+            );
+
+            let license = Some(resolve(&name, &version));
+            let is_restrictive = is_license_restrictive(&license, known_licenses, strict);
+
+            if is_restrictive {
+                log(
+                    LogLevel::Warn,
+                    &format!("Restrictive license found: {license:?} for {name}"),
+                );
+            }
+
+            crate::cli::mark_dependency_resolved();
+
+            LicenseInfo {
+                is_restrictive,
+                osi_status: match &license {
+                    Some(l) => crate::licenses::get_osi_status(l),
+                    None => crate::licenses::OsiStatus::Unknown,
+                },
+                ecosystem: ecosystem.to_string(),
+                ..LicenseInfo::new(name, version, license)
+            }
+        })
+        .collect()
+}
+
 /// Language identification
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Language {
     C(&'static [&'static str]),
     Cpp(&'static [&'static str]),
     DotNet(&'static [&'static str]),
+    Elixir(&'static str),
     Rust(&'static str),
     Node(&'static str),
+    Perl(&'static str),
     Go(&'static str),
+    Haskell(&'static [&'static str]),
     Python(&'static [&'static str]),
     R(&'static [&'static str]),
 }
@@ -47,6 +158,8 @@ impl Language {
             "Cargo.toml" => Some(Language::Rust("Cargo.toml")),
             "package.json" => Some(Language::Node("package.json")),
             "go.mod" => Some(Language::Go("go.mod")),
+            "mix.lock" => Some(Language::Elixir("mix.lock")),
+            "cpanfile" => Some(Language::Perl("cpanfile")),
             "vcpkg.json" => Some(Language::Cpp(&CPP_PATHS[..])),
             "conanfile.txt" | "conanfile.py" => Some(Language::Cpp(&CPP_PATHS[..])),
             "MODULE.bazel" => Some(Language::Cpp(&CPP_PATHS[..])),
@@ -59,6 +172,8 @@ impl Language {
                     || file_name.ends_with(".slnx")
                 {
                     Some(Language::DotNet(&DOTNET_PATHS[..]))
+                } else if file_name == "stack.yaml" || file_name.ends_with(".cabal") {
+                    Some(Language::Haskell(&HASKELL_PATHS[..]))
                 } else if PYTHON_PATHS.contains(&file_name) {
                     Some(Language::Python(&PYTHON_PATHS[..]))
                 } else if R_PATHS.contains(&file_name) {
@@ -83,16 +198,69 @@ pub const CPP_PATHS: [&str; 5] = [
     "MODULE.bazel",
 ];
 
-/// Python project file patterns
-pub const PYTHON_PATHS: [&str; 4] = [
+/// Python project file patterns, including conda environment files; lockfiles come last so
+/// a manifest next to them is preferred
+pub const PYTHON_PATHS: [&str; 8] = [
     "requirements.txt",
     "Pipfile.lock",
     "pip_freeze.txt",
     "pyproject.toml",
+    "environment.yml",
+    "environment.yaml",
+    "poetry.lock",
+    "uv.lock",
 ];
 
+/// Haskell project file patterns, preferring Stack when both are present
+pub const HASKELL_PATHS: [&str; 2] = ["stack.yaml", ".cabal"];
+
 /// R project file patterns
 pub const R_PATHS: [&str; 2] = ["DESCRIPTION", "renv.lock"];
 
 /// .NET project file patterns
 pub const DOTNET_PATHS: [&str; 4] = [".csproj", ".fsproj", ".vbproj", ".slnx"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_resolution_matches_serial() {
+        let deps: Vec<(String, String)> = (0..50)
+            .map(|i| (format!("pkg-{i}"), format!("1.{i}.0")))
+            .collect();
+        let resolve = |name: &str, _version: &str| {
+            let index: usize = name.rsplit('-').next().unwrap().parse().unwrap();
+            if index % 5 == 0 {
+                "GPL-3.0".to_string()
+            } else {
+                "MIT".to_string()
+            }
+        };
+        let known_licenses = HashMap::new();
+
+        let parallel =
+            analyze_resolved_dependencies(deps.clone(), &known_licenses, false, "pypi", resolve);
+
+        let serial: Vec<(String, String, Option<String>, bool)> = deps
+            .iter()
+            .map(|(name, version)| {
+                let license = Some(resolve(name, version));
+                let is_restrictive = is_license_restrictive(&license, &known_licenses, false);
+                (name.clone(), version.clone(), license, is_restrictive)
+            })
+            .collect();
+
+        assert_eq!(parallel.len(), 50);
+        for (info, (name, version, license, is_restrictive)) in parallel.iter().zip(&serial) {
+            assert_eq!(&info.name, name);
+            assert_eq!(&info.version, version);
+            assert_eq!(&info.license, license);
+            assert_eq!(info.is_restrictive, *is_restrictive);
+        }
+        assert_eq!(
+            parallel.iter().filter(|info| info.is_restrictive).count(),
+            10
+        );
+    }
+}
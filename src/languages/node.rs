@@ -5,11 +5,22 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::RwLock;
 
-use crate::debug::{log, log_debug, log_error, LogLevel};
+use crate::cache;
+use crate::cli::{self, NodeLicenseSource};
+use crate::config::DependencyLimit;
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
 use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
+    copyright, fetch_licenses_from_github, fingerprint, is_license_restrictive, license_files_in,
+    LicenseInfo,
 };
+use crate::network::{http_client, npm_registry_url, skip_network, with_network_permit};
+use crate::registry_auth::registry_get;
+
+/// Ecosystem name for JavaScript dependencies, whichever package manager installed them
+const ECOSYSTEM: &str = "npm";
 
 /// Type alias for dependency detection
 type DependencyDetector = fn(&Path) -> Result<HashMap<String, String>, String>;
@@ -47,12 +58,36 @@ impl PackageJson {
 
         all_dependencies
     }
+
+    /// Get production dependencies from package.json (production + peer)
+    pub fn get_production_dependencies(&self) -> HashMap<String, String> {
+        let mut production_dependencies: HashMap<String, String> = HashMap::new();
+
+        if let Some(deps) = &self.dependencies {
+            production_dependencies.extend(deps.clone());
+        }
+        if let Some(peer_deps) = &self.peer_dependencies {
+            production_dependencies.extend(peer_deps.clone());
+        }
+
+        production_dependencies
+    }
 }
 
+/// Default recursion limit for the fallback recursive resolver
+const DEFAULT_RESOLVER_DEPTH: usize = 15;
+
+/// Default nesting limit when scanning `node_modules` directories
+const DEFAULT_NODE_MODULES_SCAN_DEPTH: usize = 25;
+
+/// Default nesting limit when following pnpm's `node_modules` symlinks
+const DEFAULT_PNPM_SYMLINK_DEPTH: usize = 30;
+
 /// Recursive dependency resolver
 struct DependencyResolver {
     resolved_cache: HashMap<String, PackageMetadata>,
     processing_stack: HashSet<String>,
+    node_modules: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +104,15 @@ impl DependencyResolver {
         Self {
             resolved_cache: HashMap::new(),
             processing_stack: HashSet::new(),
+            node_modules: None,
+        }
+    }
+
+    /// Resolver that prefers packages installed under `project_root/node_modules`
+    fn with_project_root(project_root: &Path) -> Self {
+        Self {
+            node_modules: Some(project_root.join("node_modules")),
+            ..Self::new()
         }
     }
 
@@ -136,7 +180,10 @@ impl DependencyResolver {
             return Ok(cached.clone());
         }
 
-        let metadata = self.fetch_package_metadata_from_registry(name, version_spec)?;
+        let metadata = match self.read_installed_package(name) {
+            Some(metadata) => metadata,
+            None => self.fetch_package_metadata_from_registry(name, version_spec)?,
+        };
         self.resolved_cache.insert(cache_key, metadata.clone());
         Ok(metadata)
     }
@@ -146,15 +193,19 @@ impl DependencyResolver {
         name: &str,
         version_spec: &str,
     ) -> Result<PackageMetadata, String> {
+        if skip_network(&format!("npm metadata of {name}")) {
+            return Err("Registry lookup disabled in offline mode".to_string());
+        }
+
         let clean_version = clean_version_string(version_spec);
         let url = if clean_version == "latest" || clean_version.is_empty() {
-            format!("https://registry.npmjs.org/{name}")
+            npm_registry_url(name, None)
         } else {
-            format!("https://registry.npmjs.org/{name}/{clean_version}")
+            npm_registry_url(name, Some(&clean_version))
         };
 
-        let response =
-            reqwest::blocking::get(&url).map_err(|e| format!("Registry request failed: {e}"))?;
+        let response = with_network_permit(|| registry_get(&http_client()?, &url).send())
+            .map_err(|e| format!("Registry request failed: {e}"))?;
 
         if !response.status().is_success() {
             return Err(format!("Registry returned status: {}", response.status()));
@@ -230,6 +281,57 @@ impl DependencyResolver {
             .unwrap_or_default()
     }
 
+    fn read_installed_package(&self, name: &str) -> Option<PackageMetadata> {
+        let path = self.node_modules.as_ref()?.join(name).join("package.json");
+        let content = fs::read_to_string(path).ok()?;
+        let json: Value = serde_json::from_str(&content).ok()?;
+
+        Some(PackageMetadata {
+            name: name.to_string(),
+            version: json.get("version")?.as_str()?.to_string(),
+            license: json
+                .get("license")
+                .and_then(|l| l.as_str())
+                .map(String::from),
+            dependencies: self.extract_dependencies_from_json(&json, "dependencies"),
+        })
+    }
+
+    /// Copyright statement of an installed package, from its license file or `author`
+    fn installed_copyright(&self, name: &str) -> Option<String> {
+        let dir = self.node_modules.as_ref()?.join(name);
+        copyright::copyright_from_dir(&dir).or_else(|| {
+            let content = fs::read_to_string(dir.join("package.json")).ok()?;
+            let json: Value = serde_json::from_str(&content).ok()?;
+            // `author` is either "Name <email> (url)" or {"name": ..., "email": ...}
+            let author = json.get("author")?;
+            let author = author
+                .as_str()
+                .or_else(|| author.get("name").and_then(Value::as_str))?;
+            copyright::copyright_from_authors(&[author.to_string()])
+        })
+    }
+
+    /// Dependency names of an installed package, limited to packages in `known`
+    fn installed_dependency_names(
+        &self,
+        name: &str,
+        known: &HashMap<String, String>,
+    ) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .read_installed_package(name)
+            .map(|metadata| {
+                metadata
+                    .dependencies
+                    .into_keys()
+                    .filter(|dep| known.contains_key(dep))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
     fn parse_local_package_json(&self, path: &str) -> Result<PackageMetadata, String> {
         let content = std::fs::read_to_string(path)
             .map_err(|e| format!("Failed to read package.json: {e}"))?;
@@ -248,25 +350,48 @@ impl DependencyResolver {
     }
 }
 
+/// Parses Node.js projects through the [`LanguageParser`] interface
+pub struct NodeParser;
+
+impl LanguageParser for NodeParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &crate::config::FeludaConfig,
+        no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        analyze_js_licenses_with_config(project_file_str(project_file)?, config, no_local)
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Node.js"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &["package.json"]
+    }
+}
+
 #[allow(dead_code)]
 pub fn analyze_js_licenses(package_json_path: &str) -> Vec<LicenseInfo> {
     let config = crate::config::load_config().unwrap_or_default();
-    analyze_js_licenses_with_config(package_json_path, &config, false)
+    analyze_js_licenses_with_config(package_json_path, &config, false).unwrap_or_default()
 }
 
+#[allow(dead_code)]
 pub fn analyze_js_licenses_with_no_local(
     package_json_path: &str,
     no_local: bool,
 ) -> Vec<LicenseInfo> {
     let config = crate::config::load_config().unwrap_or_default();
-    analyze_js_licenses_with_config(package_json_path, &config, no_local)
+    analyze_js_licenses_with_config(package_json_path, &config, no_local).unwrap_or_default()
 }
 
 pub fn analyze_js_licenses_with_config(
     package_json_path: &str,
     config: &crate::config::FeludaConfig,
     no_local: bool,
-) -> Vec<LicenseInfo> {
+) -> FeludaResult<Vec<LicenseInfo>> {
     log(
         LogLevel::Info,
         &format!("Analyzing JavaScript dependencies from: {package_json_path}"),
@@ -276,20 +401,50 @@ pub fn analyze_js_licenses_with_config(
         .parent()
         .unwrap_or(Path::new("."));
 
-    let all_dependencies = if project_root.join("pnpm-lock.yaml").exists() {
+    let mut all_dependencies = if config.direct_only {
+        log(
+            LogLevel::Info,
+            "Direct-only mode - using the dependencies declared in package.json",
+        );
+        resolve_direct_dependencies(project_root, package_json_path, !config.include_dev)
+    } else if let Some(depth) = config.node_depth {
+        log(
+            LogLevel::Info,
+            &format!("Limiting dependency resolution to depth {depth}"),
+        );
+        resolve_dependencies_to_depth(package_json_path, depth, !config.include_dev)
+    } else if !config.include_dev {
+        log(
+            LogLevel::Info,
+            "Production-only mode - skipping dev and optional dependencies",
+        );
+        resolve_production_dependencies(project_root, package_json_path)
+    } else if project_root.join("pnpm-lock.yaml").exists() {
         log(
             LogLevel::Info,
             "Detected pnpm project - using specialized pnpm analysis",
         );
-        analyze_pnpm_project_comprehensive(project_root, package_json_path)
+        analyze_pnpm_project_comprehensive(project_root, package_json_path, config.max_deps)
     } else {
         log(LogLevel::Info, "Using general npm/yarn analysis");
-        try_all_dependency_detection_methods(project_root, package_json_path)
+        try_all_dependency_detection_methods(project_root, package_json_path, config.max_deps)?
     };
 
+    // Declared dev dependencies count even when no lockfile or package manager resolved them
+    if config.include_dev {
+        if let Ok(declared) = parse_package_json_dependencies(package_json_path, false) {
+            for (name, version) in declared {
+                all_dependencies
+                    .entry(name)
+                    .or_insert_with(|| clean_version_string(&version));
+            }
+        }
+    }
+    reached_dependency_limit(&mut all_dependencies, config.max_deps)?;
+
     if all_dependencies.is_empty() {
         log(LogLevel::Warn, "No dependencies found using any method");
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
     log(
@@ -318,8 +473,13 @@ pub fn analyze_js_licenses_with_config(
         }
     };
 
+    // Installed package.json files give us the parent/child edges for the SBOM
+    let installed = DependencyResolver::with_project_root(project_root);
+
+    cli::add_dependencies_to_resolve(all_dependencies.len());
+
     // Process dependencies in parallel
-    all_dependencies
+    Ok(all_dependencies
         .par_iter()
         .map(|(name, version)| {
             let license = get_license_for_package(project_root, name, version, no_local);
@@ -333,22 +493,138 @@ pub fn analyze_js_licenses_with_config(
                 );
             }
 
+            cli::mark_dependency_resolved();
+
             LicenseInfo {
-                name: name.to_string(),
-                version: clean_version_string(version),
-                license: Some(license.clone()),
                 is_restrictive,
-                compatibility: LicenseCompatibility::Unknown,
                 osi_status: crate::licenses::get_osi_status(&license),
+                dependencies: installed.installed_dependency_names(name, &all_dependencies),
+                ecosystem: ECOSYSTEM.to_string(),
+                copyright: installed.installed_copyright(name),
+                ..LicenseInfo::new(
+                    name.to_string(),
+                    clean_version_string(version),
+                    Some(license.clone()),
+                )
             }
         })
+        .collect())
+}
+
+/// Resolve the dependencies declared in package.json, descending at most `depth` levels
+///
+/// A depth of 0 returns only the direct dependencies; a depth of 2 adds their dependencies.
+/// Package manager tree dumps and node_modules scans cannot tell how deep a package sits,
+/// so only the recursive resolver is used here.
+fn resolve_dependencies_to_depth(
+    package_json_path: &str,
+    depth: usize,
+    production_only: bool,
+) -> HashMap<String, String> {
+    let direct = match parse_package_json_dependencies(package_json_path, production_only) {
+        Ok(deps) => deps,
+        Err(err) => {
+            log(LogLevel::Error, &err);
+            return HashMap::new();
+        }
+    };
+
+    if depth == 0 {
+        return direct
+            .into_iter()
+            .map(|(name, version)| {
+                let version = clean_version_string(&version);
+                (name, version)
+            })
+            .collect();
+    }
+
+    let project_root = Path::new(package_json_path)
+        .parent()
+        .unwrap_or(Path::new("."));
+    let mut resolver = DependencyResolver::with_project_root(project_root);
+    let mut all_deps = HashMap::new();
+
+    if let Err(err) = resolver.resolve_dependencies_recursive(direct, &mut all_deps, 0, depth) {
+        log(
+            LogLevel::Warn,
+            &format!("Depth-limited resolution incomplete: {err}"),
+        );
+    }
+
+    all_deps
+}
+
+/// Resolve the dependencies declared in package.json to their installed versions
+///
+/// Unlike `--depth 0`, which reports the declared ranges, the version comes from
+/// `node_modules` when the package is installed there.
+fn resolve_direct_dependencies(
+    project_root: &Path,
+    package_json_path: &str,
+    production_only: bool,
+) -> HashMap<String, String> {
+    let declared = match parse_package_json_dependencies(package_json_path, production_only) {
+        Ok(deps) => deps,
+        Err(err) => {
+            log(LogLevel::Error, &err);
+            return HashMap::new();
+        }
+    };
+
+    declared
+        .into_iter()
+        .map(|(name, range)| {
+            let installed = project_root.join("node_modules").join(&name);
+            let version = read_package_version_safe(&installed)
+                .unwrap_or_else(|| clean_version_string(&range));
+            (name, version)
+        })
         .collect()
 }
 
-fn try_all_dependency_detection_methods(
+/// Resolve production dependencies only, leaving out `devDependencies` and `optionalDependencies`
+///
+/// Only package manager commands that can exclude dev dependencies are used, then
+/// package-lock.json entries not marked dev/optional, then the recursive resolver.
+fn resolve_production_dependencies(
     project_root: &Path,
     package_json_path: &str,
 ) -> HashMap<String, String> {
+    let detectors: Vec<DependencyDetector> = if project_root.join("pnpm-lock.yaml").exists() {
+        vec![pnpm_list_production]
+    } else if project_root.join("yarn.lock").exists() {
+        // yarn v1 cannot list production dependencies only
+        Vec::new()
+    } else {
+        vec![npm_ls_production]
+    };
+
+    for detector in detectors {
+        if let Ok(deps) = detector(project_root) {
+            if !deps.is_empty() {
+                return deps;
+            }
+        }
+    }
+
+    if let Some(deps) = parse_npm_lockfile_production(project_root) {
+        if !deps.is_empty() {
+            return deps;
+        }
+    }
+
+    resolve_dependencies_to_depth(package_json_path, DEFAULT_RESOLVER_DEPTH, true)
+}
+
+/// Combine every npm/yarn/pnpm detection method, falling back to slower ones when few are found
+///
+/// `max_deps` is checked after every step so a huge tree stops being collected early.
+fn try_all_dependency_detection_methods(
+    project_root: &Path,
+    package_json_path: &str,
+    max_deps: DependencyLimit,
+) -> FeludaResult<HashMap<String, String>> {
     let mut all_deps = HashMap::new();
 
     // pnpm
@@ -363,6 +639,9 @@ fn try_all_dependency_detection_methods(
                         &format!("pnpm method found {} dependencies", deps.len()),
                     );
                     all_deps.extend(deps);
+                    if reached_dependency_limit(&mut all_deps, max_deps)? {
+                        return Ok(all_deps);
+                    }
                 }
             }
         }
@@ -380,6 +659,9 @@ fn try_all_dependency_detection_methods(
                         &format!("yarn method found {} dependencies", deps.len()),
                     );
                     all_deps.extend(deps);
+                    if reached_dependency_limit(&mut all_deps, max_deps)? {
+                        return Ok(all_deps);
+                    }
                     break;
                 }
             }
@@ -398,6 +680,9 @@ fn try_all_dependency_detection_methods(
                         &format!("npm method found {} dependencies", deps.len()),
                     );
                     all_deps.extend(deps);
+                    if reached_dependency_limit(&mut all_deps, max_deps)? {
+                        return Ok(all_deps);
+                    }
                     break;
                 }
             }
@@ -408,7 +693,9 @@ fn try_all_dependency_detection_methods(
     if all_deps.len() < 50 {
         log(LogLevel::Info, "node_modules scanning...");
 
-        if let Ok(scanned_deps) = comprehensive_node_modules_scan(project_root) {
+        if let Ok(scanned_deps) =
+            comprehensive_node_modules_scan(project_root, DEFAULT_NODE_MODULES_SCAN_DEPTH, max_deps)
+        {
             log(
                 LogLevel::Info,
                 &format!(
@@ -417,6 +704,9 @@ fn try_all_dependency_detection_methods(
                 ),
             );
             all_deps.extend(scanned_deps);
+            if reached_dependency_limit(&mut all_deps, max_deps)? {
+                return Ok(all_deps);
+            }
         }
     }
 
@@ -430,25 +720,32 @@ fn try_all_dependency_detection_methods(
             ),
         );
         all_deps.extend(lockfile_deps);
+        if reached_dependency_limit(&mut all_deps, max_deps)? {
+            return Ok(all_deps);
+        }
     }
 
     // Workspace detection
-    if let Ok(workspace_deps) = detect_workspace_dependencies(project_root, package_json_path) {
-        log(
-            LogLevel::Info,
-            &format!(
-                "Workspace detection found {} additional dependencies",
-                workspace_deps.len()
-            ),
-        );
-        all_deps.extend(workspace_deps);
+    let workspace_deps = detect_workspace_dependencies(project_root, package_json_path, max_deps)?;
+    log(
+        LogLevel::Info,
+        &format!(
+            "Workspace detection found {} additional dependencies",
+            workspace_deps.len()
+        ),
+    );
+    all_deps.extend(workspace_deps);
+    if reached_dependency_limit(&mut all_deps, max_deps)? {
+        return Ok(all_deps);
     }
 
     // recursive resolver
     if all_deps.len() < 20 {
         log(LogLevel::Info, "Using recursive resolver as final fallback");
         let mut resolver = DependencyResolver::new();
-        if let Ok(recursive_deps) = resolver.resolve_recursive_dependencies(package_json_path, 15) {
+        if let Ok(recursive_deps) =
+            resolver.resolve_recursive_dependencies(package_json_path, DEFAULT_RESOLVER_DEPTH)
+        {
             log(
                 LogLevel::Info,
                 &format!(
@@ -457,10 +754,40 @@ fn try_all_dependency_detection_methods(
                 ),
             );
             all_deps.extend(recursive_deps);
+            if reached_dependency_limit(&mut all_deps, max_deps)? {
+                return Ok(all_deps);
+            }
         }
     }
 
-    all_deps
+    Ok(all_deps)
+}
+
+/// Whether a scan has collected more dependencies than `--max-deps` allows and can stop
+///
+/// The caller still applies `reached_dependency_limit` to the combined result.
+fn exceeds_dependency_limit(deps: &HashMap<String, String>, max_deps: DependencyLimit) -> bool {
+    max_deps.max.is_some_and(|max| deps.len() > max)
+}
+
+/// Enforce `--max-deps` on a partially collected dependency map
+///
+/// Returns true once the limit is exceeded in truncate mode, after keeping the first `max`
+/// dependencies by name; in error mode exceeding the limit is an error.
+fn reached_dependency_limit(
+    all_deps: &mut HashMap<String, String>,
+    max_deps: DependencyLimit,
+) -> FeludaResult<bool> {
+    let Some(max) = max_deps.check(all_deps.len())? else {
+        return Ok(false);
+    };
+
+    let mut names: Vec<String> = all_deps.keys().cloned().collect();
+    names.sort();
+    for name in names.into_iter().skip(max) {
+        all_deps.remove(&name);
+    }
+    Ok(true)
 }
 
 /// Get all pnpm detection methods
@@ -538,6 +865,18 @@ fn pnpm_list_prod_dev(project_root: &Path) -> Result<HashMap<String, String>, St
     parse_pnpm_text_output(&output)
 }
 
+fn pnpm_list_production(project_root: &Path) -> Result<HashMap<String, String>, String> {
+    log(LogLevel::Info, "Trying: pnpm list --prod --json");
+
+    let output = Command::new("pnpm")
+        .args(["list", "--prod", "--json", "--depth", "Infinity"])
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| format!("pnpm list prod failed: {e}"))?;
+
+    parse_pnpm_json_output(&output)
+}
+
 fn pnpm_why_based_detection(project_root: &Path) -> Result<HashMap<String, String>, String> {
     log(LogLevel::Info, "Trying: pnpm-based package discovery");
 
@@ -646,6 +985,23 @@ fn npm_ls_all_json(project_root: &Path) -> Result<HashMap<String, String>, Strin
     parse_npm_json_output(&output)
 }
 
+fn npm_ls_production(project_root: &Path) -> Result<HashMap<String, String>, String> {
+    log(LogLevel::Info, "Trying: npm ls --all --json --omit=dev");
+
+    #[cfg(windows)]
+    const NPM: &str = "npm.cmd";
+    #[cfg(not(windows))]
+    const NPM: &str = "npm";
+
+    let output = Command::new(NPM)
+        .args(["ls", "--all", "--json", "--omit=dev", "--omit=optional"])
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| format!("npm ls production failed: {e}"))?;
+
+    parse_npm_json_output(&output)
+}
+
 fn npm_ls_long_format(project_root: &Path) -> Result<HashMap<String, String>, String> {
     log(LogLevel::Info, "Trying: npm ls --long --parseable");
 
@@ -684,7 +1040,11 @@ fn npm_list_global_style(project_root: &Path) -> Result<HashMap<String, String>,
 // NODE_MODULES SCANNING
 // =============================================================================
 
-fn comprehensive_node_modules_scan(project_root: &Path) -> Result<HashMap<String, String>, String> {
+fn comprehensive_node_modules_scan(
+    project_root: &Path,
+    max_depth: usize,
+    max_deps: DependencyLimit,
+) -> Result<HashMap<String, String>, String> {
     log(LogLevel::Info, "Starting comprehensive node_modules scan");
 
     let node_modules = project_root.join("node_modules");
@@ -695,15 +1055,22 @@ fn comprehensive_node_modules_scan(project_root: &Path) -> Result<HashMap<String
     let mut all_packages = HashMap::new();
     let mut visited_paths = HashSet::new();
 
-    scan_with_symlink_resolution(&node_modules, &mut all_packages, &mut visited_paths, 0)?;
+    scan_with_symlink_resolution(
+        &node_modules,
+        &mut all_packages,
+        &mut visited_paths,
+        0,
+        max_depth,
+        max_deps,
+    )?;
 
     let pnpm_dir = node_modules.join(".pnpm");
-    if pnpm_dir.exists() {
+    if pnpm_dir.exists() && !exceeds_dependency_limit(&all_packages, max_deps) {
         log(
             LogLevel::Info,
             "Found .pnpm directory, scanning pnpm virtual store",
         );
-        scan_pnpm_virtual_store(&pnpm_dir, &mut all_packages)?;
+        scan_pnpm_virtual_store(&pnpm_dir, &mut all_packages, max_depth, max_deps)?;
     }
 
     Ok(all_packages)
@@ -714,8 +1081,10 @@ fn scan_with_symlink_resolution(
     packages: &mut HashMap<String, String>,
     visited: &mut HashSet<PathBuf>,
     depth: usize,
+    max_depth: usize,
+    max_deps: DependencyLimit,
 ) -> Result<(), String> {
-    if depth > 25 || visited.contains(&dir.to_path_buf()) {
+    if depth > max_depth || visited.contains(&dir.to_path_buf()) {
         return Ok(());
     }
 
@@ -725,6 +1094,10 @@ fn scan_with_symlink_resolution(
         fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
 
     for entry in entries.flatten() {
+        if exceeds_dependency_limit(packages, max_deps) {
+            break;
+        }
+
         let path = entry.path();
 
         if !path.is_dir() {
@@ -754,7 +1127,14 @@ fn scan_with_symlink_resolution(
 
                         let nested = scoped_path.join("node_modules");
                         if nested.exists() {
-                            scan_with_symlink_resolution(&nested, packages, visited, depth + 1)?;
+                            scan_with_symlink_resolution(
+                                &nested,
+                                packages,
+                                visited,
+                                depth + 1,
+                                max_depth,
+                                max_deps,
+                            )?;
                         }
                     }
                 }
@@ -764,7 +1144,14 @@ fn scan_with_symlink_resolution(
 
             let nested = path.join("node_modules");
             if nested.exists() {
-                scan_with_symlink_resolution(&nested, packages, visited, depth + 1)?;
+                scan_with_symlink_resolution(
+                    &nested,
+                    packages,
+                    visited,
+                    depth + 1,
+                    max_depth,
+                    max_deps,
+                )?;
             }
         }
     }
@@ -775,10 +1162,16 @@ fn scan_with_symlink_resolution(
 fn scan_pnpm_virtual_store(
     pnpm_dir: &Path,
     packages: &mut HashMap<String, String>,
+    max_depth: usize,
+    max_deps: DependencyLimit,
 ) -> Result<(), String> {
     let entries = fs::read_dir(pnpm_dir).map_err(|e| format!("Failed to read .pnpm: {e}"))?;
 
     for entry in entries.flatten() {
+        if exceeds_dependency_limit(packages, max_deps) {
+            break;
+        }
+
         let path = entry.path();
         if path.is_dir() {
             let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
@@ -806,6 +1199,8 @@ fn scan_pnpm_virtual_store(
                             packages,
                             &mut visited,
                             0,
+                            max_depth,
+                            max_deps,
                         );
                     }
                 }
@@ -959,6 +1354,46 @@ fn parse_npm_lockfile(project_root: &Path) -> Option<HashMap<String, String>> {
     }
 }
 
+/// Parse package-lock.json, skipping packages only needed for development or optional installs
+fn parse_npm_lockfile_production(project_root: &Path) -> Option<HashMap<String, String>> {
+    let content = fs::read_to_string(project_root.join("package-lock.json")).ok()?;
+    let json: Value = serde_json::from_str(&content).ok()?;
+    let packages = json.get("packages")?.as_object()?;
+
+    log(
+        LogLevel::Info,
+        "Parsing package-lock.json for production dependencies",
+    );
+
+    let is_flagged =
+        |info: &Value, flag: &str| info.get(flag).and_then(|v| v.as_bool()) == Some(true);
+
+    let deps: HashMap<String, String> = packages
+        .iter()
+        .filter_map(|(path, info)| {
+            let (_, name) = path.rsplit_once("node_modules/")?;
+            if is_flagged(info, "dev")
+                || is_flagged(info, "optional")
+                || is_flagged(info, "devOptional")
+            {
+                return None;
+            }
+            let name = info.get("name").and_then(|n| n.as_str()).unwrap_or(name);
+            let version = info.get("version")?.as_str()?;
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect();
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Parsed {} production dependencies from package-lock.json",
+            deps.len()
+        ),
+    );
+    Some(deps)
+}
+
 // =============================================================================
 // WORKSPACE DETECTION
 // =============================================================================
@@ -966,7 +1401,8 @@ fn parse_npm_lockfile(project_root: &Path) -> Option<HashMap<String, String>> {
 fn detect_workspace_dependencies(
     project_root: &Path,
     package_json_path: &str,
-) -> Result<HashMap<String, String>, String> {
+    max_deps: DependencyLimit,
+) -> FeludaResult<HashMap<String, String>> {
     let mut workspace_deps = HashMap::new();
 
     if let Ok(content) = fs::read_to_string(package_json_path) {
@@ -990,9 +1426,9 @@ fn detect_workspace_dependencies(
                 };
 
                 for pattern in workspace_patterns {
-                    if let Ok(workspace_deps_found) = scan_workspace_pattern(project_root, pattern)
-                    {
-                        workspace_deps.extend(workspace_deps_found);
+                    workspace_deps.extend(scan_workspace_pattern(project_root, pattern, max_deps)?);
+                    if exceeds_dependency_limit(&workspace_deps, max_deps) {
+                        break;
                     }
                 }
             }
@@ -1005,7 +1441,8 @@ fn detect_workspace_dependencies(
 fn scan_workspace_pattern(
     project_root: &Path,
     pattern: &str,
-) -> Result<HashMap<String, String>, String> {
+    max_deps: DependencyLimit,
+) -> FeludaResult<HashMap<String, String>> {
     let mut deps = HashMap::new();
 
     let pattern_path = if let Some(stripped) = pattern.strip_suffix("/*") {
@@ -1014,19 +1451,40 @@ fn scan_workspace_pattern(
         project_root.join(pattern)
     };
 
+    // Vendored packages can match a workspace glob; `.feludaignore` keeps them out
+    let ignore_rules = crate::parser::load_ignore_rules(project_root);
+    if crate::parser::is_path_ignored(&ignore_rules, &pattern_path, true) {
+        log(
+            LogLevel::Info,
+            &format!("Skipping ignored workspace: {}", pattern_path.display()),
+        );
+        return Ok(deps);
+    }
+
     if pattern_path.exists() && pattern_path.is_dir() {
         if pattern.ends_with("/*") {
             if let Ok(entries) = fs::read_dir(&pattern_path) {
                 for entry in entries.flatten() {
+                    if exceeds_dependency_limit(&deps, max_deps) {
+                        break;
+                    }
+
                     let workspace_path = entry.path();
+                    if crate::parser::is_path_ignored(&ignore_rules, &workspace_path, true) {
+                        log(
+                            LogLevel::Info,
+                            &format!("Skipping ignored workspace: {}", workspace_path.display()),
+                        );
+                        continue;
+                    }
                     if workspace_path.is_dir() {
                         let workspace_package_json = workspace_path.join("package.json");
                         if workspace_package_json.exists() {
-                            let workspace_deps_found = try_all_dependency_detection_methods(
+                            deps.extend(try_all_dependency_detection_methods(
                                 &workspace_path,
                                 workspace_package_json.to_str().unwrap_or(""),
-                            );
-                            deps.extend(workspace_deps_found);
+                                max_deps,
+                            )?);
                         }
                     }
                 }
@@ -1034,11 +1492,11 @@ fn scan_workspace_pattern(
         } else {
             let workspace_package_json = pattern_path.join("package.json");
             if workspace_package_json.exists() {
-                let workspace_deps_found = try_all_dependency_detection_methods(
+                deps.extend(try_all_dependency_detection_methods(
                     &pattern_path,
                     workspace_package_json.to_str().unwrap_or(""),
-                );
-                deps.extend(workspace_deps_found);
+                    max_deps,
+                )?);
             }
         }
     }
@@ -1297,6 +1755,30 @@ fn read_package_version_from_path(path: &str) -> Option<String> {
 // LICENSE DETECTION
 // =============================================================================
 
+/// License sources in lookup order, set from `--node-license-sources` or `[node]`
+static LICENSE_SOURCES: RwLock<Vec<NodeLicenseSource>> = RwLock::new(Vec::new());
+
+/// Set the order of license sources; an empty list restores the default order
+pub fn set_license_sources(sources: Vec<NodeLicenseSource>) {
+    if sources != NodeLicenseSource::DEFAULT_ORDER {
+        log(
+            LogLevel::Info,
+            &format!("Using Node.js license sources: {sources:?}"),
+        );
+    }
+    *LICENSE_SOURCES.write().unwrap_or_else(|e| e.into_inner()) = sources;
+}
+
+/// License sources in lookup order, defaulting to `NodeLicenseSource::DEFAULT_ORDER`
+pub(crate) fn license_sources() -> Vec<NodeLicenseSource> {
+    let sources = LICENSE_SOURCES.read().unwrap_or_else(|e| e.into_inner());
+    if sources.is_empty() {
+        NodeLicenseSource::DEFAULT_ORDER.to_vec()
+    } else {
+        sources.clone()
+    }
+}
+
 fn get_license_for_package(
     project_root: &Path,
     name: &str,
@@ -1308,17 +1790,40 @@ fn get_license_for_package(
     #[cfg(not(windows))]
     const NPM: &str = "npm";
 
-    let mut result = get_license_from_package_json(project_root, name, version);
+    let sources = license_sources();
+    let resolve = || {
+        license_from_sources(&sources, project_root, name, version, no_local, NPM)
+            .unwrap_or_else(|| "Unknown (failed to retrieve)".to_string())
+    };
 
-    if result.is_none() && !no_local {
-        result = get_license_from_local_license_file(project_root, name);
+    // Cached licenses may have been read from node_modules, which --no-local rules out
+    if no_local {
+        return resolve();
     }
+    cache::cached_package_license("node", name, version, resolve)
+}
 
-    result
-        .or_else(|| get_license_from_pnpm_metadata(project_root, name, version))
-        .or_else(|| get_license_from_npm_view(NPM, name, version))
-        .or_else(|| get_license_from_npm_registry_api(name, version))
-        .unwrap_or_else(|| "Unknown (failed to retrieve)".to_string())
+/// First license found by trying `sources` in order
+fn license_from_sources(
+    sources: &[NodeLicenseSource],
+    project_root: &Path,
+    name: &str,
+    version: &str,
+    no_local: bool,
+    npm_cmd: &str,
+) -> Option<String> {
+    sources.iter().find_map(|source| match source {
+        NodeLicenseSource::PackageJson => {
+            get_license_from_package_json(project_root, name, version)
+        }
+        NodeLicenseSource::LicenseFile if no_local => None,
+        NodeLicenseSource::LicenseFile => get_license_from_local_license_file(project_root, name),
+        NodeLicenseSource::PnpmMetadata => {
+            get_license_from_pnpm_metadata(project_root, name, version)
+        }
+        NodeLicenseSource::NpmView => get_license_from_npm_view(npm_cmd, name, version),
+        NodeLicenseSource::RegistryApi => get_license_from_npm_registry_api(name, version),
+    })
 }
 
 fn get_license_from_package_json(
@@ -1411,6 +1916,11 @@ fn get_license_from_package_json(
 }
 
 fn get_license_from_npm_view(npm_cmd: &str, package_name: &str, version: &str) -> Option<String> {
+    // npm view queries the registry
+    if skip_network(&format!("npm view {package_name}")) {
+        return None;
+    }
+
     let clean_version = clean_version_string(version);
     let package_spec = if clean_version == "latest" || clean_version.is_empty() {
         package_name.to_string()
@@ -1423,13 +1933,15 @@ fn get_license_from_npm_view(npm_cmd: &str, package_name: &str, version: &str) -
         &format!("Trying npm view for: {package_spec}"),
     );
 
-    let output = Command::new(npm_cmd)
-        .arg("view")
-        .arg(&package_spec)
-        .arg("license")
-        .arg("--json")
-        .output()
-        .ok()?;
+    let output = with_network_permit(|| {
+        Command::new(npm_cmd)
+            .arg("view")
+            .arg(&package_spec)
+            .arg("license")
+            .arg("--json")
+            .output()
+    })
+    .ok()?;
 
     if !output.status.success() {
         return None;
@@ -1452,6 +1964,10 @@ fn get_license_from_npm_view(npm_cmd: &str, package_name: &str, version: &str) -
 }
 
 fn get_license_from_npm_registry_api(package_name: &str, version: &str) -> Option<String> {
+    if skip_network(&format!("npm registry license of {package_name}")) {
+        return None;
+    }
+
     log(
         LogLevel::Info,
         &format!("Trying npm registry API for {package_name}"),
@@ -1465,12 +1981,12 @@ fn get_license_from_npm_registry_api(package_name: &str, version: &str) -> Optio
 
     for ver in versions_to_try {
         let url = if ver == "latest" {
-            format!("https://registry.npmjs.org/{package_name}")
+            npm_registry_url(package_name, None)
         } else {
-            format!("https://registry.npmjs.org/{package_name}/{ver}")
+            npm_registry_url(package_name, Some(ver))
         };
 
-        if let Ok(response) = reqwest::blocking::get(&url) {
+        if let Ok(response) = with_network_permit(|| registry_get(&http_client()?, &url).send()) {
             if response.status().is_success() {
                 if let Ok(json) = response.json::<Value>() {
                     let license_paths = [
@@ -1572,31 +2088,29 @@ fn get_license_from_local_license_file(project_root: &Path, package_name: &str)
         ]
     };
 
-    let license_filenames = [
-        "LICENSE",
-        "LICENSE.md",
-        "LICENSE.txt",
-        "COPYING",
-        "COPYING.md",
-    ];
-
     for dir in package_dirs {
-        if !dir.exists() {
-            continue;
-        }
+        for license_path in license_files_in(&dir) {
+            let Ok(content) = fs::read_to_string(&license_path) else {
+                continue;
+            };
+            if content.trim().is_empty() {
+                continue;
+            }
 
-        for filename in &license_filenames {
-            let license_path = dir.join(filename);
-            if license_path.exists() {
-                if let Ok(content) = fs::read_to_string(&license_path) {
-                    if !content.trim().is_empty() {
-                        log(
-                            LogLevel::Info,
-                            &format!("Found license file for {package_name}: {filename}"),
-                        );
-                        return detect_license_from_content(&content);
-                    }
-                }
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Found license file for {package_name}: {}",
+                    license_path.display()
+                ),
+            );
+
+            // Full license texts resolve to an SPDX identifier, short notices to a keyword
+            let license = fingerprint::identify_license_text(&content)
+                .map(str::to_string)
+                .or_else(|| detect_license_from_content(&content));
+            if license.is_some() {
+                return license;
             }
         }
     }
@@ -1650,9 +2164,9 @@ fn clean_version_string(version: &str) -> String {
         .to_string()
 }
 
-#[allow(dead_code)]
 fn parse_package_json_dependencies(
     package_json_path: &str,
+    production_only: bool,
 ) -> Result<HashMap<String, String>, String> {
     log(
         LogLevel::Info,
@@ -1665,7 +2179,11 @@ fn parse_package_json_dependencies(
     let package_json: PackageJson =
         serde_json::from_str(&content).map_err(|e| format!("Failed to parse package.json: {e}"))?;
 
-    let all_deps = package_json.get_all_dependencies();
+    let all_deps = if production_only {
+        package_json.get_production_dependencies()
+    } else {
+        package_json.get_all_dependencies()
+    };
 
     log(
         LogLevel::Info,
@@ -1683,6 +2201,7 @@ fn parse_package_json_dependencies(
 fn analyze_pnpm_project_comprehensive(
     project_root: &Path,
     _package_json_path: &str,
+    max_deps: DependencyLimit,
 ) -> HashMap<String, String> {
     let mut all_deps = HashMap::new();
 
@@ -1701,6 +2220,10 @@ fn analyze_pnpm_project_comprehensive(
         all_deps.extend(lockfile_deps);
     }
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     log(LogLevel::Info, "Method 2: pnpm list commands");
     let before_pnpm_commands = all_deps.len();
 
@@ -1742,6 +2265,10 @@ fn analyze_pnpm_project_comprehensive(
         ),
     );
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     log(LogLevel::Info, "Method 3: Enhanced lockfile parsing");
     let before_enhanced_lockfile = all_deps.len();
     if let Ok(enhanced_deps) = parse_pnpm_lockfile_enhanced(project_root) {
@@ -1763,6 +2290,10 @@ fn analyze_pnpm_project_comprehensive(
         ),
     );
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     log(LogLevel::Info, "Method 4: .pnpm virtual store analysis");
     let before_virtual_store = all_deps.len();
     if let Ok(virtual_store_deps) = analyze_pnpm_virtual_store_comprehensive(project_root) {
@@ -1784,9 +2315,15 @@ fn analyze_pnpm_project_comprehensive(
         ),
     );
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     log(LogLevel::Info, "Method 5: node_modules symlink resolution");
     let before_symlinks = all_deps.len();
-    if let Ok(symlink_deps) = resolve_pnpm_symlinks(project_root) {
+    if let Ok(symlink_deps) =
+        resolve_pnpm_symlinks(project_root, DEFAULT_PNPM_SYMLINK_DEPTH, max_deps)
+    {
         log(
             LogLevel::Info,
             &format!(
@@ -1805,6 +2342,10 @@ fn analyze_pnpm_project_comprehensive(
         ),
     );
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     log(LogLevel::Info, "Method 6: Deep .pnpm directory scanning");
     let before_deep_scan = all_deps.len();
     if let Ok(deep_scan_deps) = deep_scan_pnpm_store(project_root) {
@@ -1826,10 +2367,16 @@ fn analyze_pnpm_project_comprehensive(
         ),
     );
 
+    if exceeds_dependency_limit(&all_deps, max_deps) {
+        return all_deps;
+    }
+
     if all_deps.len() < 200 {
         log(LogLevel::Info, "Method 7: node_modules scan");
         let before_fallback = all_deps.len();
-        if let Ok(fallback_deps) = comprehensive_node_modules_scan(project_root) {
+        if let Ok(fallback_deps) =
+            comprehensive_node_modules_scan(project_root, DEFAULT_NODE_MODULES_SCAN_DEPTH, max_deps)
+        {
             log(
                 LogLevel::Info,
                 &format!(
@@ -2370,7 +2917,11 @@ fn parse_pnpm_virtual_store_entry(dir_name: &str) -> Option<(String, String)> {
     None
 }
 
-fn resolve_pnpm_symlinks(project_root: &Path) -> Result<HashMap<String, String>, String> {
+fn resolve_pnpm_symlinks(
+    project_root: &Path,
+    max_depth: usize,
+    max_deps: DependencyLimit,
+) -> Result<HashMap<String, String>, String> {
     let node_modules = project_root.join("node_modules");
     if !node_modules.exists() {
         return Ok(HashMap::new());
@@ -2380,7 +2931,14 @@ fn resolve_pnpm_symlinks(project_root: &Path) -> Result<HashMap<String, String>,
     let mut packages = HashMap::new();
     let mut visited = HashSet::new();
 
-    scan_pnpm_symlinks_recursive(&node_modules, &mut packages, &mut visited, 0)?;
+    scan_pnpm_symlinks_recursive(
+        &node_modules,
+        &mut packages,
+        &mut visited,
+        0,
+        max_depth,
+        max_deps,
+    )?;
 
     log(
         LogLevel::Info,
@@ -2394,8 +2952,10 @@ fn scan_pnpm_symlinks_recursive(
     packages: &mut HashMap<String, String>,
     visited: &mut HashSet<PathBuf>,
     depth: usize,
+    max_depth: usize,
+    max_deps: DependencyLimit,
 ) -> Result<(), String> {
-    if depth > 30 || visited.contains(&dir.to_path_buf()) {
+    if depth > max_depth || visited.contains(&dir.to_path_buf()) {
         return Ok(());
     }
 
@@ -2405,6 +2965,10 @@ fn scan_pnpm_symlinks_recursive(
         .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
 
     for entry in entries.flatten() {
+        if exceeds_dependency_limit(packages, max_deps) {
+            break;
+        }
+
         let path = entry.path();
 
         if !path.is_dir() {
@@ -2434,7 +2998,14 @@ fn scan_pnpm_symlinks_recursive(
 
                         let nested = scoped_path.join("node_modules");
                         if nested.exists() {
-                            scan_pnpm_symlinks_recursive(&nested, packages, visited, depth + 1)?;
+                            scan_pnpm_symlinks_recursive(
+                                &nested,
+                                packages,
+                                visited,
+                                depth + 1,
+                                max_depth,
+                                max_deps,
+                            )?;
                         }
                     }
                 }
@@ -2444,7 +3015,14 @@ fn scan_pnpm_symlinks_recursive(
 
             let nested = path.join("node_modules");
             if nested.exists() {
-                scan_pnpm_symlinks_recursive(&nested, packages, visited, depth + 1)?;
+                scan_pnpm_symlinks_recursive(
+                    &nested,
+                    packages,
+                    visited,
+                    depth + 1,
+                    max_depth,
+                    max_deps,
+                )?;
             }
         }
     }
@@ -2502,9 +3080,346 @@ fn scan_nested_node_modules(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::MaxDepsAction;
     use std::fs;
     use tempfile::TempDir;
 
+    fn write_installed_package(root: &Path, name: &str, version: &str, deps: &str) {
+        let dir = root.join("node_modules").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            format!(r#"{{"name": "{name}", "version": "{version}", "dependencies": {{{deps}}}}}"#),
+        )
+        .unwrap();
+    }
+
+    fn depth_fixture() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"dependencies": {"alpha": "^1.0.0"}, "devDependencies": {"beta": "~2.0.0"}}"#,
+        )
+        .unwrap();
+        write_installed_package(root, "alpha", "1.2.0", r#""gamma": "^3.0.0""#);
+        write_installed_package(root, "beta", "2.0.1", "");
+        write_installed_package(root, "gamma", "3.1.0", r#""delta": "^4.0.0""#);
+        write_installed_package(root, "delta", "4.0.0", "");
+        temp_dir
+    }
+
+    #[test]
+    fn test_installed_dependency_names_only_known_packages() {
+        let temp_dir = depth_fixture();
+        let resolver = DependencyResolver::with_project_root(temp_dir.path());
+        let mut known = HashMap::new();
+        known.insert("alpha".to_string(), "1.2.0".to_string());
+        known.insert("gamma".to_string(), "3.1.0".to_string());
+
+        assert_eq!(
+            resolver.installed_dependency_names("alpha", &known),
+            vec!["gamma".to_string()]
+        );
+        // delta is installed but was not part of the analyzed set
+        assert!(resolver
+            .installed_dependency_names("gamma", &known)
+            .is_empty());
+        assert!(resolver
+            .installed_dependency_names("not-installed", &known)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_installed_copyright() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_installed_package(root, "licensed", "1.0.0", "");
+        fs::write(
+            root.join("node_modules/licensed/LICENSE"),
+            "MIT License\n\nCopyright (c) 2021 Jane Doe\n\nPermission is hereby granted...\n",
+        )
+        .unwrap();
+        let authored = root.join("node_modules/authored");
+        fs::create_dir_all(&authored).unwrap();
+        fs::write(
+            authored.join("package.json"),
+            r#"{"name": "authored", "version": "1.0.0",
+                "author": {"name": "John Roe", "email": "john@example.com"}}"#,
+        )
+        .unwrap();
+        write_installed_package(root, "anonymous", "1.0.0", "");
+
+        let resolver = DependencyResolver::with_project_root(root);
+        assert_eq!(
+            resolver.installed_copyright("licensed").as_deref(),
+            Some("Copyright (c) 2021 Jane Doe")
+        );
+        assert_eq!(
+            resolver.installed_copyright("authored").as_deref(),
+            Some("Copyright (c) John Roe")
+        );
+        assert_eq!(resolver.installed_copyright("anonymous"), None);
+        assert_eq!(resolver.installed_copyright("not-installed"), None);
+    }
+
+    #[test]
+    fn test_resolve_dependencies_depth_zero_direct_only() {
+        let temp_dir = depth_fixture();
+        let package_json = temp_dir.path().join("package.json");
+
+        let deps = resolve_dependencies_to_depth(package_json.to_str().unwrap(), 0, false);
+
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps.get("alpha"), Some(&"1.0.0".to_string()));
+        assert_eq!(deps.get("beta"), Some(&"2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_license_sources_are_tried_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_installed_package(root, "dual", "1.0.0", "");
+        let package_json = root.join("node_modules/dual/package.json");
+        fs::write(
+            &package_json,
+            r#"{"name": "dual", "version": "1.0.0", "license": "MIT"}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("node_modules/dual/LICENSE"),
+            "Apache License\nVersion 2.0, January 2004",
+        )
+        .unwrap();
+
+        let resolve = |sources: &[NodeLicenseSource], no_local| {
+            license_from_sources(sources, root, "dual", "1.0.0", no_local, "npm")
+        };
+        use NodeLicenseSource::*;
+        assert_eq!(
+            resolve(&NodeLicenseSource::DEFAULT_ORDER, false).as_deref(),
+            Some("MIT")
+        );
+        assert_eq!(
+            resolve(&[LicenseFile, PackageJson], false).as_deref(),
+            Some("Apache License")
+        );
+        // --no-local still skips license files wherever they are in the list
+        assert_eq!(
+            resolve(&[LicenseFile, PackageJson], true).as_deref(),
+            Some("MIT")
+        );
+        assert_eq!(resolve(&[PnpmMetadata], false), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_license_sources_without_npm_view_do_not_spawn_npm() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let marker = temp_dir.path().join("npm-was-spawned");
+        let fake_npm = temp_dir.path().join("fake-npm");
+        fs::write(
+            &fake_npm,
+            format!("#!/bin/sh\ntouch '{}'\necho '\"ISC\"'\n", marker.display()),
+        )
+        .unwrap();
+        fs::set_permissions(&fake_npm, fs::Permissions::from_mode(0o755)).unwrap();
+        let fake_npm = fake_npm.to_str().unwrap();
+
+        let resolve = |sources: &[NodeLicenseSource]| {
+            license_from_sources(sources, temp_dir.path(), "absent", "1.0.0", false, fake_npm)
+        };
+
+        use NodeLicenseSource::*;
+        assert_eq!(resolve(&[PackageJson, LicenseFile, PnpmMetadata]), None);
+        assert!(!marker.exists());
+
+        assert_eq!(resolve(&[PackageJson, NpmView]).as_deref(), Some("ISC"));
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn test_resolve_direct_dependencies_uses_installed_versions() {
+        let temp_dir = depth_fixture();
+        let package_json = temp_dir.path().join("package.json");
+        let package_json = package_json.to_str().unwrap();
+
+        let deps = resolve_direct_dependencies(temp_dir.path(), package_json, true);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps.get("alpha"), Some(&"1.2.0".to_string()));
+
+        let deps = resolve_direct_dependencies(temp_dir.path(), package_json, false);
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps.get("beta"), Some(&"2.0.1".to_string()));
+        assert!(!deps.contains_key("gamma"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_direct_only_skips_transitive_node_dependencies() {
+        let temp_dir = depth_fixture();
+        let package_json = temp_dir.path().join("package.json");
+        let config = crate::config::FeludaConfig {
+            direct_only: true,
+            ..Default::default()
+        };
+
+        crate::network::set_offline_mode(true);
+        let result =
+            analyze_js_licenses_with_config(package_json.to_str().unwrap(), &config, false);
+        crate::network::set_offline_mode(false);
+
+        let names: Vec<&str> = result
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|info| info.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["alpha"]);
+    }
+
+    #[test]
+    fn test_resolve_dependencies_depth_two_adds_one_level() {
+        let temp_dir = depth_fixture();
+        let package_json = temp_dir.path().join("package.json");
+
+        let deps = resolve_dependencies_to_depth(package_json.to_str().unwrap(), 2, false);
+
+        assert_eq!(deps.len(), 3);
+        assert_eq!(deps.get("alpha"), Some(&"1.2.0".to_string()));
+        assert_eq!(deps.get("beta"), Some(&"2.0.1".to_string()));
+        assert_eq!(deps.get("gamma"), Some(&"3.1.0".to_string()));
+        assert!(!deps.contains_key("delta"));
+    }
+
+    #[test]
+    fn test_node_modules_scans_stop_at_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut dir = temp_dir.path().join("node_modules");
+        for (name, version) in [("outer", "1.0.0"), ("middle", "2.0.0"), ("inner", "3.0.0")] {
+            let package = dir.join(name);
+            fs::create_dir_all(&package).unwrap();
+            fs::write(
+                package.join("package.json"),
+                format!(r#"{{"name": "{name}", "version": "{version}"}}"#),
+            )
+            .unwrap();
+            dir = package.join("node_modules");
+        }
+
+        let unlimited = DependencyLimit::default();
+        for scan in [comprehensive_node_modules_scan, resolve_pnpm_symlinks] {
+            let direct = scan(temp_dir.path(), 0, unlimited).unwrap();
+            assert_eq!(direct.keys().collect::<Vec<_>>(), vec!["outer"]);
+
+            let nested = scan(temp_dir.path(), 1, unlimited).unwrap();
+            assert_eq!(nested.len(), 2);
+            assert!(!nested.contains_key("inner"));
+
+            let all = scan(temp_dir.path(), DEFAULT_NODE_MODULES_SCAN_DEPTH, unlimited).unwrap();
+            assert_eq!(all.get("inner"), Some(&"3.0.0".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_node_modules_scans_stop_at_max_deps() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut dir = temp_dir.path().join("node_modules");
+        for name in ["outer", "middle", "inner"] {
+            let package = dir.join(name);
+            fs::create_dir_all(&package).unwrap();
+            fs::write(
+                package.join("package.json"),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .unwrap();
+            dir = package.join("node_modules");
+        }
+
+        // The scan stops once it holds more than `max`; the caller truncates the rest
+        let limit = DependencyLimit::new(Some(1), MaxDepsAction::Truncate);
+        for scan in [comprehensive_node_modules_scan, resolve_pnpm_symlinks] {
+            let limited = scan(temp_dir.path(), DEFAULT_NODE_MODULES_SCAN_DEPTH, limit).unwrap();
+            assert_eq!(limited.len(), 2);
+            assert!(!limited.contains_key("inner"));
+        }
+    }
+
+    #[test]
+    fn test_workspace_scan_honors_max_deps_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        for name in ["a", "b"] {
+            let workspace = temp_dir.path().join("packages").join(name);
+            fs::create_dir_all(&workspace).unwrap();
+            fs::write(
+                workspace.join("package.json"),
+                r#"{"name": "ws", "dependencies": {"left": "1.0.0", "right": "2.0.0"}}"#,
+            )
+            .unwrap();
+        }
+
+        let limit = DependencyLimit::new(Some(1), MaxDepsAction::Error);
+        let result =
+            detect_workspace_dependencies(temp_dir.path(), package_json.to_str().unwrap(), limit);
+        assert!(result.is_err());
+
+        let deps = detect_workspace_dependencies(
+            temp_dir.path(),
+            package_json.to_str().unwrap(),
+            DependencyLimit::default(),
+        )
+        .unwrap();
+        assert_eq!(deps.len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_dependencies_production_only_skips_dev() {
+        let temp_dir = depth_fixture();
+        let package_json = temp_dir.path().join("package.json");
+
+        let deps = resolve_dependencies_to_depth(package_json.to_str().unwrap(), 15, true);
+
+        assert!(!deps.contains_key("beta"));
+        assert_eq!(deps.get("alpha"), Some(&"1.2.0".to_string()));
+        assert_eq!(deps.get("gamma"), Some(&"3.1.0".to_string()));
+        assert_eq!(deps.get("delta"), Some(&"4.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_parse_npm_lockfile_production_skips_dev_and_optional() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package-lock.json"),
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": {"name": "app", "version": "1.0.0"},
+                    "node_modules/express": {"version": "4.18.2"},
+                    "node_modules/express/node_modules/debug": {"version": "2.6.9"},
+                    "node_modules/jest": {"version": "29.7.0", "dev": true},
+                    "node_modules/fsevents": {"version": "2.3.3", "optional": true},
+                    "node_modules/typescript": {"version": "5.3.3", "devOptional": true}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let deps = parse_npm_lockfile_production(temp_dir.path()).unwrap();
+
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps.get("express"), Some(&"4.18.2".to_string()));
+        assert_eq!(deps.get("debug"), Some(&"2.6.9".to_string()));
+    }
+
     #[test]
     fn test_detect_license_from_content_mit() {
         let mit_content = "MIT License\n\nCopyright (c) 2024";
@@ -2568,6 +3483,49 @@ mod tests {
         assert_eq!(result, Some("Apache License".to_string()));
     }
 
+    #[test]
+    fn test_get_license_from_local_license_file_verbatim_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_dir = temp_dir
+            .path()
+            .join("node_modules")
+            .join("no-license-field");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(
+            package_dir.join("package.json"),
+            r#"{"name": "no-license-field", "version": "1.0.0"}"#,
+        )
+        .unwrap();
+        fs::write(package_dir.join("LICENSE"), include_str!("../../LICENSE")).unwrap();
+
+        assert_eq!(
+            get_license_from_package_json(temp_dir.path(), "no-license-field", "1.0.0"),
+            None
+        );
+        assert_eq!(
+            get_license_from_local_license_file(temp_dir.path(), "no-license-field"),
+            Some("MIT".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_license_from_local_license_file_suffixed_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_dir = temp_dir.path().join("node_modules").join("dual");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(package_dir.join("README.md"), "MIT License").unwrap();
+        fs::write(
+            package_dir.join("licence-mit.txt"),
+            include_str!("../../LICENSE"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_license_from_local_license_file(temp_dir.path(), "dual"),
+            Some("MIT".to_string())
+        );
+    }
+
     #[test]
     fn test_get_license_from_local_license_file_not_found() {
         let temp_dir = TempDir::new().unwrap();
@@ -2592,4 +3550,76 @@ mod tests {
         let result = get_license_from_local_license_file(temp_dir.path(), "test-pkg");
         assert_eq!(result, Some("BSD".to_string()));
     }
+
+    /// A project whose package-lock.json lists `count` packages and nothing else to resolve
+    fn write_large_lockfile_project(dir: &Path, count: usize) -> PathBuf {
+        let package_json = dir.join("package.json");
+        fs::write(&package_json, r#"{"name": "monorepo", "version": "1.0.0"}"#).unwrap();
+
+        let packages: serde_json::Map<String, Value> = (0..count)
+            .map(|i| {
+                (
+                    format!("node_modules/pkg-{i:05}"),
+                    serde_json::json!({"name": format!("pkg-{i:05}"), "version": "1.0.0"}),
+                )
+            })
+            .collect();
+        let lockfile = serde_json::json!({"lockfileVersion": 3, "packages": packages});
+        fs::write(dir.join("package-lock.json"), lockfile.to_string()).unwrap();
+        package_json
+    }
+
+    #[test]
+    fn test_reached_dependency_limit_on_large_map() {
+        let large: HashMap<String, String> = (0..20_000)
+            .map(|i| (format!("pkg-{i:05}"), "1.0.0".to_string()))
+            .collect();
+
+        let mut deps = large.clone();
+        assert!(!reached_dependency_limit(&mut deps, DependencyLimit::default()).unwrap());
+        assert_eq!(deps.len(), 20_000);
+
+        let limit = DependencyLimit::new(Some(20_000), MaxDepsAction::Error);
+        assert!(!reached_dependency_limit(&mut deps, limit).unwrap());
+
+        let limit = DependencyLimit::new(Some(1_000), MaxDepsAction::Error);
+        let err = reached_dependency_limit(&mut deps, limit).unwrap_err();
+        assert!(matches!(err, crate::debug::FeludaError::Parser(_)));
+        assert!(err.to_string().contains("1000"));
+
+        let limit = DependencyLimit::new(Some(1_000), MaxDepsAction::Truncate);
+        assert!(reached_dependency_limit(&mut deps, limit).unwrap());
+        assert_eq!(deps.len(), 1_000);
+        // Truncation keeps the first packages by name, so repeated runs agree
+        assert!(deps.contains_key("pkg-00000"));
+        assert!(deps.contains_key("pkg-00999"));
+        assert!(!deps.contains_key("pkg-01000"));
+    }
+
+    #[test]
+    fn test_try_all_dependency_detection_methods_stops_at_max_deps() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = write_large_lockfile_project(temp_dir.path(), 150);
+        let package_json = package_json.to_str().unwrap();
+
+        let limit = DependencyLimit::new(Some(100), MaxDepsAction::Error);
+        let result = try_all_dependency_detection_methods(temp_dir.path(), package_json, limit);
+        assert!(matches!(
+            result,
+            Err(crate::debug::FeludaError::Parser(msg)) if msg.contains("100")
+        ));
+
+        let limit = DependencyLimit::new(Some(100), MaxDepsAction::Truncate);
+        let deps =
+            try_all_dependency_detection_methods(temp_dir.path(), package_json, limit).unwrap();
+        assert_eq!(deps.len(), 100);
+
+        let deps = try_all_dependency_detection_methods(
+            temp_dir.path(),
+            package_json,
+            DependencyLimit::default(),
+        )
+        .unwrap();
+        assert!(deps.len() >= 150);
+    }
 }
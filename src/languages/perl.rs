@@ -0,0 +1,557 @@
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::cache;
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+use crate::network::{http_client, skip_network, with_network_permit};
+
+/// Ecosystem name for CPAN distributions
+const ECOSYSTEM: &str = "cpan";
+
+/// CPAN::Meta::Spec license tokens and their SPDX equivalents
+const CPAN_LICENSE_MAP: [(&str, &str); 22] = [
+    ("agpl_3", "AGPL-3.0-only"),
+    ("apache_1_1", "Apache-1.1"),
+    ("apache_2_0", "Apache-2.0"),
+    ("artistic_1", "Artistic-1.0"),
+    ("artistic_2", "Artistic-2.0"),
+    ("bsd", "BSD-3-Clause"),
+    ("freebsd", "BSD-2-Clause"),
+    ("gfdl_1_2", "GFDL-1.2-only"),
+    ("gfdl_1_3", "GFDL-1.3-only"),
+    ("gpl_1", "GPL-1.0-only"),
+    ("gpl_2", "GPL-2.0-only"),
+    ("gpl_3", "GPL-3.0-only"),
+    ("lgpl_2_1", "LGPL-2.1-only"),
+    ("lgpl_3_0", "LGPL-3.0-only"),
+    ("mit", "MIT"),
+    ("mozilla_1_0", "MPL-1.0"),
+    ("mozilla_1_1", "MPL-1.1"),
+    ("openssl", "OpenSSL"),
+    ("perl_5", "Artistic-1.0-Perl OR GPL-1.0-or-later"),
+    ("qpl_1_0", "QPL-1.0"),
+    ("sun", "SISSL"),
+    ("zlib", "Zlib"),
+];
+
+/// A module required by a `cpanfile`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CpanRequirement {
+    module: String,
+    version: Option<String>,
+    /// Declared outside the runtime phase (`test_requires`, `on 'develop' => sub { ... }`, ...)
+    development: bool,
+}
+
+/// A distribution pinned by `cpanfile.snapshot`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SnapshotDistribution {
+    dist: String,
+    version: String,
+    /// PAUSE author from the `pathname`, needed to address a specific release on MetaCPAN
+    author: Option<String>,
+}
+
+/// Map a CPAN license token such as `perl_5` to an SPDX expression
+///
+/// Unknown tokens (`open_source`, `restricted`, ...) are returned unchanged.
+pub fn normalize_cpan_license(token: &str) -> String {
+    let token = token.trim();
+    CPAN_LICENSE_MAP
+        .iter()
+        .find(|(cpan, _)| cpan.eq_ignore_ascii_case(token))
+        .map(|(_, spdx)| spdx.to_string())
+        .unwrap_or_else(|| token.to_string())
+}
+
+/// Parses Perl projects through the [`LanguageParser`] interface
+pub struct PerlParser;
+
+impl LanguageParser for PerlParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_perl_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Perl"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &["cpanfile"]
+    }
+}
+
+pub fn analyze_perl_licenses(cpanfile_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
+    log(
+        LogLevel::Info,
+        &format!("Analyzing Perl dependencies from: {cpanfile_path}"),
+    );
+
+    let content = match fs::read_to_string(cpanfile_path) {
+        Ok(content) => content,
+        Err(err) => {
+            log_error("Failed to read cpanfile", &err);
+            return Vec::new();
+        }
+    };
+
+    let requirements: Vec<CpanRequirement> = parse_cpanfile(&content)
+        .into_iter()
+        .filter(|req| config.include_dev || !req.development)
+        .collect();
+
+    // Carton pins the exact distribution that provides each module
+    let snapshot_path = Path::new(cpanfile_path).with_file_name("cpanfile.snapshot");
+    let snapshot = match fs::read_to_string(&snapshot_path) {
+        Ok(content) => {
+            let snapshot = parse_cpanfile_snapshot(&content);
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Found {} pinned modules in cpanfile.snapshot",
+                    snapshot.len()
+                ),
+            );
+            snapshot
+        }
+        Err(_) => HashMap::new(),
+    };
+
+    log(
+        LogLevel::Info,
+        &format!("Found {} Perl dependencies", requirements.len()),
+    );
+    log_debug("cpanfile requirements", &requirements);
+
+    let known_licenses = match fetch_licenses_from_github() {
+        Ok(licenses) => licenses,
+        Err(err) => {
+            log_error("Failed to fetch licenses from GitHub", &err);
+            HashMap::new()
+        }
+    };
+
+    crate::cli::add_dependencies_to_resolve(requirements.len());
+
+    requirements
+        .into_iter()
+        .map(|req| {
+            let (version, license) = match snapshot.get(&req.module) {
+                Some(pinned) => (
+                    pinned.version.clone(),
+                    fetch_license_for_perl_dependency(
+                        &pinned.dist,
+                        &pinned.version,
+                        pinned.author.as_deref(),
+                    ),
+                ),
+                None => {
+                    let version = req.version.unwrap_or_else(|| "latest".to_string());
+                    let license = fetch_license_for_perl_dependency(
+                        &module_to_distribution(&req.module),
+                        &version,
+                        None,
+                    );
+                    (version, license)
+                }
+            };
+            let license = Some(license);
+            let is_restrictive = is_license_restrictive(&license, &known_licenses, config.strict);
+
+            if is_restrictive {
+                log(
+                    LogLevel::Warn,
+                    &format!("Restrictive license found: {license:?} for {}", req.module),
+                );
+            }
+
+            crate::cli::mark_dependency_resolved();
+
+            LicenseInfo {
+                is_restrictive,
+                osi_status: match &license {
+                    Some(l) => crate::licenses::get_osi_status(l),
+                    None => crate::licenses::OsiStatus::Unknown,
+                },
+                ecosystem: ECOSYSTEM.to_string(),
+                ..LicenseInfo::new(req.module, version, license)
+            }
+        })
+        .collect()
+}
+
+/// Parse the `requires` statements of a `cpanfile`
+///
+/// A cpanfile is Perl code, but in practice every requirement is a single
+/// `requires 'Module', 'version';` statement, optionally nested in an
+/// `on '<phase>' => sub { ... };` block, so a line-based scan is enough.
+/// `recommends`/`suggests`, the `perl` pseudo-module and duplicate modules are skipped.
+fn parse_cpanfile(content: &str) -> Vec<CpanRequirement> {
+    let (Ok(requires_re), Ok(phase_re)) = (
+        Regex::new(
+            r#"^((?:test_|build_|configure_|author_)?requires)\s*\(?\s*['"]([^'"]+)['"]\s*(?:(?:,|=>)\s*['"]?([^'";)]*)['"]?)?"#,
+        ),
+        Regex::new(r#"^on\s*\(?\s*['"]?(\w+)['"]?\s*=>\s*sub\s*\{"#),
+    ) else {
+        log(LogLevel::Error, "Failed to compile cpanfile patterns");
+        return Vec::new();
+    };
+
+    let mut requirements: Vec<CpanRequirement> = Vec::new();
+    let mut phase: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(caps) = phase_re.captures(line) {
+            phase = Some(caps[1].to_string());
+            continue;
+        }
+        if line.starts_with('}') {
+            phase = None;
+            continue;
+        }
+
+        let Some(caps) = requires_re.captures(line) else {
+            continue;
+        };
+        let keyword = &caps[1];
+        let module = caps[2].trim().to_string();
+        if module == "perl" || requirements.iter().any(|req| req.module == module) {
+            continue;
+        }
+
+        let version = caps
+            .get(3)
+            .map(|v| {
+                v.as_str()
+                    .trim()
+                    .trim_start_matches(['>', '<', '=', '!', '~'])
+                    .trim()
+                    .to_string()
+            })
+            .filter(|v| !v.is_empty() && v != "0");
+        let development =
+            keyword != "requires" || phase.as_deref().is_some_and(|phase| phase != "runtime");
+
+        requirements.push(CpanRequirement {
+            module,
+            version,
+            development,
+        });
+    }
+
+    requirements
+}
+
+/// Parse a Carton `cpanfile.snapshot` into module -> providing distribution
+fn parse_cpanfile_snapshot(content: &str) -> HashMap<String, SnapshotDistribution> {
+    let mut modules = HashMap::new();
+    let mut current: Option<SnapshotDistribution> = None;
+    let mut in_provides = false;
+
+    for line in content.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        match indent {
+            // `DISTRIBUTIONS` header
+            0 => current = None,
+            // `Try-Tiny-0.31`
+            2 => {
+                in_provides = false;
+                current = split_dist_version(trimmed).map(|(dist, version)| SnapshotDistribution {
+                    dist,
+                    version,
+                    author: None,
+                });
+            }
+            // `pathname: E/ET/ETHER/Try-Tiny-0.31.tar.gz`, `provides:`, `requirements:`
+            4 => {
+                in_provides = trimmed == "provides:";
+                if let (Some(dist), Some(pathname)) =
+                    (current.as_mut(), trimmed.strip_prefix("pathname:"))
+                {
+                    dist.author = pathname.trim().split('/').nth(2).map(str::to_string);
+                }
+            }
+            // `Try::Tiny 0.31`
+            _ if in_provides => {
+                if let (Some(dist), Some(module)) = (&current, trimmed.split_whitespace().next()) {
+                    modules.insert(module.to_string(), dist.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    modules
+}
+
+/// Split `Try-Tiny-0.31` into `("Try-Tiny", "0.31")`
+fn split_dist_version(name: &str) -> Option<(String, String)> {
+    let (dist, version) = name.rsplit_once('-')?;
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| (dist.to_string(), version.to_string()))
+}
+
+/// Best-effort distribution name for a module, following the CPAN `Foo::Bar` -> `Foo-Bar` convention
+fn module_to_distribution(module: &str) -> String {
+    module.replace("::", "-")
+}
+
+/// Combine the `license` array of a MetaCPAN release into one SPDX expression
+///
+/// CPAN::Meta::Spec lists every license the distribution may be used under, so
+/// several entries are alternatives.
+fn metacpan_license_expression(release: &Value) -> Option<String> {
+    let licenses: Vec<String> = release["license"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .map(str::trim)
+        .filter(|license| !license.is_empty() && *license != "unknown")
+        .map(normalize_cpan_license)
+        .collect();
+
+    match licenses.len() {
+        0 => None,
+        1 => Some(licenses[0].clone()),
+        _ => Some(
+            licenses
+                .iter()
+                .map(|license| {
+                    if license.contains(' ') {
+                        format!("({license})")
+                    } else {
+                        license.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" OR "),
+        ),
+    }
+}
+
+pub fn fetch_license_for_perl_dependency(
+    dist: &str,
+    version: &str,
+    author: Option<&str>,
+) -> String {
+    cache::cached_package_license("perl", dist, version, || {
+        fetch_license_from_metacpan(dist, version, author)
+    })
+}
+
+fn fetch_license_from_metacpan(dist: &str, version: &str, author: Option<&str>) -> String {
+    if skip_network(&format!("MetaCPAN license of {dist}")) {
+        return String::from("Unknown");
+    }
+
+    // A specific release needs its author; otherwise ask for the latest release
+    let api_url = match author {
+        Some(author) if version != "latest" => {
+            format!("https://fastapi.metacpan.org/v1/release/{author}/{dist}-{version}")
+        }
+        _ => format!("https://fastapi.metacpan.org/v1/release/{dist}"),
+    };
+    log(
+        LogLevel::Info,
+        &format!("Fetching license from MetaCPAN: {api_url}"),
+    );
+
+    let client = match http_client() {
+        Ok(client) => client,
+        Err(err) => {
+            log_error("Failed to build HTTP client", &err);
+            return String::from("Unknown");
+        }
+    };
+
+    match with_network_permit(|| client.get(&api_url).send()) {
+        Ok(response) => {
+            let status = response.status();
+            log(
+                LogLevel::Info,
+                &format!("MetaCPAN API response status: {status}"),
+            );
+
+            if !status.is_success() {
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to fetch release for {dist}: HTTP {status}"),
+                );
+                return String::from("Unknown");
+            }
+
+            match response.json::<Value>() {
+                Ok(json) => metacpan_license_expression(&json).unwrap_or_else(|| {
+                    log(LogLevel::Warn, &format!("No license found for {dist}"));
+                    format!("Unknown license for {dist}")
+                }),
+                Err(err) => {
+                    log_error(&format!("Failed to parse JSON for {dist}"), &err);
+                    String::from("Unknown")
+                }
+            }
+        }
+        Err(err) => {
+            log_error(&format!("Failed to fetch release for {dist}"), &err);
+            String::from("Unknown")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::set_offline_mode;
+    use serde_json::json;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    const SAMPLE_CPANFILE: &str = r#"requires 'perl', '5.010';
+requires 'Try::Tiny', '0.30';
+requires "Moo" => ">= 2.004";
+requires 'JSON::PP';  # core since 5.14
+recommends 'JSON::XS';
+
+on 'test' => sub {
+    requires 'Test::More', '0.98';
+};
+
+on develop => sub {
+    requires 'Perl::Critic';
+};
+
+test_requires 'Test::Deep';
+"#;
+
+    const SAMPLE_SNAPSHOT: &str = r#"# carton snapshot format: version 1.0
+DISTRIBUTIONS
+  Moo-2.005005
+    pathname: H/HA/HAARG/Moo-2.005005.tar.gz
+    provides:
+      Method::Generate::Accessor 2.005005
+      Moo 2.005005
+      Moo::Role 2.005005
+    requirements:
+      Class::Method::Modifiers 1.10
+      perl 5.006
+  Try-Tiny-0.31
+    pathname: E/ET/ETHER/Try-Tiny-0.31.tar.gz
+    provides:
+      Try::Tiny 0.31
+    requirements:
+      ExtUtils::MakeMaker 0
+"#;
+
+    #[test]
+    fn test_normalize_cpan_license() {
+        assert_eq!(
+            normalize_cpan_license("perl_5"),
+            "Artistic-1.0-Perl OR GPL-1.0-or-later"
+        );
+        assert_eq!(normalize_cpan_license("artistic_2"), "Artistic-2.0");
+        assert_eq!(normalize_cpan_license("apache_2_0"), "Apache-2.0");
+        assert_eq!(normalize_cpan_license(" MIT "), "MIT");
+        assert_eq!(normalize_cpan_license("lgpl_2_1"), "LGPL-2.1-only");
+        assert_eq!(normalize_cpan_license("open_source"), "open_source");
+    }
+
+    #[test]
+    fn test_parse_cpanfile() {
+        let requirements = parse_cpanfile(SAMPLE_CPANFILE);
+        let modules: Vec<&str> = requirements.iter().map(|req| req.module.as_str()).collect();
+        assert_eq!(
+            modules,
+            vec![
+                "Try::Tiny",
+                "Moo",
+                "JSON::PP",
+                "Test::More",
+                "Perl::Critic",
+                "Test::Deep"
+            ]
+        );
+
+        assert_eq!(requirements[0].version.as_deref(), Some("0.30"));
+        assert_eq!(requirements[1].version.as_deref(), Some("2.004"));
+        assert_eq!(requirements[2].version, None);
+        assert!(!requirements[0].development);
+        assert!(requirements[3].development);
+        assert!(requirements[4].development);
+        assert!(requirements[5].development);
+    }
+
+    #[test]
+    fn test_parse_cpanfile_snapshot() {
+        let snapshot = parse_cpanfile_snapshot(SAMPLE_SNAPSHOT);
+        assert_eq!(snapshot.len(), 4);
+
+        let moo = snapshot.get("Moo::Role").unwrap();
+        assert_eq!(moo.dist, "Moo");
+        assert_eq!(moo.version, "2.005005");
+        assert_eq!(moo.author.as_deref(), Some("HAARG"));
+        assert!(!snapshot.contains_key("Class::Method::Modifiers"));
+    }
+
+    #[test]
+    fn test_metacpan_license_expression() {
+        assert_eq!(
+            metacpan_license_expression(&json!({"license": ["perl_5"]})),
+            Some("Artistic-1.0-Perl OR GPL-1.0-or-later".to_string())
+        );
+        assert_eq!(
+            metacpan_license_expression(&json!({"license": ["mit", "perl_5"]})),
+            Some("MIT OR (Artistic-1.0-Perl OR GPL-1.0-or-later)".to_string())
+        );
+        assert_eq!(
+            metacpan_license_expression(&json!({"license": ["unknown"]})),
+            None
+        );
+        assert_eq!(metacpan_license_expression(&json!({})), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_analyze_perl_licenses_uses_snapshot_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let cpanfile_path = temp_dir.path().join("cpanfile");
+        fs::write(&cpanfile_path, SAMPLE_CPANFILE).unwrap();
+        fs::write(temp_dir.path().join("cpanfile.snapshot"), SAMPLE_SNAPSHOT).unwrap();
+
+        let config = FeludaConfig::default();
+        set_offline_mode(true);
+        let result = analyze_perl_licenses(cpanfile_path.to_str().unwrap(), &config);
+        set_offline_mode(false);
+
+        let names: Vec<&str> = result.iter().map(|info| info.name.as_str()).collect();
+        assert_eq!(names, vec!["Try::Tiny", "Moo", "JSON::PP"]);
+        assert_eq!(result[0].version, "0.31");
+        assert_eq!(result[1].version, "2.005005");
+        assert_eq!(result[2].version, "latest");
+    }
+}
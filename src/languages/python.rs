@@ -7,11 +7,124 @@ use std::path::Path;
 use std::process::Command;
 use toml::Value as TomlValue;
 
+use crate::cache;
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
-};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{analyze_resolved_dependencies, project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, License, LicenseInfo};
+use crate::network::{http_client, pypi_json_url, skip_network, with_network_permit};
+use crate::registry_auth::registry_get;
+
+/// Ecosystem name for Python packages
+const ECOSYSTEM: &str = "pypi";
+
+/// Ecosystem name for packages installed from conda channels
+const CONDA_ECOSYSTEM: &str = "conda";
+
+/// anaconda.org channel used for conda packages when `environment.yml` names none
+const DEFAULT_CONDA_CHANNEL: &str = "anaconda";
+
+/// SPDX identifiers for common PyPI `License ::` trove classifiers
+///
+/// Classifiers that do not pin down one license, such as `BSD License` or
+/// `GNU Library or Lesser General Public License (LGPL)`, are left out.
+const PYPI_LICENSE_CLASSIFIERS: &[(&str, &str)] = &[
+    ("License :: OSI Approved :: MIT License", "MIT"),
+    (
+        "License :: OSI Approved :: MIT No Attribution License (MIT-0)",
+        "MIT-0",
+    ),
+    (
+        "License :: OSI Approved :: Apache Software License",
+        "Apache-2.0",
+    ),
+    ("License :: OSI Approved :: ISC License (ISCL)", "ISC"),
+    (
+        "License :: OSI Approved :: Mozilla Public License 1.1 (MPL 1.1)",
+        "MPL-1.1",
+    ),
+    (
+        "License :: OSI Approved :: Mozilla Public License 2.0 (MPL 2.0)",
+        "MPL-2.0",
+    ),
+    (
+        "License :: OSI Approved :: GNU General Public License v2 (GPLv2)",
+        "GPL-2.0-only",
+    ),
+    (
+        "License :: OSI Approved :: GNU General Public License v2 or later (GPLv2+)",
+        "GPL-2.0-or-later",
+    ),
+    (
+        "License :: OSI Approved :: GNU General Public License v3 (GPLv3)",
+        "GPL-3.0-only",
+    ),
+    (
+        "License :: OSI Approved :: GNU General Public License v3 or later (GPLv3+)",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "License :: OSI Approved :: GNU Lesser General Public License v2 (LGPLv2)",
+        "LGPL-2.0-only",
+    ),
+    (
+        "License :: OSI Approved :: GNU Lesser General Public License v2 or later (LGPLv2+)",
+        "LGPL-2.0-or-later",
+    ),
+    (
+        "License :: OSI Approved :: GNU Lesser General Public License v3 (LGPLv3)",
+        "LGPL-3.0-only",
+    ),
+    (
+        "License :: OSI Approved :: GNU Lesser General Public License v3 or later (LGPLv3+)",
+        "LGPL-3.0-or-later",
+    ),
+    (
+        "License :: OSI Approved :: GNU Affero General Public License v3",
+        "AGPL-3.0-only",
+    ),
+    (
+        "License :: OSI Approved :: GNU Affero General Public License v3 or later (AGPLv3+)",
+        "AGPL-3.0-or-later",
+    ),
+    (
+        "License :: OSI Approved :: Python Software Foundation License",
+        "PSF-2.0",
+    ),
+    (
+        "License :: OSI Approved :: The Unlicense (Unlicense)",
+        "Unlicense",
+    ),
+    (
+        "License :: OSI Approved :: Eclipse Public License 1.0 (EPL-1.0)",
+        "EPL-1.0",
+    ),
+    (
+        "License :: OSI Approved :: Eclipse Public License 2.0 (EPL-2.0)",
+        "EPL-2.0",
+    ),
+    (
+        "License :: OSI Approved :: Boost Software License 1.0 (BSL-1.0)",
+        "BSL-1.0",
+    ),
+    (
+        "License :: OSI Approved :: European Union Public Licence 1.2 (EUPL 1.2)",
+        "EUPL-1.2",
+    ),
+    (
+        "License :: OSI Approved :: Universal Permissive License (UPL)",
+        "UPL-1.0",
+    ),
+    (
+        "License :: OSI Approved :: Historical Permission Notice and Disclaimer (HPND)",
+        "HPND",
+    ),
+    ("License :: OSI Approved :: zlib/libpng License", "Zlib"),
+    (
+        "License :: CC0 1.0 Universal (CC0 1.0) Public Domain Dedication",
+        "CC0-1.0",
+    ),
+];
 
 /// Represents an environment marker in a Python requirement
 /// Environment markers follow PEP 508 and are used to specify conditional dependencies
@@ -116,6 +229,31 @@ fn parse_marker_components(marker_str: &str) -> Vec<MarkerComponent> {
     components
 }
 
+/// Parses Python projects through the [`LanguageParser`] interface
+pub struct PythonParser;
+
+impl LanguageParser for PythonParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_python_licenses(
+            project_file_str(project_file)?,
+            config,
+        ))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Python"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::PYTHON_PATHS
+    }
+}
+
 /// Analyze the licenses of Python dependencies with transitive resolution
 pub fn analyze_python_licenses(package_file_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
     let mut licenses = Vec::new();
@@ -138,103 +276,56 @@ pub fn analyze_python_licenses(package_file_path: &str, config: &FeludaConfig) -
         }
     };
 
-    // Check if it's a pyproject.toml file
-    if package_file_path.ends_with("pyproject.toml") {
+    if is_conda_environment_file(package_file_path) {
+        licenses = analyze_conda_environment(package_file_path, &known_licenses, config);
+    } else if package_file_path.ends_with("poetry.lock") || package_file_path.ends_with("uv.lock") {
+        // A lockfile without a manifest next to it: every locked package is analyzed
+        let project_dir = Path::new(package_file_path)
+            .parent()
+            .unwrap_or(Path::new("."));
+        match read_python_lockfile(project_dir, !config.include_dev) {
+            Some(locked) => {
+                licenses = analyze_resolved_dependencies(
+                    locked,
+                    &known_licenses,
+                    config.strict,
+                    ECOSYSTEM,
+                    fetch_license_for_python_dependency,
+                );
+            }
+            None => log(
+                LogLevel::Warn,
+                &format!("Failed to read locked packages from {package_file_path}"),
+            ),
+        }
+    } else if package_file_path.ends_with("pyproject.toml") {
         match fs::read_to_string(package_file_path) {
             Ok(content) => match toml::from_str::<TomlValue>(&content) {
-                Ok(toml_config) => {
-                    if let Some(project) = toml_config.as_table().and_then(|t| t.get("project")) {
-                        if let Some(deps) = project
-                            .as_table()
-                            .and_then(|t| t.get("dependencies"))
-                            .and_then(|d| d.as_array())
-                        {
-                            log(
-                                LogLevel::Info,
-                                &format!("Found {} Python dependencies", deps.len()),
-                            );
-                            log_debug("Dependencies", deps);
-
-                            // First collect direct dependencies
-                            let mut direct_deps = Vec::new();
-                            for dep in deps {
-                                if let Some(dep_str) = dep.as_str() {
-                                    let (name, version) = if let Some((n, v)) = dep_str
-                                        .split_once("==")
-                                        .or_else(|| dep_str.split_once(">="))
-                                        .or_else(|| dep_str.split_once(">"))
-                                        .or_else(|| dep_str.split_once("~="))
-                                        .or_else(|| dep_str.split_once("<="))
-                                        .or_else(|| dep_str.split_once("<"))
-                                    {
-                                        (n.trim(), v.trim())
-                                    } else {
-                                        (dep_str.trim(), "latest")
-                                    };
-
-                                    direct_deps.push((name.to_string(), version.to_string()));
-                                }
-                            }
-
-                            // Try to resolve all dependencies (direct + transitive) using uv or fallback to PyPI
-                            let max_depth = config.dependencies.max_depth;
-                            log(
-                                LogLevel::Info,
-                                &format!("Using max dependency depth: {max_depth}"),
-                            );
-                            let all_deps = resolve_python_dependencies(
-                                &direct_deps,
-                                package_file_path,
-                                max_depth,
-                            );
-
-                            // Process all resolved dependencies
-                            for (name, version) in all_deps {
-                                log(
-                                    LogLevel::Info,
-                                    &format!("Processing dependency: {name} ({version})"),
-                                );
-
-                                let license_result =
-                                    fetch_license_for_python_dependency(&name, &version);
-                                let license = Some(license_result);
-                                let is_restrictive = is_license_restrictive(
-                                    &license,
-                                    &known_licenses,
-                                    config.strict,
-                                );
-
-                                if is_restrictive {
-                                    log(
-                                        LogLevel::Warn,
-                                        &format!(
-                                            "Restrictive license found: {license:?} for {name}"
-                                        ),
-                                    );
-                                }
-
-                                licenses.push(LicenseInfo {
-                                    name,
-                                    version,
-                                    license: license.clone(),
-                                    is_restrictive,
-                                    compatibility: LicenseCompatibility::Unknown,
-                                    osi_status: match &license {
-                                        Some(l) => crate::licenses::get_osi_status(l),
-                                        None => crate::licenses::OsiStatus::Unknown,
-                                    },
-                                });
-                            }
-                        } else {
-                            log(
-                                LogLevel::Warn,
-                                "Failed to find dependencies in pyproject.toml",
-                            );
-                        }
-                    } else {
+                Ok(pyproject) => {
+                    let direct_deps = pyproject_dependencies(&pyproject, config.include_dev);
+                    if direct_deps.is_empty() {
                         log(
                             LogLevel::Warn,
-                            "No 'project' section found in pyproject.toml",
+                            "No PEP 621, Poetry or PDM dependencies found in pyproject.toml",
+                        );
+                    } else {
+                        log(
+                            LogLevel::Info,
+                            &format!("Found {} Python dependencies", direct_deps.len()),
+                        );
+                        log_debug("Dependencies", &direct_deps);
+
+                        // Try to resolve all dependencies (direct + transitive) using uv or fallback to PyPI
+                        let all_deps =
+                            resolve_unless_direct_only(direct_deps, package_file_path, config);
+
+                        // Process all resolved dependencies
+                        licenses = analyze_resolved_dependencies(
+                            all_deps,
+                            &known_licenses,
+                            config.strict,
+                            ECOSYSTEM,
+                            fetch_license_for_python_dependency,
                         );
                     }
                 }
@@ -285,45 +376,16 @@ pub fn analyze_python_licenses(package_file_path: &str, config: &FeludaConfig) -
                 );
 
                 // Try to resolve all dependencies (direct + transitive)
-                let max_depth = config.dependencies.max_depth;
-                log(
-                    LogLevel::Info,
-                    &format!("Using max dependency depth: {max_depth}"),
-                );
-                let all_deps =
-                    resolve_python_dependencies(&direct_deps, package_file_path, max_depth);
+                let all_deps = resolve_unless_direct_only(direct_deps, package_file_path, config);
 
                 // Process all resolved dependencies
-                for (name, version) in all_deps {
-                    log(
-                        LogLevel::Info,
-                        &format!("Processing dependency: {name} ({version})"),
-                    );
-
-                    let license_result = fetch_license_for_python_dependency(&name, &version);
-                    let license = Some(license_result);
-                    let is_restrictive =
-                        is_license_restrictive(&license, &known_licenses, config.strict);
-
-                    if is_restrictive {
-                        log(
-                            LogLevel::Warn,
-                            &format!("Restrictive license found: {license:?} for {name}"),
-                        );
-                    }
-
-                    licenses.push(LicenseInfo {
-                        name,
-                        version,
-                        license: license.clone(),
-                        is_restrictive,
-                        compatibility: LicenseCompatibility::Unknown,
-                        osi_status: match &license {
-                            Some(l) => crate::licenses::get_osi_status(l),
-                            None => crate::licenses::OsiStatus::Unknown,
-                        },
-                    });
-                }
+                licenses = analyze_resolved_dependencies(
+                    all_deps,
+                    &known_licenses,
+                    config.strict,
+                    ECOSYSTEM,
+                    fetch_license_for_python_dependency,
+                );
 
                 log(
                     LogLevel::Info,
@@ -346,17 +408,577 @@ pub fn analyze_python_licenses(package_file_path: &str, config: &FeludaConfig) -
     licenses
 }
 
-/// Fetch the license for a Python dependency, trying local sources first, then PyPI
-pub fn fetch_license_for_python_dependency(name: &str, version: &str) -> String {
-    if let Some(license) = get_license_from_local_site_packages(name) {
+/// Dependencies declared in a `pyproject.toml`, in PEP 621, Poetry or PDM style
+///
+/// PEP 621 `[project.dependencies]` and `[project.optional-dependencies]` (extras ship to
+/// users) and Poetry's main dependencies always count. Dev dependencies only count with
+/// `include_dev`: Poetry `dev-dependencies` and groups other than `main`, PDM
+/// `dev-dependencies` and PEP 735 `[dependency-groups]`. The first declaration of a name wins.
+fn pyproject_dependencies(pyproject: &TomlValue, include_dev: bool) -> Vec<(String, String)> {
+    /// PEP 508 strings of an array, or of every array in a table of named groups
+    fn requirements(value: Option<&TomlValue>) -> Vec<(String, String)> {
+        let arrays: Vec<&TomlValue> = match value {
+            Some(TomlValue::Array(_)) => value.into_iter().collect(),
+            Some(TomlValue::Table(groups)) => groups.values().collect(),
+            _ => Vec::new(),
+        };
+        arrays
+            .into_iter()
+            .filter_map(TomlValue::as_array)
+            .flatten()
+            // PEP 735 `{include-group = "..."}` entries repeat another group
+            .filter_map(TomlValue::as_str)
+            .filter_map(parse_pep508_requirement)
+            .collect()
+    }
+
+    /// Entries of a Poetry dependency table, e.g. `requests = "^2.31"` or `{version = "^2.31"}`
+    fn poetry_table(value: Option<&TomlValue>) -> Vec<(String, String)> {
+        let Some(table) = value.and_then(TomlValue::as_table) else {
+            return Vec::new();
+        };
+        table
+            .iter()
+            .filter(|(name, _)| !name.eq_ignore_ascii_case("python"))
+            .map(|(name, spec)| {
+                // Multiple-constraint dependencies are an array of tables; use the first
+                let spec = spec
+                    .as_array()
+                    .and_then(|specs| specs.first())
+                    .unwrap_or(spec);
+                let constraint = spec
+                    .as_str()
+                    .or_else(|| spec.get("version").and_then(TomlValue::as_str))
+                    .unwrap_or("*");
+                (name.clone(), clean_python_version(constraint))
+            })
+            .collect()
+    }
+
+    let project = pyproject.get("project");
+    let tool = pyproject.get("tool");
+    let poetry = tool.and_then(|tool| tool.get("poetry"));
+
+    let mut deps = requirements(project.and_then(|p| p.get("dependencies")));
+    deps.extend(requirements(
+        project.and_then(|p| p.get("optional-dependencies")),
+    ));
+    deps.extend(poetry_table(poetry.and_then(|p| p.get("dependencies"))));
+
+    let groups = poetry
+        .and_then(|p| p.get("group"))
+        .and_then(TomlValue::as_table);
+    for (group, table) in groups.into_iter().flatten() {
+        if group == "main" || include_dev {
+            deps.extend(poetry_table(table.get("dependencies")));
+        }
+    }
+
+    if include_dev {
+        deps.extend(poetry_table(poetry.and_then(|p| p.get("dev-dependencies"))));
+        deps.extend(requirements(
+            tool.and_then(|tool| tool.get("pdm"))
+                .and_then(|pdm| pdm.get("dev-dependencies")),
+        ));
+        deps.extend(requirements(pyproject.get("dependency-groups")));
+    }
+
+    let mut seen = HashSet::new();
+    deps.retain(|(name, _)| seen.insert(name.to_lowercase()));
+    deps
+}
+
+/// Name and version of a PEP 508 requirement such as `requests[socks]>=2,<3; python_version > "3.8"`
+fn parse_pep508_requirement(requirement: &str) -> Option<(String, String)> {
+    let base = requirement.split(';').next()?.trim();
+    let name_end = base
+        .find(|c: char| "[(<>=!~@ ".contains(c))
+        .unwrap_or(base.len());
+    let name = base[..name_end].trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let rest = base[name_end..].trim_start();
+    let constraint = match rest.strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map_or("", |(_, after)| after),
+        None => rest,
+    };
+    let constraint = constraint
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')');
+    Some((name.to_string(), clean_python_version(constraint)))
+}
+
+/// Version to look up for a PEP 440 or Poetry constraint: the exact or lowest allowed version,
+/// e.g. `^1.0` -> `1.0`, `>=2,<3` -> `2`, `~=1.4` -> `1.4`, or `latest` without a lower bound
+fn clean_python_version(constraint: &str) -> String {
+    // Of Poetry's `||` alternatives, the first
+    let constraint = constraint.split("||").next().unwrap_or_default();
+
+    constraint
+        .split(',')
+        .map(str::trim)
+        .find_map(|part| {
+            let version = ["===", "==", "~=", ">=", ">", "^", "~", "="]
+                .iter()
+                .find_map(|operator| part.strip_prefix(operator))
+                .or_else(|| {
+                    part.starts_with(|c: char| c.is_ascii_digit())
+                        .then_some(part)
+                })?;
+            let version = version.trim().trim_end_matches(".*");
+            (!version.is_empty() && version != "*").then(|| version.to_string())
+        })
+        .unwrap_or_else(|| "latest".to_string())
+}
+
+/// Add transitive dependencies to the declared ones, unless `--direct-only` was given
+fn resolve_unless_direct_only(
+    direct_deps: Vec<(String, String)>,
+    package_file_path: &str,
+    config: &FeludaConfig,
+) -> Vec<(String, String)> {
+    let project_dir = Path::new(package_file_path)
+        .parent()
+        .unwrap_or(Path::new("."));
+    let locked = read_python_lockfile(project_dir, !config.include_dev);
+
+    if config.direct_only {
         log(
             LogLevel::Info,
-            &format!("Found license in local site-packages for {name}: {license}"),
+            "Direct-only: skipping transitive Python dependency resolution",
         );
-        return license;
+        return match locked {
+            Some(locked) => pin_locked_versions(direct_deps, &locked),
+            None => direct_deps,
+        };
+    }
+
+    // Locked versions are exact, so PyPI lookups hit the version actually installed
+    if let Some(locked) = locked {
+        return locked;
+    }
+
+    let max_depth = config.dependencies.max_depth;
+    log(
+        LogLevel::Info,
+        &format!("Using max dependency depth: {max_depth}"),
+    );
+    resolve_python_dependencies(
+        &direct_deps,
+        package_file_path,
+        max_depth,
+        !config.include_dev,
+    )
+}
+
+/// Python lockfiles with exact versions, in order of preference
+const PYTHON_LOCKFILES: [&str; 2] = ["uv.lock", "poetry.lock"];
+
+/// Exact versions of the packages in the project's `uv.lock` or `poetry.lock`, if it has one
+///
+/// With `production_only`, packages only needed by dev groups are left out.
+fn read_python_lockfile(
+    project_dir: &Path,
+    production_only: bool,
+) -> Option<Vec<(String, String)>> {
+    PYTHON_LOCKFILES.iter().find_map(|file_name| {
+        let lock_file = project_dir.join(file_name);
+        if !lock_file.is_file() {
+            return None;
+        }
+
+        let result = if *file_name == "uv.lock" {
+            parse_uv_lock(&lock_file, u32::MAX, production_only)
+        } else {
+            parse_poetry_lock(&lock_file, production_only)
+        };
+        match result {
+            Ok(locked) if !locked.is_empty() => {
+                log(
+                    LogLevel::Info,
+                    &format!(
+                        "Using {} locked Python packages from {}",
+                        locked.len(),
+                        lock_file.display()
+                    ),
+                );
+                Some(locked)
+            }
+            Ok(_) => None,
+            Err(err) => {
+                log(LogLevel::Warn, &err);
+                None
+            }
+        }
+    })
+}
+
+/// Replace the version constraints of `direct_deps` with their locked versions
+fn pin_locked_versions(
+    direct_deps: Vec<(String, String)>,
+    locked: &[(String, String)],
+) -> Vec<(String, String)> {
+    let locked: HashMap<String, &str> = locked
+        .iter()
+        .map(|(name, version)| (normalize_package_name(name), version.as_str()))
+        .collect();
+    direct_deps
+        .into_iter()
+        .map(|(name, version)| {
+            let version = locked
+                .get(&normalize_package_name(&name))
+                .map_or(version, |locked| locked.to_string());
+            (name, version)
+        })
+        .collect()
+}
+
+/// PEP 503 normalized package name, so `Typing_Extensions` and `typing-extensions` match
+fn normalize_package_name(name: &str) -> String {
+    name.split(['-', '_', '.'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Parse poetry.lock into `(name, version)` pairs
+///
+/// Production packages are told apart by `groups` (Poetry 2) or `category` (Poetry 1.4 and
+/// earlier); lockfiles with neither are walked from the main dependencies in the
+/// `pyproject.toml` next to them.
+fn parse_poetry_lock(
+    lock_file: &Path,
+    production_only: bool,
+) -> Result<Vec<(String, String)>, String> {
+    let content =
+        fs::read_to_string(lock_file).map_err(|e| format!("Failed to read poetry.lock: {e}"))?;
+    let lock_data: TomlValue =
+        toml::from_str(&content).map_err(|e| format!("Failed to parse poetry.lock: {e}"))?;
+    let packages = lock_data
+        .get("package")
+        .and_then(TomlValue::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let production = if production_only {
+        poetry_production_packages(packages, lock_file)
+    } else {
+        None
+    };
+
+    let deps: Vec<(String, String)> = packages
+        .iter()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            if production
+                .as_ref()
+                .is_some_and(|p| !p.contains(&normalize_package_name(name)))
+            {
+                return None;
+            }
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect();
+
+    log(
+        LogLevel::Info,
+        &format!("Extracted {} packages from poetry.lock", deps.len()),
+    );
+    Ok(deps)
+}
+
+/// Normalized names of the poetry.lock packages needed outside dev groups
+///
+/// Returns `None` when they cannot be told apart, keeping every package.
+fn poetry_production_packages(packages: &[TomlValue], lock_file: &Path) -> Option<HashSet<String>> {
+    let name = |package: &TomlValue| {
+        package
+            .get("name")
+            .and_then(TomlValue::as_str)
+            .map(normalize_package_name)
+    };
+
+    let tagged: Vec<(String, bool)> = packages
+        .iter()
+        .filter_map(|package| {
+            let main = if let Some(groups) = package.get("groups").and_then(TomlValue::as_array) {
+                groups.iter().any(|group| group.as_str() == Some("main"))
+            } else {
+                package.get("category")?.as_str()? == "main"
+            };
+            Some((name(package)?, main))
+        })
+        .collect();
+    if !tagged.is_empty() {
+        return Some(
+            tagged
+                .into_iter()
+                .filter(|(_, main)| *main)
+                .map(|(name, _)| name)
+                .collect(),
+        );
+    }
+
+    let pyproject = fs::read_to_string(lock_file.with_file_name("pyproject.toml")).ok()?;
+    let pyproject: TomlValue = toml::from_str(&pyproject).ok()?;
+    let by_name: HashMap<String, &TomlValue> = packages
+        .iter()
+        .filter_map(|package| Some((name(package)?, package)))
+        .collect();
+
+    let mut reachable = HashSet::new();
+    let mut stack: Vec<String> = pyproject_dependencies(&pyproject, false)
+        .into_iter()
+        .map(|(name, _)| normalize_package_name(&name))
+        .collect();
+    while let Some(name) = stack.pop() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+        if let Some(dependencies) = by_name
+            .get(&name)
+            .and_then(|package| package.get("dependencies"))
+            .and_then(TomlValue::as_table)
+        {
+            stack.extend(dependencies.keys().map(|dep| normalize_package_name(dep)));
+        }
+    }
+    Some(reachable)
+}
+
+/// Whether a Python project file is a conda environment definition
+fn is_conda_environment_file(package_file_path: &str) -> bool {
+    package_file_path.ends_with("environment.yml")
+        || package_file_path.ends_with("environment.yaml")
+}
+
+/// A conda package from the `dependencies` list of `environment.yml`
+#[derive(Debug, Clone, PartialEq)]
+struct CondaPackage {
+    channel: String,
+    name: String,
+    version: String,
+}
+
+/// Dependencies declared in a conda `environment.yml`
+#[derive(Debug, Default, PartialEq)]
+struct CondaEnvironment {
+    /// Packages installed by conda
+    conda: Vec<CondaPackage>,
+    /// Requirements from the nested `pip:` list, as `(name, version)`
+    pip: Vec<(String, String)>,
+}
+
+/// Parse a conda `environment.yml`
+///
+/// Conda entries are match specs such as `numpy=1.26.0`, `scipy>=1.11` or
+/// `conda-forge::pandas 2.1.0 py311h_0`. Packages without a `channel::` prefix are looked
+/// up in the first listed channel, with `defaults` mapped to anaconda.org's `anaconda`.
+fn parse_environment_yml(content: &str) -> Result<CondaEnvironment, serde_yaml::Error> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(content)?;
+
+    let default_channel = yaml["channels"]
+        .as_sequence()
+        .and_then(|channels| channels.first())
+        .and_then(|channel| channel.as_str())
+        .map(conda_channel)
+        .unwrap_or(DEFAULT_CONDA_CHANNEL);
+
+    let mut environment = CondaEnvironment::default();
+    for dependency in yaml["dependencies"].as_sequence().into_iter().flatten() {
+        if let Some(spec) = dependency.as_str() {
+            match parse_conda_spec(spec, default_channel) {
+                Some(package) => environment.conda.push(package),
+                None => log(LogLevel::Warn, &format!("Invalid conda dependency: {spec}")),
+            }
+        } else if let Some(pip) = dependency["pip"].as_sequence() {
+            for requirement in pip.iter().filter_map(|r| r.as_str()) {
+                match parse_requirement_line(requirement) {
+                    Some(dep) => environment.pip.push(dep),
+                    None => log(
+                        LogLevel::Warn,
+                        &format!("Invalid pip requirement in environment.yml: {requirement}"),
+                    ),
+                }
+            }
+        }
+    }
+
+    Ok(environment)
+}
+
+/// anaconda.org name of a conda channel
+fn conda_channel(channel: &str) -> &str {
+    match channel {
+        "defaults" | "main" => DEFAULT_CONDA_CHANNEL,
+        other => other.trim_end_matches('/'),
+    }
+}
+
+/// Characters that end a package name in a conda match spec
+const CONDA_SPEC_OPERATORS: [char; 6] = ['=', '<', '>', '!', '~', ' '];
+
+/// Parse a conda match spec into its channel, name and pinned version
+fn parse_conda_spec(spec: &str, default_channel: &str) -> Option<CondaPackage> {
+    let spec = spec.trim();
+    let (channel, spec) = match spec.split_once("::") {
+        Some((channel, rest)) => (conda_channel(channel), rest),
+        None => (default_channel, spec),
+    };
+
+    let name_end = spec.find(CONDA_SPEC_OPERATORS).unwrap_or(spec.len());
+    let name = spec[..name_end].trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    // `=1.26.0=py311_0` and `1.26.0 py311_0` both carry a build string after the version
+    let version = spec[name_end..]
+        .trim_start_matches(CONDA_SPEC_OPERATORS)
+        .split(['=', ' ', ','])
+        .next()
+        .map(|version| version.trim_end_matches(".*"))
+        .filter(|version| !version.is_empty())
+        .unwrap_or("latest");
+
+    Some(CondaPackage {
+        channel: channel.to_string(),
+        name: name.to_string(),
+        version: version.to_string(),
+    })
+}
+
+/// Analyze a conda environment: conda packages via anaconda.org, pip entries via PyPI
+fn analyze_conda_environment(
+    package_file_path: &str,
+    known_licenses: &HashMap<String, License>,
+    config: &FeludaConfig,
+) -> Vec<LicenseInfo> {
+    let environment = match fs::read_to_string(package_file_path) {
+        Ok(content) => match parse_environment_yml(&content) {
+            Ok(environment) => environment,
+            Err(err) => {
+                log_error("Failed to parse environment.yml", &err);
+                return Vec::new();
+            }
+        },
+        Err(err) => {
+            log_error("Failed to read environment.yml file", &err);
+            return Vec::new();
+        }
+    };
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Found {} conda and {} pip dependencies in environment.yml",
+            environment.conda.len(),
+            environment.pip.len()
+        ),
+    );
+    log_debug("Conda environment", &environment);
+
+    let channels: HashMap<String, String> = environment
+        .conda
+        .iter()
+        .map(|package| (package.name.clone(), package.channel.clone()))
+        .collect();
+    let conda_deps = environment
+        .conda
+        .into_iter()
+        .map(|package| (package.name, package.version))
+        .collect();
+    let mut licenses = analyze_resolved_dependencies(
+        conda_deps,
+        known_licenses,
+        config.strict,
+        CONDA_ECOSYSTEM,
+        |name, version| fetch_license_for_conda_package(&channels[name], name, version),
+    );
+
+    if !environment.pip.is_empty() {
+        let pip_deps = resolve_with_pypi(&environment.pip, config.dependencies.max_depth);
+        licenses.extend(analyze_resolved_dependencies(
+            pip_deps,
+            known_licenses,
+            config.strict,
+            ECOSYSTEM,
+            fetch_license_for_python_dependency,
+        ));
+    }
+
+    licenses
+}
+
+fn fetch_license_for_conda_package(channel: &str, name: &str, version: &str) -> String {
+    cache::cached_package_license(CONDA_ECOSYSTEM, name, version, || {
+        fetch_license_from_anaconda(channel, name, version)
+    })
+}
+
+fn fetch_license_from_anaconda(channel: &str, name: &str, version: &str) -> String {
+    if skip_network(&format!("anaconda.org license of {name}")) {
+        return format!("Unknown license for {name}: {version}");
+    }
+
+    let api_url = format!("https://api.anaconda.org/package/{channel}/{name}");
+    log(
+        LogLevel::Info,
+        &format!("Fetching license from anaconda.org: {api_url}"),
+    );
+
+    match with_network_permit(|| http_client()?.get(&api_url).send()) {
+        Ok(response) if response.status().is_success() => match response.json::<Value>() {
+            Ok(json) => match json["license"].as_str().map(str::trim) {
+                Some(license) if !license.is_empty() => license.to_string(),
+                _ => {
+                    log(
+                        LogLevel::Warn,
+                        &format!("No license found for {name} on {channel}"),
+                    );
+                    format!("Unknown license for {name}: {version}")
+                }
+            },
+            Err(err) => {
+                log_error(&format!("Failed to parse JSON for {name}: {version}"), &err);
+                String::from("Unknown")
+            }
+        },
+        Ok(response) => {
+            log(
+                LogLevel::Error,
+                &format!(
+                    "Failed to fetch conda metadata for {name}: HTTP {}",
+                    response.status()
+                ),
+            );
+            String::from("Unknown")
+        }
+        Err(err) => {
+            log_error(&format!("Failed to fetch conda metadata for {name}"), &err);
+            String::from("Unknown")
+        }
     }
+}
 
-    fetch_license_from_pypi(name, version)
+/// Fetch the license for a Python dependency, trying local sources first, then PyPI
+pub fn fetch_license_for_python_dependency(name: &str, version: &str) -> String {
+    cache::cached_package_license("python", name, version, || {
+        if let Some(license) = get_license_from_local_site_packages(name) {
+            log(
+                LogLevel::Info,
+                &format!("Found license in local site-packages for {name}: {license}"),
+            );
+            return license;
+        }
+
+        fetch_license_from_pypi(name, version)
+    })
 }
 
 fn get_license_from_local_site_packages(package_name: &str) -> Option<String> {
@@ -513,13 +1135,17 @@ fn detect_license_from_content(content: &str) -> Option<String> {
 }
 
 fn fetch_license_from_pypi(name: &str, version: &str) -> String {
-    let api_url = format!("https://pypi.org/pypi/{name}/{version}/json");
+    if skip_network(&format!("PyPI license of {name}")) {
+        return format!("Unknown license for {name}: {version}");
+    }
+
+    let api_url = pypi_json_url(name, version);
     log(
         LogLevel::Info,
         &format!("Fetching license from PyPI: {api_url}"),
     );
 
-    match reqwest::blocking::get(&api_url) {
+    match with_network_permit(|| registry_get(&http_client()?, &api_url).send()) {
         Ok(response) => {
             let status = response.status();
             log(
@@ -529,8 +1155,8 @@ fn fetch_license_from_pypi(name: &str, version: &str) -> String {
 
             if status.is_success() {
                 match response.json::<Value>() {
-                    Ok(json) => match json["info"]["license"].as_str() {
-                        Some(license_str) if !license_str.is_empty() => {
+                    Ok(json) => match license_from_pypi_info(&json["info"]) {
+                        Some(license_str) => {
                             log(
                                 LogLevel::Info,
                                 &format!("License found for {name}: {license_str}"),
@@ -565,6 +1191,55 @@ fn fetch_license_from_pypi(name: &str, version: &str) -> String {
     }
 }
 
+/// License declared in the `info` object of a PyPI JSON response
+///
+/// Prefers the PEP 639 `license_expression`, then `license`, then the `License ::` trove
+/// classifiers, which many packages declare instead; several classifiers mean a choice.
+fn license_from_pypi_info(info: &Value) -> Option<String> {
+    let declared = |field: &str| {
+        info[field]
+            .as_str()
+            .map(str::trim)
+            .filter(|license| !license.is_empty() && !license.eq_ignore_ascii_case("UNKNOWN"))
+            .map(String::from)
+    };
+    if let Some(license) = declared("license_expression").or_else(|| declared("license")) {
+        return Some(license);
+    }
+
+    let mut licenses: Vec<String> = Vec::new();
+    for license in info["classifiers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(license_from_classifier)
+    {
+        if !licenses.contains(&license) {
+            licenses.push(license);
+        }
+    }
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+}
+
+/// SPDX identifier for a `License ::` trove classifier, or the classifier's last segment
+/// (e.g. `BSD License`) when it is not in [`PYPI_LICENSE_CLASSIFIERS`]
+fn license_from_classifier(classifier: &str) -> Option<String> {
+    let classifier = classifier.trim();
+    let rest = classifier.strip_prefix("License ::")?;
+
+    if let Some((_, spdx)) = PYPI_LICENSE_CLASSIFIERS
+        .iter()
+        .find(|(known, _)| *known == classifier)
+    {
+        return Some(spdx.to_string());
+    }
+
+    let name = rest.rsplit("::").next()?.trim();
+    // A bare `License :: OSI Approved` names no license
+    (!name.is_empty() && name != "OSI Approved").then(|| name.to_string())
+}
+
 /// Parse a requirement line from requirements.txt supporting various formats
 /// Handles requirements.txt format with optional environment markers
 /// Examples:
@@ -618,6 +1293,7 @@ fn resolve_python_dependencies(
     direct_deps: &[(String, String)],
     package_file_path: &str,
     max_depth: u32,
+    production_only: bool,
 ) -> Vec<(String, String)> {
     log(
         LogLevel::Info,
@@ -625,7 +1301,7 @@ fn resolve_python_dependencies(
     );
 
     // First, try using uv for complete dependency resolution
-    if let Ok(uv_deps) = resolve_with_uv(package_file_path, max_depth) {
+    if let Ok(uv_deps) = resolve_with_uv(package_file_path, max_depth, production_only) {
         if !uv_deps.is_empty() {
             log(
                 LogLevel::Info,
@@ -651,6 +1327,7 @@ fn resolve_python_dependencies(
 fn resolve_with_uv(
     package_file_path: &str,
     max_depth: u32,
+    production_only: bool,
 ) -> Result<Vec<(String, String)>, String> {
     let project_dir = Path::new(package_file_path)
         .parent()
@@ -671,7 +1348,7 @@ fn resolve_with_uv(
             // Parse uv.lock file if it exists
             let lock_file = project_dir.join("uv.lock");
             if lock_file.exists() {
-                if let Ok(deps) = parse_uv_lock(&lock_file, max_depth) {
+                if let Ok(deps) = parse_uv_lock(&lock_file, max_depth, production_only) {
                     log(
                         LogLevel::Info,
                         &format!("Resolved {} dependencies from uv.lock", deps.len()),
@@ -706,7 +1383,11 @@ fn resolve_with_uv(
 }
 
 /// Parse uv.lock file to extract dependencies with depth awareness
-fn parse_uv_lock(lock_file: &Path, max_depth: u32) -> Result<Vec<(String, String)>, String> {
+fn parse_uv_lock(
+    lock_file: &Path,
+    max_depth: u32,
+    production_only: bool,
+) -> Result<Vec<(String, String)>, String> {
     let content =
         fs::read_to_string(lock_file).map_err(|e| format!("Failed to read uv.lock: {e}"))?;
 
@@ -723,12 +1404,28 @@ fn parse_uv_lock(lock_file: &Path, max_depth: u32) -> Result<Vec<(String, String
 
     // Extract packages from uv.lock format
     if let Some(packages) = lock_data.get("package").and_then(|p| p.as_array()) {
+        let production = if production_only {
+            uv_production_packages(packages)
+        } else {
+            None
+        };
+
         for package in packages {
             if let Some(package_table) = package.as_table() {
                 if let (Some(name), Some(version)) = (
                     package_table.get("name").and_then(|n| n.as_str()),
                     package_table.get("version").and_then(|v| v.as_str()),
                 ) {
+                    if production.as_ref().is_some_and(|p| !p.contains(name)) {
+                        continue;
+                    }
+                    // The project itself and workspace members are not dependencies
+                    let source = package_table.get("source").and_then(|s| s.as_table());
+                    if source
+                        .is_some_and(|s| s.contains_key("editable") || s.contains_key("virtual"))
+                    {
+                        continue;
+                    }
                     deps.push((name.to_string(), version.to_string()));
                 }
             }
@@ -746,6 +1443,63 @@ fn parse_uv_lock(lock_file: &Path, max_depth: u32) -> Result<Vec<(String, String
     Ok(deps)
 }
 
+/// Names of uv.lock packages reachable from the project without dev groups or extras
+///
+/// Returns `None` when the project package cannot be identified.
+fn uv_production_packages(packages: &[TomlValue]) -> Option<HashSet<String>> {
+    let dependency_names = |package: &TomlValue| -> Vec<String> {
+        package
+            .get("dependencies")
+            .and_then(|d| d.as_array())
+            .map(|deps| {
+                deps.iter()
+                    .filter_map(|dep| dep.get("name").and_then(|n| n.as_str()))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let by_name: HashMap<&str, &TomlValue> = packages
+        .iter()
+        .filter_map(|p| Some((p.get("name")?.as_str()?, p)))
+        .collect();
+
+    let roots: Vec<&TomlValue> = packages
+        .iter()
+        .filter(|p| {
+            p.get("source")
+                .and_then(|s| s.as_table())
+                .is_some_and(|s| s.contains_key("editable") || s.contains_key("virtual"))
+        })
+        .collect();
+
+    if roots.is_empty() {
+        log(
+            LogLevel::Warn,
+            "Could not find the project package in uv.lock, keeping all packages",
+        );
+        return None;
+    }
+
+    let mut reachable = HashSet::new();
+    let mut stack: Vec<String> = roots
+        .iter()
+        .flat_map(|root| dependency_names(root))
+        .collect();
+
+    while let Some(name) = stack.pop() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+        if let Some(package) = by_name.get(name.as_str()) {
+            stack.extend(dependency_names(package));
+        }
+    }
+
+    Some(reachable)
+}
+
 /// Parse pip-compile style output to extract dependencies
 fn parse_pip_compile_output(output: &str) -> Vec<(String, String)> {
     let mut deps = Vec::new();
@@ -856,9 +1610,13 @@ fn resolve_with_pypi(direct_deps: &[(String, String)], max_depth: u32) -> Vec<(S
 
 /// Fetch dependencies from PyPI for a specific package
 fn fetch_pypi_dependencies(name: &str, version: &str) -> Result<Vec<(String, String)>, String> {
-    let api_url = format!("https://pypi.org/pypi/{name}/{version}/json");
+    if skip_network(&format!("PyPI dependencies of {name}")) {
+        return Ok(Vec::new());
+    }
 
-    match reqwest::blocking::get(&api_url) {
+    let api_url = pypi_json_url(name, version);
+
+    match with_network_permit(|| registry_get(&http_client()?, &api_url).send()) {
         Ok(response) => {
             if response.status().is_success() {
                 if let Ok(json) = response.json::<Value>() {
@@ -1010,6 +1768,284 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_parse_uv_lock_production_only_skips_dev_groups() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_file = temp_dir.path().join("uv.lock");
+        fs::write(
+            &lock_file,
+            r#"version = 1
+
+[[package]]
+name = "myapp"
+version = "0.1.0"
+source = { editable = "." }
+dependencies = [{ name = "requests" }]
+
+[package.dev-dependencies]
+dev = [{ name = "pytest" }]
+
+[package.optional-dependencies]
+docs = [{ name = "sphinx" }]
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "urllib3" }]
+
+[[package]]
+name = "urllib3"
+version = "2.1.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "pytest"
+version = "7.4.3"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "sphinx"
+version = "7.2.6"
+source = { registry = "https://pypi.org/simple" }
+"#,
+        )
+        .unwrap();
+
+        let all = parse_uv_lock(&lock_file, 10, false).unwrap();
+        // Every package but the project itself
+        assert_eq!(all.len(), 4);
+        assert!(!all.iter().any(|(name, _)| name == "myapp"));
+
+        let production = parse_uv_lock(&lock_file, 10, true).unwrap();
+        let names: Vec<&str> = production.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(production.len(), 2);
+        assert!(names.contains(&"requests"));
+        assert!(names.contains(&"urllib3"));
+        assert!(!names.contains(&"pytest"));
+        assert!(!names.contains(&"sphinx"));
+    }
+
+    const SAMPLE_POETRY_LOCK: &str = r#"# This file is automatically @generated by Poetry 2.1.1 and should not be changed by hand.
+
+[[package]]
+name = "certifi"
+version = "2024.2.2"
+description = "Python package for providing Mozilla's CA Bundle."
+optional = false
+python-versions = ">=3.6"
+groups = ["main"]
+files = []
+
+[[package]]
+name = "iniconfig"
+version = "2.0.0"
+description = "brain-dead simple config-ini parsing"
+optional = false
+python-versions = ">=3.7"
+groups = ["dev"]
+files = []
+
+[[package]]
+name = "pytest"
+version = "8.1.1"
+description = "pytest: simple powerful testing with Python"
+optional = false
+python-versions = ">=3.8"
+groups = ["dev"]
+files = []
+
+[package.dependencies]
+iniconfig = "*"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+description = "Python HTTP for Humans."
+optional = false
+python-versions = ">=3.7"
+groups = ["main"]
+files = []
+
+[package.dependencies]
+certifi = ">=2017.4.17"
+urllib3 = ">=1.21.1,<3"
+
+[[package]]
+name = "urllib3"
+version = "2.2.1"
+description = "HTTP library with thread-safe connection pooling"
+optional = false
+python-versions = ">=3.8"
+groups = ["main"]
+files = []
+
+[metadata]
+lock-version = "2.1"
+python-versions = "^3.11"
+"#;
+
+    #[test]
+    fn test_parse_poetry_lock_exact_versions_and_transitive_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_file = temp_dir.path().join("poetry.lock");
+        fs::write(&lock_file, SAMPLE_POETRY_LOCK).unwrap();
+
+        let production = parse_poetry_lock(&lock_file, true).unwrap();
+        assert_eq!(
+            production,
+            [
+                ("certifi".to_string(), "2024.2.2".to_string()),
+                ("requests".to_string(), "2.31.0".to_string()),
+                ("urllib3".to_string(), "2.2.1".to_string()),
+            ]
+        );
+        assert_eq!(parse_poetry_lock(&lock_file, false).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_parse_poetry_lock_without_groups_walks_pyproject() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_file = temp_dir.path().join("poetry.lock");
+        // Poetry 1.5 to 1.8 record neither `groups` nor `category`
+        fs::write(
+            &lock_file,
+            SAMPLE_POETRY_LOCK
+                .replace("groups = [\"main\"]\n", "")
+                .replace("groups = [\"dev\"]\n", ""),
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[tool.poetry.dependencies]\npython = \"^3.11\"\nRequests = \"^2.31\"\n\n[tool.poetry.group.dev.dependencies]\npytest = \"^8.1\"\n",
+        )
+        .unwrap();
+
+        let names: Vec<String> = parse_poetry_lock(&lock_file, true)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["certifi", "requests", "urllib3"]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_poetry_lock_preferred_over_manifest_constraints() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject_path = temp_dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject_path,
+            "[tool.poetry.dependencies]\npython = \"^3.11\"\nrequests = \"^2.0\"\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("poetry.lock"), SAMPLE_POETRY_LOCK).unwrap();
+
+        crate::network::set_offline_mode(true);
+        let resolved =
+            analyze_python_licenses(pyproject_path.to_str().unwrap(), &FeludaConfig::default());
+        let direct_only = analyze_python_licenses(
+            pyproject_path.to_str().unwrap(),
+            &FeludaConfig {
+                direct_only: true,
+                ..Default::default()
+            },
+        );
+        crate::network::set_offline_mode(false);
+
+        let deps = |result: &[LicenseInfo]| {
+            result
+                .iter()
+                .map(|info| format!("{}@{}", info.name, info.version))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            deps(&resolved),
+            ["certifi@2024.2.2", "requests@2.31.0", "urllib3@2.2.1"]
+        );
+        assert_eq!(deps(&direct_only), ["requests@2.31.0"]);
+    }
+
+    #[test]
+    fn test_license_from_pypi_classifiers() {
+        let info = serde_json::json!({
+            "name": "attrs",
+            "license": "",
+            "classifiers": [
+                "Development Status :: 5 - Production/Stable",
+                "License :: OSI Approved :: MIT License",
+                "Programming Language :: Python :: 3",
+            ],
+        });
+        assert_eq!(license_from_pypi_info(&info).as_deref(), Some("MIT"));
+
+        let info = serde_json::json!({
+            "license": "UNKNOWN",
+            "classifiers": [
+                "License :: OSI Approved",
+                "License :: OSI Approved :: Apache Software License",
+                "License :: OSI Approved :: GNU General Public License v3 or later (GPLv3+)",
+            ],
+        });
+        assert_eq!(
+            license_from_pypi_info(&info).as_deref(),
+            Some("Apache-2.0 OR GPL-3.0-or-later")
+        );
+
+        // Classifiers that name no single SPDX license keep their own name
+        let info = serde_json::json!({
+            "license": null,
+            "classifiers": ["License :: OSI Approved :: BSD License"],
+        });
+        assert_eq!(
+            license_from_pypi_info(&info).as_deref(),
+            Some("BSD License")
+        );
+
+        let info = serde_json::json!({"license": "", "classifiers": ["Topic :: Utilities"]});
+        assert_eq!(license_from_pypi_info(&info), None);
+    }
+
+    #[test]
+    fn test_license_from_pypi_info_prefers_declared_license() {
+        let info = serde_json::json!({
+            "license_expression": "MIT OR Apache-2.0",
+            "license": "MIT",
+            "classifiers": ["License :: OSI Approved :: MIT License"],
+        });
+        assert_eq!(
+            license_from_pypi_info(&info).as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+
+        let info = serde_json::json!({
+            "license": "BSD-3-Clause",
+            "classifiers": ["License :: OSI Approved :: BSD License"],
+        });
+        assert_eq!(
+            license_from_pypi_info(&info).as_deref(),
+            Some("BSD-3-Clause")
+        );
+    }
+
+    #[test]
+    fn test_pypi_license_classifiers_are_exact() {
+        for (classifier, spdx) in PYPI_LICENSE_CLASSIFIERS {
+            assert!(classifier.starts_with("License :: "), "{classifier}");
+            assert_eq!(license_from_classifier(classifier).as_deref(), Some(*spdx));
+        }
+    }
+
+    #[test]
+    fn test_normalize_package_name() {
+        assert_eq!(
+            normalize_package_name("Typing_Extensions"),
+            "typing-extensions"
+        );
+        assert_eq!(normalize_package_name("zope.interface"), "zope-interface");
+        assert_eq!(normalize_package_name("a--b"), "a-b");
+    }
+
     #[test]
     fn test_analyze_python_licenses_pyproject_toml() {
         let temp_dir = TempDir::new().unwrap();
@@ -1035,6 +2071,294 @@ mod tests {
         assert!(result.iter().any(|info| info.name == "flask"));
     }
 
+    fn pyproject(content: &str) -> TomlValue {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_pyproject_dependencies_pep621() {
+        let pyproject = pyproject(
+            r#"
+[project]
+name = "service"
+dependencies = [
+    "requests[socks]>=2.31,<3",
+    "pydantic~=2.5",
+    "typing-extensions; python_version < '3.11'",
+    "attrs (==23.1.0)",
+    "mylib @ https://example.com/mylib-1.0.tar.gz",
+]
+
+[project.optional-dependencies]
+postgres = ["psycopg>=3.1"]
+
+[dependency-groups]
+test = ["pytest>=8", {include-group = "lint"}]
+lint = ["ruff==0.4.1"]
+"#,
+        );
+
+        assert_eq!(
+            pyproject_dependencies(&pyproject, false),
+            [
+                ("requests".to_string(), "2.31".to_string()),
+                ("pydantic".to_string(), "2.5".to_string()),
+                ("typing-extensions".to_string(), "latest".to_string()),
+                ("attrs".to_string(), "23.1.0".to_string()),
+                ("mylib".to_string(), "latest".to_string()),
+                ("psycopg".to_string(), "3.1".to_string()),
+            ]
+        );
+
+        let with_dev = pyproject_dependencies(&pyproject, true);
+        let names: Vec<&str> = with_dev.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(names.contains(&"ruff") && names.contains(&"pytest"));
+    }
+
+    #[test]
+    fn test_pyproject_dependencies_poetry_groups() {
+        let pyproject = pyproject(
+            r#"
+[tool.poetry]
+name = "service"
+
+[tool.poetry.dependencies]
+python = "^3.10"
+fastapi = "^0.110.0"
+httpx = { version = ">=0.25,<1.0", extras = ["http2"] }
+internal = { git = "https://example.com/internal.git" }
+numpy = [
+    { version = "~1.24", python = "<3.12" },
+    { version = "^1.26", python = ">=3.12" },
+]
+
+[tool.poetry.group.main.dependencies]
+uvicorn = "0.29.0"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0"
+black = "*"
+
+[tool.poetry.dev-dependencies]
+mypy = "^1.9"
+"#,
+        );
+
+        let production = pyproject_dependencies(&pyproject, false);
+        let mut production_sorted = production.clone();
+        production_sorted.sort();
+        assert_eq!(
+            production_sorted,
+            [
+                ("fastapi".to_string(), "0.110.0".to_string()),
+                ("httpx".to_string(), "0.25".to_string()),
+                ("internal".to_string(), "latest".to_string()),
+                ("numpy".to_string(), "1.24".to_string()),
+                ("uvicorn".to_string(), "0.29.0".to_string()),
+            ]
+        );
+
+        let mut dev: Vec<(String, String)> = pyproject_dependencies(&pyproject, true)
+            .into_iter()
+            .filter(|dep| !production.contains(dep))
+            .collect();
+        dev.sort();
+        assert_eq!(
+            dev,
+            [
+                ("black".to_string(), "latest".to_string()),
+                ("mypy".to_string(), "1.9".to_string()),
+                ("pytest".to_string(), "8.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pyproject_dependencies_pdm_dev() {
+        let pyproject = pyproject(
+            r#"
+[project]
+dependencies = ["rich>=13"]
+
+[tool.pdm.dev-dependencies]
+test = ["pytest-cov>=5"]
+"#,
+        );
+
+        assert_eq!(pyproject_dependencies(&pyproject, false).len(), 1);
+        assert_eq!(
+            pyproject_dependencies(&pyproject, true)[1],
+            ("pytest-cov".to_string(), "5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_clean_python_version() {
+        assert_eq!(clean_python_version("^1.0"), "1.0");
+        assert_eq!(clean_python_version(">=2,<3"), "2");
+        assert_eq!(clean_python_version("~=1.4"), "1.4");
+        assert_eq!(clean_python_version("~1.4.2"), "1.4.2");
+        assert_eq!(clean_python_version("== 2.31.0"), "2.31.0");
+        assert_eq!(clean_python_version("1.2.3"), "1.2.3");
+        assert_eq!(clean_python_version("==1.4.*"), "1.4");
+        assert_eq!(clean_python_version("<3,>=1.21.1"), "1.21.1");
+        assert_eq!(clean_python_version("^1.2 || ^2.0"), "1.2");
+        assert_eq!(clean_python_version("<3"), "latest");
+        assert_eq!(clean_python_version("*"), "latest");
+        assert_eq!(clean_python_version(""), "latest");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analyze_poetry_pyproject_skips_dev_group() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject_path = temp_dir.path().join("pyproject.toml");
+        std::fs::write(
+            &pyproject_path,
+            r#"[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0"
+"#,
+        )
+        .unwrap();
+
+        let config = FeludaConfig {
+            direct_only: true,
+            ..Default::default()
+        };
+        crate::network::set_offline_mode(true);
+        let result = analyze_python_licenses(pyproject_path.to_str().unwrap(), &config);
+        crate::network::set_offline_mode(false);
+
+        let deps: Vec<(&str, &str)> = result
+            .iter()
+            .map(|info| (info.name.as_str(), info.version.as_str()))
+            .collect();
+        assert_eq!(deps, vec![("requests", "2.31.0")]);
+    }
+
+    const SAMPLE_ENVIRONMENT_YML: &str = r#"name: analysis
+channels:
+  - conda-forge
+  - defaults
+dependencies:
+  - python=3.11
+  - numpy=1.26.0=py311h64a7726_0
+  - scipy>=1.11,<1.12
+  - defaults::pandas 2.1.0 py311ha02d727_0
+  - matplotlib
+  - pip
+  - pip:
+      - requests==2.31.0
+      - rich>=13.0
+"#;
+
+    #[test]
+    fn test_parse_environment_yml_mixed_conda_and_pip() {
+        let environment = parse_environment_yml(SAMPLE_ENVIRONMENT_YML).unwrap();
+
+        let conda: Vec<(&str, &str, &str)> = environment
+            .conda
+            .iter()
+            .map(|p| (p.channel.as_str(), p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            conda,
+            vec![
+                ("conda-forge", "python", "3.11"),
+                ("conda-forge", "numpy", "1.26.0"),
+                ("conda-forge", "scipy", "1.11"),
+                ("anaconda", "pandas", "2.1.0"),
+                ("conda-forge", "matplotlib", "latest"),
+                ("conda-forge", "pip", "latest"),
+            ]
+        );
+        assert_eq!(
+            environment.pip,
+            vec![
+                ("requests".to_string(), "2.31.0".to_string()),
+                ("rich".to_string(), "13.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_conda_spec() {
+        let package = parse_conda_spec("numpy==1.26.*", "anaconda").unwrap();
+        assert_eq!(package.name, "numpy");
+        assert_eq!(package.version, "1.26");
+        assert_eq!(package.channel, "anaconda");
+
+        let package = parse_conda_spec("bioconda::samtools=1.18", "anaconda").unwrap();
+        assert_eq!(package.channel, "bioconda");
+        assert_eq!(package.name, "samtools");
+        assert_eq!(package.version, "1.18");
+
+        assert!(parse_conda_spec("=1.0", "anaconda").is_none());
+        assert!(parse_environment_yml("dependencies: [").is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analyze_python_licenses_environment_yml() {
+        let temp_dir = TempDir::new().unwrap();
+        let environment_path = temp_dir.path().join("environment.yml");
+        std::fs::write(
+            &environment_path,
+            "channels:\n  - conda-forge\ndependencies:\n  - numpy=1.26.0\n  - pip:\n      - requests==2.31.0\n",
+        )
+        .unwrap();
+
+        let config = FeludaConfig {
+            dependencies: crate::config::DependencyConfig {
+                max_depth: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        crate::network::set_offline_mode(true);
+        let result = analyze_python_licenses(environment_path.to_str().unwrap(), &config);
+        crate::network::set_offline_mode(false);
+
+        let numpy = result.iter().find(|info| info.name == "numpy").unwrap();
+        assert_eq!(numpy.version, "1.26.0");
+        assert_eq!(numpy.ecosystem, "conda");
+        let requests = result.iter().find(|info| info.name == "requests").unwrap();
+        assert_eq!(requests.version, "2.31.0");
+        assert_eq!(requests.ecosystem, "pypi");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_direct_only_returns_declared_requirements() {
+        let temp_dir = TempDir::new().unwrap();
+        let requirements_path = temp_dir.path().join("requirements.txt");
+        std::fs::write(
+            &requirements_path,
+            "# web stack\nrequests==2.31.0\nflask>=3.0.0\n",
+        )
+        .unwrap();
+
+        let config = FeludaConfig {
+            direct_only: true,
+            ..Default::default()
+        };
+
+        crate::network::set_offline_mode(true);
+        let result = analyze_python_licenses(requirements_path.to_str().unwrap(), &config);
+        crate::network::set_offline_mode(false);
+
+        let deps: Vec<(&str, &str)> = result
+            .iter()
+            .map(|info| (info.name.as_str(), info.version.as_str()))
+            .collect();
+        assert_eq!(deps, vec![("requests", "2.31.0"), ("flask", "3.0.0")]);
+    }
+
     #[test]
     fn test_analyze_python_licenses_empty_file() {
         let temp_dir = TempDir::new().unwrap();
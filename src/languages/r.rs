@@ -1,12 +1,108 @@
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
+use crate::cache;
 use crate::config::FeludaConfig;
-use crate::debug::{log, log_debug, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, License, LicenseCompatibility, LicenseInfo,
-};
+use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::languages::{project_file_str, LanguageParser};
+use crate::licenses::{fetch_licenses_from_github, is_license_restrictive, License, LicenseInfo};
+use crate::network::{http_client, skip_network, with_network_permit};
+
+/// Ecosystem name for R packages
+const ECOSYSTEM: &str = "r";
+
+/// CRAN license identifiers and their SPDX equivalents, compared with all
+/// whitespace removed so `GPL (>= 2)` and `GPL(>=2)` match the same entry
+const CRAN_LICENSE_MAP: &[(&str, &str)] = &[
+    ("GPL", "GPL-2.0-or-later"),
+    ("GPL(>=2)", "GPL-2.0-or-later"),
+    ("GPL(>=2.0)", "GPL-2.0-or-later"),
+    ("GPL(>=3)", "GPL-3.0-or-later"),
+    ("GPL(>=3.0)", "GPL-3.0-or-later"),
+    ("GPL-2", "GPL-2.0-only"),
+    ("GPL-3", "GPL-3.0-only"),
+    ("LGPL", "LGPL-2.0-or-later"),
+    ("LGPL(>=2)", "LGPL-2.0-or-later"),
+    ("LGPL(>=2.1)", "LGPL-2.1-or-later"),
+    ("LGPL(>=3)", "LGPL-3.0-or-later"),
+    ("LGPL-2", "LGPL-2.0-only"),
+    ("LGPL-2.1", "LGPL-2.1-only"),
+    ("LGPL-3", "LGPL-3.0-only"),
+    ("AGPL-3", "AGPL-3.0-only"),
+    ("AGPL(>=3)", "AGPL-3.0-or-later"),
+    ("MIT", "MIT"),
+    ("BSD_2_clause", "BSD-2-Clause"),
+    ("BSD_3_clause", "BSD-3-Clause"),
+    ("ApacheLicense", "Apache-2.0"),
+    ("ApacheLicense2.0", "Apache-2.0"),
+    ("ApacheLicense(==2.0)", "Apache-2.0"),
+    ("ApacheLicense(>=2)", "Apache-2.0"),
+    ("ApacheLicense(>=2.0)", "Apache-2.0"),
+    ("Artistic-2.0", "Artistic-2.0"),
+    ("MPL-2.0", "MPL-2.0"),
+    ("CC0", "CC0-1.0"),
+    ("CCBY4.0", "CC-BY-4.0"),
+    ("CCBY-SA4.0", "CC-BY-SA-4.0"),
+];
+
+/// Convert a CRAN `License` field to an SPDX expression.
+///
+/// Alternatives separated by `|` become `OR` terms and the `+ file LICENSE`
+/// suffix is dropped; unrecognized terms are kept verbatim.
+pub fn normalize_cran_license(license: &str) -> String {
+    let mut terms: Vec<String> = Vec::new();
+
+    for term in license.split('|') {
+        let term = term.trim();
+        let term = term
+            .split_once('+')
+            .filter(|(_, rest)| rest.trim_start().starts_with("file"))
+            .map_or(term, |(base, _)| base.trim());
+        if term.is_empty() {
+            continue;
+        }
+
+        let key: String = term.chars().filter(|c| !c.is_whitespace()).collect();
+        let spdx = CRAN_LICENSE_MAP
+            .iter()
+            .find(|(cran, _)| cran.eq_ignore_ascii_case(&key))
+            .map(|(_, spdx)| spdx.to_string())
+            .unwrap_or_else(|| term.to_string());
+
+        if !terms.contains(&spdx) {
+            terms.push(spdx);
+        }
+    }
+
+    if terms.is_empty() {
+        return license.trim().to_string();
+    }
+    terms.join(" OR ")
+}
+
+/// Parses R projects through the [`LanguageParser`] interface
+pub struct RParser;
+
+impl LanguageParser for RParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        _no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        Ok(analyze_r_licenses(project_file_str(project_file)?, config))
+    }
+
+    fn language_name(&self) -> &'static str {
+        "R"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &super::R_PATHS
+    }
+}
 
 pub fn analyze_r_licenses(package_file_path: &str, config: &FeludaConfig) -> Vec<LicenseInfo> {
     let mut licenses = Vec::new();
@@ -69,8 +165,10 @@ fn parse_renv_lock(
                         &format!("Found {} packages in renv.lock", packages.len()),
                     );
                     log_debug("Packages", packages);
+                    crate::cli::add_dependencies_to_resolve(packages.len());
 
-                    for (name, pkg_info) in packages {
+                    for (key, pkg_info) in packages {
+                        let name = pkg_info["Package"].as_str().unwrap_or(key);
                         let version = pkg_info["Version"]
                             .as_str()
                             .unwrap_or("unknown")
@@ -93,16 +191,16 @@ fn parse_renv_lock(
                             );
                         }
 
+                        crate::cli::mark_dependency_resolved();
+
                         licenses.push(LicenseInfo {
-                            name: name.clone(),
-                            version,
-                            license: license.clone(),
                             is_restrictive,
-                            compatibility: LicenseCompatibility::Unknown,
                             osi_status: match &license {
                                 Some(l) => crate::licenses::get_osi_status(l),
                                 None => crate::licenses::OsiStatus::Unknown,
                             },
+                            ecosystem: ECOSYSTEM.to_string(),
+                            ..LicenseInfo::new(name.to_string(), version, license)
                         });
                     }
                 } else {
@@ -147,6 +245,7 @@ fn parse_description_file(
             );
 
             let all_deps = direct_deps;
+            crate::cli::add_dependencies_to_resolve(all_deps.len());
 
             for (name, version) in all_deps {
                 log(
@@ -166,16 +265,16 @@ fn parse_description_file(
                     );
                 }
 
+                crate::cli::mark_dependency_resolved();
+
                 licenses.push(LicenseInfo {
-                    name,
-                    version,
-                    license: license.clone(),
                     is_restrictive,
-                    compatibility: LicenseCompatibility::Unknown,
                     osi_status: match &license {
                         Some(l) => crate::licenses::get_osi_status(l),
                         None => crate::licenses::OsiStatus::Unknown,
                     },
+                    ecosystem: ECOSYSTEM.to_string(),
+                    ..LicenseInfo::new(name, version, license)
                 });
             }
         }
@@ -247,13 +346,90 @@ fn process_dependency_field(field: &str, value: &str, deps: &mut Vec<(String, St
 }
 
 pub fn fetch_license_for_r_dependency(name: &str, version: &str) -> String {
+    cache::cached_package_license("r", name, version, || {
+        // Packages outside CRAN (Bioconductor, GitHub) are only on R-universe
+        let license = fetch_license_from_cran(name, version)
+            .unwrap_or_else(|| fetch_license_from_r_universe(name, version));
+        normalize_cran_license(&license)
+    })
+}
+
+fn fetch_license_from_cran(name: &str, version: &str) -> Option<String> {
+    if skip_network(&format!("CRAN license of {name}")) {
+        return None;
+    }
+
+    let api_url = if version == "latest" || version == "unknown" {
+        format!("https://crandb.r-pkg.org/{name}")
+    } else {
+        format!("https://crandb.r-pkg.org/{name}/{version}")
+    };
+    log(
+        LogLevel::Info,
+        &format!("Fetching license from CRAN: {api_url}"),
+    );
+
+    let client = match http_client() {
+        Ok(client) => client,
+        Err(err) => {
+            log_error("Failed to build HTTP client", &err);
+            return None;
+        }
+    };
+
+    match with_network_permit(|| client.get(&api_url).send()) {
+        Ok(response) => {
+            let status = response.status();
+            log(
+                LogLevel::Info,
+                &format!("CRAN API response status: {status}"),
+            );
+
+            if !status.is_success() {
+                log(
+                    LogLevel::Warn,
+                    &format!("{name} ({version}) not found on CRAN: HTTP {status}"),
+                );
+                return None;
+            }
+
+            match response.json::<Value>() {
+                Ok(json) => json["License"]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|license| !license.is_empty())
+                    .map(|license| {
+                        log(
+                            LogLevel::Info,
+                            &format!("License found for {name}: {license}"),
+                        );
+                        license.to_string()
+                    }),
+                Err(err) => {
+                    log_error(&format!("Failed to parse JSON for {name}: {version}"), &err);
+                    None
+                }
+            }
+        }
+        Err(err) => {
+            log_error(&format!("Failed to fetch CRAN metadata for {name}"), &err);
+            None
+        }
+    }
+}
+
+fn fetch_license_from_r_universe(name: &str, version: &str) -> String {
+    if skip_network(&format!("R-universe license of {name}")) {
+        return String::from("Unknown");
+    }
+
     let search_url = format!("https://r-universe.dev/api/search?q={name}&limit=1");
     log(
         LogLevel::Info,
         &format!("Fetching license from R-universe: {search_url}"),
     );
 
-    match reqwest::blocking::get(&search_url) {
+    match with_network_permit(|| http_client()?.get(&search_url).send()) {
         Ok(response) => {
             let status = response.status();
             log(
@@ -275,7 +451,9 @@ pub fn fetch_license_for_r_dependency(name: &str, version: &str) -> String {
                                         &format!("Fetching package details from: {package_url}"),
                                     );
 
-                                    if let Ok(pkg_response) = reqwest::blocking::get(&package_url) {
+                                    if let Ok(pkg_response) = with_network_permit(|| {
+                                        http_client()?.get(&package_url).send()
+                                    }) {
                                         if let Ok(pkg_json) = pkg_response.json::<Value>() {
                                             if let Some(license) = pkg_json["License"].as_str() {
                                                 if !license.is_empty() {
@@ -323,6 +501,8 @@ pub fn fetch_license_for_r_dependency(name: &str, version: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::network::set_offline_mode;
+    use serial_test::serial;
     use tempfile::TempDir;
 
     #[test]
@@ -404,6 +584,93 @@ Suggests:
         assert!(result.iter().any(|info| info.name == "ggplot2"));
     }
 
+    #[test]
+    #[serial]
+    fn test_parse_renv_lock_reads_package_and_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("renv.lock");
+
+        let lock_content = r#"{
+  "R": {
+    "Version": "4.3.1",
+    "Repositories": [{"Name": "CRAN", "URL": "https://cloud.r-project.org"}]
+  },
+  "Packages": {
+    "Rcpp": {
+      "Package": "Rcpp",
+      "Version": "1.0.11",
+      "Source": "Repository",
+      "Repository": "CRAN"
+    },
+    "rlang": {
+      "Package": "rlang",
+      "Version": "1.1.1",
+      "Source": "Repository",
+      "Repository": "CRAN"
+    },
+    "BiocGenerics": {
+      "Package": "BiocGenerics",
+      "Source": "Bioconductor"
+    }
+  }
+}"#;
+        fs::write(&lock_path, lock_content).unwrap();
+
+        set_offline_mode(true);
+        let result = parse_renv_lock(
+            lock_path.to_str().unwrap(),
+            &HashMap::new(),
+            &FeludaConfig::default(),
+        );
+        set_offline_mode(false);
+
+        assert_eq!(result.len(), 3);
+        let rcpp = result.iter().find(|info| info.name == "Rcpp").unwrap();
+        assert_eq!(rcpp.version, "1.0.11");
+        let rlang = result.iter().find(|info| info.name == "rlang").unwrap();
+        assert_eq!(rlang.version, "1.1.1");
+        let bioc = result
+            .iter()
+            .find(|info| info.name == "BiocGenerics")
+            .unwrap();
+        assert_eq!(bioc.version, "unknown");
+    }
+
+    #[test]
+    fn test_normalize_cran_license() {
+        assert_eq!(normalize_cran_license("GPL (>= 2)"), "GPL-2.0-or-later");
+        assert_eq!(normalize_cran_license("GPL(>=3)"), "GPL-3.0-or-later");
+        assert_eq!(normalize_cran_license("GPL-3"), "GPL-3.0-only");
+        assert_eq!(normalize_cran_license("LGPL (>= 2.1)"), "LGPL-2.1-or-later");
+        assert_eq!(normalize_cran_license("MIT + file LICENSE"), "MIT");
+        assert_eq!(
+            normalize_cran_license("BSD_3_clause + file LICENSE"),
+            "BSD-3-Clause"
+        );
+        assert_eq!(
+            normalize_cran_license("Apache License (== 2.0)"),
+            "Apache-2.0"
+        );
+        assert_eq!(
+            normalize_cran_license("GPL-2 | GPL-3"),
+            "GPL-2.0-only OR GPL-3.0-only"
+        );
+        assert_eq!(
+            normalize_cran_license("MIT + file LICENSE | Apache License 2.0"),
+            "MIT OR Apache-2.0"
+        );
+    }
+
+    #[test]
+    fn test_normalize_cran_license_keeps_unknown_terms() {
+        assert_eq!(normalize_cran_license("file LICENSE"), "file LICENSE");
+        assert_eq!(normalize_cran_license("Unknown"), "Unknown");
+        assert_eq!(
+            normalize_cran_license("GPL (>= 2) | Custom License"),
+            "GPL-2.0-or-later OR Custom License"
+        );
+    }
+
     #[test]
     fn test_analyze_r_licenses_description() {
         let temp_dir = TempDir::new().unwrap();
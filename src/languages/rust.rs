@@ -1,11 +1,64 @@
-use cargo_metadata::Package;
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, Package, PackageId};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::cli;
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_error, FeludaResult, LogLevel};
+use crate::languages::LanguageParser;
+use crate::licenses::{copyright, fetch_licenses_from_github, is_license_restrictive, LicenseInfo};
+
+/// Ecosystem name for Cargo crates
+const ECOSYSTEM: &str = "cargo";
+
+/// Parses Rust projects through the [`LanguageParser`] interface
+///
+/// Packages come from `cargo metadata`; a manifest cargo cannot load yields no dependencies.
+pub struct RustParser;
+
+impl LanguageParser for RustParser {
+    fn parse_dependencies(
+        &self,
+        project_file: &Path,
+        config: &FeludaConfig,
+        no_local: bool,
+    ) -> FeludaResult<Vec<LicenseInfo>> {
+        let metadata = match MetadataCommand::new().manifest_path(project_file).exec() {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to fetch cargo metadata: {err}"),
+                );
+                return Ok(Vec::new());
+            }
+        };
+        log(
+            LogLevel::Info,
+            &format!("Found {} packages in Rust project", metadata.packages.len()),
+        );
+
+        let packages = if config.direct_only {
+            direct_packages(&metadata, config.include_dev)
+        } else if config.include_dev {
+            metadata.packages
+        } else {
+            production_packages(&metadata)
+        };
+        Ok(analyze_rust_licenses_with_config(
+            packages, config, no_local,
+        ))
+    }
 
-use crate::debug::{log, log_error, LogLevel};
-use crate::licenses::{
-    fetch_licenses_from_github, is_license_restrictive, LicenseCompatibility, LicenseInfo,
-};
+    fn language_name(&self) -> &'static str {
+        "Rust"
+    }
+
+    fn supported_files(&self) -> &'static [&'static str] {
+        &["Cargo.toml"]
+    }
+}
 
 /// Analyze the licenses of Rust dependencies from Cargo packages
 #[allow(dead_code)]
@@ -14,6 +67,7 @@ pub fn analyze_rust_licenses(packages: Vec<Package>) -> Vec<LicenseInfo> {
     analyze_rust_licenses_with_config(packages, &config, false)
 }
 
+#[allow(dead_code)]
 pub fn analyze_rust_licenses_with_no_local(
     packages: Vec<Package>,
     no_local: bool,
@@ -54,6 +108,8 @@ pub fn analyze_rust_licenses_with_config(
         }
     };
 
+    cli::add_dependencies_to_resolve(packages.len());
+
     packages
         .par_iter()
         .map(|package| {
@@ -82,21 +138,115 @@ pub fn analyze_rust_licenses_with_config(
                 );
             }
 
+            cli::mark_dependency_resolved();
+
             LicenseInfo {
-                name: package.name.to_string(),
-                version: package.version.to_string(),
-                license,
                 is_restrictive,
-                compatibility: LicenseCompatibility::Unknown,
                 osi_status: match &package.license {
                     Some(license) => crate::licenses::get_osi_status(license),
                     None => crate::licenses::OsiStatus::Unknown,
                 },
+                ecosystem: ECOSYSTEM.to_string(),
+                copyright: package
+                    .manifest_path
+                    .parent()
+                    .and_then(|dir| copyright::copyright_from_dir(dir.as_std_path()))
+                    .or_else(|| copyright::copyright_from_authors(&package.authors)),
+                ..LicenseInfo::new(
+                    package.name.to_string(),
+                    package.version.to_string(),
+                    license,
+                )
             }
         })
         .collect()
 }
 
+/// Keep only packages reachable from the workspace through normal dependencies
+///
+/// Crates pulled in solely via `[dev-dependencies]` or `[build-dependencies]` are dropped.
+pub fn production_packages(metadata: &Metadata) -> Vec<Package> {
+    let Some(resolve) = &metadata.resolve else {
+        log(
+            LogLevel::Warn,
+            "Cargo metadata has no dependency graph, keeping all packages",
+        );
+        return metadata.packages.clone();
+    };
+
+    let nodes: HashMap<&PackageId, _> = resolve.nodes.iter().map(|n| (&n.id, n)).collect();
+    let mut reachable: HashSet<&PackageId> = HashSet::new();
+    let mut stack: Vec<&PackageId> = metadata.workspace_members.iter().collect();
+
+    while let Some(id) = stack.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        if let Some(node) = nodes.get(id) {
+            for dep in &node.deps {
+                if dep
+                    .dep_kinds
+                    .iter()
+                    .any(|info| info.kind == DependencyKind::Normal)
+                {
+                    stack.push(&dep.pkg);
+                }
+            }
+        }
+    }
+
+    let packages: Vec<Package> = metadata
+        .packages
+        .iter()
+        .filter(|package| reachable.contains(&package.id))
+        .cloned()
+        .collect();
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Production-only: kept {} of {} Rust packages",
+            packages.len(),
+            metadata.packages.len()
+        ),
+    );
+
+    packages
+}
+
+/// Keep only the workspace members and the crates they declare directly
+///
+/// `[dev-dependencies]` and `[build-dependencies]` count only with `include_dev`.
+pub fn direct_packages(metadata: &Metadata, include_dev: bool) -> Vec<Package> {
+    let members: HashSet<&PackageId> = metadata.workspace_members.iter().collect();
+    let declared: HashSet<&str> = metadata
+        .packages
+        .iter()
+        .filter(|package| members.contains(&package.id))
+        .flat_map(|package| &package.dependencies)
+        .filter(|dep| include_dev || dep.kind == DependencyKind::Normal)
+        .map(|dep| dep.name.as_str())
+        .collect();
+
+    let packages: Vec<Package> = metadata
+        .packages
+        .iter()
+        .filter(|package| members.contains(&package.id) || declared.contains(package.name.as_str()))
+        .cloned()
+        .collect();
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Direct-only: kept {} of {} Rust packages",
+            packages.len(),
+            metadata.packages.len()
+        ),
+    );
+
+    packages
+}
+
 fn get_license_from_manifest<P: AsRef<std::path::Path>>(manifest_path: P) -> Option<String> {
     use std::fs;
     use toml::Value;
@@ -140,6 +290,111 @@ mod tests {
         tempfile::tempdir().unwrap()
     }
 
+    fn write_crate(root: &std::path::Path, name: &str, extra: &str) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\nlicense = \"MIT\"\n{extra}"
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+    }
+
+    #[test]
+    fn test_production_packages_excludes_dev_and_build_dependencies() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        write_crate(root, "prodlib", "");
+        write_crate(root, "devlib", "");
+        write_crate(root, "buildlib", "");
+        write_crate(
+            root,
+            "app",
+            "[dependencies]\nprodlib = { path = \"../prodlib\" }\n\
+             [dev-dependencies]\ndevlib = { path = \"../devlib\" }\n\
+             [build-dependencies]\nbuildlib = { path = \"../buildlib\" }\n",
+        );
+
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("app/Cargo.toml"))
+            .current_dir(root)
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap();
+        assert_eq!(metadata.packages.len(), 4);
+
+        let names: Vec<String> = production_packages(&metadata)
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect();
+        assert!(names.contains(&"app".to_string()));
+        assert!(names.contains(&"prodlib".to_string()));
+        assert!(!names.contains(&"devlib".to_string()));
+        assert!(!names.contains(&"buildlib".to_string()));
+    }
+
+    #[test]
+    fn test_direct_packages_excludes_transitive_dependencies() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        write_crate(root, "translib", "");
+        write_crate(
+            root,
+            "prodlib",
+            "[dependencies]\ntranslib = { path = \"../translib\" }\n",
+        );
+        write_crate(root, "devlib", "");
+        write_crate(
+            root,
+            "app",
+            "[dependencies]\nprodlib = { path = \"../prodlib\" }\n\
+             [dev-dependencies]\ndevlib = { path = \"../devlib\" }\n",
+        );
+
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("app/Cargo.toml"))
+            .current_dir(root)
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap();
+
+        let names = |include_dev| {
+            let mut names: Vec<String> = direct_packages(&metadata, include_dev)
+                .iter()
+                .map(|p| p.name.to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(false), vec!["app", "prodlib"]);
+        assert_eq!(names(true), vec!["app", "devlib", "prodlib"]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analyze_rust_licenses_stamps_cargo_ecosystem() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        write_crate(root, "app", "");
+
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("app/Cargo.toml"))
+            .current_dir(root)
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap();
+
+        crate::network::set_offline_mode(true);
+        let result = analyze_rust_licenses(metadata.packages);
+        crate::network::set_offline_mode(false);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].ecosystem, "cargo");
+    }
+
     #[test]
     fn test_analyze_rust_licenses_empty() {
         let packages = vec![];
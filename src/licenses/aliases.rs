@@ -0,0 +1,431 @@
+//! Canonical SPDX identifiers for common non-SPDX license spellings
+//!
+//! Registries return free-form license strings (`Apache 2.0`, `apache-2`, `The MIT License`,
+//! PyPI trove classifiers, Maven POM names, ...). With `--normalize-licenses` these are
+//! mapped to SPDX identifiers before restrictiveness and compatibility checks.
+//!
+//! Matching is exact after lowercasing and collapsing whitespace, so only the spellings
+//! listed in [`LICENSE_ALIASES`] are rewritten. Version-specific GNU names map to the
+//! `-only` identifier and names mentioning "or later" (or `+`) to `-or-later`.
+
+use std::collections::HashMap;
+
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_error, LogLevel};
+use crate::licenses::{
+    fetch_licenses_from_github, get_osi_status, is_license_restrictive_with_config, LicenseInfo,
+};
+
+/// Alias (lowercase, single-spaced) -> canonical SPDX identifier
+pub const LICENSE_ALIASES: &[(&str, &str)] = &[
+    // MIT
+    ("mit license", "MIT"),
+    ("the mit license", "MIT"),
+    ("the mit license (mit)", "MIT"),
+    ("mit license (mit)", "MIT"),
+    ("license :: osi approved :: mit license", "MIT"),
+    ("expat", "MIT"),
+    ("expat license", "MIT"),
+    ("mit/x11", "MIT"),
+    // Apache-2.0
+    ("apache 2", "Apache-2.0"),
+    ("apache 2.0", "Apache-2.0"),
+    ("apache-2", "Apache-2.0"),
+    ("apache2", "Apache-2.0"),
+    ("apache 2.0 license", "Apache-2.0"),
+    ("apache-2.0 license", "Apache-2.0"),
+    ("apache license 2.0", "Apache-2.0"),
+    ("apache license, 2.0", "Apache-2.0"),
+    ("apache license version 2.0", "Apache-2.0"),
+    ("apache license, version 2.0", "Apache-2.0"),
+    ("apache license (2.0)", "Apache-2.0"),
+    ("apache software license 2.0", "Apache-2.0"),
+    ("apache software license, version 2.0", "Apache-2.0"),
+    ("the apache license, version 2.0", "Apache-2.0"),
+    ("the apache software license, version 2.0", "Apache-2.0"),
+    ("asl 2.0", "Apache-2.0"),
+    ("asl2", "Apache-2.0"),
+    (
+        "license :: osi approved :: apache software license",
+        "Apache-2.0",
+    ),
+    // BSD
+    ("bsd-2", "BSD-2-Clause"),
+    ("bsd 2-clause", "BSD-2-Clause"),
+    ("bsd 2 clause", "BSD-2-Clause"),
+    ("bsd-2-clause license", "BSD-2-Clause"),
+    ("2-clause bsd", "BSD-2-Clause"),
+    ("2-clause bsd license", "BSD-2-Clause"),
+    ("the 2-clause bsd license", "BSD-2-Clause"),
+    ("simplified bsd", "BSD-2-Clause"),
+    ("simplified bsd license", "BSD-2-Clause"),
+    ("freebsd", "BSD-2-Clause"),
+    ("bsd-3", "BSD-3-Clause"),
+    ("bsd 3-clause", "BSD-3-Clause"),
+    ("bsd 3 clause", "BSD-3-Clause"),
+    ("bsd-3-clause license", "BSD-3-Clause"),
+    ("3-clause bsd", "BSD-3-Clause"),
+    ("3-clause bsd license", "BSD-3-Clause"),
+    ("the 3-clause bsd license", "BSD-3-Clause"),
+    ("new bsd", "BSD-3-Clause"),
+    ("new bsd license", "BSD-3-Clause"),
+    ("modified bsd", "BSD-3-Clause"),
+    ("modified bsd license", "BSD-3-Clause"),
+    ("revised bsd", "BSD-3-Clause"),
+    ("bsd-new", "BSD-3-Clause"),
+    ("0-clause bsd", "0BSD"),
+    ("bsd zero clause", "0BSD"),
+    ("bsd-zero-clause", "0BSD"),
+    // ISC
+    ("isc license", "ISC"),
+    ("isc license (iscl)", "ISC"),
+    ("iscl", "ISC"),
+    // GNU GPL
+    ("gpl2", "GPL-2.0-only"),
+    ("gplv2", "GPL-2.0-only"),
+    ("gpl v2", "GPL-2.0-only"),
+    ("gpl-2", "GPL-2.0-only"),
+    ("gnu gpl v2", "GPL-2.0-only"),
+    ("gnu general public license v2", "GPL-2.0-only"),
+    ("gnu general public license v2.0", "GPL-2.0-only"),
+    ("gnu general public license, version 2", "GPL-2.0-only"),
+    ("gplv2+", "GPL-2.0-or-later"),
+    (
+        "gnu general public license v2 or later (gplv2+)",
+        "GPL-2.0-or-later",
+    ),
+    ("gpl3", "GPL-3.0-only"),
+    ("gplv3", "GPL-3.0-only"),
+    ("gpl v3", "GPL-3.0-only"),
+    ("gpl-3", "GPL-3.0-only"),
+    ("gnu gpl v3", "GPL-3.0-only"),
+    ("gnu general public license v3", "GPL-3.0-only"),
+    ("gnu general public license v3.0", "GPL-3.0-only"),
+    ("gnu general public license v3 (gplv3)", "GPL-3.0-only"),
+    ("gnu general public license, version 3", "GPL-3.0-only"),
+    ("gplv3+", "GPL-3.0-or-later"),
+    (
+        "gnu general public license v3 or later (gplv3+)",
+        "GPL-3.0-or-later",
+    ),
+    // GNU LGPL
+    ("lgplv2.1", "LGPL-2.1-only"),
+    ("lgpl v2.1", "LGPL-2.1-only"),
+    ("lgpl-2.1", "LGPL-2.1-only"),
+    ("gnu lesser general public license v2.1", "LGPL-2.1-only"),
+    ("lgplv2.1+", "LGPL-2.1-or-later"),
+    ("lgplv3", "LGPL-3.0-only"),
+    ("lgpl v3", "LGPL-3.0-only"),
+    ("lgpl-3", "LGPL-3.0-only"),
+    ("gnu lesser general public license v3", "LGPL-3.0-only"),
+    (
+        "gnu lesser general public license v3 (lgplv3)",
+        "LGPL-3.0-only",
+    ),
+    ("lgplv3+", "LGPL-3.0-or-later"),
+    (
+        "gnu lesser general public license v3 or later (lgplv3+)",
+        "LGPL-3.0-or-later",
+    ),
+    // GNU AGPL
+    ("agplv3", "AGPL-3.0-only"),
+    ("agpl v3", "AGPL-3.0-only"),
+    ("agpl-3", "AGPL-3.0-only"),
+    ("gnu affero general public license v3", "AGPL-3.0-only"),
+    ("agplv3+", "AGPL-3.0-or-later"),
+    (
+        "gnu affero general public license v3 or later (agplv3+)",
+        "AGPL-3.0-or-later",
+    ),
+    // Mozilla
+    ("mpl 2.0", "MPL-2.0"),
+    ("mpl-2", "MPL-2.0"),
+    ("mpl2", "MPL-2.0"),
+    ("mozilla public license 2.0", "MPL-2.0"),
+    ("mozilla public license, version 2.0", "MPL-2.0"),
+    ("mozilla public license 2.0 (mpl 2.0)", "MPL-2.0"),
+    // Eclipse
+    ("epl 2.0", "EPL-2.0"),
+    ("epl-2", "EPL-2.0"),
+    ("eclipse public license 2.0", "EPL-2.0"),
+    ("eclipse public license - v 2.0", "EPL-2.0"),
+    ("epl 1.0", "EPL-1.0"),
+    ("eclipse public license 1.0", "EPL-1.0"),
+    ("eclipse public license - v 1.0", "EPL-1.0"),
+    // Public domain style
+    ("the unlicense", "Unlicense"),
+    ("the unlicense (unlicense)", "Unlicense"),
+    ("cc0", "CC0-1.0"),
+    ("cc0 1.0", "CC0-1.0"),
+    ("cc0 1.0 universal", "CC0-1.0"),
+    ("creative commons zero", "CC0-1.0"),
+    ("zlib license", "Zlib"),
+    ("zlib/libpng", "Zlib"),
+    ("boost software license", "BSL-1.0"),
+    ("boost software license 1.0", "BSL-1.0"),
+    ("boost software license - version 1.0", "BSL-1.0"),
+    ("python software foundation license", "PSF-2.0"),
+    ("psf", "PSF-2.0"),
+    ("psfl", "PSF-2.0"),
+];
+
+/// SPDX identifiers recognized regardless of the case they are written in
+const CANONICAL_IDS: &[&str] = &[
+    "0BSD",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "CC0-1.0",
+    "EPL-1.0",
+    "EPL-2.0",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "ISC",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "MIT",
+    "MPL-2.0",
+    "PSF-2.0",
+    "Unlicense",
+    "Zlib",
+];
+
+/// Canonical SPDX identifier for a license string, or `None` if it is not recognized
+///
+/// SPDX expressions such as `MIT OR Apache-2.0` and unknown strings return `None` so the
+/// caller keeps the original value.
+pub fn normalize_license(raw: &str) -> Option<String> {
+    let key = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if key.is_empty() {
+        return None;
+    }
+
+    CANONICAL_IDS
+        .iter()
+        .find(|id| id.to_lowercase() == key)
+        .copied()
+        .or_else(|| {
+            LICENSE_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == key)
+                .map(|(_, canonical)| *canonical)
+        })
+        .map(str::to_string)
+}
+
+/// Rewrite recognized aliases to their SPDX identifier and re-evaluate each rewritten dependency
+///
+/// Restrictiveness and OSI status were derived from the raw string by the language analyzers,
+/// so both are recomputed for every dependency whose license changed.
+pub fn normalize_dependency_licenses(licenses: &mut [LicenseInfo], config: &FeludaConfig) {
+    let mut known_licenses = None;
+
+    for info in licenses.iter_mut() {
+        let Some(raw) = info.license.as_deref() else {
+            continue;
+        };
+        let Some(canonical) = normalize_license(raw).filter(|canonical| canonical != raw) else {
+            continue;
+        };
+
+        log(
+            LogLevel::Info,
+            &format!(
+                "Normalized license of {}@{}: '{raw}' -> '{canonical}'",
+                info.name, info.version
+            ),
+        );
+
+        let known = known_licenses.get_or_insert_with(|| {
+            fetch_licenses_from_github().unwrap_or_else(|err| {
+                log_error("Failed to fetch licenses from GitHub", &err);
+                HashMap::new()
+            })
+        });
+
+        info.osi_status = get_osi_status(&canonical);
+        info.license = Some(canonical);
+        info.is_restrictive =
+            is_license_restrictive_with_config(&info.license, known, config.strict, config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::{test_dependency, OsiStatus};
+    use crate::network::set_offline_mode;
+    use serial_test::serial;
+
+    #[test]
+    fn test_normalize_mit_aliases() {
+        for raw in [
+            "MIT License",
+            "The MIT License",
+            "the  mit   license",
+            "The MIT License (MIT)",
+            "License :: OSI Approved :: MIT License",
+            "Expat",
+            "mit",
+        ] {
+            assert_eq!(normalize_license(raw).as_deref(), Some("MIT"), "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_apache_aliases() {
+        for raw in [
+            "Apache 2.0",
+            "apache-2",
+            "Apache2",
+            "Apache License 2.0",
+            "Apache License, Version 2.0",
+            "The Apache Software License, Version 2.0",
+            "ASL 2.0",
+            "License :: OSI Approved :: Apache Software License",
+            "apache-2.0",
+        ] {
+            assert_eq!(
+                normalize_license(raw).as_deref(),
+                Some("Apache-2.0"),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_bsd_aliases() {
+        assert_eq!(
+            normalize_license("New BSD License").as_deref(),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            normalize_license("BSD 3-Clause").as_deref(),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            normalize_license("Simplified BSD").as_deref(),
+            Some("BSD-2-Clause")
+        );
+        assert_eq!(
+            normalize_license("bsd-2-clause").as_deref(),
+            Some("BSD-2-Clause")
+        );
+        // Bare "BSD" does not say which variant is meant
+        assert_eq!(normalize_license("BSD"), None);
+    }
+
+    #[test]
+    fn test_normalize_gnu_aliases() {
+        assert_eq!(normalize_license("GPLv3").as_deref(), Some("GPL-3.0-only"));
+        assert_eq!(
+            normalize_license("GNU General Public License v3 or later (GPLv3+)").as_deref(),
+            Some("GPL-3.0-or-later")
+        );
+        assert_eq!(
+            normalize_license("GPLv2+").as_deref(),
+            Some("GPL-2.0-or-later")
+        );
+        assert_eq!(
+            normalize_license("LGPL v2.1").as_deref(),
+            Some("LGPL-2.1-only")
+        );
+        assert_eq!(
+            normalize_license("GNU Affero General Public License v3").as_deref(),
+            Some("AGPL-3.0-only")
+        );
+    }
+
+    #[test]
+    fn test_normalize_other_aliases() {
+        assert_eq!(
+            normalize_license("Mozilla Public License 2.0 (MPL 2.0)").as_deref(),
+            Some("MPL-2.0")
+        );
+        assert_eq!(
+            normalize_license("Eclipse Public License - v 2.0").as_deref(),
+            Some("EPL-2.0")
+        );
+        assert_eq!(
+            normalize_license("The Unlicense").as_deref(),
+            Some("Unlicense")
+        );
+        assert_eq!(
+            normalize_license("CC0 1.0 Universal").as_deref(),
+            Some("CC0-1.0")
+        );
+        assert_eq!(
+            normalize_license("ISC License (ISCL)").as_deref(),
+            Some("ISC")
+        );
+        assert_eq!(
+            normalize_license("Boost Software License 1.0").as_deref(),
+            Some("BSL-1.0")
+        );
+    }
+
+    #[test]
+    fn test_normalize_leaves_expressions_and_unknown_inputs() {
+        assert_eq!(normalize_license("MIT OR Apache-2.0"), None);
+        assert_eq!(
+            normalize_license("(MIT OR Apache-2.0) AND BSD-3-Clause"),
+            None
+        );
+        assert_eq!(normalize_license("Unknown license for foo"), None);
+        assert_eq!(normalize_license("Proprietary"), None);
+        assert_eq!(normalize_license("   "), None);
+    }
+
+    #[test]
+    fn test_alias_keys_are_normalized() {
+        for (alias, canonical) in LICENSE_ALIASES {
+            assert_eq!(
+                *alias,
+                alias
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase(),
+                "alias keys must be lowercase and single-spaced"
+            );
+            assert!(CANONICAL_IDS.contains(canonical), "{canonical}");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_normalize_dependency_licenses_reevaluates_rewritten_entries() {
+        let mut licenses = vec![
+            test_dependency("serde", "1.0.0", "Apache License, Version 2.0"),
+            test_dependency("readline", "1.0.0", "GPLv3"),
+            test_dependency("either", "1.0.0", "MIT OR Apache-2.0"),
+            test_dependency("mystery", "1.0.0", "Custom license"),
+        ];
+
+        set_offline_mode(true);
+        normalize_dependency_licenses(&mut licenses, &FeludaConfig::default());
+        set_offline_mode(false);
+
+        assert_eq!(licenses[0].license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(licenses[0].osi_status, OsiStatus::Approved);
+        assert!(!licenses[0].is_restrictive);
+
+        assert_eq!(licenses[1].license.as_deref(), Some("GPL-3.0-only"));
+        assert!(licenses[1].is_restrictive);
+
+        assert_eq!(licenses[2].license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(licenses[3].license.as_deref(), Some("Custom license"));
+        assert_eq!(licenses[3].osi_status, OsiStatus::Unknown);
+    }
+}
@@ -0,0 +1,272 @@
+//! License family classification
+//!
+//! Groups licenses by the obligations they put on a distributor: permissive licenses only
+//! ask for attribution, weak copyleft licenses (LGPL, MPL, EPL) cover changes to the licensed
+//! files or library, and strong copyleft licenses (GPL, AGPL) extend to the combined work.
+//!
+//! Licenses are matched by SPDX identifier family after alias normalization, so `LGPL-2.1`
+//! and `LGPL-3.0-or-later` both classify as weak copyleft. In an `OR` expression the most
+//! permissive option counts, since it can be chosen; in an `AND` expression the strictest.
+
+use serde::{Deserialize, Serialize};
+
+use crate::licenses::expression::{self, LicenseExpr};
+use crate::licenses::{aliases, normalize_license_id};
+
+/// License family, from least to most restrictive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LicenseCategory {
+    PublicDomain,
+    Permissive,
+    WeakCopyleft,
+    StrongCopyleft,
+    Proprietary,
+    Unknown,
+}
+
+impl std::fmt::Display for LicenseCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PublicDomain => write!(f, "public-domain"),
+            Self::Permissive => write!(f, "permissive"),
+            Self::WeakCopyleft => write!(f, "weak-copyleft"),
+            Self::StrongCopyleft => write!(f, "strong-copyleft"),
+            Self::Proprietary => write!(f, "proprietary"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Identifier prefixes per category, checked in order against the uppercased identifier
+///
+/// Proprietary comes first so npm's `UNLICENSED` is not read as `Unlicense`.
+const CATEGORY_PREFIXES: &[(LicenseCategory, &[&str])] = &[
+    (
+        LicenseCategory::Proprietary,
+        &["PROPRIETARY", "UNLICENSED", "COMMERCIAL", "BUSL", "ELASTIC"],
+    ),
+    (
+        LicenseCategory::PublicDomain,
+        &[
+            "CC0",
+            "UNLICENSE",
+            "PUBLIC DOMAIN",
+            "PUBLIC-DOMAIN",
+            "PDDL",
+            "CC-PDDC",
+        ],
+    ),
+    (
+        LicenseCategory::WeakCopyleft,
+        &["LGPL", "MPL", "EPL", "CDDL", "CPL", "MS-RL"],
+    ),
+    (
+        LicenseCategory::StrongCopyleft,
+        &["GPL", "AGPL", "SSPL", "OSL", "EUPL"],
+    ),
+    (
+        LicenseCategory::Permissive,
+        &[
+            "MIT",
+            "APACHE",
+            "BSD",
+            "0BSD",
+            "ISC",
+            "ZLIB",
+            "BSL-1.0",
+            "UNICODE",
+            "PSF",
+            "PYTHON",
+            "X11",
+            "ARTISTIC",
+            "WTFPL",
+            "POSTGRESQL",
+            "NCSA",
+            "BLUEOAK",
+            "UPL",
+            "MS-PL",
+            "CC-BY-3.0",
+            "CC-BY-4.0",
+        ],
+    ),
+];
+
+/// GPL exceptions that permit linking without the combined work becoming GPL
+const LINKING_EXCEPTIONS: &[&str] = &["CLASSPATH-EXCEPTION", "GCC-EXCEPTION"];
+
+impl LicenseCategory {
+    /// Position from least to most restrictive; `None` for `Unknown`
+    fn strictness(self) -> Option<u8> {
+        match self {
+            Self::PublicDomain => Some(0),
+            Self::Permissive => Some(1),
+            Self::WeakCopyleft => Some(2),
+            Self::StrongCopyleft => Some(3),
+            Self::Proprietary => Some(4),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// Classify a declared license string or SPDX expression
+pub fn license_category(license: &str) -> LicenseCategory {
+    // Whole-string aliases such as "Apache License 2.0" do not parse as expressions
+    if let Some(id) = aliases::normalize_license(license) {
+        return classify_id(&id);
+    }
+
+    match expression::parse(license) {
+        Ok(expr) if expr.is_compound() => classify_expr(&expr),
+        _ => classify_id(license),
+    }
+}
+
+fn classify_expr(expr: &LicenseExpr) -> LicenseCategory {
+    match expr {
+        LicenseExpr::Id(id) => classify_id(id),
+        LicenseExpr::With(license, exception) => {
+            let category = classify_expr(license);
+            let exception = exception.to_uppercase();
+            if category == LicenseCategory::StrongCopyleft
+                && LINKING_EXCEPTIONS.iter().any(|e| exception.starts_with(e))
+            {
+                LicenseCategory::WeakCopyleft
+            } else {
+                category
+            }
+        }
+        // Every side applies: an unknown side makes the whole unknown
+        LicenseExpr::And(left, right) => {
+            let (left, right) = (classify_expr(left), classify_expr(right));
+            match (left.strictness(), right.strictness()) {
+                (Some(l), Some(r)) if l >= r => left,
+                (Some(_), Some(_)) => right,
+                _ => LicenseCategory::Unknown,
+            }
+        }
+        // The most permissive known option can be chosen
+        LicenseExpr::Or(left, right) => {
+            let (left, right) = (classify_expr(left), classify_expr(right));
+            match (left.strictness(), right.strictness()) {
+                (Some(l), Some(r)) if l <= r => left,
+                (Some(_), Some(_)) | (None, Some(_)) => right,
+                _ => left,
+            }
+        }
+    }
+}
+
+fn classify_id(raw: &str) -> LicenseCategory {
+    let id = aliases::normalize_license(raw)
+        .unwrap_or_else(|| normalize_license_id(raw))
+        .to_uppercase();
+
+    CATEGORY_PREFIXES
+        .iter()
+        .find(|(_, prefixes)| prefixes.iter().any(|prefix| id.starts_with(prefix)))
+        .map(|(category, _)| *category)
+        .unwrap_or(LicenseCategory::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permissive_licenses() {
+        for license in ["MIT", "Apache-2.0", "BSD-3-Clause", "ISC", "0BSD", "Zlib"] {
+            assert_eq!(
+                license_category(license),
+                LicenseCategory::Permissive,
+                "{license}"
+            );
+        }
+        assert_eq!(
+            license_category("Apache License 2.0"),
+            LicenseCategory::Permissive
+        );
+    }
+
+    #[test]
+    fn test_weak_copyleft_licenses() {
+        for license in [
+            "LGPL-2.1-only",
+            "LGPL-3.0-or-later",
+            "MPL-2.0",
+            "EPL-2.0",
+            "CDDL-1.0",
+        ] {
+            assert_eq!(
+                license_category(license),
+                LicenseCategory::WeakCopyleft,
+                "{license}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_strong_copyleft_licenses() {
+        for license in ["GPL-2.0-only", "GPL-3.0-or-later", "AGPL-3.0", "GPL v3"] {
+            assert_eq!(
+                license_category(license),
+                LicenseCategory::StrongCopyleft,
+                "{license}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_public_domain_and_proprietary_licenses() {
+        assert_eq!(license_category("CC0-1.0"), LicenseCategory::PublicDomain);
+        assert_eq!(license_category("Unlicense"), LicenseCategory::PublicDomain);
+        assert_eq!(license_category("UNLICENSED"), LicenseCategory::Proprietary);
+        assert_eq!(license_category("BUSL-1.1"), LicenseCategory::Proprietary);
+        assert_eq!(
+            license_category("Proprietary"),
+            LicenseCategory::Proprietary
+        );
+    }
+
+    #[test]
+    fn test_unknown_licenses() {
+        assert_eq!(license_category(""), LicenseCategory::Unknown);
+        assert_eq!(
+            license_category("Unknown license for foo: 1.0.0"),
+            LicenseCategory::Unknown
+        );
+        assert_eq!(
+            license_category("SEE LICENSE IN LICENSE.md"),
+            LicenseCategory::Unknown
+        );
+    }
+
+    #[test]
+    fn test_expressions() {
+        // A permissive option can be chosen
+        assert_eq!(
+            license_category("MIT OR GPL-3.0-only"),
+            LicenseCategory::Permissive
+        );
+        // Both obligations apply
+        assert_eq!(
+            license_category("MIT AND LGPL-2.1-only"),
+            LicenseCategory::WeakCopyleft
+        );
+        assert_eq!(
+            license_category("MIT AND CustomRef"),
+            LicenseCategory::Unknown
+        );
+        assert_eq!(
+            license_category("CustomRef OR MPL-2.0"),
+            LicenseCategory::WeakCopyleft
+        );
+        assert_eq!(
+            license_category("GPL-2.0-only WITH Classpath-exception-2.0"),
+            LicenseCategory::WeakCopyleft
+        );
+        assert_eq!(
+            license_category("GPL-2.0-or-later WITH Bison-exception-2.2"),
+            LicenseCategory::StrongCopyleft
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Copyright statements for NOTICE attribution
+//!
+//! The statement comes from the first `Copyright ...` line of a package's license file, or
+//! failing that from the authors in its metadata (npm `author`, Cargo `authors`). Template
+//! lines such as `Copyright [yyyy] [name of copyright owner]` and the license steward's own
+//! notice at the top of GNU license texts are skipped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest statement kept; anything longer is a paragraph, not a copyright line
+const MAX_STATEMENT_LEN: usize = 200;
+
+/// Markers of a license template's placeholder copyright line
+const PLACEHOLDERS: &[&str] = &[
+    "<year>",
+    "[year]",
+    "[yyyy]",
+    "{yyyy}",
+    "<name of author>",
+    "copyright owner]",
+    "<copyright holders>",
+];
+
+/// Authors of the license texts themselves, not of the package
+const LICENSE_STEWARDS: &[&str] = &["free software foundation"];
+
+fn is_statement(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let starts_like_copyright = line.starts_with("Copyright")
+        || line.starts_with("COPYRIGHT")
+        || lower.starts_with("(c) ")
+        || line.starts_with('©');
+
+    starts_like_copyright
+        && line.len() <= MAX_STATEMENT_LEN
+        && lower.chars().any(|c| c.is_ascii_alphanumeric())
+        && !lower.starts_with("copyright notice")
+        && !PLACEHOLDERS.iter().any(|marker| lower.contains(marker))
+        && !LICENSE_STEWARDS
+            .iter()
+            .any(|steward| lower.contains(steward))
+}
+
+/// First copyright statement in a license text
+pub fn extract_copyright(text: &str) -> Option<String> {
+    text.lines()
+        .map(|line| line.trim().trim_start_matches(['#', '*', '/']).trim())
+        .find(|line| is_statement(line))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// First copyright statement in any of the given license files
+pub fn copyright_from_files(paths: &[PathBuf]) -> Option<String> {
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| extract_copyright(&content))
+}
+
+/// Copyright statement naming the package authors, without their e-mail or URL
+pub fn copyright_from_authors(authors: &[String]) -> Option<String> {
+    let names: Vec<&str> = authors
+        .iter()
+        .map(|author| author.split(['<', '(']).next().unwrap_or_default().trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+    (!names.is_empty()).then(|| format!("Copyright (c) {}", names.join(", ")))
+}
+
+/// Copyright statement for a package directory, from its license files
+pub fn copyright_from_dir(dir: &Path) -> Option<String> {
+    copyright_from_files(&super::license_files_in(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extract_copyright() {
+        let mit = "MIT License\n\nCopyright (c) 2016  Sindre Sorhus <sindresorhus@gmail.com>\n\n\
+                   Permission is hereby granted, free of charge, ...\n\
+                   The above copyright notice and this permission notice shall be included";
+        assert_eq!(
+            extract_copyright(mit).as_deref(),
+            Some("Copyright (c) 2016 Sindre Sorhus <sindresorhus@gmail.com>")
+        );
+
+        let gpl = "GNU GENERAL PUBLIC LICENSE\n\
+                   Copyright (C) 2007 Free Software Foundation, Inc. <https://fsf.org/>\n\
+                   ...\n    Copyright (C) <year>  <name of author>\n";
+        assert_eq!(extract_copyright(gpl), None);
+
+        let apache = "   Copyright [yyyy] [name of copyright owner]\n";
+        assert_eq!(extract_copyright(apache), None);
+
+        assert_eq!(
+            extract_copyright("// Copyright 2018 The Rust Project Developers\n").as_deref(),
+            Some("Copyright 2018 The Rust Project Developers")
+        );
+    }
+
+    #[test]
+    fn test_copyright_from_authors() {
+        let authors = vec![
+            "David Tolnay <dtolnay@gmail.com>".to_string(),
+            "Erick Tryzelaar (https://github.com/erickt)".to_string(),
+        ];
+        assert_eq!(
+            copyright_from_authors(&authors).as_deref(),
+            Some("Copyright (c) David Tolnay, Erick Tryzelaar")
+        );
+        assert_eq!(copyright_from_authors(&[]), None);
+        assert_eq!(copyright_from_authors(&[" <x@y.z>".to_string()]), None);
+    }
+
+    #[test]
+    fn test_copyright_from_dir() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("COPYING"),
+            "Copyright 2001 Copying Author\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("LICENSE-MIT"),
+            "Copyright (c) 2020 Jane Doe\n",
+        )
+        .unwrap();
+
+        // LICENSE files are read before COPYING
+        assert_eq!(
+            copyright_from_dir(dir.path()).as_deref(),
+            Some("Copyright (c) 2020 Jane Doe")
+        );
+        assert_eq!(copyright_from_dir(&dir.path().join("missing")), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Deprecated SPDX license identifiers
+//!
+//! SPDX has retired several identifiers, most notably the bare GNU ones such as `GPL-3.0`
+//! in favour of explicit `-only`/`-or-later` variants, and the `-with-<exception>` forms in
+//! favour of `WITH` expressions. The declared license is never rewritten; dependencies are
+//! only annotated with the suggested replacement.
+//!
+//! A bare GNU identifier does not say which variant was meant. The suggestion is the
+//! `-or-later` form, which is what most package manifests using it intend, but the
+//! license header of the dependency should be checked before adopting it.
+
+use crate::debug::{log, LogLevel};
+use crate::licenses::expression::{self, LicenseExpr};
+use crate::licenses::LicenseInfo;
+
+/// Deprecated SPDX identifier -> suggested replacement expression
+pub const DEPRECATED_LICENSES: &[(&str, &str)] = &[
+    ("AGPL-1.0", "AGPL-1.0-or-later"),
+    ("AGPL-3.0", "AGPL-3.0-or-later"),
+    ("BSD-2-Clause-FreeBSD", "BSD-2-Clause"),
+    ("BSD-2-Clause-NetBSD", "BSD-2-Clause"),
+    ("bzip2-1.0.5", "bzip2-1.0.6"),
+    ("eCos-2.0", "GPL-2.0-or-later WITH eCos-exception-2.0"),
+    ("GFDL-1.1", "GFDL-1.1-or-later"),
+    ("GFDL-1.2", "GFDL-1.2-or-later"),
+    ("GFDL-1.3", "GFDL-1.3-or-later"),
+    ("GPL-1.0", "GPL-1.0-or-later"),
+    ("GPL-1.0+", "GPL-1.0-or-later"),
+    ("GPL-2.0", "GPL-2.0-or-later"),
+    ("GPL-2.0+", "GPL-2.0-or-later"),
+    (
+        "GPL-2.0-with-autoconf-exception",
+        "GPL-2.0-only WITH Autoconf-exception-2.0",
+    ),
+    (
+        "GPL-2.0-with-bison-exception",
+        "GPL-2.0-or-later WITH Bison-exception-2.2",
+    ),
+    (
+        "GPL-2.0-with-classpath-exception",
+        "GPL-2.0-only WITH Classpath-exception-2.0",
+    ),
+    (
+        "GPL-2.0-with-font-exception",
+        "GPL-2.0-only WITH Font-exception-2.0",
+    ),
+    (
+        "GPL-2.0-with-GCC-exception",
+        "GPL-2.0-only WITH GCC-exception-2.0",
+    ),
+    ("GPL-3.0", "GPL-3.0-or-later"),
+    ("GPL-3.0+", "GPL-3.0-or-later"),
+    (
+        "GPL-3.0-with-autoconf-exception",
+        "GPL-3.0-only WITH Autoconf-exception-3.0",
+    ),
+    (
+        "GPL-3.0-with-GCC-exception",
+        "GPL-3.0-only WITH GCC-exception-3.1",
+    ),
+    ("LGPL-2.0", "LGPL-2.0-or-later"),
+    ("LGPL-2.0+", "LGPL-2.0-or-later"),
+    ("LGPL-2.1", "LGPL-2.1-or-later"),
+    ("LGPL-2.1+", "LGPL-2.1-or-later"),
+    ("LGPL-3.0", "LGPL-3.0-or-later"),
+    ("LGPL-3.0+", "LGPL-3.0-or-later"),
+    ("Nunit", "zlib-acknowledgement"),
+    ("StandardML-NJ", "SMLNJ"),
+    ("wxWindows", "GPL-2.0-or-later WITH WxWindows-exception-3.1"),
+];
+
+/// Replacement for a single deprecated identifier (case-insensitive)
+fn replacement_for(id: &str) -> Option<&'static str> {
+    DEPRECATED_LICENSES
+        .iter()
+        .find(|(deprecated, _)| deprecated.eq_ignore_ascii_case(id.trim()))
+        .map(|(_, replacement)| *replacement)
+}
+
+/// Rewrite deprecated identifiers in an expression, returning whether anything changed
+fn replace_deprecated(expr: &mut LicenseExpr) -> bool {
+    match expr {
+        LicenseExpr::Id(id) => match replacement_for(id) {
+            Some(replacement) => {
+                *id = replacement.to_string();
+                true
+            }
+            None => false,
+        },
+        LicenseExpr::And(left, right) | LicenseExpr::Or(left, right) => {
+            let left_changed = replace_deprecated(left);
+            let right_changed = replace_deprecated(right);
+            left_changed || right_changed
+        }
+        LicenseExpr::With(license, _) => replace_deprecated(license),
+    }
+}
+
+/// Suggested current SPDX expression if the license uses a deprecated identifier
+///
+/// Identifiers are replaced inside compound expressions too, so `GPL-2.0 OR MIT`
+/// suggests `GPL-2.0-or-later OR MIT`. Returns `None` for current identifiers.
+pub fn suggest_replacement(license: &str) -> Option<String> {
+    match expression::parse(license) {
+        Ok(mut expr) => replace_deprecated(&mut expr).then(|| expr.to_string()),
+        Err(_) => replacement_for(license).map(str::to_string),
+    }
+}
+
+/// Annotate dependencies that declare a deprecated identifier, warning once per dependency
+pub fn annotate_deprecated_licenses(licenses: &mut [LicenseInfo]) {
+    for info in licenses.iter_mut() {
+        let Some(license) = info.license.as_deref() else {
+            continue;
+        };
+        info.license_deprecated = suggest_replacement(license);
+
+        if let Some(replacement) = &info.license_deprecated {
+            log(
+                LogLevel::Warn,
+                &format!(
+                    "{}@{} declares deprecated SPDX license '{license}', consider '{replacement}'",
+                    info.name, info.version
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::OsiStatus;
+
+    #[test]
+    fn test_suggest_replacement_for_deprecated_identifier() {
+        assert_eq!(
+            suggest_replacement("GPL-3.0"),
+            Some("GPL-3.0-or-later".to_string())
+        );
+        assert_eq!(
+            suggest_replacement("lgpl-2.1+"),
+            Some("LGPL-2.1-or-later".to_string())
+        );
+        assert_eq!(
+            suggest_replacement("GPL-2.0-with-classpath-exception"),
+            Some("GPL-2.0-only WITH Classpath-exception-2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_suggest_replacement_for_current_identifier() {
+        assert_eq!(suggest_replacement("GPL-3.0-or-later"), None);
+        assert_eq!(suggest_replacement("MIT"), None);
+        assert_eq!(suggest_replacement("MIT OR Apache-2.0"), None);
+        assert_eq!(suggest_replacement("Unknown license for foo"), None);
+    }
+
+    #[test]
+    fn test_suggest_replacement_inside_expression() {
+        assert_eq!(
+            suggest_replacement("GPL-2.0 OR MIT"),
+            Some("GPL-2.0-or-later OR MIT".to_string())
+        );
+    }
+
+    #[test]
+    fn test_annotate_keeps_declared_license() {
+        let mut licenses = vec![LicenseInfo {
+            is_restrictive: true,
+            osi_status: OsiStatus::Approved,
+            ..LicenseInfo::new("readline", "8.0.0", Some("GPL-3.0".to_string()))
+        }];
+
+        annotate_deprecated_licenses(&mut licenses);
+
+        assert_eq!(licenses[0].license.as_deref(), Some("GPL-3.0"));
+        assert_eq!(
+            licenses[0].license_deprecated.as_deref(),
+            Some("GPL-3.0-or-later")
+        );
+    }
+}
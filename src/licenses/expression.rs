@@ -0,0 +1,309 @@
+//! SPDX license expression parsing
+//!
+//! Parses expressions such as `MIT OR Apache-2.0`, `(MIT OR Apache-2.0) AND BSD-3-Clause`
+//! or `GPL-2.0-only WITH Classpath-exception-2.0` into a small AST.
+//!
+//! Operator precedence follows the SPDX specification: `WITH` binds tighter than `AND`,
+//! which binds tighter than `OR`. Parentheses can be used to override precedence.
+//! Operators are matched case-insensitively since many registries use lowercase `or`/`and`.
+
+use crate::debug::{FeludaError, FeludaResult};
+
+/// Parsed SPDX license expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpr {
+    /// A single license identifier (e.g. `MIT`, `GPL-3.0-or-later`)
+    Id(String),
+    /// Both sides apply (`MIT AND BSD-3-Clause`)
+    And(Box<LicenseExpr>, Box<LicenseExpr>),
+    /// Either side may be chosen (`MIT OR Apache-2.0`)
+    Or(Box<LicenseExpr>, Box<LicenseExpr>),
+    /// A license with an exception (`GPL-2.0-only WITH Classpath-exception-2.0`)
+    With(Box<LicenseExpr>, String),
+}
+
+impl LicenseExpr {
+    /// Returns true if the expression contains more than a single identifier
+    pub fn is_compound(&self) -> bool {
+        !matches!(self, Self::Id(_))
+    }
+
+    /// Returns true if an `OR` anywhere in the expression leaves a choice of license
+    pub fn offers_choice(&self) -> bool {
+        match self {
+            Self::Id(_) => false,
+            Self::Or(..) => true,
+            Self::And(left, right) => left.offers_choice() || right.offers_choice(),
+            Self::With(license, _) => license.offers_choice(),
+        }
+    }
+}
+
+impl std::fmt::Display for LicenseExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{id}"),
+            Self::And(left, right) => {
+                write_operand(f, left, matches!(**left, Self::Or(..)))?;
+                write!(f, " AND ")?;
+                write_operand(f, right, matches!(**right, Self::Or(..)))
+            }
+            Self::Or(left, right) => write!(f, "{left} OR {right}"),
+            Self::With(license, exception) => {
+                write_operand(f, license, license.is_compound())?;
+                write!(f, " WITH {exception}")
+            }
+        }
+    }
+}
+
+fn write_operand(
+    f: &mut std::fmt::Formatter<'_>,
+    expr: &LicenseExpr,
+    parenthesize: bool,
+) -> std::fmt::Result {
+    if parenthesize {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Ident(String),
+}
+
+fn tokenize(input: &str) -> FeludaResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                let token = match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "WITH" => Token::With,
+                    _ => Token::Ident(word),
+                };
+                tokens.push(token);
+            }
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(FeludaError::License("Empty license expression".to_string()));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> FeludaResult<LicenseExpr> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = LicenseExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> FeludaResult<LicenseExpr> {
+        let mut left = self.parse_with()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            let right = self.parse_with()?;
+            left = LicenseExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_with(&mut self) -> FeludaResult<LicenseExpr> {
+        let license = self.parse_primary()?;
+        if self.peek() == Some(&Token::With) {
+            self.next();
+            match self.next() {
+                Some(Token::Ident(exception)) => {
+                    return Ok(LicenseExpr::With(Box::new(license), exception));
+                }
+                other => {
+                    return Err(FeludaError::License(format!(
+                        "Expected exception identifier after WITH, found {other:?}"
+                    )));
+                }
+            }
+        }
+        Ok(license)
+    }
+
+    fn parse_primary(&mut self) -> FeludaResult<LicenseExpr> {
+        match self.next() {
+            Some(Token::Ident(id)) => Ok(LicenseExpr::Id(id)),
+            Some(Token::Open) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    other => Err(FeludaError::License(format!(
+                        "Expected closing parenthesis, found {other:?}"
+                    ))),
+                }
+            }
+            other => Err(FeludaError::License(format!(
+                "Expected license identifier or '(', found {other:?}"
+            ))),
+        }
+    }
+}
+
+/// Parse an SPDX license expression into a [`LicenseExpr`]
+pub fn parse(input: &str) -> FeludaResult<LicenseExpr> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+
+    if let Some(token) = parser.peek() {
+        return Err(FeludaError::License(format!(
+            "Unexpected token {token:?} in license expression '{input}'"
+        )));
+    }
+
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(s: &str) -> Box<LicenseExpr> {
+        Box::new(LicenseExpr::Id(s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_single_identifier() {
+        assert_eq!(parse("MIT").unwrap(), LicenseExpr::Id("MIT".to_string()));
+        assert!(!parse("MIT").unwrap().is_compound());
+    }
+
+    #[test]
+    fn test_parse_or() {
+        assert_eq!(
+            parse("MIT OR GPL-3.0").unwrap(),
+            LicenseExpr::Or(id("MIT"), id("GPL-3.0"))
+        );
+    }
+
+    #[test]
+    fn test_parse_parenthesized_and() {
+        assert_eq!(
+            parse("(MIT OR Apache-2.0) AND BSD-3-Clause").unwrap(),
+            LicenseExpr::And(
+                Box::new(LicenseExpr::Or(id("MIT"), id("Apache-2.0"))),
+                id("BSD-3-Clause")
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_with_exception() {
+        assert_eq!(
+            parse("GPL-2.0-only WITH Classpath-exception-2.0").unwrap(),
+            LicenseExpr::With(id("GPL-2.0-only"), "Classpath-exception-2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        // AND binds tighter than OR
+        assert_eq!(
+            parse("MIT OR Apache-2.0 AND BSD-3-Clause").unwrap(),
+            LicenseExpr::Or(
+                id("MIT"),
+                Box::new(LicenseExpr::And(id("Apache-2.0"), id("BSD-3-Clause")))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_lowercase_operators() {
+        assert_eq!(
+            parse("mit or apache-2.0").unwrap(),
+            LicenseExpr::Or(id("mit"), id("apache-2.0"))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("MIT OR").is_err());
+        assert!(parse("(MIT OR Apache-2.0").is_err());
+        assert!(parse("MIT Apache-2.0").is_err());
+        assert!(parse("MIT WITH").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for input in [
+            "MIT",
+            "MIT OR GPL-3.0",
+            "(MIT OR Apache-2.0) AND BSD-3-Clause",
+            "GPL-2.0-only WITH Classpath-exception-2.0",
+        ] {
+            assert_eq!(parse(input).unwrap().to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_offers_choice() {
+        assert!(!parse("MIT").unwrap().offers_choice());
+        assert!(parse("MIT OR Apache-2.0").unwrap().offers_choice());
+        assert!(!parse("MIT AND Apache-2.0").unwrap().offers_choice());
+        assert!(parse("BSD-3-Clause AND (MIT OR Apache-2.0)")
+            .unwrap()
+            .offers_choice());
+        assert!(parse("(GPL-2.0-only OR MIT) WITH Classpath-exception-2.0")
+            .unwrap()
+            .offers_choice());
+    }
+}
@@ -0,0 +1,312 @@
+//! License identification from the raw text of a `LICENSE`/`COPYING` file
+//!
+//! Both the file and a set of license templates are reduced to lowercase word bigrams, so
+//! line wrapping, punctuation, case and Markdown decoration do not matter. A template
+//! matches when at least [`MIN_CONFIDENCE`] of its bigrams appear in the file. Short
+//! permissive licenses use their full text (minus the copyright line); long licenses use
+//! distinctive passages, since only those need to be present to tell them apart.
+//!
+//! When several templates match (a BSD-3-Clause text also contains all of BSD-2-Clause),
+//! the one with the most matched bigrams wins, which is the more specific license. If a
+//! match is not explained by the winner's own text, the file bundles several licenses
+//! (a third-party notice file, for example) and no single identifier is returned.
+
+use std::collections::HashSet;
+
+/// Share of a template's bigrams that must appear in the text to accept a match
+pub const MIN_CONFIDENCE: f64 = 0.9;
+
+const MIT: &str = r#"Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE."#;
+
+const ISC: &str = r#"Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted, provided that the above
+copyright notice and this permission notice appear in all copies.
+
+THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE."#;
+
+const ZERO_BSD: &str = r#"Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted.
+
+THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE."#;
+
+const BSD_2_CLAUSE: &str = r#"Redistribution and use in source and binary forms, with or without
+modification, are permitted provided that the following conditions are met:
+
+1. Redistributions of source code must retain the above copyright notice, this
+   list of conditions and the following disclaimer.
+
+2. Redistributions in binary form must reproduce the above copyright notice,
+   this list of conditions and the following disclaimer in the documentation
+   and/or other materials provided with the distribution.
+
+THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE."#;
+
+const BSD_3_CLAUSE: &str = r#"Redistribution and use in source and binary forms, with or without
+modification, are permitted provided that the following conditions are met:
+
+1. Redistributions of source code must retain the above copyright notice, this
+   list of conditions and the following disclaimer.
+
+2. Redistributions in binary form must reproduce the above copyright notice,
+   this list of conditions and the following disclaimer in the documentation
+   and/or other materials provided with the distribution.
+
+3. Neither the name of the copyright holder nor the names of its
+   contributors may be used to endorse or promote products derived from
+   this software without specific prior written permission.
+
+THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE."#;
+
+const APACHE_2_0: &str = r#"Apache License Version 2.0, January 2004
+
+TERMS AND CONDITIONS FOR USE, REPRODUCTION, AND DISTRIBUTION
+
+1. Definitions.
+
+"License" shall mean the terms and conditions for use, reproduction,
+and distribution as defined by Sections 1 through 9 of this document.
+
+"Licensor" shall mean the copyright owner or entity authorized by
+the copyright owner that is granting the License.
+
+2. Grant of Copyright License. Subject to the terms and conditions of
+this License, each Contributor hereby grants to You a perpetual,
+worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+copyright license to reproduce, prepare Derivative Works of,
+publicly display, publicly perform, sublicense, and distribute the
+Work and such Derivative Works in Source or Object form."#;
+
+const MPL_2_0: &str = r#"Mozilla Public License Version 2.0
+
+1. Definitions
+
+1.1. "Contributor" means each individual or legal entity that creates,
+contributes to the creation of, or owns Covered Software.
+
+1.2. "Contributor Version" means the combination of the Contributions of
+others (if any) used by a Contributor and that particular Contributor's
+Contribution.
+
+1.3. "Contribution" means Covered Software of a particular Contributor."#;
+
+const GPL_2_0: &str = r#"GNU GENERAL PUBLIC LICENSE Version 2, June 1991
+
+The licenses for most software are designed to take away your freedom to
+share and change it. By contrast, the GNU General Public License is intended
+to guarantee your freedom to share and change free software--to make sure
+the software is free for all its users.
+
+0. This License applies to any program or other work which contains a
+notice placed by the copyright holder saying it may be distributed under
+the terms of this General Public License."#;
+
+const LGPL_2_1: &str = r#"GNU LESSER GENERAL PUBLIC LICENSE Version 2.1, February 1999
+
+This license, the Lesser General Public License, applies to some
+specially designated software packages--typically libraries--of the
+Free Software Foundation and other authors who decide to use it."#;
+
+const GPL_3_0: &str = r#"GNU GENERAL PUBLIC LICENSE Version 3, 29 June 2007
+
+The GNU General Public License is a free, copyleft license for software
+and other kinds of works.
+
+By contrast, the GNU General Public License is intended to guarantee your
+freedom to share and change all versions of a program--to make sure it
+remains free software for all its users. We, the Free Software Foundation,
+use the GNU General Public License for most of our software.
+
+"This License" refers to version 3 of the GNU General Public License."#;
+
+const LGPL_3_0: &str = r#"GNU LESSER GENERAL PUBLIC LICENSE Version 3, 29 June 2007
+
+This version of the GNU Lesser General Public License incorporates the
+terms and conditions of version 3 of the GNU General Public License,
+supplemented by the additional permissions listed below."#;
+
+const AGPL_3_0: &str = r#"GNU AFFERO GENERAL PUBLIC LICENSE Version 3, 19 November 2007
+
+The GNU Affero General Public License is a free, copyleft license for
+software and other kinds of works, specifically designed to ensure
+cooperation with the community in the case of network server software.
+
+"This License" refers to version 3 of the GNU Affero General Public License."#;
+
+/// SPDX identifier -> template text
+const LICENSE_TEMPLATES: &[(&str, &str)] = &[
+    ("MIT", MIT),
+    ("ISC", ISC),
+    ("0BSD", ZERO_BSD),
+    ("BSD-2-Clause", BSD_2_CLAUSE),
+    ("BSD-3-Clause", BSD_3_CLAUSE),
+    ("Apache-2.0", APACHE_2_0),
+    ("MPL-2.0", MPL_2_0),
+    ("GPL-2.0-only", GPL_2_0),
+    ("LGPL-2.1-only", LGPL_2_1),
+    ("GPL-3.0-only", GPL_3_0),
+    ("LGPL-3.0-only", LGPL_3_0),
+    ("AGPL-3.0-only", AGPL_3_0),
+];
+
+type Bigrams = HashSet<(String, String)>;
+
+/// Lowercase word bigrams of a text, ignoring punctuation and layout
+fn bigrams(text: &str) -> Bigrams {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    words
+        .windows(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect()
+}
+
+/// Best-matching SPDX identifier for a license text, or `None` if no template matches confidently
+pub fn identify_license_text(content: &str) -> Option<&'static str> {
+    let text = bigrams(content);
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut matches: Vec<(&'static str, Bigrams, usize)> = LICENSE_TEMPLATES
+        .iter()
+        .filter_map(|(id, template)| {
+            let template = bigrams(template);
+            let matched = template.intersection(&text).count();
+            (confidence(matched, &template) >= MIN_CONFIDENCE).then_some((*id, template, matched))
+        })
+        .collect();
+    matches.sort_by_key(|(_, _, matched)| std::cmp::Reverse(*matched));
+
+    let ((id, best, _), others) = matches.split_first()?;
+    let ambiguous = others.iter().any(|(_, template, _)| {
+        confidence(template.intersection(best).count(), template) < MIN_CONFIDENCE
+    });
+    (!ambiguous).then_some(*id)
+}
+
+/// Share of a template's bigrams covered by `matched`
+fn confidence(matched: usize, template: &Bigrams) -> f64 {
+    matched as f64 / template.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identify_verbatim_mit() {
+        let text = include_str!("../../LICENSE");
+        assert_eq!(identify_license_text(text), Some("MIT"));
+    }
+
+    #[test]
+    fn test_identify_reflowed_apache() {
+        // Different wrapping, indentation and case must not matter
+        let text = format!(
+            "                              Apache License\n{}\n\nEND OF TERMS AND CONDITIONS",
+            APACHE_2_0
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .chunks(7)
+                .map(|line| format!("   {}", line.join(" ")))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .to_uppercase()
+        );
+        assert_eq!(identify_license_text(&text), Some("Apache-2.0"));
+    }
+
+    #[test]
+    fn test_identify_prefers_more_specific_bsd() {
+        let bsd3 = format!("Copyright (c) 2024, Example Corp\n\n{BSD_3_CLAUSE}");
+        assert_eq!(identify_license_text(&bsd3), Some("BSD-3-Clause"));
+
+        let bsd2 = format!("Copyright (c) 2024, Example Corp\n\n{BSD_2_CLAUSE}");
+        assert_eq!(identify_license_text(&bsd2), Some("BSD-2-Clause"));
+    }
+
+    #[test]
+    fn test_identify_distinguishes_similar_permissive_licenses() {
+        assert_eq!(identify_license_text(ISC), Some("ISC"));
+        assert_eq!(identify_license_text(ZERO_BSD), Some("0BSD"));
+    }
+
+    #[test]
+    fn test_identify_gnu_family() {
+        assert_eq!(identify_license_text(GPL_3_0), Some("GPL-3.0-only"));
+        assert_eq!(identify_license_text(AGPL_3_0), Some("AGPL-3.0-only"));
+        assert_eq!(identify_license_text(LGPL_3_0), Some("LGPL-3.0-only"));
+        assert_eq!(identify_license_text(GPL_2_0), Some("GPL-2.0-only"));
+        assert_eq!(identify_license_text(LGPL_2_1), Some("LGPL-2.1-only"));
+    }
+
+    #[test]
+    fn test_identify_rejects_bundled_licenses() {
+        let notice = format!("Copyright (c) Foo\n\n{MIT}\n\n---\n\n{APACHE_2_0}");
+        assert_eq!(identify_license_text(&notice), None);
+    }
+
+    #[test]
+    fn test_identify_rejects_unrelated_text() {
+        assert_eq!(
+            identify_license_text("lorem ipsum dolor sit amet, consectetur adipiscing elit"),
+            None
+        );
+        assert_eq!(identify_license_text(""), None);
+        // A partial MIT text is not a confident match
+        assert_eq!(
+            identify_license_text("Permission is hereby granted, free of charge"),
+            None
+        );
+    }
+}
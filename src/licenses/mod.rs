@@ -1,10 +1,18 @@
 //! Core license analysis functionality and types
 
+pub mod aliases;
+pub mod category;
+pub mod copyright;
+pub mod deprecations;
+pub mod expression;
+pub mod fingerprint;
+pub mod overrides;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -14,7 +22,8 @@ use toml::Value as TomlValue;
 use crate::cache;
 use crate::cli;
 use crate::config;
-use crate::debug::{log, log_debug, log_error, FeludaResult, LogLevel};
+use crate::debug::{log, log_debug, log_error, FeludaError, FeludaResult, LogLevel};
+use crate::network::{async_http_client_builder, http_client, skip_network, with_network_permit};
 
 static GITHUB_TOKEN: OnceLock<Option<String>> = OnceLock::new();
 
@@ -23,9 +32,80 @@ pub fn set_github_token(token: Option<String>) {
     let _ = GITHUB_TOKEN.set(token);
 }
 
-/// Get the GitHub API token if set
-fn get_github_token() -> Option<&'static str> {
-    GITHUB_TOKEN.get().and_then(|t| t.as_deref())
+/// Get the GitHub API token: `--github-token`/`FELUDA_GITHUB_TOKEN` first, then `GITHUB_TOKEN`
+fn get_github_token() -> Option<String> {
+    let non_empty = |token: String| (!token.trim().is_empty()).then(|| token.trim().to_string());
+
+    GITHUB_TOKEN
+        .get()
+        .cloned()
+        .flatten()
+        .and_then(non_empty)
+        .or_else(|| {
+            std::env::var("FELUDA_GITHUB_TOKEN")
+                .ok()
+                .and_then(non_empty)
+        })
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok().and_then(non_empty))
+}
+
+/// Whether a GitHub API token is configured from any source
+pub fn has_github_token() -> bool {
+    get_github_token().is_some()
+}
+
+/// `Authorization` header for GitHub API requests, or `None` when no token is configured
+pub fn github_auth_header() -> Option<reqwest::header::HeaderValue> {
+    let Some(token) = get_github_token() else {
+        log(
+            LogLevel::Info,
+            "No GitHub token set, using unauthenticated GitHub API requests (60 requests/hour)",
+        );
+        return None;
+    };
+
+    match reqwest::header::HeaderValue::from_str(&format!("Bearer {token}")) {
+        Ok(mut value) => {
+            value.set_sensitive(true);
+            log(
+                LogLevel::Info,
+                "Using authenticated GitHub API requests (higher rate limits)",
+            );
+            Some(value)
+        }
+        Err(_) => {
+            log(
+                LogLevel::Warn,
+                "Ignoring GitHub token containing invalid header characters",
+            );
+            None
+        }
+    }
+}
+
+/// SPDX identifier GitHub detected for a repository's license
+pub fn fetch_github_repository_license(owner: &str, repo: &str) -> Option<String> {
+    let url = format!("https://api.github.com/repos/{owner}/{repo}/license");
+    with_network_permit(|| {
+        let mut request = http_client()?.get(&url);
+        if let Some(auth) = github_auth_header() {
+            request = request.header(reqwest::header::AUTHORIZATION, auth);
+        }
+        request.send()
+    })
+    .ok()
+    .filter(|response| response.status().is_success())
+    .and_then(|response| response.json::<Value>().ok())
+    .and_then(|json| github_license_spdx_id(&json))
+}
+
+/// SPDX identifier from a GitHub `/repos/{owner}/{repo}/license` response
+pub(crate) fn github_license_spdx_id(json: &Value) -> Option<String> {
+    json.get("license")?
+        .get("spdx_id")?
+        .as_str()
+        .filter(|id| !id.is_empty() && *id != "NOASSERTION")
+        .map(str::to_string)
 }
 
 /// License compatibility enum
@@ -39,9 +119,24 @@ pub enum LicenseCompatibility {
 impl std::fmt::Display for LicenseCompatibility {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Compatible => write!(f, "Compatible"),
-            Self::Incompatible => write!(f, "Incompatible"),
-            Self::Unknown => write!(f, "Unknown"),
+            Self::Compatible => write!(f, "compatible"),
+            Self::Incompatible => write!(f, "incompatible"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl std::str::FromStr for LicenseCompatibility {
+    type Err = FeludaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "compatible" => Ok(Self::Compatible),
+            "incompatible" => Ok(Self::Incompatible),
+            "unknown" => Ok(Self::Unknown),
+            other => Err(FeludaError::License(format!(
+                "Invalid license compatibility '{other}'. Expected compatible, incompatible or unknown"
+            ))),
         }
     }
 }
@@ -115,7 +210,7 @@ pub struct OsiLicenseInfo {
 }
 
 /// License Info of dependencies
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LicenseInfo {
     pub name: String,                        // The name of the software or library
     pub version: String,                     // The version of the software or library
@@ -123,9 +218,58 @@ pub struct LicenseInfo {
     pub is_restrictive: bool,    // A boolean indicating whether the license is restrictive or not
     pub compatibility: LicenseCompatibility, // Compatibility with project license
     pub osi_status: OsiStatus,   // OSI approval status
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>, // Names of direct dependencies, when the ecosystem reports them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_deprecated: Option<String>, // Suggested replacement when the license is a deprecated SPDX identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_direct: Option<bool>, // Whether the manifest declares the dependency directly, when the ecosystem records it
+    #[serde(default)]
+    pub ecosystem: String, // Package ecosystem the dependency comes from, e.g. `cargo` or `npm`
+    #[serde(default = "default_occurrences")]
+    pub occurrences: usize, // How many times the analyzers reported this dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>, // Copyright statement from the license file or author metadata
+}
+
+fn default_occurrences() -> usize {
+    1
+}
+
+impl Default for LicenseInfo {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            version: String::new(),
+            license: None,
+            is_restrictive: false,
+            compatibility: LicenseCompatibility::Unknown,
+            osi_status: OsiStatus::Unknown,
+            dependencies: Vec::new(),
+            license_deprecated: None,
+            is_direct: None,
+            ecosystem: String::new(),
+            occurrences: default_occurrences(),
+            copyright: None,
+        }
+    }
 }
 
 impl LicenseInfo {
+    /// Dependency `name@version` under `license`, every other field at its default
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        license: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            license,
+            ..Self::default()
+        }
+    }
+
     pub fn get_license(&self) -> String {
         match &self.license {
             Some(license_name) => String::from(license_name),
@@ -153,6 +297,34 @@ impl LicenseInfo {
         &self.osi_status
     }
 
+    /// Returns true if no license was found or the analyzer could not resolve it
+    pub fn has_unknown_license(&self) -> bool {
+        match self.license.as_deref() {
+            None => true,
+            Some(license) => {
+                let license = license.trim();
+                license.is_empty() || license.starts_with("Unknown") || license == "No License"
+            }
+        }
+    }
+
+    /// License family (permissive, weak or strong copyleft, ...) of the declared license
+    pub fn category(&self) -> category::LicenseCategory {
+        match &self.license {
+            Some(license) => category::license_category(license),
+            None => category::LicenseCategory::Unknown,
+        }
+    }
+
+    /// Returns true if the license is an SPDX expression with an `OR`, so one arm must be chosen
+    ///
+    /// Licenses that do not parse as an expression are treated as a single license.
+    pub fn is_multi_license(&self) -> bool {
+        expression::parse(&self.get_license())
+            .map(|expr| expr.offers_choice())
+            .unwrap_or(false)
+    }
+
     #[allow(dead_code)]
     pub fn osi_info(&self) -> Option<OsiLicenseInfo> {
         self.license.as_ref().map(|license| OsiLicenseInfo {
@@ -197,6 +369,10 @@ pub fn fetch_licenses_from_github() -> FeludaResult<HashMap<String, License>> {
         }
     }
 
+    if skip_network("GitHub Licenses API") {
+        return Ok(HashMap::new());
+    }
+
     let licenses_map = cli::with_spinner("Fetching licenses from GitHub API", |indicator| {
         // Use tokio runtime for async operations
         let rt = match tokio::runtime::Runtime::new() {
@@ -231,22 +407,11 @@ async fn fetch_licenses_concurrent(
     let mut licenses_map = HashMap::new();
 
     // Create async HTTP client with optional authentication
-    let mut client_builder = reqwest::Client::builder()
-        .user_agent("feluda-license-checker/1.0")
-        .timeout(Duration::from_secs(30));
+    let mut client_builder = async_http_client_builder();
 
-    if let Some(token) = get_github_token() {
-        log(
-            LogLevel::Info,
-            "Using authenticated GitHub API requests (higher rate limits)",
-        );
+    if let Some(auth) = github_auth_header() {
         let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            reqwest::header::AUTHORIZATION,
-            format!("Bearer {token}")
-                .parse()
-                .expect("Invalid token format"),
-        );
+        headers.insert(reqwest::header::AUTHORIZATION, auth);
         client_builder = client_builder.default_headers(headers);
     }
 
@@ -289,8 +454,8 @@ async fn fetch_licenses_concurrent(
     let total_licenses = licenses_list.len();
     indicator.update_progress(&format!("found {total_licenses} licenses"));
 
-    // Rate limiting: Allow max 10 concurrent requests (GitHub's recommended limit)
-    let semaphore = Arc::new(Semaphore::new(10));
+    // Rate limiting: bounded by --max-concurrency
+    let semaphore = Arc::new(Semaphore::new(crate::network::max_concurrency()));
     let client = Arc::new(client);
 
     // Collect all license keys
@@ -463,6 +628,10 @@ static OSI_LICENSES: OnceLock<HashMap<String, OsiStatus>> = OnceLock::new();
 pub fn fetch_osi_licenses() -> FeludaResult<HashMap<String, OsiStatus>> {
     log(LogLevel::Info, "Fetching OSI approved licenses");
 
+    if skip_network("OSI licenses API") {
+        return Ok(HashMap::new());
+    }
+
     let osi_map = cli::with_spinner("Fetching OSI approved licenses", |indicator| {
         // Use tokio runtime for async operations
         let rt = match tokio::runtime::Runtime::new() {
@@ -486,11 +655,7 @@ async fn fetch_osi_licenses_async(
     let mut osi_map = HashMap::new();
 
     // Create async HTTP client
-    let client = match reqwest::Client::builder()
-        .user_agent("feluda-license-checker/1.0")
-        .timeout(Duration::from_secs(30))
-        .build()
-    {
+    let client = match async_http_client_builder().build() {
         Ok(client) => client,
         Err(err) => {
             log_error("Failed to create HTTP client", &err);
@@ -633,6 +798,55 @@ pub fn is_license_restrictive(
         }
     };
 
+    is_license_restrictive_with_config(license, known_licenses, strict, &config)
+}
+
+/// Check if a license is restrictive, applying the `[restrictive]` overrides from the given config
+///
+/// The `additional` list is added on top of the normal determination and the `allow`
+/// list is subtracted afterwards, so `allow` wins when a license appears in both.
+pub fn is_license_restrictive_with_config(
+    license: &Option<String>,
+    known_licenses: &HashMap<String, License>,
+    strict: bool,
+    config: &config::FeludaConfig,
+) -> bool {
+    let is_restrictive = is_license_restrictive_by_default(license, known_licenses, strict, config);
+
+    let Some(license_str) = license else {
+        return is_restrictive;
+    };
+
+    if config.restrictive.is_allowed(license_str) {
+        if is_restrictive {
+            log(
+                LogLevel::Info,
+                &format!("License {license_str} is allowlisted in config, not restrictive"),
+            );
+        }
+        return false;
+    }
+
+    if config.restrictive.is_additional(license_str) {
+        if !is_restrictive {
+            log(
+                LogLevel::Warn,
+                &format!("License {license_str} is marked restrictive in config"),
+            );
+        }
+        return true;
+    }
+
+    is_restrictive
+}
+
+/// Restrictiveness based on the GitHub license conditions and the `[licenses]` config
+fn is_license_restrictive_by_default(
+    license: &Option<String>,
+    known_licenses: &HashMap<String, License>,
+    strict: bool,
+    config: &config::FeludaConfig,
+) -> bool {
     if license.as_deref() == Some("No License") {
         log(
             LogLevel::Warn,
@@ -771,7 +985,7 @@ pub fn is_license_ignored(license: Option<&str>) -> bool {
 
 /// This is the default configuration
 const EMBEDDED_LICENSE_COMPATIBILITY_TOML: &str =
-    include_str!("../config/license_compatibility.toml");
+    include_str!("../../config/license_compatibility.toml");
 
 /// Load license compatibility matrix from external TOML file if available
 /// Looks for the file in the following order:
@@ -916,11 +1130,22 @@ fn get_compatibility_matrix() -> &'static HashMap<String, Vec<String>> {
     }
 }
 
-/// Check if a license is compatible with the base project license
-pub fn is_license_compatible(
+/// Check if a license is compatible with the base project license, using the
+/// `[compatibility]` overrides from the given config
+///
+/// SPDX expressions are evaluated per branch: an `OR` expression is compatible if any
+/// branch is compatible, an `AND` expression only if every branch is. License exceptions
+/// (`WITH`) are evaluated against the underlying license.
+///
+/// Precedence:
+/// 1. A `[compatibility]` entry for the project license in `.feluda.toml`
+/// 2. `.feluda/license_compatibility.toml`
+/// 3. The embedded compatibility matrix
+pub fn is_license_compatible_with_config(
     dependency_license: &str,
     project_license: &str,
     strict: bool,
+    config: &config::FeludaConfig,
 ) -> LicenseCompatibility {
     log(
         LogLevel::Info,
@@ -929,6 +1154,77 @@ pub fn is_license_compatible(
         ),
     );
 
+    match expression::parse(dependency_license) {
+        Ok(expr) if expr.is_compound() => {
+            log(
+                LogLevel::Info,
+                &format!("Evaluating SPDX expression: {expr}"),
+            );
+            is_expression_compatible(&expr, project_license, strict, config)
+        }
+        Ok(_) => is_single_license_compatible(dependency_license, project_license, strict, config),
+        Err(e) => {
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Could not parse {dependency_license} as an SPDX expression ({e}), treating as a single license"
+                ),
+            );
+            is_single_license_compatible(dependency_license, project_license, strict, config)
+        }
+    }
+}
+
+/// Evaluate a parsed SPDX expression against the project license
+fn is_expression_compatible(
+    expr: &expression::LicenseExpr,
+    project_license: &str,
+    strict: bool,
+    config: &config::FeludaConfig,
+) -> LicenseCompatibility {
+    use expression::LicenseExpr;
+
+    match expr {
+        LicenseExpr::Id(id) => is_single_license_compatible(id, project_license, strict, config),
+        LicenseExpr::With(license, _exception) => {
+            is_expression_compatible(license, project_license, strict, config)
+        }
+        LicenseExpr::Or(left, right) => {
+            let results = [
+                is_expression_compatible(left, project_license, strict, config),
+                is_expression_compatible(right, project_license, strict, config),
+            ];
+            if results.contains(&LicenseCompatibility::Compatible) {
+                LicenseCompatibility::Compatible
+            } else if results.contains(&LicenseCompatibility::Unknown) {
+                LicenseCompatibility::Unknown
+            } else {
+                LicenseCompatibility::Incompatible
+            }
+        }
+        LicenseExpr::And(left, right) => {
+            let results = [
+                is_expression_compatible(left, project_license, strict, config),
+                is_expression_compatible(right, project_license, strict, config),
+            ];
+            if results.contains(&LicenseCompatibility::Incompatible) {
+                LicenseCompatibility::Incompatible
+            } else if results.contains(&LicenseCompatibility::Unknown) {
+                LicenseCompatibility::Unknown
+            } else {
+                LicenseCompatibility::Compatible
+            }
+        }
+    }
+}
+
+/// Check a single license identifier against the compatibility matrix
+fn is_single_license_compatible(
+    dependency_license: &str,
+    project_license: &str,
+    strict: bool,
+    config: &config::FeludaConfig,
+) -> LicenseCompatibility {
     let compatibility_matrix = get_compatibility_matrix();
     let norm_dependency_license = normalize_license_id(dependency_license);
     let norm_project_license = normalize_license_id(project_license);
@@ -940,6 +1236,49 @@ pub fn is_license_compatible(
         ),
     );
 
+    // User-defined allowlists from the config take precedence over the built-in matrix
+    if let Some(allowed) = config
+        .compatibility
+        .iter()
+        .find(|(key, _)| normalize_license_id(key) == norm_project_license)
+        .map(|(_, allowed)| allowed)
+    {
+        if allowed
+            .iter()
+            .any(|license| normalize_license_id(license) == norm_dependency_license)
+        {
+            log(
+                LogLevel::Info,
+                &format!(
+                    "License {norm_dependency_license} is allowed for project license {norm_project_license} by config"
+                ),
+            );
+            return LicenseCompatibility::Compatible;
+        }
+
+        let is_known_license = compatibility_matrix.iter().any(|(key, licenses)| {
+            *key == norm_dependency_license || licenses.contains(&norm_dependency_license)
+        });
+
+        return if is_known_license || strict {
+            log(
+                LogLevel::Warn,
+                &format!(
+                    "License {norm_dependency_license} is not in the configured allowlist for project license {norm_project_license}"
+                ),
+            );
+            LicenseCompatibility::Incompatible
+        } else {
+            log(
+                LogLevel::Warn,
+                &format!(
+                    "Unknown license {norm_dependency_license} is not in the configured allowlist for project license {norm_project_license}"
+                ),
+            );
+            LicenseCompatibility::Unknown
+        };
+    }
+
     match compatibility_matrix.get(&norm_project_license) {
         Some(compatible_licenses) => {
             if compatible_licenses.contains(&norm_dependency_license) {
@@ -979,7 +1318,7 @@ pub fn is_license_compatible(
 }
 
 /// Normalize license identifier to a standard format
-fn normalize_license_id(license_id: &str) -> String {
+pub fn normalize_license_id(license_id: &str) -> String {
     let trimmed = license_id.trim().to_uppercase();
 
     // Handle common variations and aliases
@@ -1028,92 +1367,15 @@ fn normalize_license_id(license_id: &str) -> String {
 }
 
 /// Detect the project's license
+///
+/// Declared metadata (`package.json`, `Cargo.toml`, `pyproject.toml`) is preferred; the
+/// text of a `LICENSE`/`COPYING` file is only used when no metadata declares a license.
 pub fn detect_project_license(project_path: &str) -> FeludaResult<Option<String>> {
     log(
         LogLevel::Info,
         &format!("Detecting license for project at path: {project_path}"),
     );
 
-    // Check LICENSE file
-    let license_paths = [
-        Path::new(project_path).join("LICENSE"),
-        Path::new(project_path).join("LICENSE.txt"),
-        Path::new(project_path).join("LICENSE.md"),
-        Path::new(project_path).join("license"),
-        Path::new(project_path).join("COPYING"),
-    ];
-
-    for license_path in &license_paths {
-        if license_path.exists() {
-            log(
-                LogLevel::Info,
-                &format!("Found license file: {}", license_path.display()),
-            );
-
-            match fs::read_to_string(license_path) {
-                Ok(content) => {
-                    // Check for MIT license
-                    if content.contains("MIT License")
-                        || content.contains("Permission is hereby granted, free of charge")
-                    {
-                        log(LogLevel::Info, "Detected MIT license");
-                        return Ok(Some("MIT".to_string()));
-                    }
-
-                    // Check for GPL-3.0
-                    if content.contains("GNU GENERAL PUBLIC LICENSE")
-                        && content.contains("Version 3")
-                    {
-                        log(LogLevel::Info, "Detected GPL-3.0 license");
-                        return Ok(Some("GPL-3.0".to_string()));
-                    }
-
-                    // Check for Apache-2.0
-                    if content.contains("Apache License") && content.contains("Version 2.0") {
-                        log(LogLevel::Info, "Detected Apache-2.0 license");
-                        return Ok(Some("Apache-2.0".to_string()));
-                    }
-
-                    // Check for BSD-3-Clause
-                    if content.contains("BSD")
-                        && content.contains("Redistribution and use")
-                        && content.contains("Neither the name")
-                    {
-                        log(LogLevel::Info, "Detected BSD-3-Clause license");
-                        return Ok(Some("BSD-3-Clause".to_string()));
-                    }
-
-                    // Check for LGPL-3.0
-                    if content.contains("GNU LESSER GENERAL PUBLIC LICENSE")
-                        && content.contains("Version 3")
-                    {
-                        log(LogLevel::Info, "Detected LGPL-3.0 license");
-                        return Ok(Some("LGPL-3.0".to_string()));
-                    }
-
-                    // Check for MPL-2.0
-                    if content.contains("Mozilla Public License") && content.contains("Version 2.0")
-                    {
-                        log(LogLevel::Info, "Detected MPL-2.0 license");
-                        return Ok(Some("MPL-2.0".to_string()));
-                    }
-
-                    log(
-                        LogLevel::Warn,
-                        "License file found but could not determine license type",
-                    );
-                }
-                Err(err) => {
-                    log(
-                        LogLevel::Error,
-                        &format!("Failed to read license file: {}", license_path.display()),
-                    );
-                    log_debug("Error details", &err);
-                }
-            }
-        }
-    }
-
     // Check package.json for Node.js projects
     let package_json_path = Path::new(project_path).join("package.json");
     if package_json_path.exists() {
@@ -1246,34 +1508,202 @@ pub fn detect_project_license(project_path: &str) -> FeludaResult<Option<String>
         }
     }
 
+    // Fall back to the text of a LICENSE/COPYING file
+    if let Some(license) = detect_license_from_files(project_path) {
+        return Ok(Some(license));
+    }
+
     log(LogLevel::Warn, "No license detected for project");
     Ok(None)
 }
 
+/// `LICENSE*`, `LICENCE*` and `COPYING*` files in a package directory, `LICENSE` files first
+pub fn license_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<(bool, String, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_uppercase();
+            let is_license = name.starts_with("LICENSE") || name.starts_with("LICENCE");
+            (is_license || name.starts_with("COPYING")).then(|| (!is_license, name, entry.path()))
+        })
+        .collect();
+    files.sort();
+
+    files.into_iter().map(|(_, _, path)| path).collect()
+}
+
+/// Detect the license from the text of a `LICENSE`/`COPYING` file in the project root
+///
+/// The text is matched against known license templates first, then against a few keywords.
+pub fn detect_license_from_files(project_path: &str) -> Option<String> {
+    let license_paths = [
+        Path::new(project_path).join("LICENSE"),
+        Path::new(project_path).join("LICENSE.txt"),
+        Path::new(project_path).join("LICENSE.md"),
+        Path::new(project_path).join("license"),
+        Path::new(project_path).join("COPYING"),
+    ];
+
+    for license_path in &license_paths {
+        if license_path.exists() {
+            log(
+                LogLevel::Info,
+                &format!("Found license file: {}", license_path.display()),
+            );
+
+            match fs::read_to_string(license_path) {
+                Ok(content) => {
+                    if let Some(license) = fingerprint::identify_license_text(&content) {
+                        log(
+                            LogLevel::Info,
+                            &format!("Detected {license} license from license text"),
+                        );
+                        return Some(license.to_string());
+                    }
+
+                    // Check for MIT license
+                    if content.contains("MIT License")
+                        || content.contains("Permission is hereby granted, free of charge")
+                    {
+                        log(LogLevel::Info, "Detected MIT license");
+                        return Some("MIT".to_string());
+                    }
+
+                    // Check for GPL-3.0
+                    if content.contains("GNU GENERAL PUBLIC LICENSE")
+                        && content.contains("Version 3")
+                    {
+                        log(LogLevel::Info, "Detected GPL-3.0 license");
+                        return Some("GPL-3.0".to_string());
+                    }
+
+                    // Check for Apache-2.0
+                    if content.contains("Apache License") && content.contains("Version 2.0") {
+                        log(LogLevel::Info, "Detected Apache-2.0 license");
+                        return Some("Apache-2.0".to_string());
+                    }
+
+                    // Check for BSD-3-Clause
+                    if content.contains("BSD")
+                        && content.contains("Redistribution and use")
+                        && content.contains("Neither the name")
+                    {
+                        log(LogLevel::Info, "Detected BSD-3-Clause license");
+                        return Some("BSD-3-Clause".to_string());
+                    }
+
+                    // Check for LGPL-3.0
+                    if content.contains("GNU LESSER GENERAL PUBLIC LICENSE")
+                        && content.contains("Version 3")
+                    {
+                        log(LogLevel::Info, "Detected LGPL-3.0 license");
+                        return Some("LGPL-3.0".to_string());
+                    }
+
+                    // Check for MPL-2.0
+                    if content.contains("Mozilla Public License") && content.contains("Version 2.0")
+                    {
+                        log(LogLevel::Info, "Detected MPL-2.0 license");
+                        return Some("MPL-2.0".to_string());
+                    }
+
+                    log(
+                        LogLevel::Warn,
+                        "License file found but could not determine license type",
+                    );
+                }
+                Err(err) => {
+                    log(
+                        LogLevel::Error,
+                        &format!("Failed to read license file: {}", license_path.display()),
+                    );
+                    log_debug("Error details", &err);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Test dependency `name@version` under `license`, shared by the test modules
+#[cfg(test)]
+pub fn test_dependency(name: &str, version: &str, license: &str) -> LicenseInfo {
+    LicenseInfo::new(name, version, Some(license.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
     use tempfile::TempDir;
 
+    fn is_license_compatible(
+        dependency_license: &str,
+        project_license: &str,
+        strict: bool,
+    ) -> LicenseCompatibility {
+        let config = config::FeludaConfig::default();
+        is_license_compatible_with_config(dependency_license, project_license, strict, &config)
+    }
+
+    #[test]
+    fn test_license_info_deserializes_without_optional_fields() {
+        let mut json = serde_json::to_value(test_dependency("serde", "1.0.0", "MIT")).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("ecosystem");
+        fields.remove("occurrences");
+
+        let info: LicenseInfo = serde_json::from_value(json).unwrap();
+        assert_eq!(info.name, "serde");
+        assert_eq!(info.ecosystem, "");
+        assert_eq!(info.occurrences, 1);
+        assert_eq!(info.copyright, None);
+    }
+
     #[test]
     fn test_license_compatibility_display() {
-        assert_eq!(LicenseCompatibility::Compatible.to_string(), "Compatible");
+        assert_eq!(LicenseCompatibility::Compatible.to_string(), "compatible");
         assert_eq!(
             LicenseCompatibility::Incompatible.to_string(),
-            "Incompatible"
+            "incompatible"
         );
-        assert_eq!(LicenseCompatibility::Unknown.to_string(), "Unknown");
+        assert_eq!(LicenseCompatibility::Unknown.to_string(), "unknown");
+    }
+
+    #[test]
+    fn test_license_compatibility_round_trip() {
+        for compatibility in [
+            LicenseCompatibility::Compatible,
+            LicenseCompatibility::Incompatible,
+            LicenseCompatibility::Unknown,
+        ] {
+            let parsed: LicenseCompatibility = compatibility.to_string().parse().unwrap();
+            assert_eq!(parsed, compatibility);
+        }
+
+        assert_eq!(
+            "Incompatible".parse::<LicenseCompatibility>().unwrap(),
+            LicenseCompatibility::Incompatible
+        );
+        assert_eq!(
+            " UNKNOWN ".parse::<LicenseCompatibility>().unwrap(),
+            LicenseCompatibility::Unknown
+        );
+        assert!("maybe".parse::<LicenseCompatibility>().is_err());
     }
 
     #[test]
     fn test_license_info_methods() {
         let info = LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         };
 
         assert_eq!(info.name(), "test_package");
@@ -1286,15 +1716,27 @@ mod tests {
     #[test]
     fn test_license_info_no_license() {
         let info = LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: None,
             is_restrictive: true,
-            compatibility: LicenseCompatibility::Unknown,
-            osi_status: OsiStatus::Unknown,
+            ..LicenseInfo::new("test_package", "1.0.0", None)
         };
 
         assert_eq!(info.get_license(), "No License");
+        assert!(!info.is_multi_license());
+    }
+
+    #[test]
+    fn test_license_info_is_multi_license() {
+        let with_license =
+            |license: &str| LicenseInfo::new("pkg", "1.0.0", Some(license.to_string()));
+
+        assert!(!with_license("MIT").is_multi_license());
+        assert!(with_license("MIT OR Apache-2.0").is_multi_license());
+        assert!(with_license("(MIT or Apache-2.0) AND BSD-3-Clause").is_multi_license());
+        assert!(!with_license("MIT AND BSD-3-Clause").is_multi_license());
+        assert!(!with_license("GPL-2.0-only WITH Classpath-exception-2.0").is_multi_license());
+        // Malformed expressions are not reported as a choice
+        assert!(!with_license("MIT OR").is_multi_license());
+        assert!(!with_license("(MIT OR Apache-2.0").is_multi_license());
     }
 
     #[test]
@@ -1346,6 +1788,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_license_compatible_or_expression() {
+        // Dual-licensed dependency: the MIT branch is acceptable
+        assert_eq!(
+            is_license_compatible("MIT OR GPL-3.0", "MIT", false),
+            LicenseCompatibility::Compatible
+        );
+        assert_eq!(
+            is_license_compatible("GPL-3.0 OR LGPL-3.0", "MIT", false),
+            LicenseCompatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn test_is_license_compatible_and_expression() {
+        assert_eq!(
+            is_license_compatible("(MIT OR Apache-2.0) AND BSD-3-Clause", "Apache-2.0", false),
+            LicenseCompatibility::Compatible
+        );
+        // BSD-3-Clause is not in the BSD-2-Clause allowlist, so the AND fails
+        assert_eq!(
+            is_license_compatible(
+                "(MIT OR Apache-2.0) AND BSD-3-Clause",
+                "BSD-2-Clause",
+                false
+            ),
+            LicenseCompatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn test_is_license_compatible_with_exception() {
+        assert_eq!(
+            is_license_compatible(
+                "GPL-2.0-only WITH Classpath-exception-2.0",
+                "GPL-2.0",
+                false
+            ),
+            LicenseCompatibility::Compatible
+        );
+        assert_eq!(
+            is_license_compatible("GPL-2.0-only WITH Classpath-exception-2.0", "MIT", false),
+            LicenseCompatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn test_is_license_compatible_expression_unknown_project_license() {
+        assert_eq!(
+            is_license_compatible("MIT OR Apache-2.0", "Some-Custom-License", false),
+            LicenseCompatibility::Unknown
+        );
+        assert_eq!(
+            is_license_compatible("MIT OR Apache-2.0", "Some-Custom-License", true),
+            LicenseCompatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn test_config_compatibility_overrides_builtin_matrix() {
+        let config = config::FeludaConfig {
+            compatibility: HashMap::from([(
+                "MIT".to_string(),
+                vec!["MIT".to_string(), "MPL-2.0".to_string()],
+            )]),
+            ..Default::default()
+        };
+
+        // MPL-2.0 is incompatible with MIT in the built-in matrix but allowed by config
+        assert_eq!(
+            is_license_compatible_with_config("MPL-2.0", "MIT", false, &config),
+            LicenseCompatibility::Compatible
+        );
+        // Apache-2.0 is compatible in the built-in matrix but missing from the allowlist
+        assert_eq!(
+            is_license_compatible_with_config("Apache-2.0", "MIT", false, &config),
+            LicenseCompatibility::Incompatible
+        );
+        // Project licenses without an override still use the built-in matrix
+        assert_eq!(
+            is_license_compatible_with_config("Apache-2.0", "GPL-3.0", false, &config),
+            LicenseCompatibility::Compatible
+        );
+    }
+
+    #[test]
+    fn test_config_compatibility_unknown_dependency_license() {
+        let config = config::FeludaConfig {
+            compatibility: HashMap::from([("MIT".to_string(), vec!["MIT".to_string()])]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            is_license_compatible_with_config("Custom-Corp-License", "MIT", false, &config),
+            LicenseCompatibility::Unknown
+        );
+        assert_eq!(
+            is_license_compatible_with_config("Custom-Corp-License", "MIT", true, &config),
+            LicenseCompatibility::Incompatible
+        );
+        assert_eq!(
+            is_license_compatible_with_config("MIT OR Custom-Corp-License", "MIT", true, &config),
+            LicenseCompatibility::Compatible
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_licenses_from_github_uses_fresh_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        cache::with_test_cache_dir(temp_dir.path(), || {
+            let mut cached = HashMap::new();
+            cached.insert(
+                "FELUDA-TEST-1.0".to_string(),
+                License {
+                    title: "Feluda Test License".to_string(),
+                    spdx_id: "FELUDA-TEST-1.0".to_string(),
+                    permissions: vec![],
+                    conditions: vec![],
+                    limitations: vec![],
+                },
+            );
+            cache::save_github_licenses_to_cache(&cached).unwrap();
+
+            // A license GitHub never returns proves both calls were served from the cache
+            for _ in 0..2 {
+                let licenses = fetch_licenses_from_github().unwrap();
+                assert_eq!(licenses.len(), 1);
+                assert!(licenses.contains_key("FELUDA-TEST-1.0"));
+            }
+
+            // --refresh-cache bypasses the cached copy
+            cache::set_refresh_cache(true);
+            crate::network::set_offline_mode(true);
+            let licenses = fetch_licenses_from_github().unwrap();
+            crate::network::set_offline_mode(false);
+            cache::set_refresh_cache(false);
+            assert!(licenses.is_empty());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_offline_mode_skips_osi_fetch() {
+        crate::network::set_offline_mode(true);
+        let osi = fetch_osi_licenses().unwrap();
+        crate::network::set_offline_mode(false);
+
+        assert!(osi.is_empty());
+    }
+
+    #[test]
+    fn test_restrictive_additional_license() {
+        let config = config::FeludaConfig {
+            restrictive: config::RestrictiveConfig {
+                additional: vec!["BUSL-1.1".to_string()],
+                allow: Vec::new(),
+            },
+            ..Default::default()
+        };
+        let known_licenses = HashMap::new();
+
+        assert!(!is_license_restrictive_with_config(
+            &Some("BUSL-1.1".to_string()),
+            &known_licenses,
+            false,
+            &config::FeludaConfig::default()
+        ));
+        assert!(is_license_restrictive_with_config(
+            &Some("busl-1.1".to_string()),
+            &known_licenses,
+            false,
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_restrictive_allowlisted_gpl() {
+        let config = config::FeludaConfig {
+            restrictive: config::RestrictiveConfig {
+                additional: Vec::new(),
+                allow: vec!["gpl-3.0".to_string()],
+            },
+            ..Default::default()
+        };
+        let known_licenses = HashMap::new();
+
+        assert!(is_license_restrictive_with_config(
+            &Some("GPL-3.0".to_string()),
+            &known_licenses,
+            false,
+            &config::FeludaConfig::default()
+        ));
+        assert!(!is_license_restrictive_with_config(
+            &Some("GPL-3.0".to_string()),
+            &known_licenses,
+            false,
+            &config
+        ));
+    }
+
     #[test]
     fn test_detect_project_license_mit_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -1361,6 +2004,68 @@ mod tests {
         assert_eq!(result, Some("MIT".to_string()));
     }
 
+    #[test]
+    fn test_detect_project_license_apache_text() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("LICENSE.md"),
+            r#"# Apache License
+Version 2.0, January 2004
+
+TERMS AND CONDITIONS FOR USE, REPRODUCTION, AND DISTRIBUTION
+
+1. Definitions.
+
+"License" shall mean the terms and conditions for use, reproduction, and distribution as
+defined by Sections 1 through 9 of this document.
+
+"Licensor" shall mean the copyright owner or entity authorized by the copyright owner that
+is granting the License.
+
+2. Grant of Copyright License. Subject to the terms and conditions of this License, each
+Contributor hereby grants to You a perpetual, worldwide, non-exclusive, no-charge,
+royalty-free, irrevocable copyright license to reproduce, prepare Derivative Works of,
+publicly display, publicly perform, sublicense, and distribute the Work and such
+Derivative Works in Source or Object form.
+"#,
+        )
+        .unwrap();
+
+        let result = detect_project_license(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(result, Some("Apache-2.0".to_string()));
+    }
+
+    #[test]
+    fn test_detect_project_license_garbage_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("COPYING"),
+            "All rights reserved. Ask the author before doing anything with this.",
+        )
+        .unwrap();
+
+        let result = detect_project_license(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_detect_project_license_prefers_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("LICENSE"),
+            include_str!("../../LICENSE"),
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\nlicense = \"MIT OR Apache-2.0\"\n",
+        )
+        .unwrap();
+
+        let result = detect_project_license(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(result, Some("MIT OR Apache-2.0".to_string()));
+    }
+
     #[test]
     fn test_detect_project_license_no_license() {
         let temp_dir = TempDir::new().unwrap();
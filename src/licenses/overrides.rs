@@ -0,0 +1,117 @@
+//! Per-dependency license overrides
+//!
+//! Registry metadata is sometimes wrong, for example a package declaring `UNLICENSED`
+//! that is MIT licensed in its repository. The `[overrides]` configuration section forces
+//! the license of such dependencies:
+//!
+//! ```toml
+//! [overrides]
+//! "left-pad" = "MIT"                 # every version
+//! "some-crate@0.3.1" = "Apache-2.0"  # only this version, wins over a bare name
+//! ```
+
+use std::collections::HashMap;
+
+use crate::config::FeludaConfig;
+use crate::debug::{log, log_error, LogLevel};
+use crate::licenses::{
+    fetch_licenses_from_github, get_osi_status, is_license_restrictive_with_config, LicenseInfo,
+};
+
+/// Replace the license of every dependency listed in `[overrides]` and re-evaluate it
+///
+/// Restrictiveness and OSI status are recomputed from the forced license; compatibility
+/// is set later from the project license like for any other dependency.
+pub fn apply_license_overrides(licenses: &mut [LicenseInfo], config: &FeludaConfig) {
+    if config.overrides.is_empty() {
+        return;
+    }
+
+    let mut known_licenses = None;
+
+    for info in licenses.iter_mut() {
+        let Some(forced) = config.license_override(&info.name, &info.version) else {
+            continue;
+        };
+        let forced = forced.trim().to_string();
+
+        log(
+            LogLevel::Info,
+            &format!(
+                "Overriding license of {}@{}: '{}' -> '{forced}'",
+                info.name,
+                info.version,
+                info.get_license()
+            ),
+        );
+
+        let known = known_licenses.get_or_insert_with(|| {
+            fetch_licenses_from_github().unwrap_or_else(|err| {
+                log_error("Failed to fetch licenses from GitHub", &err);
+                HashMap::new()
+            })
+        });
+
+        info.osi_status = get_osi_status(&forced);
+        info.license = Some(forced);
+        info.is_restrictive =
+            is_license_restrictive_with_config(&info.license, known, config.strict, config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::{test_dependency, OsiStatus};
+    use crate::network::set_offline_mode;
+    use serial_test::serial;
+
+    fn config_with(overrides: &[(&str, &str)]) -> FeludaConfig {
+        FeludaConfig {
+            overrides: overrides
+                .iter()
+                .map(|(key, license)| (key.to_string(), license.to_string()))
+                .collect(),
+            ..FeludaConfig::default()
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_version_specific_override_wins() {
+        let config = config_with(&[("left-pad", "MIT"), ("left-pad@1.3.0", "GPL-3.0-only")]);
+        let mut licenses = vec![
+            test_dependency("left-pad", "1.3.0", "UNLICENSED"),
+            test_dependency("left-pad", "1.1.0", "UNLICENSED"),
+            test_dependency("right-pad", "1.0.0", "UNLICENSED"),
+        ];
+
+        set_offline_mode(true);
+        apply_license_overrides(&mut licenses, &config);
+        set_offline_mode(false);
+
+        assert_eq!(licenses[0].license.as_deref(), Some("GPL-3.0-only"));
+        assert!(licenses[0].is_restrictive);
+        assert_eq!(licenses[1].license.as_deref(), Some("MIT"));
+        assert!(!licenses[1].is_restrictive);
+        assert_eq!(licenses[1].osi_status, OsiStatus::Approved);
+        assert_eq!(licenses[2].license.as_deref(), Some("UNLICENSED"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_scoped_package_override() {
+        let config = config_with(&[("@types/node@20.1.0", "MIT")]);
+        let mut licenses = vec![
+            test_dependency("@types/node", "20.1.0", "Unknown"),
+            test_dependency("@types/node", "18.0.0", "Unknown"),
+        ];
+
+        set_offline_mode(true);
+        apply_license_overrides(&mut licenses, &config);
+        set_offline_mode(false);
+
+        assert_eq!(licenses[0].license.as_deref(), Some("MIT"));
+        assert_eq!(licenses[1].license.as_deref(), Some("Unknown"));
+    }
+}
@@ -1,29 +1,48 @@
+mod baseline;
 mod cache;
 mod cli;
 mod config;
 mod debug;
+mod doctor;
 mod generate;
 mod languages;
 mod licenses;
+mod network;
 mod parser;
+mod policy;
+mod registry_auth;
 mod reporter;
+mod revision;
 mod sbom;
+mod stats;
 mod table;
+mod tree;
 mod utils;
 
+use baseline::Baseline;
 use clap::Parser;
 use cli::{print_version_info, Cli, Commands};
-use debug::{log, log_debug, set_debug_mode, FeludaError, FeludaResult, LogLevel};
-use generate::handle_generate_command;
+use colored::Colorize;
+use debug::{
+    is_quiet_mode, log, log_debug, set_debug_mode, set_log_format, set_quiet_mode, FeludaError,
+    FeludaResult, LogLevel,
+};
+use generate::{handle_generate_command, GenerateOption};
 use licenses::{
-    detect_project_license, is_license_compatible, set_github_token, LicenseCompatibility,
+    detect_project_license, is_license_compatible_with_config, set_github_token,
+    LicenseCompatibility, LicenseInfo,
 };
-use parser::parse_root;
-use reporter::{generate_report, ReportConfig};
+use network::set_offline_mode;
+use parser::{parse_root, parse_root_with_config};
+use reporter::{generate_report, print_license_inventory, print_stats, FailOn, ReportConfig};
+use sbom::convert::handle_sbom_convert_command;
+use sbom::diff::handle_sbom_diff_command;
 use sbom::handle_sbom_command;
+use sbom::merge::handle_sbom_merge_command;
 use sbom::validate::handle_sbom_validate_command;
 use std::env;
-use std::path::Path;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
 use table::App;
 use tempfile::TempDir;
@@ -41,14 +60,31 @@ struct CheckConfig {
     language: Option<String>,
     ci_format: Option<cli::CiFormat>,
     output_file: Option<String>,
-    fail_on_restrictive: bool,
+    summary_json: bool,
+    toml: bool,
+    ndjson: bool,
     incompatible: bool,
-    fail_on_incompatible: bool,
+    fail_on: FailOn,
+    deny_license: Vec<String>,
+    warn_license: Vec<String>,
+    baseline: Option<String>,
+    write_baseline: bool,
+    diff: Option<String>,
     project_license: Option<String>,
     gist: bool,
+    summary_only: bool,
     osi: Option<cli::OsiFilter>,
+    min_severity: Option<cli::Severity>,
+    sort: Option<cli::ReportSort>,
     strict: bool,
     no_local: bool,
+    depth: Option<usize>,
+    include_dev: bool,
+    direct_only: bool,
+    why: Option<String>,
+    normalize_licenses: bool,
+    manifest: Option<String>,
+    max_deps: config::DependencyLimit,
 }
 
 fn main() {
@@ -71,6 +107,18 @@ fn main() {
 fn run() -> FeludaResult<()> {
     let args = Cli::parse();
 
+    // Colors: off for --no-color, NO_COLOR and redirected output
+    colored::control::set_override(cli::should_use_color(
+        args.no_color,
+        env::var_os("NO_COLOR"),
+        std::io::stdout().is_terminal(),
+    ));
+
+    // Quiet mode and log format, checked by logging and report output
+    set_quiet_mode(args.quiet);
+    set_log_format(args.log_format);
+    reporter::set_compact_json(args.compact);
+
     // Debug mode
     if args.debug {
         set_debug_mode(true);
@@ -80,6 +128,61 @@ fn run() -> FeludaResult<()> {
         );
     }
 
+    // Size the rayon pool before any parallel analysis runs
+    utils::configure_thread_pool(args.threads)?;
+
+    // Offline mode
+    if args.offline {
+        set_offline_mode(true);
+        if !args.quiet {
+            eprintln!(
+                "{}",
+                "Offline mode: network lookups are disabled, licenses are resolved from local data only"
+                    .yellow()
+            );
+        }
+    }
+
+    // License cache behaviour
+    if let Some(hours) = args.cache_ttl {
+        cache::set_cache_ttl_hours(hours);
+    }
+    cache::set_refresh_cache(args.refresh_cache);
+    cache::set_package_cache_disabled(args.no_cache);
+    // A `--repo` clone lives in a new temporary directory, so its analysis is never reused
+    cache::set_incremental_disabled(args.no_incremental || args.repo.is_some());
+
+    // Bound parallel registry requests
+    network::set_max_concurrency(args.max_concurrency);
+
+    // An explicit config file must load cleanly instead of falling back to defaults
+    if let Some(config_path) = &args.config {
+        config::set_config_path(Some(PathBuf::from(config_path)));
+        config::load_config()?;
+    }
+
+    // Registry mirrors and request timeout: CLI flags take precedence over .feluda.toml
+    let configured = config::load_config().unwrap_or_default();
+    network::set_registries(config::RegistryConfig {
+        npm: args.npm_registry.clone().or(configured.registries.npm),
+        pypi: args.pypi_index.clone().or(configured.registries.pypi),
+        crates: args
+            .crates_registry
+            .clone()
+            .or(configured.registries.crates),
+    });
+    network::set_request_timeout(
+        args.timeout
+            .or(configured.timeout)
+            .unwrap_or(network::DEFAULT_REQUEST_TIMEOUT_SECS),
+    );
+    languages::node::set_license_sources(
+        args.node_license_sources
+            .clone()
+            .or(configured.node.license_sources)
+            .unwrap_or_else(|| cli::NodeLicenseSource::DEFAULT_ORDER.to_vec()),
+    );
+
     // Set GitHub API token for authenticated requests
     set_github_token(args.github_token.clone());
 
@@ -121,6 +224,12 @@ fn run() -> FeludaResult<()> {
         &format!("Analysing project at: {}", analysis_path.display()),
     );
 
+    // Private registry credentials: --registry-token, then .npmrc, then .netrc
+    registry_auth::set_registry_credentials(registry_auth::load_registry_credentials(
+        &analysis_path,
+        &args.registry_token,
+    ));
+
     // Handle the command based on whether a subcommand was provided
     if args.is_default_command() {
         // Default behavior: license analysis
@@ -134,14 +243,38 @@ fn run() -> FeludaResult<()> {
             language: args.language,
             ci_format: args.ci_format,
             output_file: args.output_file,
-            fail_on_restrictive: args.fail_on_restrictive,
+            summary_json: args.summary_json,
+            toml: args.toml,
+            ndjson: args.ndjson,
             incompatible: args.incompatible,
-            fail_on_incompatible: args.fail_on_incompatible,
+            fail_on: FailOn {
+                restrictive: args.fail_on_restrictive,
+                incompatible: args.fail_on_incompatible,
+                unknown: args.fail_on_unknown,
+                non_osi: args.fail_on_non_osi,
+                unknown_osi: args.fail_on_unknown_osi,
+                unknown_ratio: args.fail_on_unknown_ratio,
+            },
+            deny_license: args.deny_license,
+            warn_license: args.warn_license,
+            baseline: args.baseline,
+            write_baseline: args.write_baseline,
+            diff: args.diff,
             project_license: args.project_license,
             gist: args.gist,
+            summary_only: args.summary_only,
             osi: args.osi,
+            min_severity: args.min_severity,
+            sort: args.sort,
             strict: args.strict,
             no_local: args.no_local,
+            depth: args.depth,
+            include_dev: args.include_dev,
+            direct_only: args.direct_only,
+            why: args.why,
+            normalize_licenses: args.normalize_licenses,
+            manifest: args.manifest,
+            max_deps: config::DependencyLimit::new(args.max_deps, args.max_deps_action),
         };
         handle_check_command(config)
     } else {
@@ -152,20 +285,40 @@ fn run() -> FeludaResult<()> {
                 path,
                 language,
                 project_license,
+                format,
+                notice,
+                third_party_licenses,
+                notice_template,
             } => {
-                handle_generate_command(path, language, project_license);
+                let mut targets = Vec::new();
+                if notice {
+                    targets.push(GenerateOption::Notice);
+                }
+                if third_party_licenses {
+                    targets.push(GenerateOption::ThirdPartyLicenses);
+                }
+                handle_generate_command(
+                    path,
+                    language,
+                    project_license,
+                    format,
+                    &targets,
+                    notice_template,
+                );
                 Ok(())
             }
             Commands::Sbom {
                 path,
                 format,
                 output,
+                output_dir,
             } => {
                 // Determine which format to use
                 match format {
                     Some(cli::SbomCommand::Spdx {
                         path: fmt_path,
                         output: fmt_output,
+                        tag_value,
                     }) => {
                         // Use the subcommand path/output if provided, otherwise use the parent command's
                         let final_path = if fmt_path != "./" {
@@ -174,11 +327,34 @@ fn run() -> FeludaResult<()> {
                             path.clone()
                         };
                         let final_output = fmt_output.or(output.clone());
-                        handle_sbom_command(final_path, &cli::SbomFormat::Spdx, final_output)
+                        let format = if tag_value {
+                            cli::SbomFormat::SpdxTagValue
+                        } else {
+                            cli::SbomFormat::Spdx
+                        };
+                        handle_sbom_command(final_path, &format, final_output, output_dir)
                     }
                     Some(cli::SbomCommand::Cyclonedx {
                         path: fmt_path,
                         output: fmt_output,
+                        xml,
+                    }) => {
+                        let final_path = if fmt_path != "./" {
+                            fmt_path
+                        } else {
+                            path.clone()
+                        };
+                        let final_output = fmt_output.or(output.clone());
+                        let format = if xml {
+                            cli::SbomFormat::CyclonedxXml
+                        } else {
+                            cli::SbomFormat::Cyclonedx
+                        };
+                        handle_sbom_command(final_path, &format, final_output, output_dir)
+                    }
+                    Some(cli::SbomCommand::Vex {
+                        path: fmt_path,
+                        output: fmt_output,
                     }) => {
                         let final_path = if fmt_path != "./" {
                             fmt_path
@@ -186,16 +362,36 @@ fn run() -> FeludaResult<()> {
                             path.clone()
                         };
                         let final_output = fmt_output.or(output.clone());
-                        handle_sbom_command(final_path, &cli::SbomFormat::Cyclonedx, final_output)
+                        handle_sbom_command(
+                            final_path,
+                            &cli::SbomFormat::Vex,
+                            final_output,
+                            output_dir,
+                        )
                     }
                     Some(cli::SbomCommand::Validate {
                         sbom_file,
                         output: validation_output,
                         json,
                     }) => handle_sbom_validate_command(sbom_file, validation_output, json),
+                    Some(cli::SbomCommand::Diff {
+                        old,
+                        new,
+                        output: diff_output,
+                        json,
+                    }) => handle_sbom_diff_command(old, new, diff_output, json),
+                    Some(cli::SbomCommand::Merge {
+                        files,
+                        output: merge_output,
+                    }) => handle_sbom_merge_command(files, merge_output.or(output)),
+                    Some(cli::SbomCommand::Convert {
+                        input,
+                        to,
+                        output: convert_output,
+                    }) => handle_sbom_convert_command(input, to, convert_output.or(output)),
                     None => {
                         // Default: generate both formats
-                        handle_sbom_command(path, &cli::SbomFormat::All, output)
+                        handle_sbom_command(path, &cli::SbomFormat::All, output, output_dir)
                     }
                 }
             }
@@ -203,6 +399,13 @@ fn run() -> FeludaResult<()> {
                 handle_cache_command(clear)?;
                 Ok(())
             }
+            Commands::ListLicenses { path, json } => handle_list_licenses_command(path, json),
+            Commands::Stats { path, json } => handle_stats_command(path, json),
+            Commands::Tree { path, language } => handle_tree_command(path, language),
+            Commands::Doctor => {
+                doctor::print_checks(&doctor::run_checks());
+                Ok(())
+            }
         }
     }
 }
@@ -219,7 +422,7 @@ fn handle_check_command(config: CheckConfig) -> FeludaResult<()> {
         &format!("Parsing dependencies in path: {}", config.path),
     );
 
-    let mut project_license = config.project_license;
+    let mut project_license = config.project_license.clone();
 
     // If no project license is provided via CLI, try to detect it
     if let Some(ref license) = project_license {
@@ -252,72 +455,50 @@ fn handle_check_command(config: CheckConfig) -> FeludaResult<()> {
         }
     }
 
+    let machine_output = config.json
+        || config.yaml
+        || config.toml
+        || config.ndjson
+        || config.summary_json
+        || config.ci_format.is_some()
+        || is_quiet_mode();
+    cli::set_progress_enabled(cli::should_show_progress(
+        std::io::stdout().is_terminal(),
+        machine_output,
+    ));
+    cli::reset_resolution_progress();
+
     // Parse and analyze dependencies
-    let mut analyzed_data = parse_root(
+    let scan = scan_config(&config)?;
+    let mut analyzed_data = parse_root_with_config(
         &config.path,
         config.language.as_deref(),
-        config.strict,
+        &scan,
         config.no_local,
     )
     .map_err(|e| FeludaError::Parser(format!("Failed to parse dependencies: {e}")))?;
 
     log_debug("Analyzed dependencies", &analyzed_data);
 
-    if analyzed_data.is_empty() {
-        log(LogLevel::Warn, "No dependencies found to analyze. Exiting.");
+    if let Some(target) = &config.why {
+        tree::print_why(&analyzed_data, target);
         return Ok(());
     }
 
-    // Update each dependency with compatibility information if project license is known
-    if let Some(ref proj_license) = project_license {
-        log(
-            LogLevel::Info,
-            &format!("Checking license compatibility against project license: {proj_license}"),
-        );
-
-        for info in &mut analyzed_data {
-            if let Some(ref dep_license) = info.license {
-                info.compatibility =
-                    is_license_compatible(dep_license, proj_license, config.strict);
-
-                log(
-                    LogLevel::Info,
-                    &format!(
-                        "License compatibility for {} ({}): {:?}",
-                        info.name, dep_license, info.compatibility
-                    ),
-                );
-            } else {
-                info.compatibility = if config.strict {
-                    LicenseCompatibility::Incompatible
-                } else {
-                    LicenseCompatibility::Unknown
-                };
-
-                log(
-                    LogLevel::Info,
-                    &format!(
-                        "License compatibility for {} {} (no license info)",
-                        info.name,
-                        if config.strict {
-                            "incompatible"
-                        } else {
-                            "unknown"
-                        }
-                    ),
-                );
-            }
+    // With --diff, an empty tree can still have removed dependencies
+    if analyzed_data.is_empty() && config.diff.is_none() {
+        log(LogLevel::Warn, "No dependencies found to analyze. Exiting.");
+        // CI wrappers parse the summary even when there is nothing to count
+        if config.summary_json {
+            reporter::print_summary_json(&[], config.output_file.as_deref());
         }
-    } else {
-        // If no project license is known, mark all as unknown compatibility
-        log(
-            LogLevel::Warn,
-            "No project license specified or detected, marking all dependencies as unknown compatibility",
-        );
+        return Ok(());
+    }
 
-        for info in &mut analyzed_data {
-            info.compatibility = LicenseCompatibility::Unknown;
-        }
+    apply_compatibility(&mut analyzed_data, project_license.as_deref(), &scan);
+
+    if let Some(git_ref) = &config.diff {
+        return handle_revision_diff(&config, git_ref, &analyzed_data, project_license.as_deref());
     }
 
     // Either run the GUI or generate a report
@@ -434,6 +615,19 @@ fn handle_check_command(config: CheckConfig) -> FeludaResult<()> {
             }
         }
 
+        if let Some(min_severity) = config.min_severity {
+            let before_count = analyzed_data.len();
+            analyzed_data.retain(|info| reporter::severity_of(info) >= min_severity);
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Filtered for minimum severity {min_severity:?}: {} of {} dependencies",
+                    analyzed_data.len(),
+                    before_count
+                ),
+            );
+        }
+
         log(LogLevel::Info, "Starting TUI mode");
 
         // Initialize the terminal
@@ -466,21 +660,71 @@ fn handle_check_command(config: CheckConfig) -> FeludaResult<()> {
             project_license,
             config.gist,
             config.osi,
-        );
+        )
+        .with_summary_json(config.summary_json)
+        .with_toml(config.toml)
+        .with_ndjson(config.ndjson)
+        .with_sort(config.sort)
+        .with_min_severity(config.min_severity)
+        .with_summary_only(config.summary_only);
+
+        // Evaluate the license policy on every dependency, before report filters apply
+        let policy_outcome = policy::merge_license_flags(
+            config::load_config()?.policy,
+            &config.deny_license,
+            &config.warn_license,
+        )
+        .map(|policy| policy::evaluate_policy(&analyzed_data, &policy));
+
+        // Compare findings against the baseline, before report filters apply
+        let baseline_check = match &config.baseline {
+            Some(path) => {
+                let path = Path::new(path);
+                let baseline = if config.write_baseline {
+                    let baseline = Baseline::from_findings(&analyzed_data);
+                    baseline.save(path)?;
+                    baseline
+                } else {
+                    Baseline::load(path)?
+                };
+                Some(baseline.check(&analyzed_data))
+            }
+            None => None,
+        };
 
         // Generate a report based on the analyzed data
-        let (has_restrictive, has_incompatible) = generate_report(analyzed_data, report_config);
+        let mut outcome = generate_report(analyzed_data, report_config);
+        if let Some(check) = baseline_check {
+            outcome = check.apply(outcome);
+        }
 
-        log(
-            LogLevel::Info,
-            &format!(
-                "Report generated, has_restrictive: {has_restrictive}, has_incompatible: {has_incompatible}"
-            ),
-        );
+        let policy_denied = match &policy_outcome {
+            Some(outcome) => {
+                reporter::print_policy_outcome(outcome);
+                outcome.has_denials()
+            }
+            None => false,
+        };
+
+        log(LogLevel::Info, &format!("Report generated: {outcome:?}"));
 
-        if (config.fail_on_restrictive && has_restrictive)
-            || (config.fail_on_incompatible && has_incompatible)
-        {
+        if let Some(max) = config.fail_on.unknown_ratio {
+            let ratio = reporter::unknown_ratio(outcome.unknown, outcome.total);
+            let level = if ratio > f64::from(max) {
+                LogLevel::Warn
+            } else {
+                LogLevel::Info
+            };
+            log(
+                level,
+                &format!(
+                    "Unknown license ratio: {ratio:.1}% ({} of {}), threshold {max}%",
+                    outcome.unknown, outcome.total
+                ),
+            );
+        }
+
+        if outcome.fails(&config.fail_on) || policy_denied {
             log(
                 LogLevel::Warn,
                 "Exiting with non-zero status due to license issues",
@@ -494,9 +738,174 @@ fn handle_check_command(config: CheckConfig) -> FeludaResult<()> {
     Ok(())
 }
 
+/// Configuration file settings with the scan options given to `check`
+fn scan_config(config: &CheckConfig) -> FeludaResult<config::FeludaConfig> {
+    let mut scan = config::load_config()?;
+    scan.strict = config.strict;
+    scan.node_depth = config.depth;
+    scan.include_dev = config.include_dev;
+    scan.direct_only = config.direct_only;
+    scan.normalize_licenses = config.normalize_licenses;
+    scan.manifest = config.manifest.as_ref().map(PathBuf::from);
+    scan.max_deps = config.max_deps;
+    Ok(scan)
+}
+
+/// Mark each dependency compatible or incompatible with the project license, or unknown
+/// when the project license is not known
+fn apply_compatibility(
+    analyzed_data: &mut [LicenseInfo],
+    project_license: Option<&str>,
+    scan: &config::FeludaConfig,
+) {
+    let strict = scan.strict;
+    if let Some(proj_license) = project_license {
+        log(
+            LogLevel::Info,
+            &format!("Checking license compatibility against project license: {proj_license}"),
+        );
+
+        for info in analyzed_data.iter_mut() {
+            if let Some(ref dep_license) = info.license {
+                info.compatibility =
+                    is_license_compatible_with_config(dep_license, proj_license, strict, scan);
+
+                log(
+                    LogLevel::Info,
+                    &format!(
+                        "License compatibility for {} ({}): {:?}",
+                        info.name, dep_license, info.compatibility
+                    ),
+                );
+            } else {
+                info.compatibility = if strict {
+                    LicenseCompatibility::Incompatible
+                } else {
+                    LicenseCompatibility::Unknown
+                };
+
+                log(
+                    LogLevel::Info,
+                    &format!(
+                        "License compatibility for {} {} (no license info)",
+                        info.name,
+                        if strict { "incompatible" } else { "unknown" }
+                    ),
+                );
+            }
+        }
+    } else {
+        // If no project license is known, mark all as unknown compatibility
+        log(
+            LogLevel::Warn,
+            "No project license specified or detected, marking all dependencies as unknown compatibility",
+        );
+
+        for info in analyzed_data.iter_mut() {
+            info.compatibility = LicenseCompatibility::Unknown;
+        }
+    }
+}
+
+/// Analyze `git_ref` the same way as the working tree and report what changed in between
+fn handle_revision_diff(
+    config: &CheckConfig,
+    git_ref: &str,
+    analyzed_data: &[LicenseInfo],
+    project_license: Option<&str>,
+) -> FeludaResult<()> {
+    let temp_dir = TempDir::new()
+        .map_err(|e| FeludaError::TempDir(format!("Failed to create temporary directory: {e}")))?;
+    let base_path = utils::checkout_revision(Path::new(&config.path), git_ref, temp_dir.path())?;
+
+    // `--manifest` names a working-tree file; the base side reads its copy in the checkout
+    let mut scan = scan_config(config)?;
+    if let Some(manifest) = &scan.manifest {
+        scan.manifest = Some(revision::rebase_manifest(
+            manifest,
+            Path::new(&config.path),
+            &base_path,
+        )?);
+    }
+
+    let base_exists = match &scan.manifest {
+        Some(manifest) => manifest.is_file(),
+        None => base_path.exists(),
+    };
+    let mut base_data = if base_exists {
+        // The checkout is a new temporary directory on every run, so a cached analysis of it
+        // would never be read again
+        let incremental = cache::is_incremental_enabled();
+        cache::set_incremental_disabled(true);
+        let parsed = parse_root_with_config(
+            &base_path,
+            config.language.as_deref(),
+            &scan,
+            config.no_local,
+        );
+        cache::set_incremental_disabled(!incremental);
+        parsed.map_err(|e| {
+            FeludaError::Parser(format!("Failed to parse dependencies at {git_ref}: {e}"))
+        })?
+    } else {
+        // The project did not exist yet at the base revision
+        Vec::new()
+    };
+    apply_compatibility(&mut base_data, project_license, &scan);
+
+    let diff = revision::diff_analyses(&base_data, analyzed_data);
+    revision::print_revision_diff(git_ref, &diff, config.json)?;
+
+    if diff.outcome.fails(&config.fail_on) {
+        log(
+            LogLevel::Warn,
+            "Exiting with non-zero status due to license issues introduced since the base revision",
+        );
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn handle_list_licenses_command(path: String, json: bool) -> FeludaResult<()> {
+    let analyzed_data = parse_root(&path, None)
+        .map_err(|e| FeludaError::Parser(format!("Failed to parse dependencies: {e}")))?;
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Building license inventory for {} dependencies",
+            analyzed_data.len()
+        ),
+    );
+
+    print_license_inventory(&analyzed_data, json);
+    Ok(())
+}
+
+fn handle_stats_command(path: String, json: bool) -> FeludaResult<()> {
+    let analyzed_data = parse_root(&path, None)
+        .map_err(|e| FeludaError::Parser(format!("Failed to parse dependencies: {e}")))?;
+
+    let stats = stats::compute_stats(&analyzed_data);
+    log_debug("License stats", &stats);
+
+    print_stats(&stats, json);
+    Ok(())
+}
+
+fn handle_tree_command(path: String, language: Option<String>) -> FeludaResult<()> {
+    let analyzed_data = parse_root(&path, language.as_deref())
+        .map_err(|e| FeludaError::Parser(format!("Failed to parse dependencies: {e}")))?;
+
+    tree::print_tree(&analyzed_data);
+    Ok(())
+}
+
 fn handle_cache_command(clear: bool) -> FeludaResult<()> {
     if clear {
         cache::clear_github_licenses_cache()?;
+        cache::clear_package_license_cache()?;
+        cache::clear_analysis_cache()?;
         println!("✓ Cache cleared successfully\n");
     } else {
         let status = cache::get_cache_status()?;
@@ -0,0 +1,335 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, RwLock};
+use std::time::Duration;
+
+use crate::config::RegistryConfig;
+use crate::debug::{log, LogLevel};
+
+// Static atomic flag for offline mode
+pub static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Default number of HTTP requests allowed in flight at once
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+static MAX_CONCURRENCY: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_CONCURRENCY);
+
+/// Default per-request timeout for registry and API lookups, in seconds
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// User agent sent with every registry and API request
+const USER_AGENT: &str = "feluda-license-checker/1.0";
+
+static REQUEST_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_REQUEST_TIMEOUT_SECS);
+static IN_FLIGHT: Mutex<usize> = Mutex::new(0);
+static SLOT_FREED: Condvar = Condvar::new();
+
+pub const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org";
+pub const DEFAULT_PYPI_INDEX: &str = "https://pypi.org";
+pub const DEFAULT_CRATES_REGISTRY: &str = "https://crates.io";
+
+static REGISTRIES: RwLock<RegistryConfig> = RwLock::new(RegistryConfig {
+    npm: None,
+    pypi: None,
+    crates: None,
+});
+
+/// Set the offline mode flag
+pub fn set_offline_mode(offline: bool) {
+    OFFLINE_MODE.store(offline, Ordering::Relaxed);
+    if offline {
+        log(LogLevel::Info, "Offline mode enabled");
+    }
+}
+
+/// Check if offline mode is enabled
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::Relaxed)
+}
+
+/// Returns true if a network lookup should be skipped because offline mode is enabled
+///
+/// HTTP helpers call this before constructing a client so that no request is attempted.
+pub fn skip_network(context: &str) -> bool {
+    if is_offline_mode() {
+        log(
+            LogLevel::Info,
+            &format!("Offline mode: skipping network lookup for {context}"),
+        );
+        return true;
+    }
+    false
+}
+
+/// Set how many HTTP requests may be in flight at once (at least 1)
+pub fn set_max_concurrency(max: usize) {
+    MAX_CONCURRENCY.store(max.max(1), Ordering::Relaxed);
+    SLOT_FREED.notify_all();
+}
+
+/// Get the maximum number of HTTP requests allowed in flight at once
+pub fn max_concurrency() -> usize {
+    MAX_CONCURRENCY.load(Ordering::Relaxed)
+}
+
+/// Set the timeout applied to every HTTP request (at least 1 second)
+pub fn set_request_timeout(secs: u64) {
+    let secs = secs.max(1);
+    if secs != DEFAULT_REQUEST_TIMEOUT_SECS {
+        log(
+            LogLevel::Info,
+            &format!("Using HTTP request timeout of {secs}s"),
+        );
+    }
+    REQUEST_TIMEOUT_SECS.store(secs, Ordering::Relaxed);
+}
+
+/// Get the timeout applied to every HTTP request
+pub fn request_timeout() -> Duration {
+    Duration::from_secs(REQUEST_TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+/// Blocking client builder with Feluda's user agent and the configured request timeout
+pub fn http_client_builder() -> reqwest::blocking::ClientBuilder {
+    reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(request_timeout())
+}
+
+/// Async client builder with the same user agent and timeout, for concurrent fetches
+pub fn async_http_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(request_timeout())
+}
+
+/// Blocking client with the configured request timeout, for one-off lookups
+pub fn http_client() -> reqwest::Result<reqwest::blocking::Client> {
+    http_client_builder().build()
+}
+
+/// A slot for one in-flight HTTP request, released when dropped
+#[must_use = "the request slot is released as soon as the permit is dropped"]
+pub struct NetworkPermit(());
+
+impl Drop for NetworkPermit {
+    fn drop(&mut self) {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        *in_flight -= 1;
+        SLOT_FREED.notify_one();
+    }
+}
+
+/// Block until fewer than `max_concurrency()` requests are in flight, then take a slot
+///
+/// Hold the returned permit only around the request itself, never across a nested lookup,
+/// so a limit of 1 cannot deadlock.
+pub fn acquire_network_permit() -> NetworkPermit {
+    let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+    while *in_flight >= max_concurrency() {
+        in_flight = SLOT_FREED
+            .wait(in_flight)
+            .unwrap_or_else(|e| e.into_inner());
+    }
+    *in_flight += 1;
+    NetworkPermit(())
+}
+
+/// Use registry mirrors instead of the public registries; `None` entries keep the default
+pub fn set_registries(registries: RegistryConfig) {
+    if registries != RegistryConfig::default() {
+        log(
+            LogLevel::Info,
+            &format!("Using registry overrides: {registries:?}"),
+        );
+    }
+    *REGISTRIES.write().unwrap_or_else(|e| e.into_inner()) = registries;
+}
+
+/// Registry mirrors currently in use
+pub fn registries() -> RegistryConfig {
+    REGISTRIES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn registry_base(select: impl Fn(&RegistryConfig) -> Option<&String>, default: &str) -> String {
+    let registries = REGISTRIES.read().unwrap_or_else(|e| e.into_inner());
+    select(&registries)
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Hosts (with the port, when given) of the registries overriding the public ones, which
+/// are the only ones that receive registry credentials
+pub fn private_registry_hosts() -> Vec<String> {
+    let registries = REGISTRIES.read().unwrap_or_else(|e| e.into_inner());
+    [&registries.npm, &registries.pypi, &registries.crates]
+        .into_iter()
+        .flatten()
+        .filter_map(|url| url_host(url.trim()))
+        .collect()
+}
+
+/// Lowercase host of a URL, with the port when one is given
+pub fn url_host(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    Some(match parsed.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host,
+    })
+}
+
+/// npm registry metadata URL for a package, or for a specific version of it
+pub fn npm_registry_url(name: &str, version: Option<&str>) -> String {
+    let base = registry_base(|r| r.npm.as_ref(), DEFAULT_NPM_REGISTRY);
+    match version {
+        Some(version) => format!("{base}/{name}/{version}"),
+        None => format!("{base}/{name}"),
+    }
+}
+
+/// PyPI JSON API URL for a package version
+pub fn pypi_json_url(name: &str, version: &str) -> String {
+    let base = registry_base(|r| r.pypi.as_ref(), DEFAULT_PYPI_INDEX);
+    format!("{base}/pypi/{name}/{version}/json")
+}
+
+/// crates.io API URL for a crate
+pub fn crates_api_url(name: &str) -> String {
+    let base = registry_base(|r| r.crates.as_ref(), DEFAULT_CRATES_REGISTRY);
+    format!("{base}/api/v1/crates/{name}")
+}
+
+/// Run a blocking HTTP call while holding a network permit
+pub fn with_network_permit<T>(request: impl FnOnce() -> T) -> T {
+    let _permit = acquire_network_permit();
+    request()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_offline_mode_toggle() {
+        set_offline_mode(false);
+        assert!(!is_offline_mode());
+        assert!(!skip_network("test"));
+
+        set_offline_mode(true);
+        assert!(is_offline_mode());
+        assert!(skip_network("test"));
+
+        set_offline_mode(false);
+        assert!(!is_offline_mode());
+    }
+
+    #[test]
+    #[serial]
+    fn test_max_concurrency_one_serializes_requests() {
+        use rayon::prelude::*;
+        use std::sync::atomic::AtomicUsize;
+        use std::time::Duration;
+
+        set_max_concurrency(1);
+        let current = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        (0..16).into_par_iter().for_each(|_| {
+            let _permit = acquire_network_permit();
+            let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            current.fetch_sub(1, Ordering::SeqCst);
+        });
+        set_max_concurrency(DEFAULT_MAX_CONCURRENCY);
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_max_concurrency_is_at_least_one() {
+        set_max_concurrency(0);
+        assert_eq!(max_concurrency(), 1);
+        set_max_concurrency(DEFAULT_MAX_CONCURRENCY);
+        assert_eq!(max_concurrency(), DEFAULT_MAX_CONCURRENCY);
+    }
+
+    #[test]
+    #[serial]
+    fn test_registry_urls_use_overrides() {
+        set_registries(RegistryConfig::default());
+        assert_eq!(
+            npm_registry_url("left-pad", Some("1.3.0")),
+            "https://registry.npmjs.org/left-pad/1.3.0"
+        );
+        assert_eq!(
+            pypi_json_url("requests", "2.31.0"),
+            "https://pypi.org/pypi/requests/2.31.0/json"
+        );
+        assert_eq!(
+            crates_api_url("serde"),
+            "https://crates.io/api/v1/crates/serde"
+        );
+
+        set_registries(RegistryConfig {
+            npm: Some("https://npm.example.com/repository/npm/".to_string()),
+            pypi: Some("https://pypi.example.com".to_string()),
+            crates: Some("https://crates.example.com/".to_string()),
+        });
+        assert_eq!(
+            npm_registry_url("@scope/pkg", None),
+            "https://npm.example.com/repository/npm/@scope/pkg"
+        );
+        assert_eq!(
+            pypi_json_url("requests", "2.31.0"),
+            "https://pypi.example.com/pypi/requests/2.31.0/json"
+        );
+        assert_eq!(
+            crates_api_url("serde"),
+            "https://crates.example.com/api/v1/crates/serde"
+        );
+
+        set_registries(RegistryConfig::default());
+    }
+
+    #[test]
+    #[serial]
+    fn test_request_timeout_setting() {
+        assert_eq!(
+            request_timeout(),
+            Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS)
+        );
+
+        set_request_timeout(30);
+        assert_eq!(request_timeout(), Duration::from_secs(30));
+
+        set_request_timeout(0);
+        assert_eq!(request_timeout(), Duration::from_secs(1));
+
+        set_request_timeout(DEFAULT_REQUEST_TIMEOUT_SECS);
+    }
+
+    #[test]
+    #[serial]
+    fn test_http_client_applies_request_timeout() {
+        // Accepted by the kernel backlog but never answered
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        set_request_timeout(1);
+        let client = http_client_builder().no_proxy().build().unwrap();
+        set_request_timeout(DEFAULT_REQUEST_TIMEOUT_SECS);
+
+        let started = std::time::Instant::now();
+        let err = client.get(&url).send().unwrap_err();
+        assert!(err.is_timeout(), "expected a timeout, got {err}");
+        assert!(started.elapsed() < Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS));
+        drop(listener);
+    }
+}
@@ -1,32 +1,79 @@
 //! Core parsing coordination and project discovery functionality
 
 use crate::cli;
-use crate::debug::{log, log_debug, FeludaResult, LogLevel};
-use crate::languages::{
-    c::analyze_c_licenses, cpp::analyze_cpp_licenses, dotnet::analyze_dotnet_licenses,
-    go::analyze_go_licenses, node::analyze_js_licenses_with_no_local,
-    python::analyze_python_licenses, r::analyze_r_licenses,
-    rust::analyze_rust_licenses_with_no_local,
-};
-use crate::languages::{Language, CPP_PATHS, C_PATHS, DOTNET_PATHS, PYTHON_PATHS, R_PATHS};
+use crate::debug::{log, log_debug, FeludaError, FeludaResult, LogLevel};
+use crate::languages::{Language, LanguageParser, ParserRegistry};
 use crate::licenses::{
-    detect_project_license, is_license_compatible, LicenseCompatibility, LicenseInfo,
+    detect_project_license, is_license_compatible_with_config, LicenseCompatibility, LicenseInfo,
 };
-use cargo_metadata::MetadataCommand;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Name of the ignore file read from the scan root
+pub const FELUDAIGNORE_FILE: &str = ".feludaignore";
+
+/// Load the gitignore-style exclusions from `.feludaignore` in the scan root
+///
+/// A missing file excludes nothing; an unreadable or invalid one is logged and ignored.
+pub fn load_ignore_rules(root: &Path) -> Gitignore {
+    let ignore_path = root.join(FELUDAIGNORE_FILE);
+    if !ignore_path.is_file() {
+        return Gitignore::empty();
+    }
+
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(err) = builder.add(&ignore_path) {
+        log(
+            LogLevel::Warn,
+            &format!("Failed to read {}: {err}", ignore_path.display()),
+        );
+    }
+
+    match builder.build() {
+        Ok(rules) => {
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Loaded {} exclusion patterns from {}",
+                    rules.num_ignores(),
+                    ignore_path.display()
+                ),
+            );
+            rules
+        }
+        Err(err) => {
+            log(
+                LogLevel::Warn,
+                &format!("Invalid pattern in {}: {err}", ignore_path.display()),
+            );
+            Gitignore::empty()
+        }
+    }
+}
+
+/// Whether `path` (under the root the rules were loaded from) or one of its parents is excluded
+pub fn is_path_ignored(rules: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    path.strip_prefix(rules.path()).is_ok()
+        && rules.matched_path_or_any_parents(path, is_dir).is_ignore()
+}
+
 /// Project root information
 #[derive(Debug)]
 struct ProjectRoot {
     pub path: PathBuf,
     pub project_type: Language,
+    /// Project file to analyze, when given explicitly instead of discovered in `path`
+    pub manifest: Option<String>,
 }
 
 /// Find project files only in the root directory (not recursive)
 fn find_project_roots(root_path: impl AsRef<Path>) -> FeludaResult<Vec<ProjectRoot>> {
     let mut project_roots = Vec::new();
     let root = root_path.as_ref();
+    let ignore_rules = load_ignore_rules(root);
 
     log(
         LogLevel::Info,
@@ -47,7 +94,22 @@ fn find_project_roots(root_path: impl AsRef<Path>) -> FeludaResult<Vec<ProjectRo
             let path = entry.path();
             let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
+            if is_path_ignored(&ignore_rules, &path, false) {
+                log(
+                    LogLevel::Info,
+                    &format!("Skipping {} (matched {FELUDAIGNORE_FILE})", path.display()),
+                );
+                continue;
+            }
+
             if let Some(project_type) = Language::from_file_name(file_name) {
+                // Each language analyzes one project file per directory
+                if project_roots.iter().any(|root: &ProjectRoot| {
+                    std::mem::discriminant(&root.project_type)
+                        == std::mem::discriminant(&project_type)
+                }) {
+                    continue;
+                }
                 log(
                     LogLevel::Info,
                     &format!(
@@ -59,6 +121,7 @@ fn find_project_roots(root_path: impl AsRef<Path>) -> FeludaResult<Vec<ProjectRo
                 project_roots.push(ProjectRoot {
                     path: root.to_path_buf(),
                     project_type,
+                    manifest: None,
                 });
             }
         }
@@ -73,108 +136,61 @@ fn find_project_roots(root_path: impl AsRef<Path>) -> FeludaResult<Vec<ProjectRo
     Ok(project_roots)
 }
 
-/// Check which C project file exists in the given path
-fn check_which_c_file_exists(project_path: impl AsRef<Path>) -> Option<String> {
-    for &path in C_PATHS.iter() {
-        let full_path = Path::new(project_path.as_ref()).join(path);
-        if full_path.exists() {
-            log(
-                LogLevel::Info,
-                &format!("Found C project file: {}", full_path.display()),
-            );
-            return Some(path.to_string());
-        }
-    }
-
-    log(
-        LogLevel::Warn,
-        &format!(
-            "No C project file found in: {}",
-            project_path.as_ref().display()
-        ),
-    );
-    None
-}
-
-/// Check which C++ project file exists in the given path
-fn check_which_cpp_file_exists(project_path: impl AsRef<Path>) -> Option<String> {
-    for &path in CPP_PATHS.iter() {
-        let full_path = Path::new(project_path.as_ref()).join(path);
-        if full_path.exists() {
-            log(
-                LogLevel::Info,
-                &format!("Found C++ project file: {}", full_path.display()),
-            );
-            return Some(path.to_string());
-        }
+/// Build the project root for an explicit `--manifest` file, bypassing directory discovery
+fn manifest_root(manifest: &Path) -> FeludaResult<ProjectRoot> {
+    let file_name = manifest
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            FeludaError::Parser(format!("Invalid manifest path: {}", manifest.display()))
+        })?;
+
+    let project_type = Language::from_file_name(file_name).ok_or_else(|| {
+        FeludaError::Parser(format!(
+            "Unrecognized manifest file name '{file_name}'; expected a supported project file such as Cargo.toml, package.json or go.mod"
+        ))
+    })?;
+
+    if !manifest.is_file() {
+        return Err(FeludaError::Parser(format!(
+            "Manifest file not found: {}",
+            manifest.display()
+        )));
     }
 
-    log(
-        LogLevel::Warn,
-        &format!(
-            "No C++ project file found in: {}",
-            project_path.as_ref().display()
-        ),
-    );
-    None
-}
-
-/// Check which Python project file exists in the given path
-fn check_which_python_file_exists(project_path: impl AsRef<Path>) -> Option<String> {
-    for &path in PYTHON_PATHS.iter() {
-        let full_path = Path::new(project_path.as_ref()).join(path);
-        if full_path.exists() {
-            log(
-                LogLevel::Info,
-                &format!("Found Python project file: {}", full_path.display()),
-            );
-            return Some(path.to_string());
-        }
-    }
+    let path = match manifest.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
 
     log(
-        LogLevel::Warn,
-        &format!(
-            "No Python project file found in: {}",
-            project_path.as_ref().display()
-        ),
+        LogLevel::Info,
+        &format!("Using manifest {} ({:?})", manifest.display(), project_type),
     );
-    None
-}
-
-/// Check which R project file exists in the given path
-fn check_which_r_file_exists(project_path: impl AsRef<Path>) -> Option<String> {
-    for &path in R_PATHS.iter() {
-        let full_path = Path::new(project_path.as_ref()).join(path);
-        if full_path.exists() {
-            log(
-                LogLevel::Info,
-                &format!("Found R project file: {}", full_path.display()),
-            );
-            return Some(path.to_string());
-        }
-    }
 
-    log(
-        LogLevel::Warn,
-        &format!(
-            "No R project file found in: {}",
-            project_path.as_ref().display()
-        ),
-    );
-    None
+    Ok(ProjectRoot {
+        path,
+        project_type,
+        manifest: Some(file_name.to_string()),
+    })
 }
 
-fn check_which_dotnet_file_exists(project_path: impl AsRef<Path>) -> Option<String> {
-    for &path in DOTNET_PATHS.iter() {
-        if path.starts_with('.') {
-            if let Ok(entries) = std::fs::read_dir(project_path.as_ref()) {
+/// Find which of the parser's project files exists in the given path, in the order the
+/// parser lists them; patterns starting with `.` match any file with that extension
+fn find_project_file(project_path: &Path, parser: &dyn LanguageParser) -> Option<String> {
+    for &pattern in parser.supported_files() {
+        if pattern.starts_with('.') {
+            if let Ok(entries) = std::fs::read_dir(project_path) {
                 for entry in entries.filter_map(|e| e.ok()) {
                     if let Some(file_name) = entry.file_name().to_str() {
-                        if file_name.ends_with(path) {
+                        if file_name.ends_with(pattern) {
                             log(
                                 LogLevel::Info,
-                                &format!("Found .NET project file: {}", entry.path().display()),
+                                &format!(
+                                    "Found {} project file: {}",
+                                    parser.language_name(),
+                                    entry.path().display()
+                                ),
                             );
                             return Some(file_name.to_string());
                         }
@@ -182,13 +198,17 @@ fn check_which_dotnet_file_exists(project_path: impl AsRef<Path>) -> Option<Stri
                 }
             }
         } else {
-            let full_path = Path::new(project_path.as_ref()).join(path);
+            let full_path = project_path.join(pattern);
             if full_path.exists() {
                 log(
                     LogLevel::Info,
-                    &format!("Found .NET project file: {}", full_path.display()),
+                    &format!(
+                        "Found {} project file: {}",
+                        parser.language_name(),
+                        full_path.display()
+                    ),
                 );
-                return Some(path.to_string());
+                return Some(pattern.to_string());
             }
         }
     }
@@ -196,23 +216,21 @@ fn check_which_dotnet_file_exists(project_path: impl AsRef<Path>) -> Option<Stri
     log(
         LogLevel::Warn,
         &format!(
-            "No .NET project file found in: {}",
-            project_path.as_ref().display()
+            "No {} project file found in: {}",
+            parser.language_name(),
+            project_path.display()
         ),
     );
     None
 }
 
-/// Main entry point for parsing project dependencies
+/// Parse project dependencies with the configuration file and default scan options
 pub fn parse_root(
     root_path: impl AsRef<Path>,
     language: Option<&str>,
-    strict: bool,
-    no_local: bool,
 ) -> FeludaResult<Vec<LicenseInfo>> {
-    let mut config = crate::config::load_config()?;
-    config.strict = strict;
-    parse_root_with_config(root_path, language, &config, no_local)
+    let config = crate::config::load_config()?;
+    parse_root_with_config(root_path, language, &config, false)
 }
 
 /// Main entry point for parsing project dependencies
@@ -230,21 +248,28 @@ pub fn parse_root_with_config(
         log(LogLevel::Info, &format!("Filtering by language: {lang}"));
     }
 
-    let project_roots = find_project_roots(&root_path)?;
+    let project_roots = match &config.manifest {
+        Some(manifest) => vec![manifest_root(manifest)?],
+        None => find_project_roots(&root_path)?,
+    };
 
     if project_roots.is_empty() {
         log(
             LogLevel::Warn,
             "No project files found in the specified path",
         );
-        println!(
-            "❌ No supported project files found.\n\
-            Feluda supports: C, C++, .NET, Rust, Node.js, Go, Python, R"
-        );
+        // On stderr, so machine-readable output such as `--summary-json` stays parseable
+        if !crate::debug::is_quiet_mode() {
+            eprintln!(
+                "❌ No supported project files found.\n\
+                Feluda supports: C, C++, .NET, Rust, Node.js, Go, Elixir, Haskell, Perl, Python, R"
+            );
+        }
         return Ok(Vec::new());
     }
 
-    let licenses: Vec<LicenseInfo> = project_roots
+    let registry = ParserRegistry::builtin();
+    let licenses: Vec<Vec<LicenseInfo>> = project_roots
         .into_par_iter()
         .filter_map(|root| {
             if let Some(language) = language {
@@ -260,7 +285,7 @@ pub fn parse_root_with_config(
                 }
             }
 
-            match parse_dependencies(&root, config, no_local) {
+            match parse_dependencies_incremental(&root, &registry, config, no_local) {
                 Ok(deps) => {
                     log(
                         LogLevel::Info,
@@ -270,7 +295,7 @@ pub fn parse_root_with_config(
                             root.path.display()
                         ),
                     );
-                    Some(deps)
+                    Some(Ok(deps))
                 }
                 Err(err) => {
                     log(
@@ -281,20 +306,39 @@ pub fn parse_root_with_config(
                             err
                         ),
                     );
-                    None
+                    Some(Err(err))
                 }
             }
         })
-        .flatten()
-        .collect();
+        .collect::<FeludaResult<_>>()?;
+    let mut licenses = deduplicate_licenses(licenses.into_iter().flatten().collect());
 
     log(
         LogLevel::Info,
         &format!("Total dependencies found: {}", licenses.len()),
     );
 
+    // Each project is capped while collecting; this caps the total across projects
+    if let Some(max) = config.max_deps.check(licenses.len())? {
+        licenses.truncate(max);
+    }
+
+    if let Err(e) = crate::cache::save_package_license_cache() {
+        log(
+            LogLevel::Warn,
+            &format!("Failed to save package license cache: {e}"),
+        );
+    }
+
+    // Forced licenses from [overrides] replace whatever the registry reported
+    crate::licenses::overrides::apply_license_overrides(&mut licenses, config);
+
+    // Canonicalize aliases first so the ignore list and compatibility see SPDX identifiers
+    if config.normalize_licenses {
+        crate::licenses::aliases::normalize_dependency_licenses(&mut licenses, config);
+    }
+
     // Filter out ignored licenses
-    let mut licenses = licenses;
     let ignored_count = licenses.len();
     licenses.retain(|license| !crate::licenses::is_license_ignored(license.license.as_deref()));
     let filtered_count = licenses.len();
@@ -328,21 +372,79 @@ pub fn parse_root_with_config(
         );
     }
 
+    // Annotate deprecated SPDX identifiers without touching the declared license
+    crate::licenses::deprecations::annotate_deprecated_licenses(&mut licenses);
+
     // Set license compatibility based on project license
     let project_license =
         detect_project_license(root_path.as_ref().to_str().unwrap_or("")).unwrap_or(None);
 
-    set_license_compatibility(&mut licenses, &project_license);
+    set_license_compatibility(&mut licenses, &project_license, config);
 
     Ok(licenses)
 }
 
+/// Merge entries with the same name, version, license and ecosystem into one, keeping the
+/// first entry's position and counting how often the dependency was reported
+fn deduplicate_licenses(licenses: Vec<LicenseInfo>) -> Vec<LicenseInfo> {
+    let total = licenses.len();
+    let mut index: HashMap<(String, String, Option<String>, String), usize> = HashMap::new();
+    let mut deduplicated: Vec<LicenseInfo> = Vec::with_capacity(licenses.len());
+
+    for info in licenses {
+        let key = (
+            info.name.clone(),
+            info.version.clone(),
+            info.license.clone(),
+            info.ecosystem.clone(),
+        );
+        match index.get(&key) {
+            Some(&i) => {
+                let existing = &mut deduplicated[i];
+                existing.occurrences += info.occurrences;
+                for dependency in info.dependencies {
+                    if !existing.dependencies.contains(&dependency) {
+                        existing.dependencies.push(dependency);
+                    }
+                }
+                if existing.copyright.is_none() {
+                    existing.copyright = info.copyright;
+                }
+                if info.is_direct == Some(true) {
+                    existing.is_direct = Some(true);
+                } else if existing.is_direct.is_none() {
+                    existing.is_direct = info.is_direct;
+                }
+            }
+            None => {
+                index.insert(key, deduplicated.len());
+                deduplicated.push(info);
+            }
+        }
+    }
+
+    if deduplicated.len() < total {
+        log(
+            LogLevel::Info,
+            &format!(
+                "Merged {} duplicate dependency entries",
+                total - deduplicated.len()
+            ),
+        );
+    }
+    deduplicated
+}
+
 /// Set license compatibility for all dependencies
-fn set_license_compatibility(licenses: &mut [LicenseInfo], project_license: &Option<String>) {
+fn set_license_compatibility(
+    licenses: &mut [LicenseInfo],
+    project_license: &Option<String>,
+    config: &crate::config::FeludaConfig,
+) {
     for license in licenses {
         license.compatibility = match (project_license, &license.license) {
             (Some(proj_license), Some(dep_license)) => {
-                is_license_compatible(dep_license, proj_license, false)
+                is_license_compatible_with_config(dep_license, proj_license, config.strict, config)
             }
             _ => LicenseCompatibility::Unknown,
         };
@@ -362,247 +464,232 @@ fn matches_language(project_type: Language, language: &str) -> bool {
             | (Language::Rust(_), "rust")
             | (Language::Node(_), "node")
             | (Language::Go(_), "go")
+            | (Language::Elixir(_), "elixir")
+            | (Language::Haskell(_), "haskell")
+            | (Language::Perl(_), "perl")
             | (Language::Python(_), "python")
             | (Language::R(_), "r")
     )
 }
 
-/// Parse dependencies based on the project type
-fn parse_dependencies(
+/// Lockfiles hashed along with the manifests, since they pin what gets resolved
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "go.sum",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile",
+    "cpanfile.snapshot",
+    "packages.lock.json",
+    "stack.yaml.lock",
+    "cabal.project.freeze",
+    "conan.lock",
+];
+
+/// Settings outside the project that change which licenses are resolved: the effective
+/// configuration, the `--config` file, registry mirrors and Node.js license sources
+///
+/// `FELUDA_*` variables that override configuration keys are part of the effective
+/// configuration; the others (tokens, logging, SBOM output) do not change what is resolved.
+fn license_source_settings(config: &crate::config::FeludaConfig) -> Option<String> {
+    // `serde_json::Value` keeps object keys sorted, so map-valued settings hash stably
+    Some(format!(
+        "config={}|config_path={:?}|registries={:?}|node_sources={:?}",
+        serde_json::to_value(config).ok()?,
+        crate::config::config_path(),
+        crate::network::registries(),
+        crate::languages::node::license_sources(),
+    ))
+}
+
+/// Lockfiles of the Node.js package managers
+const NODE_LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+];
+
+/// Whether a Node.js project is resolved from files in its own directory only
+///
+/// Workspace members live in subdirectories, and without a lockfile the installed
+/// `node_modules` tree decides the versions, so neither is covered by the fingerprint.
+fn is_self_contained_node_project(project: &Path) -> bool {
+    let has_lockfile = NODE_LOCKFILES
+        .iter()
+        .any(|lockfile| project.join(lockfile).is_file());
+    let has_workspaces = project.join("pnpm-workspace.yaml").exists()
+        || std::fs::read_to_string(project.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|json| json.get("workspaces").is_some());
+    has_lockfile && !has_workspaces
+}
+
+/// Cache key of a project and a hash of what its analysis depends on: the manifests,
+/// lockfiles and `.feludaignore` in its directory, the options and settings that change what
+/// is resolved, and the version
+///
+/// Returns `None`, so the project is always analyzed, when its dependencies are read from
+/// files outside that set.
+fn analysis_fingerprint(
     root: &ProjectRoot,
     config: &crate::config::FeludaConfig,
     no_local: bool,
-) -> FeludaResult<Vec<LicenseInfo>> {
-    let project_path = &root.path;
-    let project_type = root.project_type;
-
-    let licenses = cli::with_spinner(&format!("🔎: {}", project_path.display()), |indicator| {
-        match project_type {
-            Language::Rust(_) => {
-                let project_path = Path::new(project_path).join("Cargo.toml");
-                log(
-                    LogLevel::Info,
-                    &format!("Parsing Rust project: {}", project_path.display()),
-                );
-
-                indicator.update_progress("analyzing Cargo.toml");
-
-                match MetadataCommand::new()
-                    .manifest_path(Path::new(&project_path))
-                    .exec()
-                {
-                    Ok(metadata) => {
-                        log(
-                            LogLevel::Info,
-                            &format!("Found {} packages in Rust project", metadata.packages.len()),
-                        );
-                        indicator.update_progress(&format!(
-                            "found {} packages",
-                            metadata.packages.len()
-                        ));
-
-                        analyze_rust_licenses_with_no_local(metadata.packages, no_local)
-                    }
-                    Err(err) => {
-                        log(
-                            LogLevel::Error,
-                            &format!("Failed to fetch cargo metadata: {err}"),
-                        );
-                        Vec::new()
-                    }
-                }
-            }
-            Language::Node(_) => {
-                let project_path = Path::new(project_path).join("package.json");
-                log(
-                    LogLevel::Info,
-                    &format!("Parsing Node.js project: {}", project_path.display()),
-                );
-
-                indicator.update_progress("analyzing package.json");
-
-                match project_path.to_str() {
-                    Some(path_str) => {
-                        let deps = analyze_js_licenses_with_no_local(path_str, no_local);
-                        indicator.update_progress(&format!("found {} dependencies", deps.len()));
-                        deps
-                    }
-                    None => {
-                        log(LogLevel::Error, "Failed to convert Node.js path to string");
-                        Vec::new()
-                    }
-                }
-            }
-            Language::Go(_) => {
-                let project_path = Path::new(project_path).join("go.mod");
-                log(
-                    LogLevel::Info,
-                    &format!("Parsing Go project: {}", project_path.display()),
-                );
-
-                indicator.update_progress("analyzing go.mod");
-
-                match project_path.to_str() {
-                    Some(path_str) => {
-                        let deps = analyze_go_licenses(path_str, config);
-                        indicator.update_progress(&format!("found {} dependencies", deps.len()));
-                        deps
-                    }
-                    None => {
-                        log(LogLevel::Error, "Failed to convert Go path to string");
-                        Vec::new()
-                    }
-                }
-            }
-            Language::Python(_) => match check_which_python_file_exists(project_path) {
-                Some(python_package_file) => {
-                    let project_path = Path::new(project_path).join(&python_package_file);
-                    log(
-                        LogLevel::Info,
-                        &format!("Parsing Python project: {}", project_path.display()),
-                    );
-
-                    indicator.update_progress(&format!("analyzing {python_package_file}"));
-
-                    match project_path.to_str() {
-                        Some(path_str) => {
-                            let deps = analyze_python_licenses(path_str, config);
-                            indicator
-                                .update_progress(&format!("found {} dependencies", deps.len()));
-                            deps
-                        }
-                        None => {
-                            log(LogLevel::Error, "Failed to convert Python path to string");
-                            Vec::new()
-                        }
-                    }
-                }
-                None => {
-                    log(LogLevel::Error, "Python package file not found");
-                    Vec::new()
-                }
-            },
-            Language::C(_) => match check_which_c_file_exists(project_path) {
-                Some(c_build_file) => {
-                    let project_path = Path::new(project_path).join(&c_build_file);
-                    log(
-                        LogLevel::Info,
-                        &format!("Parsing C project: {}", project_path.display()),
-                    );
-
-                    indicator.update_progress(&format!("analyzing {c_build_file}"));
-
-                    match project_path.to_str() {
-                        Some(path_str) => {
-                            let deps = analyze_c_licenses(path_str, config);
-                            indicator
-                                .update_progress(&format!("found {} dependencies", deps.len()));
-                            deps
-                        }
-                        None => {
-                            log(LogLevel::Error, "Failed to convert C path to string");
-                            Vec::new()
-                        }
-                    }
-                }
-                None => {
-                    log(LogLevel::Error, "C build file not found");
-                    Vec::new()
-                }
-            },
-            Language::Cpp(_) => match check_which_cpp_file_exists(project_path) {
-                Some(cpp_build_file) => {
-                    let project_path = Path::new(project_path).join(&cpp_build_file);
-                    log(
-                        LogLevel::Info,
-                        &format!("Parsing C++ project: {}", project_path.display()),
-                    );
-
-                    indicator.update_progress(&format!("analyzing {cpp_build_file}"));
+) -> Option<(String, String)> {
+    let project = root.path.canonicalize().ok()?;
+    if matches!(root.project_type, Language::Node(_)) && !is_self_contained_node_project(&project) {
+        log(
+            LogLevel::Info,
+            &format!(
+                "Not caching analysis of {}: it has workspaces or no lockfile",
+                project.display()
+            ),
+        );
+        return None;
+    }
+    let key = Sha256::digest(format!("{}\n{:?}", project.display(), root.project_type));
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&project)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        Language::from_file_name(name).is_some()
+                            || LOCKFILES.contains(&name)
+                            || name == FELUDAIGNORE_FILE
+                    })
+        })
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!(
+        "{}|strict={}|depth={:?}|dev={}|direct={}|normalize={}|manifest={:?}|no_local={}|offline={}|max_deps={:?}",
+        env!("CARGO_PKG_VERSION"),
+        config.strict,
+        config.node_depth,
+        config.include_dev,
+        config.direct_only,
+        config.normalize_licenses,
+        config.manifest,
+        no_local,
+        crate::network::is_offline_mode(),
+        config.max_deps,
+    ));
+    hasher.update(license_source_settings(config)?);
+    for file in files {
+        hasher.update(file.file_name()?.as_encoded_bytes());
+        hasher.update([0]);
+        hasher.update(std::fs::read(&file).ok()?);
+        hasher.update([0]);
+    }
 
-                    match project_path.to_str() {
-                        Some(path_str) => {
-                            let deps = analyze_cpp_licenses(path_str, config);
-                            indicator
-                                .update_progress(&format!("found {} dependencies", deps.len()));
-                            deps
-                        }
-                        None => {
-                            log(LogLevel::Error, "Failed to convert C++ path to string");
-                            Vec::new()
-                        }
-                    }
-                }
-                None => {
-                    log(LogLevel::Error, "C++ build file not found");
-                    Vec::new()
-                }
-            },
-            Language::DotNet(_) => match check_which_dotnet_file_exists(project_path) {
-                Some(dotnet_project_file) => {
-                    let project_path = Path::new(project_path).join(&dotnet_project_file);
-                    log(
-                        LogLevel::Info,
-                        &format!("Parsing .NET project: {}", project_path.display()),
-                    );
+    Some((format!("{key:x}"), format!("{:x}", hasher.finalize())))
+}
 
-                    indicator.update_progress(&format!("analyzing {dotnet_project_file}"));
+/// Reuse the cached analysis of a project whose manifests and lockfiles are unchanged,
+/// otherwise parse it and cache the result
+fn parse_dependencies_incremental(
+    root: &ProjectRoot,
+    registry: &ParserRegistry,
+    config: &crate::config::FeludaConfig,
+    no_local: bool,
+) -> FeludaResult<Vec<LicenseInfo>> {
+    let fingerprint = crate::cache::is_incremental_enabled()
+        .then(|| analysis_fingerprint(root, config, no_local))
+        .flatten();
+    let Some((key, manifest_hash)) = fingerprint else {
+        return parse_dependencies(root, registry, config, no_local);
+    };
+
+    if let Some(licenses) = crate::cache::load_analysis(&key, &manifest_hash) {
+        return Ok(licenses);
+    }
 
-                    match project_path.to_str() {
-                        Some(path_str) => {
-                            let deps = analyze_dotnet_licenses(path_str, config);
-                            indicator
-                                .update_progress(&format!("found {} dependencies", deps.len()));
-                            deps
-                        }
-                        None => {
-                            log(LogLevel::Error, "Failed to convert .NET path to string");
-                            Vec::new()
-                        }
-                    }
-                }
-                None => {
-                    log(LogLevel::Error, ".NET project file not found");
-                    Vec::new()
-                }
-            },
-            Language::R(_) => match check_which_r_file_exists(project_path) {
-                Some(r_package_file) => {
-                    let project_path = Path::new(project_path).join(&r_package_file);
-                    log(
-                        LogLevel::Info,
-                        &format!("Parsing R project: {}", project_path.display()),
-                    );
+    let licenses = parse_dependencies(root, registry, config, no_local)?;
+    // Lookups that failed this time are retried on the next run instead of being cached
+    if licenses.iter().any(LicenseInfo::has_unknown_license) {
+        log(
+            LogLevel::Info,
+            &format!(
+                "Not caching analysis of {}: some licenses are unresolved",
+                root.path.display()
+            ),
+        );
+        return Ok(licenses);
+    }
+    if let Err(e) = crate::cache::save_analysis(&key, &root.path, &manifest_hash, &licenses) {
+        log(
+            LogLevel::Warn,
+            &format!("Failed to cache analysis of {}: {e}", root.path.display()),
+        );
+    }
+    Ok(licenses)
+}
 
-                    indicator.update_progress(&format!("analyzing {r_package_file}"));
+/// Parse dependencies with the parser registered for the project type
+fn parse_dependencies(
+    root: &ProjectRoot,
+    registry: &ParserRegistry,
+    config: &crate::config::FeludaConfig,
+    no_local: bool,
+) -> FeludaResult<Vec<LicenseInfo>> {
+    let project_path = &root.path;
+    let parser = registry.get(root.project_type).ok_or_else(|| {
+        FeludaError::Parser(format!(
+            "No parser registered for {:?} projects",
+            root.project_type
+        ))
+    })?;
+
+    cli::with_spinner(&format!("🔎: {}", project_path.display()), |indicator| {
+        let Some(project_file) = root
+            .manifest
+            .clone()
+            .or_else(|| find_project_file(project_path, parser))
+        else {
+            log(
+                LogLevel::Error,
+                &format!("{} project file not found", parser.language_name()),
+            );
+            return Ok(Vec::new());
+        };
 
-                    match project_path.to_str() {
-                        Some(path_str) => {
-                            let deps = analyze_r_licenses(path_str, config);
-                            indicator
-                                .update_progress(&format!("found {} dependencies", deps.len()));
-                            deps
-                        }
-                        None => {
-                            log(LogLevel::Error, "Failed to convert R path to string");
-                            Vec::new()
-                        }
-                    }
-                }
-                None => {
-                    log(LogLevel::Error, "R package file not found");
-                    Vec::new()
-                }
-            },
-        }
-    });
+        let project_file_path = project_path.join(&project_file);
+        log(
+            LogLevel::Info,
+            &format!(
+                "Parsing {} project: {}",
+                parser.language_name(),
+                project_file_path.display()
+            ),
+        );
 
-    Ok(licenses)
+        indicator.update_progress(&format!("analyzing {project_file}"));
+        let deps = parser.parse_dependencies(&project_file_path, config, no_local)?;
+        indicator.update_progress(&format!("found {} dependencies", deps.len()));
+        Ok(deps)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{DependencyLimit, FeludaConfig, MaxDepsAction};
+    use crate::languages::{CPP_PATHS, C_PATHS, HASKELL_PATHS, PYTHON_PATHS};
+    use crate::licenses::test_dependency;
 
     #[test]
     fn test_matches_language() {
@@ -625,9 +712,33 @@ mod tests {
         assert!(matches_language(Language::Go("go.mod"), "GO"));
         assert!(matches_language(Language::Go("go.mod"), "Go"));
 
+        assert!(matches_language(Language::Elixir("mix.lock"), "elixir"));
+        assert!(matches_language(
+            Language::Haskell(&HASKELL_PATHS),
+            "haskell"
+        ));
+        assert_eq!(
+            Language::from_file_name("my-app.cabal"),
+            Some(Language::Haskell(&HASKELL_PATHS))
+        );
+        assert_eq!(
+            Language::from_file_name("mix.lock"),
+            Some(Language::Elixir("mix.lock"))
+        );
+        assert!(matches_language(Language::Perl("cpanfile"), "perl"));
+        assert_eq!(
+            Language::from_file_name("cpanfile"),
+            Some(Language::Perl("cpanfile"))
+        );
+        assert_eq!(Language::from_file_name("cpanfile.snapshot"), None);
+
         assert!(matches_language(Language::Python(&PYTHON_PATHS), "python"));
         assert!(matches_language(Language::Python(&PYTHON_PATHS), "PYTHON"));
         assert!(matches_language(Language::Python(&PYTHON_PATHS), "Python"));
+        assert_eq!(
+            Language::from_file_name("environment.yml"),
+            Some(Language::Python(&PYTHON_PATHS))
+        );
 
         assert!(!matches_language(Language::Rust("Cargo.toml"), "node"));
         assert!(!matches_language(Language::Node("package.json"), "python"));
@@ -641,16 +752,19 @@ mod tests {
     }
 
     #[test]
-    fn test_check_which_python_file_exists() {
+    fn test_find_project_file() {
         let temp_dir = tempfile::TempDir::new().unwrap();
+        let registry = ParserRegistry::builtin();
+        let python = registry.get(Language::Python(&PYTHON_PATHS)).unwrap();
+        let find_python_file = |path| find_project_file(path, python);
 
         // Test when no Python files exist
-        let result = check_which_python_file_exists(temp_dir.path());
+        let result = find_python_file(temp_dir.path());
         assert_eq!(result, None);
 
         // Test when requirements.txt exists
         std::fs::write(temp_dir.path().join("requirements.txt"), "requests==2.28.1").unwrap();
-        let result = check_which_python_file_exists(temp_dir.path());
+        let result = find_python_file(temp_dir.path());
         assert_eq!(result, Some("requirements.txt".to_string()));
 
         // Test when multiple Python files exist
@@ -660,8 +774,433 @@ mod tests {
         )
         .unwrap();
         std::fs::write(temp_dir.path().join("Pipfile.lock"), "{}").unwrap();
-        let result = check_which_python_file_exists(temp_dir.path());
+        let result = find_python_file(temp_dir.path());
         assert_eq!(result, Some("requirements.txt".to_string()));
+
+        // Extension patterns match any file name
+        let dotnet = registry.get(Language::DotNet(&[])).unwrap();
+        assert_eq!(find_project_file(temp_dir.path(), dotnet), None);
+        std::fs::write(temp_dir.path().join("App.fsproj"), "<Project />").unwrap();
+        assert_eq!(
+            find_project_file(temp_dir.path(), dotnet),
+            Some("App.fsproj".to_string())
+        );
+    }
+
+    fn dependency(name: &str, version: &str, license: &str, ecosystem: &str) -> LicenseInfo {
+        LicenseInfo {
+            osi_status: crate::licenses::OsiStatus::Approved,
+            ecosystem: ecosystem.to_string(),
+            ..test_dependency(name, version, license)
+        }
+    }
+
+    #[test]
+    fn test_deduplicate_licenses() {
+        let lodash = dependency("lodash", "4.17.21", "MIT", "npm");
+        let licenses = vec![
+            LicenseInfo {
+                dependencies: vec!["a".to_string()],
+                ..lodash.clone()
+            },
+            dependency("serde", "1.0.0", "MIT", "cargo"),
+            LicenseInfo {
+                dependencies: vec!["a".to_string(), "b".to_string()],
+                is_direct: Some(true),
+                ..lodash.clone()
+            },
+            lodash.clone(),
+            // Differs in version, license or ecosystem, so kept apart
+            dependency("lodash", "4.17.20", "MIT", "npm"),
+            dependency("lodash", "4.17.21", "ISC", "npm"),
+            dependency("lodash", "4.17.21", "MIT", "pypi"),
+        ];
+
+        let deduplicated = deduplicate_licenses(licenses);
+
+        assert_eq!(deduplicated.len(), 5);
+        assert_eq!(deduplicated[0].name, "lodash");
+        assert_eq!(deduplicated[0].occurrences, 3);
+        assert_eq!(deduplicated[0].dependencies, ["a", "b"]);
+        assert_eq!(deduplicated[0].is_direct, Some(true));
+        assert_eq!(deduplicated[1].name, "serde");
+        assert!(deduplicated[1..].iter().all(|info| info.occurrences == 1));
+
+        let json = serde_json::to_value(&deduplicated[0]).unwrap();
+        assert_eq!(json["occurrences"], 3);
+    }
+
+    #[test]
+    fn test_occurrences_default_when_missing() {
+        let info: LicenseInfo = serde_json::from_str(
+            r#"{"name": "lodash", "version": "4.17.21", "license": "MIT", "is_restrictive": false,
+                "compatibility": "Unknown", "osi_status": "Approved", "ecosystem": "npm"}"#,
+        )
+        .unwrap();
+        assert_eq!(info.occurrences, 1);
+    }
+
+    /// Stands in for a language's analyzer, returning one dependency named after the file
+    struct StubParser;
+
+    impl LanguageParser for StubParser {
+        fn parse_dependencies(
+            &self,
+            project_file: &Path,
+            _config: &crate::config::FeludaConfig,
+            _no_local: bool,
+        ) -> FeludaResult<Vec<LicenseInfo>> {
+            Ok(vec![LicenseInfo {
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ecosystem: "stub".to_string(),
+                ..LicenseInfo::new(
+                    project_file.file_name().unwrap().to_string_lossy(),
+                    "1.0.0".to_string(),
+                    Some("MIT".to_string()),
+                )
+            }])
+        }
+
+        fn language_name(&self) -> &'static str {
+            "Stub"
+        }
+
+        fn supported_files(&self) -> &'static [&'static str] {
+            &["stub.lock", ".stub"]
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatches_by_language() {
+        let registry = ParserRegistry::builtin();
+        let names = [
+            (Language::Rust("Cargo.toml"), "Rust"),
+            (Language::Node("package.json"), "Node.js"),
+            (Language::Go("go.mod"), "Go"),
+            (Language::Python(&PYTHON_PATHS), "Python"),
+            (Language::DotNet(&[]), ".NET"),
+            (Language::Haskell(&HASKELL_PATHS), "Haskell"),
+        ];
+        for (language, name) in names {
+            assert_eq!(registry.get(language).unwrap().language_name(), name);
+        }
+        assert_eq!(
+            registry
+                .get(Language::Go("go.mod"))
+                .unwrap()
+                .supported_files(),
+            ["go.mod"]
+        );
+
+        assert!(ParserRegistry::default()
+            .get(Language::Rust("Cargo.toml"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_dependencies_uses_registered_parser() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("app.stub"), "").unwrap();
+        let root = ProjectRoot {
+            path: temp_dir.path().to_path_buf(),
+            project_type: Language::Perl("cpanfile"),
+            manifest: None,
+        };
+        let config = crate::config::FeludaConfig::default();
+
+        let mut registry = ParserRegistry::default();
+        let err = parse_dependencies(&root, &registry, &config, false).unwrap_err();
+        assert!(err.to_string().contains("No parser registered"));
+
+        registry.register(Language::Perl("cpanfile"), Box::new(StubParser));
+        let deps = parse_dependencies(&root, &registry, &config, false).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "app.stub");
+        assert_eq!(deps[0].ecosystem, "stub");
+    }
+
+    #[test]
+    fn test_set_license_compatibility_honors_strict() {
+        let project_license = Some("Some-Custom-License".to_string());
+        let compatibility = |strict| {
+            let mut licenses = [test_dependency("dual", "1.0.0", "MIT OR Apache-2.0")];
+            let config = FeludaConfig {
+                strict,
+                ..Default::default()
+            };
+            set_license_compatibility(&mut licenses, &project_license, &config);
+            licenses[0].compatibility
+        };
+
+        assert_eq!(compatibility(false), LicenseCompatibility::Unknown);
+        assert_eq!(compatibility(true), LicenseCompatibility::Incompatible);
+    }
+
+    /// Fails the test if the analyzer runs, e.g. because a cached analysis was not reused
+    struct UnreachableParser;
+
+    impl LanguageParser for UnreachableParser {
+        fn parse_dependencies(
+            &self,
+            project_file: &Path,
+            _config: &crate::config::FeludaConfig,
+            _no_local: bool,
+        ) -> FeludaResult<Vec<LicenseInfo>> {
+            panic!("{} was analyzed again", project_file.display());
+        }
+
+        fn language_name(&self) -> &'static str {
+            "Python"
+        }
+
+        fn supported_files(&self) -> &'static [&'static str] {
+            &PYTHON_PATHS
+        }
+    }
+
+    /// Resolves every Python project to `requests` under a fixed license
+    struct FixedLicenseParser(&'static str);
+
+    impl LanguageParser for FixedLicenseParser {
+        fn parse_dependencies(
+            &self,
+            _project_file: &Path,
+            _config: &crate::config::FeludaConfig,
+            _no_local: bool,
+        ) -> FeludaResult<Vec<LicenseInfo>> {
+            Ok(vec![test_dependency("requests", "2.31.0", self.0)])
+        }
+
+        fn language_name(&self) -> &'static str {
+            "Python"
+        }
+
+        fn supported_files(&self) -> &'static [&'static str] {
+            &PYTHON_PATHS
+        }
+    }
+
+    fn fixed_license_registry(license: &'static str) -> ParserRegistry {
+        let mut registry = ParserRegistry::default();
+        registry.register(
+            Language::Python(&PYTHON_PATHS),
+            Box::new(FixedLicenseParser(license)),
+        );
+        registry
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_incremental_analysis_reuses_unchanged_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        std::fs::write(project.join("requirements.txt"), "requests==2.31.0\n").unwrap();
+        let root = ProjectRoot {
+            path: project.to_path_buf(),
+            project_type: Language::Python(&PYTHON_PATHS),
+            manifest: None,
+        };
+        let config = crate::config::FeludaConfig::default();
+        let mut cached_only = ParserRegistry::default();
+        cached_only.register(Language::Python(&PYTHON_PATHS), Box::new(UnreachableParser));
+
+        crate::network::set_offline_mode(true);
+        crate::cache::set_incremental_disabled(false);
+        let runs = crate::cache::with_test_cache_dir(cache_dir.path(), || {
+            let first = parse_dependencies_incremental(
+                &root,
+                &fixed_license_registry("Apache-2.0"),
+                &config,
+                false,
+            )
+            .unwrap();
+            // Identical manifests: nothing is parsed or resolved, so nothing hits the network
+            let second =
+                parse_dependencies_incremental(&root, &cached_only, &config, false).unwrap();
+
+            // A changed lockfile invalidates the cached analysis
+            std::fs::write(project.join("poetry.lock"), "# changed\n").unwrap();
+            let changed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                parse_dependencies_incremental(&root, &cached_only, &config, false)
+            }));
+
+            crate::cache::set_incremental_disabled(true);
+            let bypassed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                parse_dependencies_incremental(&root, &cached_only, &config, false)
+            }));
+            (first, second, changed.is_err(), bypassed.is_err())
+        });
+        crate::network::set_offline_mode(false);
+
+        let (first, second, changed_reanalyzed, bypass_reanalyzed) = runs;
+        assert_eq!(first.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&first).unwrap(),
+            serde_json::to_value(&second).unwrap()
+        );
+        assert!(changed_reanalyzed);
+        assert!(bypass_reanalyzed);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_incremental_analysis_skips_unresolved_licenses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("requirements.txt"),
+            "requests==2.31.0\n",
+        )
+        .unwrap();
+        let root = ProjectRoot {
+            path: temp_dir.path().to_path_buf(),
+            project_type: Language::Python(&PYTHON_PATHS),
+            manifest: None,
+        };
+        let config = crate::config::FeludaConfig::default();
+        let mut cached_only = ParserRegistry::default();
+        cached_only.register(Language::Python(&PYTHON_PATHS), Box::new(UnreachableParser));
+
+        crate::cache::set_incremental_disabled(false);
+        let reanalyzed = crate::cache::with_test_cache_dir(cache_dir.path(), || {
+            parse_dependencies_incremental(
+                &root,
+                &fixed_license_registry("Unknown (failed to retrieve)"),
+                &config,
+                false,
+            )
+            .unwrap();
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                parse_dependencies_incremental(&root, &cached_only, &config, false)
+            }))
+            .is_err()
+        });
+        crate::cache::set_incremental_disabled(true);
+
+        assert!(reanalyzed);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analysis_fingerprint_skips_open_node_projects() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let root = ProjectRoot {
+            path: project.to_path_buf(),
+            project_type: Language::Node("package.json"),
+            manifest: None,
+        };
+        let config = FeludaConfig::default();
+        let hash = || analysis_fingerprint(&root, &config, false).map(|(_, hash)| hash);
+
+        std::fs::write(project.join("package.json"), r#"{"name": "app"}"#).unwrap();
+        assert!(hash().is_none(), "versions come from node_modules");
+
+        std::fs::write(project.join("package-lock.json"), "{}").unwrap();
+        let locked = hash().unwrap();
+        std::fs::write(project.join(FELUDAIGNORE_FILE), "vendor/\n").unwrap();
+        assert_ne!(hash().unwrap(), locked);
+
+        std::fs::write(
+            project.join("package.json"),
+            r#"{"name": "app", "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        assert!(hash().is_none(), "workspace members are not hashed");
+
+        std::fs::write(project.join("package.json"), r#"{"name": "app"}"#).unwrap();
+        std::fs::write(project.join("pnpm-workspace.yaml"), "packages: []\n").unwrap();
+        assert!(hash().is_none());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analysis_fingerprint_covers_license_settings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("requirements.txt"),
+            "requests==2.31.0\n",
+        )
+        .unwrap();
+        let root = ProjectRoot {
+            path: temp_dir.path().to_path_buf(),
+            project_type: Language::Python(&PYTHON_PATHS),
+            manifest: None,
+        };
+        let hash = |config: &FeludaConfig| analysis_fingerprint(&root, config, false).unwrap().1;
+
+        let default = hash(&FeludaConfig::default());
+        assert_eq!(default, hash(&FeludaConfig::default()));
+
+        let mut with_override = FeludaConfig::default();
+        with_override
+            .overrides
+            .insert("requests".to_string(), "MIT".to_string());
+        let mut with_registry = FeludaConfig::default();
+        with_registry.registries.pypi = Some("https://pypi.example.com".to_string());
+        let with_manifest = FeludaConfig {
+            manifest: Some(temp_dir.path().join("requirements.txt")),
+            ..Default::default()
+        };
+        for config in [&with_override, &with_registry, &with_manifest] {
+            assert_ne!(hash(config), default);
+        }
+
+        crate::network::set_registries(crate::config::RegistryConfig {
+            npm: Some("https://npm.example.com".to_string()),
+            ..Default::default()
+        });
+        let with_mirror = hash(&FeludaConfig::default());
+        crate::network::set_registries(crate::config::RegistryConfig::default());
+        assert_ne!(with_mirror, default);
+
+        // Tokens and logging settings do not invalidate cached analyses
+        std::env::set_var("FELUDA_GITHUB_TOKEN", "token");
+        std::env::set_var("FELUDA_LOG_FORMAT", "json");
+        let with_unrelated_env = hash(&FeludaConfig::default());
+        std::env::remove_var("FELUDA_GITHUB_TOKEN");
+        std::env::remove_var("FELUDA_LOG_FORMAT");
+        assert_eq!(with_unrelated_env, default);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_builtin_parsers_through_trait_objects() {
+        let registry = ParserRegistry::builtin();
+        let config = crate::config::FeludaConfig::default();
+        let fixtures = [
+            (
+                Language::Python(&PYTHON_PATHS),
+                "requirements.txt",
+                "requests==2.31.0\n",
+                "pypi",
+            ),
+            (
+                Language::Go("go.mod"),
+                "go.mod",
+                "module example.com/app\n\ngo 1.21\n\nrequire github.com/pkg/errors v0.9.1\n",
+                "go",
+            ),
+        ];
+
+        for (language, file_name, content, ecosystem) in fixtures {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(temp_dir.path().join(file_name), content).unwrap();
+            let root = ProjectRoot {
+                path: temp_dir.path().to_path_buf(),
+                project_type: language,
+                manifest: None,
+            };
+
+            crate::network::set_offline_mode(true);
+            let result = parse_dependencies(&root, &registry, &config, false);
+            crate::network::set_offline_mode(false);
+
+            let deps = result.unwrap();
+            assert_eq!(deps.len(), 1, "{file_name}");
+            assert_eq!(deps[0].ecosystem, ecosystem);
+        }
     }
 
     #[test]
@@ -685,6 +1224,30 @@ mod tests {
         assert_eq!(result[0].path, root_path);
     }
 
+    #[test]
+    fn test_find_project_roots_one_root_per_language() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root_path = temp_dir.path();
+        for file_name in [
+            "pyproject.toml",
+            "poetry.lock",
+            "requirements.txt",
+            "go.mod",
+        ] {
+            std::fs::write(root_path.join(file_name), "").unwrap();
+        }
+
+        let result = find_project_roots(root_path.to_str().unwrap()).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result
+                .iter()
+                .filter(|root| matches!(root.project_type, Language::Python(_)))
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_parse_root_with_language_filter() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -696,35 +1259,35 @@ mod tests {
         std::fs::write(root_path.join("requirements.txt"), "# No dependencies").unwrap();
 
         // Test filtering by node
-        let result = parse_root(root_path, Some("node"), false, false);
+        let result = parse_root(root_path, Some("node"));
         assert!(result.is_ok());
 
         // Test filtering by go
-        let result = parse_root(root_path, Some("go"), false, false);
+        let result = parse_root(root_path, Some("go"));
         assert!(result.is_ok());
 
         // Test filtering by python
-        let result = parse_root(root_path, Some("python"), false, false);
+        let result = parse_root(root_path, Some("python"));
         assert!(result.is_ok());
 
         // Test filtering by non-existent language
-        let result = parse_root(root_path, Some("java"), false, false);
+        let result = parse_root(root_path, Some("java"));
         assert!(result.is_ok());
         let licenses = result.unwrap();
         assert!(licenses.is_empty());
 
         // Test case-insensitive filtering
-        let result = parse_root(root_path, Some("NODE"), false, false);
+        let result = parse_root(root_path, Some("NODE"));
         assert!(result.is_ok());
 
-        let result = parse_root(root_path, Some("Python"), false, false);
+        let result = parse_root(root_path, Some("Python"));
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_parse_root_no_projects() {
         let temp_dir = tempfile::TempDir::new().unwrap();
-        let result = parse_root(temp_dir.path(), None, false, false).unwrap();
+        let result = parse_root(temp_dir.path(), None).unwrap();
         assert!(result.is_empty());
     }
 
@@ -747,7 +1310,7 @@ mod tests {
         std::fs::write(root_path.join("go.mod"), "module test\n\ngo 1.19").unwrap();
         std::fs::write(root_path.join("requirements.txt"), "# No dependencies").unwrap();
 
-        let result = parse_root(root_path, None, false, false);
+        let result = parse_root(root_path, None);
         assert!(result.is_ok());
     }
 
@@ -756,6 +1319,7 @@ mod tests {
         let project_root = ProjectRoot {
             path: std::path::PathBuf::from("/test/path"),
             project_type: Language::Rust("Cargo.toml"),
+            manifest: None,
         };
 
         let debug_str = format!("{project_root:?}");
@@ -796,6 +1360,7 @@ mod tests {
         let rust_project_root = ProjectRoot {
             path: temp_dir.path().to_path_buf(),
             project_type: Language::Rust("Cargo.toml"),
+            manifest: None,
         };
 
         // Create Cargo.toml without lib.rs
@@ -806,7 +1371,12 @@ mod tests {
         .unwrap();
 
         let config = crate::config::FeludaConfig::default();
-        let result = parse_dependencies(&rust_project_root, &config, false);
+        let result = parse_dependencies(
+            &rust_project_root,
+            &ParserRegistry::builtin(),
+            &config,
+            false,
+        );
         assert!(result.is_ok());
         let licenses = result.unwrap();
         assert!(licenses.is_empty());
@@ -819,13 +1389,19 @@ mod tests {
         let node_project_root = ProjectRoot {
             path: temp_dir.path().to_path_buf(),
             project_type: Language::Node("package.json"),
+            manifest: None,
         };
 
         // Create invalid package.json
         std::fs::write(temp_dir.path().join("package.json"), "invalid json content").unwrap();
 
         let config = crate::config::FeludaConfig::default();
-        let result = parse_dependencies(&node_project_root, &config, false);
+        let result = parse_dependencies(
+            &node_project_root,
+            &ParserRegistry::builtin(),
+            &config,
+            false,
+        );
         assert!(result.is_ok());
         let licenses = result.unwrap();
         assert!(licenses.is_empty());
@@ -838,13 +1414,19 @@ mod tests {
         let python_project_root = ProjectRoot {
             path: temp_dir.path().to_path_buf(),
             project_type: Language::Python(&PYTHON_PATHS),
+            manifest: None,
         };
 
         // Create empty requirements.txt
         std::fs::write(temp_dir.path().join("requirements.txt"), "").unwrap();
 
         let config = crate::config::FeludaConfig::default();
-        let result = parse_dependencies(&python_project_root, &config, false);
+        let result = parse_dependencies(
+            &python_project_root,
+            &ParserRegistry::builtin(),
+            &config,
+            false,
+        );
         assert!(result.is_ok());
         let licenses = result.unwrap();
         assert!(licenses.is_empty());
@@ -852,9 +1434,371 @@ mod tests {
 
     #[test]
     fn test_parse_root_invalid_path() {
-        let result = parse_root("/definitely/nonexistent/path", None, false, false);
+        let result = parse_root("/definitely/nonexistent/path", None);
         assert!(result.is_ok());
         let licenses = result.unwrap();
         assert!(licenses.is_empty());
     }
+
+    #[test]
+    fn test_manifest_root_uses_file_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let locks_dir = temp_dir.path().join("ci").join("locks");
+        std::fs::create_dir_all(&locks_dir).unwrap();
+        let manifest = locks_dir.join("package.json");
+        std::fs::write(&manifest, r#"{"name": "test"}"#).unwrap();
+
+        let root = manifest_root(&manifest).unwrap();
+        assert_eq!(root.project_type, Language::Node("package.json"));
+        assert_eq!(root.path, locks_dir);
+        assert_eq!(root.manifest.as_deref(), Some("package.json"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_root_with_explicit_package_json() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("project");
+        let locks_dir = temp_dir.path().join("locks");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::create_dir_all(&locks_dir).unwrap();
+        // A Go project under --path must not be picked up when a manifest is given
+        std::fs::write(project_dir.join("go.mod"), "module test").unwrap();
+        let manifest = locks_dir.join("package.json");
+        std::fs::write(
+            &manifest,
+            r#"{"name": "test", "dependencies": {"left-pad": "1.3.0"}}"#,
+        )
+        .unwrap();
+
+        crate::network::set_offline_mode(true);
+        let config = FeludaConfig {
+            manifest: Some(manifest),
+            ..Default::default()
+        };
+        let result = parse_root_with_config(&project_dir, None, &config, false);
+        crate::network::set_offline_mode(false);
+
+        let names: Vec<String> = result.unwrap().into_iter().map(|info| info.name).collect();
+        assert!(names.iter().any(|name| name == "left-pad"));
+    }
+
+    #[test]
+    fn test_parse_root_with_unrecognized_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("deps.lock");
+        std::fs::write(&manifest, "").unwrap();
+
+        let config = FeludaConfig {
+            manifest: Some(manifest),
+            ..Default::default()
+        };
+        let result = parse_root_with_config(temp_dir.path(), None, &config, false);
+        match result {
+            Err(FeludaError::Parser(message)) => assert!(message.contains("deps.lock")),
+            other => panic!("expected a parser error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_root_enforces_max_deps() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("requirements.txt"),
+            "requests==2.31.0\nflask==3.0.0\nclick==8.1.7\n",
+        )
+        .unwrap();
+        let parse = |max_deps| {
+            let config = FeludaConfig {
+                max_deps,
+                ..Default::default()
+            };
+            parse_root_with_config(temp_dir.path(), None, &config, false)
+        };
+
+        crate::network::set_offline_mode(true);
+        let within = parse(DependencyLimit::new(Some(3), MaxDepsAction::Error));
+        let exceeded = parse(DependencyLimit::new(Some(2), MaxDepsAction::Error));
+        let truncated = parse(DependencyLimit::new(Some(2), MaxDepsAction::Truncate));
+        crate::network::set_offline_mode(false);
+
+        assert_eq!(within.unwrap().len(), 3);
+        assert!(matches!(exceeded, Err(FeludaError::Parser(_))));
+        assert_eq!(truncated.unwrap().len(), 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_root_applies_license_overrides() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        let manifest = temp_dir.path().join("requirements.txt");
+        std::fs::write(&manifest, "requests==2.31.0\nflask==3.0.0\n").unwrap();
+
+        let config = FeludaConfig {
+            manifest: Some(manifest),
+            overrides: [
+                ("requests", "GPL-3.0-only"),
+                ("flask", "GPL-3.0-only"),
+                ("flask@3.0.0", "Apache-2.0"),
+            ]
+            .into_iter()
+            .map(|(key, license)| (key.to_string(), license.to_string()))
+            .collect(),
+            ..Default::default()
+        };
+
+        crate::network::set_offline_mode(true);
+        let result = parse_root_with_config(temp_dir.path(), None, &config, false);
+        crate::network::set_offline_mode(false);
+
+        let licenses = result.unwrap();
+        let requests = licenses
+            .iter()
+            .find(|info| info.name == "requests")
+            .unwrap();
+        assert_eq!(requests.license.as_deref(), Some("GPL-3.0-only"));
+        assert!(requests.is_restrictive);
+        assert_eq!(requests.compatibility, LicenseCompatibility::Incompatible);
+
+        let flask = licenses.iter().find(|info| info.name == "flask").unwrap();
+        assert_eq!(flask.license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(flask.compatibility, LicenseCompatibility::Compatible);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_analyzers_stamp_ecosystem() {
+        let fixtures = [
+            (
+                "package.json",
+                r#"{"name": "app", "dependencies": {"left-pad": "1.3.0"}}"#,
+                "npm",
+            ),
+            ("requirements.txt", "requests==2.31.0\n", "pypi"),
+            (
+                "go.mod",
+                "module example.com/app\n\ngo 1.21\n\nrequire github.com/pkg/errors v0.9.1\n",
+                "go",
+            ),
+            ("configure.ac", "AC_CHECK_LIB([z], [deflate])\n", "c"),
+            (
+                "vcpkg.json",
+                r#"{"name": "app", "dependencies": ["fmt"]}"#,
+                "cpp",
+            ),
+            (
+                "app.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk"><ItemGroup><PackageReference Include="Newtonsoft.Json" Version="13.0.3" /></ItemGroup></Project>"#,
+                "nuget",
+            ),
+            (
+                "mix.lock",
+                "%{\n  \"jason\": {:hex, :jason, \"1.4.1\", \"af15\", [:mix], [], \"hexpm\", \"fbb0\"},\n}\n",
+                "hex",
+            ),
+            ("cpanfile", "requires 'Moose', '2.2';\n", "cpan"),
+            (
+                "DESCRIPTION",
+                "Package: app\nVersion: 1.0.0\nImports: jsonlite\n",
+                "r",
+            ),
+            (
+                "app.cabal",
+                "name: app\nversion: 1.0.0\nlibrary\n  build-depends: text\n",
+                "hackage",
+            ),
+        ];
+
+        for (file_name, content, ecosystem) in fixtures {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join(file_name);
+            std::fs::write(&manifest, content).unwrap();
+
+            crate::network::set_offline_mode(true);
+            let config = FeludaConfig {
+                manifest: Some(manifest),
+                ..Default::default()
+            };
+            let result = parse_root_with_config(temp_dir.path(), None, &config, false);
+            crate::network::set_offline_mode(false);
+
+            let licenses = result.unwrap();
+            assert!(!licenses.is_empty(), "{file_name} found no dependencies");
+            assert!(
+                licenses.iter().all(|info| info.ecosystem == ecosystem),
+                "{file_name} should report {ecosystem}"
+            );
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_include_dev_controls_dev_dependencies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "app", "dependencies": {"left-pad": "1.3.0"}, "devDependencies": {"jest": "29.7.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("cpanfile"),
+            "requires 'Moo', '2.004';\non 'test' => sub {\n    requires 'Test::More', '0.98';\n};\n",
+        )
+        .unwrap();
+        for (name, extra) in [
+            (
+                "app",
+                "[dependencies]\nprodlib = { path = \"prodlib\" }\n\
+                 [dev-dependencies]\ndevlib = { path = \"devlib\" }\n",
+            ),
+            ("prodlib", ""),
+            ("devlib", ""),
+        ] {
+            let dir = if name == "app" {
+                root.to_path_buf()
+            } else {
+                root.join(name)
+            };
+            std::fs::create_dir_all(dir.join("src")).unwrap();
+            std::fs::write(
+                dir.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\nlicense = \"MIT\"\n{extra}"
+                ),
+            )
+            .unwrap();
+            std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        }
+
+        let scan = |include_dev| {
+            let config = FeludaConfig {
+                include_dev,
+                ..Default::default()
+            };
+            let names: Vec<String> = parse_root_with_config(root, None, &config, false)
+                .unwrap()
+                .into_iter()
+                .map(|info| info.name)
+                .collect();
+            names
+        };
+
+        // cargo metadata fails if an earlier test left the working directory deleted
+        std::env::set_current_dir(root).unwrap();
+        crate::network::set_offline_mode(true);
+        let production = scan(false);
+        let with_dev = scan(true);
+        crate::network::set_offline_mode(false);
+
+        for (production_dep, dev_dep) in [
+            ("left-pad", "jest"),
+            ("Moo", "Test::More"),
+            ("prodlib", "devlib"),
+        ] {
+            assert!(
+                production.iter().any(|name| name == production_dep),
+                "{production_dep}"
+            );
+            assert!(!production.iter().any(|name| name == dev_dep), "{dev_dep}");
+            assert!(
+                with_dev.iter().any(|name| name == production_dep),
+                "{production_dep}"
+            );
+            assert!(with_dev.iter().any(|name| name == dev_dep), "{dev_dep}");
+        }
+    }
+
+    #[test]
+    fn test_manifest_root_missing_file() {
+        let result = manifest_root(Path::new("/definitely/nonexistent/package.json"));
+        assert!(matches!(result, Err(FeludaError::Parser(_))));
+    }
+
+    #[test]
+    fn test_feludaignore_skips_root_manifests() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root_path = temp_dir.path();
+        std::fs::write(root_path.join("package.json"), "{}").unwrap();
+        std::fs::write(root_path.join("go.mod"), "module test").unwrap();
+        std::fs::write(
+            root_path.join(FELUDAIGNORE_FILE),
+            "# generated tooling module\n\ngo.mod\n",
+        )
+        .unwrap();
+
+        let result = find_project_roots(root_path).unwrap();
+        let project_types: Vec<_> = result.iter().map(|r| r.project_type).collect();
+        assert_eq!(project_types, vec![Language::Node("package.json")]);
+    }
+
+    #[test]
+    fn test_is_path_ignored_matches_subtrees() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root_path = temp_dir.path();
+        std::fs::write(
+            root_path.join(FELUDAIGNORE_FILE),
+            "vendor/\n**/fixtures\n!fixtures/keep\n",
+        )
+        .unwrap();
+
+        let rules = load_ignore_rules(root_path);
+        assert!(is_path_ignored(
+            &rules,
+            &root_path.join("vendor/lib/package.json"),
+            false
+        ));
+        assert!(is_path_ignored(
+            &rules,
+            &root_path.join("tests/fixtures"),
+            true
+        ));
+        assert!(!is_path_ignored(
+            &rules,
+            &root_path.join("package.json"),
+            false
+        ));
+        assert!(!is_path_ignored(
+            &rules,
+            Path::new("/elsewhere/vendor/package.json"),
+            false
+        ));
+
+        let empty = load_ignore_rules(&root_path.join("missing"));
+        assert!(!is_path_ignored(&empty, &root_path.join("vendor"), true));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_feludaignore_excludes_vendored_workspace() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root_path = temp_dir.path();
+        let vendored = root_path.join("vendor").join("lib");
+        std::fs::create_dir_all(&vendored).unwrap();
+        std::fs::write(
+            root_path.join("package.json"),
+            r#"{"name": "app", "dependencies": {"left-pad": "1.3.0"}, "workspaces": ["vendor/*"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            vendored.join("package.json"),
+            r#"{"name": "lib", "dependencies": {"vendored-dep": "1.0.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(root_path.join(FELUDAIGNORE_FILE), "vendor/\n").unwrap();
+
+        crate::network::set_offline_mode(true);
+        let result = parse_root(root_path, None);
+        crate::network::set_offline_mode(false);
+
+        let names: Vec<String> = result.unwrap().into_iter().map(|info| info.name).collect();
+        assert!(names.iter().any(|name| name == "left-pad"));
+        assert!(!names.iter().any(|name| name == "vendored-dep"));
+    }
 }
@@ -0,0 +1,445 @@
+//! License policy evaluation
+//!
+//! A `[policy]` section in `.feluda.toml` maps license patterns to an action:
+//!
+//! ```toml
+//! [policy]
+//! default = "deny"                       # licenses no pattern matches
+//! allow = ["MIT", "Apache-2.0", "BSD-*"]
+//! warn = ["LGPL-*", "unknown"]
+//! deny = ["GPL-*", "AGPL-*"]
+//! ```
+//!
+//! Patterns are matched case-insensitively against the SPDX identifier, after alias
+//! normalization, and `*` matches any run of characters. The keyword `unknown` matches
+//! dependencies without a recognized license. When several patterns match, an exact
+//! identifier beats a wildcard and otherwise the strictest action wins. In an `OR`
+//! expression the most lenient option counts, in an `AND` expression the strictest.
+//!
+//! `--deny-license` and `--warn-license` add exact identifiers to the policy for a single
+//! run, with or without a `[policy]` section. An identifier without an `-only` or
+//! `-or-later` suffix also covers both variants, so `--deny-license AGPL-3.0` denies
+//! `AGPL-3.0-only` and `AGPL-3.0-or-later`.
+//!
+//! Evaluation does not print anything; see `reporter::print_policy_outcome`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::debug::{FeludaError, FeludaResult};
+use crate::licenses::expression::{self, LicenseExpr};
+use crate::licenses::{aliases, LicenseInfo};
+
+/// Pattern matching dependencies without a recognized license
+pub const UNKNOWN_PATTERN: &str = "unknown";
+
+/// What a policy does with a dependency, from most lenient to strictest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    #[default]
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl std::fmt::Display for PolicyAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// License patterns per action, from the `[policy]` configuration section
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    /// Action for licenses that no pattern matches
+    #[serde(default)]
+    pub default: PolicyAction,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub warn: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl Policy {
+    /// Validates that no pattern is empty
+    pub fn validate(&self) -> FeludaResult<()> {
+        if self.rules().any(|(_, pattern)| pattern.trim().is_empty()) {
+            return Err(FeludaError::Config(
+                "Empty license pattern found in [policy]".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Add the identifiers of `--deny-license` and `--warn-license` to the policy
+    pub fn with_license_flags(mut self, deny: &[String], warn: &[String]) -> Self {
+        self.deny
+            .extend(deny.iter().flat_map(|id| flag_patterns(id)));
+        self.warn
+            .extend(warn.iter().flat_map(|id| flag_patterns(id)));
+        self
+    }
+
+    fn rules(&self) -> impl Iterator<Item = (PolicyAction, &str)> {
+        self.allow
+            .iter()
+            .map(|p| (PolicyAction::Allow, p.as_str()))
+            .chain(self.warn.iter().map(|p| (PolicyAction::Warn, p.as_str())))
+            .chain(self.deny.iter().map(|p| (PolicyAction::Deny, p.as_str())))
+    }
+
+    /// Decide the action for a dependency, with the pattern that matched (`None` = default)
+    pub fn decide(&self, info: &LicenseInfo) -> (PolicyAction, Option<&str>) {
+        match info.license.as_deref() {
+            Some(license) if !info.has_unknown_license() => self.decide_license(license),
+            _ => self.decide_id(UNKNOWN_PATTERN),
+        }
+    }
+
+    fn decide_license(&self, license: &str) -> (PolicyAction, Option<&str>) {
+        // Whole-string aliases such as "Apache License 2.0" do not parse as expressions
+        if let Some(id) = aliases::normalize_license(license) {
+            return self.decide_id(&id);
+        }
+
+        match expression::parse(license) {
+            Ok(expr) if expr.is_compound() => self.decide_expr(&expr),
+            _ => self.decide_id(license),
+        }
+    }
+
+    fn decide_expr(&self, expr: &LicenseExpr) -> (PolicyAction, Option<&str>) {
+        match expr {
+            LicenseExpr::Id(id) => self.decide_id(id),
+            // A pattern for the full `X WITH exception` form wins over the bare license
+            LicenseExpr::With(license, _) => match self.matching_rule(&expr.to_string()) {
+                Some((action, rule)) => (action, Some(rule)),
+                None => self.decide_expr(license),
+            },
+            LicenseExpr::And(left, right) => {
+                let (left, right) = (self.decide_expr(left), self.decide_expr(right));
+                if left.0 >= right.0 {
+                    left
+                } else {
+                    right
+                }
+            }
+            LicenseExpr::Or(left, right) => {
+                let (left, right) = (self.decide_expr(left), self.decide_expr(right));
+                if left.0 <= right.0 {
+                    left
+                } else {
+                    right
+                }
+            }
+        }
+    }
+
+    fn decide_id(&self, id: &str) -> (PolicyAction, Option<&str>) {
+        let id = aliases::normalize_license(id).unwrap_or_else(|| id.trim().to_string());
+        match self.matching_rule(&id) {
+            Some((action, rule)) => (action, Some(rule)),
+            None => (self.default, None),
+        }
+    }
+
+    /// The rule for an identifier: exact patterns beat wildcards, then the strictest wins
+    fn matching_rule(&self, id: &str) -> Option<(PolicyAction, &str)> {
+        self.rules()
+            .filter(|(_, pattern)| pattern_matches(pattern, id))
+            .max_by_key(|(action, pattern)| (!pattern.contains('*'), *action))
+    }
+}
+
+/// The policy action taken for one dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDecision {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+    pub action: PolicyAction,
+    /// Pattern that decided the action; `None` when the default applied
+    pub rule: Option<String>,
+}
+
+/// Result of evaluating a policy against every dependency
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyOutcome {
+    pub decisions: Vec<PolicyDecision>,
+}
+
+impl PolicyOutcome {
+    /// Dependencies whose license is allowed with a warning
+    pub fn warnings(&self) -> impl Iterator<Item = &PolicyDecision> {
+        self.with_action(PolicyAction::Warn)
+    }
+
+    /// Dependencies whose license the policy denies
+    pub fn denials(&self) -> impl Iterator<Item = &PolicyDecision> {
+        self.with_action(PolicyAction::Deny)
+    }
+
+    /// Returns true if any dependency was denied, which fails the run
+    pub fn has_denials(&self) -> bool {
+        self.denials().next().is_some()
+    }
+
+    fn with_action(&self, action: PolicyAction) -> impl Iterator<Item = &PolicyDecision> {
+        self.decisions.iter().filter(move |d| d.action == action)
+    }
+}
+
+/// The configured policy with the license flags of this run, or `None` if there is neither
+pub fn merge_license_flags(
+    policy: Option<Policy>,
+    deny: &[String],
+    warn: &[String],
+) -> Option<Policy> {
+    if deny.is_empty() && warn.is_empty() {
+        return policy;
+    }
+    Some(policy.unwrap_or_default().with_license_flags(deny, warn))
+}
+
+/// Exact patterns for a flag identifier; a bare identifier also covers its `-only` and
+/// `-or-later` variants
+fn flag_patterns(id: &str) -> Vec<String> {
+    let id = aliases::normalize_license(id).unwrap_or_else(|| id.trim().to_string());
+    let lower = id.to_lowercase();
+    if lower.ends_with("-only") || lower.ends_with("-or-later") || lower.ends_with('+') {
+        return vec![id];
+    }
+    vec![
+        format!("{id}-only"),
+        format!("{id}-or-later"),
+        format!("{id}+"),
+        id,
+    ]
+}
+
+/// Classify every dependency by the policy
+pub fn evaluate_policy(data: &[LicenseInfo], policy: &Policy) -> PolicyOutcome {
+    let decisions = data
+        .iter()
+        .map(|info| {
+            let (action, rule) = policy.decide(info);
+            PolicyDecision {
+                name: info.name.clone(),
+                version: info.version.clone(),
+                license: info.get_license(),
+                action,
+                rule: rule.map(str::to_string),
+            }
+        })
+        .collect();
+    PolicyOutcome { decisions }
+}
+
+/// Case-insensitive match where `*` stands for any run of characters
+fn pattern_matches(pattern: &str, id: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+    let id: Vec<char> = id.trim().to_lowercase().chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+    // Position after the last `*` and the id position it is currently standing in for
+    let mut backtrack: Option<(usize, usize)> = None;
+    while i < id.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p + 1, i));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == id[i] {
+            p += 1;
+            i += 1;
+        } else if let Some((star_p, star_i)) = backtrack {
+            p = star_p;
+            i = star_i + 1;
+            backtrack = Some((star_p, star_i + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, license: Option<&str>) -> LicenseInfo {
+        LicenseInfo::new(name, "1.0.0", license.map(str::to_string))
+    }
+
+    fn policy(default: PolicyAction, allow: &[&str], warn: &[&str], deny: &[&str]) -> Policy {
+        let owned = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect();
+        Policy {
+            default,
+            allow: owned(allow),
+            warn: owned(warn),
+            deny: owned(deny),
+        }
+    }
+
+    #[test]
+    fn test_deny_on_gpl() {
+        let policy = policy(PolicyAction::Allow, &[], &[], &["GPL-*", "AGPL-*"]);
+        let data = vec![
+            dependency("serde", Some("MIT")),
+            dependency("readline", Some("GPL-3.0-or-later")),
+            dependency("ghostscript", Some("AGPL-3.0-only")),
+            dependency("glib", Some("LGPL-2.1-or-later")),
+        ];
+
+        let outcome = evaluate_policy(&data, &policy);
+        assert!(outcome.has_denials());
+        let denied: Vec<&str> = outcome.denials().map(|d| d.name.as_str()).collect();
+        assert_eq!(denied, vec!["readline", "ghostscript"]);
+        assert_eq!(outcome.decisions[1].rule.as_deref(), Some("GPL-*"));
+        assert_eq!(outcome.decisions[0].rule, None);
+    }
+
+    #[test]
+    fn test_warn_on_unknown() {
+        let policy = policy(PolicyAction::Allow, &[], &[UNKNOWN_PATTERN], &[]);
+        let data = vec![
+            dependency("serde", Some("MIT")),
+            dependency("mystery", None),
+            dependency("unresolved", Some("Unknown license for unresolved: 1.0.0")),
+        ];
+
+        let outcome = evaluate_policy(&data, &policy);
+        assert!(!outcome.has_denials());
+        let warned: Vec<&str> = outcome.warnings().map(|d| d.name.as_str()).collect();
+        assert_eq!(warned, vec!["mystery", "unresolved"]);
+    }
+
+    #[test]
+    fn test_allowlist_overrides_default_deny() {
+        let policy = policy(
+            PolicyAction::Deny,
+            &["MIT", "Apache-2.0", "BSD-*"],
+            &[],
+            &[],
+        );
+        let data = vec![
+            dependency("serde", Some("MIT OR Apache-2.0")),
+            dependency("regex", Some("Apache License 2.0")),
+            dependency("libc", Some("BSD-3-Clause")),
+            dependency("nalgebra", Some("MPL-2.0")),
+            dependency("mystery", None),
+        ];
+
+        let outcome = evaluate_policy(&data, &policy);
+        let denied: Vec<&str> = outcome.denials().map(|d| d.name.as_str()).collect();
+        assert_eq!(denied, vec!["nalgebra", "mystery"]);
+        assert!(outcome.warnings().next().is_none());
+    }
+
+    #[test]
+    fn test_expressions_and_specificity() {
+        let policy = policy(
+            PolicyAction::Allow,
+            &["GPL-2.0-only WITH Classpath-exception-2.0"],
+            &["LGPL-*"],
+            &["GPL-*"],
+        );
+
+        let decide = |license| policy.decide(&dependency("dep", Some(license))).0;
+        // The lenient option of an OR can be chosen, every side of an AND applies
+        assert_eq!(decide("MIT OR GPL-3.0-only"), PolicyAction::Allow);
+        assert_eq!(decide("MIT AND LGPL-2.1-only"), PolicyAction::Warn);
+        assert_eq!(decide("LGPL-2.1-only AND GPL-2.0-only"), PolicyAction::Deny);
+        // An exact pattern beats a wildcard
+        assert_eq!(
+            decide("GPL-2.0-only WITH Classpath-exception-2.0"),
+            PolicyAction::Allow
+        );
+        assert_eq!(
+            decide("GPL-2.0-or-later WITH Bison-exception-2.2"),
+            PolicyAction::Deny
+        );
+    }
+
+    #[test]
+    fn test_deny_license_flag_covers_variants() {
+        let deny = vec!["agpl-3.0".to_string()];
+        let policy = merge_license_flags(None, &deny, &[]).unwrap();
+        let data = vec![
+            dependency("ghostscript", Some("AGPL-3.0-only")),
+            dependency("mongo-tools", Some("AGPL-3.0-or-later")),
+            dependency("legacy", Some("AGPL-3.0")),
+            dependency("readline", Some("GPL-3.0-only")),
+        ];
+
+        let outcome = evaluate_policy(&data, &policy);
+        let denied: Vec<&str> = outcome.denials().map(|d| d.name.as_str()).collect();
+        assert_eq!(denied, vec!["ghostscript", "mongo-tools", "legacy"]);
+    }
+
+    #[test]
+    fn test_deny_license_flag_expressions() {
+        let deny = vec!["AGPL-3.0".to_string(), "GPL-3.0-only".to_string()];
+        let policy = merge_license_flags(None, &deny, &[]).unwrap();
+        let decide = |license| policy.decide(&dependency("dep", Some(license))).0;
+
+        // Denied only when every option of an OR is denied
+        assert_eq!(decide("AGPL-3.0-only OR MIT"), PolicyAction::Allow);
+        assert_eq!(decide("AGPL-3.0-only OR GPL-3.0-only"), PolicyAction::Deny);
+        assert_eq!(decide("MIT AND AGPL-3.0-or-later"), PolicyAction::Deny);
+        // An explicit variant does not cover the other one
+        assert_eq!(decide("GPL-3.0-or-later"), PolicyAction::Allow);
+    }
+
+    #[test]
+    fn test_license_flags_merge_with_config() {
+        assert_eq!(merge_license_flags(None, &[], &[]), None);
+
+        let config = policy(PolicyAction::Allow, &["AGPL-*"], &[], &["GPL-*"]);
+        let warn = vec!["MPL-2.0".to_string()];
+        let deny = vec!["AGPL-3.0".to_string()];
+        let policy = merge_license_flags(Some(config), &deny, &warn).unwrap();
+
+        let outcome = evaluate_policy(
+            &[
+                dependency("ghostscript", Some("AGPL-3.0-only")),
+                dependency("firefox", Some("MPL-2.0")),
+                dependency("readline", Some("GPL-3.0-only")),
+            ],
+            &policy,
+        );
+        let denied: Vec<&str> = outcome.denials().map(|d| d.name.as_str()).collect();
+        assert_eq!(denied, vec!["ghostscript", "readline"]);
+        let warned: Vec<&str> = outcome.warnings().map(|d| d.name.as_str()).collect();
+        assert_eq!(warned, vec!["firefox"]);
+
+        let warn_only = merge_license_flags(None, &[], &warn).unwrap();
+        let outcome = evaluate_policy(&[dependency("firefox", Some("MPL-2.0"))], &warn_only);
+        assert!(!outcome.has_denials());
+        assert_eq!(outcome.warnings().count(), 1);
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("GPL-*", "gpl-3.0-only"));
+        assert!(pattern_matches("*-or-later", "LGPL-2.1-or-later"));
+        assert!(pattern_matches("BSD-*-Clause", "BSD-3-Clause"));
+        assert!(pattern_matches("*", "anything"));
+        assert!(!pattern_matches("GPL-*", "LGPL-3.0-only"));
+        assert!(!pattern_matches("MIT", "MIT-0"));
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_pattern() {
+        assert!(policy(PolicyAction::Allow, &["MIT"], &[], &[])
+            .validate()
+            .is_ok());
+        assert!(policy(PolicyAction::Allow, &[], &[" "], &[])
+            .validate()
+            .is_err());
+    }
+}
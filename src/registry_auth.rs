@@ -0,0 +1,391 @@
+//! Credentials for private package registries
+//!
+//! Requests to a registry configured with `--npm-registry`, `--pypi-index`,
+//! `--crates-registry` or `[registries]` are authenticated with the first credential found
+//! for its host, in this order:
+//!
+//! 1. `--registry-token <HOST=TOKEN>`, sent as a bearer token
+//! 2. `//<host>/:_authToken=<token>` in the project's `.npmrc`, then `~/.npmrc`
+//! 3. `machine <host> login <login> password <password>` in `$NETRC` or `~/.netrc`, sent as
+//!    basic auth
+//!
+//! The public registries never receive credentials, and tokens are never logged.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use reqwest::blocking::{Client, RequestBuilder};
+
+use crate::debug::{log, LogLevel};
+use crate::network::{private_registry_hosts, url_host};
+
+static CREDENTIALS: RwLock<Vec<(String, RegistryCredential)>> = RwLock::new(Vec::new());
+
+/// How requests to a private registry authenticate
+#[derive(Clone, PartialEq, Eq)]
+pub enum RegistryCredential {
+    /// `Authorization: Bearer <token>`
+    Token(String),
+    /// HTTP basic auth
+    Basic { login: String, password: String },
+}
+
+impl fmt::Debug for RegistryCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryCredential::Token(_) => write!(f, "Token(<redacted>)"),
+            RegistryCredential::Basic { login, .. } => f
+                .debug_struct("Basic")
+                .field("login", login)
+                .field("password", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// A `--registry-token <HOST=TOKEN>` argument
+#[derive(Clone, PartialEq, Eq)]
+pub struct RegistryToken {
+    pub host: String,
+    pub token: String,
+}
+
+impl fmt::Debug for RegistryToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistryToken")
+            .field("host", &self.host)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl FromStr for RegistryToken {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (host, token) = value
+            .split_once('=')
+            .ok_or("expected HOST=TOKEN, e.g. npm.example.com=<token>")?;
+        let host = normalize_host(host);
+        let token = token.trim();
+        if host.is_empty() || token.is_empty() {
+            return Err("expected HOST=TOKEN with a non-empty host and token".to_string());
+        }
+        Ok(Self {
+            host,
+            token: token.to_string(),
+        })
+    }
+}
+
+/// `host` or `host:port` in lowercase, accepting a full URL too
+fn normalize_host(host: &str) -> String {
+    let host = host.trim();
+    let host = host.split_once("://").map_or(host, |(_, rest)| rest);
+    let host = host.trim_start_matches("//");
+    host.split('/').next().unwrap_or("").to_lowercase()
+}
+
+/// `_authToken` entries of an `.npmrc` as (host, token), expanding `${VAR}` references
+pub fn parse_npmrc(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("//"))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let registry = key.trim().strip_suffix(":_authToken")?;
+            let token = expand_env(value.trim().trim_matches('"').trim_matches('\''));
+            let host = normalize_host(registry);
+            (!host.is_empty() && !token.is_empty()).then_some((host, token))
+        })
+        .collect()
+}
+
+fn expand_env(value: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&std::env::var(&rest[start + 2..start + end]).unwrap_or_default());
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// `machine` entries of a `.netrc` as (host, login, password)
+pub fn parse_netrc(content: &str) -> Vec<(String, String, String)> {
+    let mut entries = Vec::new();
+    let mut current: Option<(String, String, String)> = None;
+    let mut tokens = content.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" | "default" => {
+                entries.extend(current.take());
+                if token == "machine" {
+                    current = tokens
+                        .next()
+                        .map(|host| (normalize_host(host), String::new(), String::new()));
+                }
+            }
+            "login" => {
+                if let (Some(entry), Some(login)) = (current.as_mut(), tokens.next()) {
+                    entry.1 = login.to_string();
+                }
+            }
+            "password" => {
+                if let (Some(entry), Some(password)) = (current.as_mut(), tokens.next()) {
+                    entry.2 = password.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    entries.extend(current);
+    entries.retain(|(_, _, password)| !password.is_empty());
+    entries
+}
+
+fn netrc_path() -> Option<PathBuf> {
+    std::env::var_os("NETRC")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".netrc")))
+}
+
+/// Gather credentials from `--registry-token`, `.npmrc` files and `.netrc`, in precedence order
+pub fn load_registry_credentials(
+    project_path: &Path,
+    tokens: &[RegistryToken],
+) -> Vec<(String, RegistryCredential)> {
+    let mut credentials: Vec<(String, RegistryCredential)> = tokens
+        .iter()
+        .map(|t| (t.host.clone(), RegistryCredential::Token(t.token.clone())))
+        .collect();
+
+    let npmrc_paths = [
+        Some(project_path.join(".npmrc")),
+        dirs::home_dir().map(|home| home.join(".npmrc")),
+    ];
+    for path in npmrc_paths.into_iter().flatten() {
+        if let Ok(content) = fs::read_to_string(&path) {
+            credentials.extend(
+                parse_npmrc(&content)
+                    .into_iter()
+                    .map(|(host, token)| (host, RegistryCredential::Token(token))),
+            );
+        }
+    }
+
+    if let Some(content) = netrc_path().and_then(|path| fs::read_to_string(path).ok()) {
+        credentials.extend(
+            parse_netrc(&content)
+                .into_iter()
+                .map(|(host, login, password)| {
+                    (host, RegistryCredential::Basic { login, password })
+                }),
+        );
+    }
+    credentials
+}
+
+/// Whether a credential stored for `credential_host` applies to `host`; a credential without
+/// a port applies to every port of the host
+fn applies_to(credential_host: &str, host: &str) -> bool {
+    credential_host == host || host.split(':').next() == Some(credential_host)
+}
+
+/// Use these credentials for private registry requests; earlier entries win per host
+pub fn set_registry_credentials(credentials: Vec<(String, RegistryCredential)>) {
+    for host in private_registry_hosts() {
+        if credentials.iter().any(|(h, _)| applies_to(h, &host)) {
+            log(
+                LogLevel::Info,
+                &format!("Using credentials for private registry {host}"),
+            );
+        }
+    }
+    *CREDENTIALS.write().unwrap_or_else(|e| e.into_inner()) = credentials;
+}
+
+/// Credential for `url`, only when it points at a configured private registry
+fn credential_for(url: &str) -> Option<RegistryCredential> {
+    let host = url_host(url)?;
+    if !private_registry_hosts().contains(&host) {
+        return None;
+    }
+
+    let credentials = CREDENTIALS.read().unwrap_or_else(|e| e.into_inner());
+    credentials
+        .iter()
+        .find(|(h, _)| applies_to(h, &host))
+        .map(|(_, credential)| credential.clone())
+}
+
+/// GET request for a registry URL, authenticated when it targets a private registry
+pub fn registry_get(client: &Client, url: &str) -> RequestBuilder {
+    let request = client.get(url);
+    match credential_for(url) {
+        Some(RegistryCredential::Token(token)) => request.bearer_auth(token),
+        Some(RegistryCredential::Basic { login, password }) => {
+            request.basic_auth(login, Some(password))
+        }
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RegistryConfig;
+    use crate::network::set_registries;
+    use reqwest::header::AUTHORIZATION;
+
+    fn authorization(url: &str) -> Option<String> {
+        let request = registry_get(&Client::new(), url).build().unwrap();
+        request
+            .headers()
+            .get(AUTHORIZATION)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_parse_registry_token() {
+        let token: RegistryToken = "https://NPM.example.com/repo/=s3cret".parse().unwrap();
+        assert_eq!(token.host, "npm.example.com");
+        assert_eq!(token.token, "s3cret");
+        assert!(!format!("{token:?}").contains("s3cret"));
+
+        assert!("npm.example.com".parse::<RegistryToken>().is_err());
+        assert!("npm.example.com=".parse::<RegistryToken>().is_err());
+    }
+
+    #[test]
+    fn test_parse_npmrc() {
+        std::env::set_var("FELUDA_TEST_NPM_TOKEN", "from-env");
+        let npmrc = r#"
+registry=https://npm.example.com/
+//npm.example.com/:_authToken=abc123
+//npm.internal:8443/repository/npm/:_authToken="${FELUDA_TEST_NPM_TOKEN}"
+//registry.npmjs.org/:_password=ignored
+"#;
+        assert_eq!(
+            parse_npmrc(npmrc),
+            [
+                ("npm.example.com".to_string(), "abc123".to_string()),
+                ("npm.internal:8443".to_string(), "from-env".to_string()),
+            ]
+        );
+        std::env::remove_var("FELUDA_TEST_NPM_TOKEN");
+    }
+
+    #[test]
+    fn test_parse_netrc() {
+        let netrc = "machine pypi.example.com\n  login ci\n  password hunter2\n\
+                     machine nopassword.example.com login ci\n\
+                     default login anonymous password guest\n";
+        assert_eq!(
+            parse_netrc(netrc),
+            [(
+                "pypi.example.com".to_string(),
+                "ci".to_string(),
+                "hunter2".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_token_only_sent_to_private_registry() {
+        set_registries(RegistryConfig {
+            npm: Some("https://npm.example.com/repository/npm".to_string()),
+            pypi: Some("https://pypi.example.com:8443".to_string()),
+            crates: None,
+        });
+        set_registry_credentials(vec![
+            (
+                "npm.example.com".to_string(),
+                RegistryCredential::Token("s3cret".to_string()),
+            ),
+            (
+                "pypi.example.com".to_string(),
+                RegistryCredential::Basic {
+                    login: "ci".to_string(),
+                    password: "hunter2".to_string(),
+                },
+            ),
+            (
+                "registry.npmjs.org".to_string(),
+                RegistryCredential::Token("public".to_string()),
+            ),
+            (
+                "crates.io".to_string(),
+                RegistryCredential::Token("public".to_string()),
+            ),
+        ]);
+
+        assert_eq!(
+            authorization("https://npm.example.com/repository/npm/left-pad").as_deref(),
+            Some("Bearer s3cret")
+        );
+        assert_eq!(
+            authorization("https://pypi.example.com:8443/pypi/requests/2.31.0/json").as_deref(),
+            Some("Basic Y2k6aHVudGVyMg==")
+        );
+        assert_eq!(authorization("https://registry.npmjs.org/left-pad"), None);
+        assert_eq!(authorization("https://crates.io/api/v1/crates/serde"), None);
+        assert_eq!(authorization("https://evil.example.com/left-pad"), None);
+
+        set_registry_credentials(Vec::new());
+        set_registries(RegistryConfig::default());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_load_registry_credentials_precedence() {
+        let project = tempfile::TempDir::new().unwrap();
+        fs::write(
+            project.path().join(".npmrc"),
+            "//npm.example.com/:_authToken=from-npmrc\n",
+        )
+        .unwrap();
+        let netrc = project.path().join("netrc");
+        fs::write(
+            &netrc,
+            "machine npm.example.com login ci password from-netrc\n",
+        )
+        .unwrap();
+        std::env::set_var("NETRC", &netrc);
+
+        let flag: RegistryToken = "npm.example.com=from-flag".parse().unwrap();
+        let with_flag = load_registry_credentials(project.path(), &[flag]);
+        let without_flag = load_registry_credentials(project.path(), &[]);
+        std::env::remove_var("NETRC");
+
+        assert_eq!(
+            with_flag[0],
+            (
+                "npm.example.com".to_string(),
+                RegistryCredential::Token("from-flag".to_string())
+            )
+        );
+        assert_eq!(
+            without_flag[0].1,
+            RegistryCredential::Token("from-npmrc".to_string())
+        );
+        assert!(without_flag.contains(&(
+            "npm.example.com".to_string(),
+            RegistryCredential::Basic {
+                login: "ci".to_string(),
+                password: "from-netrc".to_string()
+            }
+        )));
+    }
+}
@@ -1,9 +1,32 @@
-use crate::cli::{CiFormat, OsiFilter};
-use crate::debug::{log, log_debug, log_error, LogLevel};
+use crate::cli::{CiFormat, OsiFilter, ReportSort, Severity};
+use crate::debug::{is_quiet_mode, log, log_debug, log_error, LogLevel};
+use crate::licenses::category::LicenseCategory;
 use crate::licenses::{LicenseCompatibility, LicenseInfo, OsiStatus};
+use crate::policy::{PolicyDecision, PolicyOutcome};
+use crate::sbom::cyclonedx::xml_escape;
+use crate::stats::{Stats, StatsSummary};
 use colored::*;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+static COMPACT_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Write JSON reports and SBOMs on a single line instead of indented (`--compact`)
+pub fn set_compact_json(compact: bool) {
+    COMPACT_JSON.store(compact, AtomicOrdering::Relaxed);
+}
+
+/// Serialize `value` as JSON, indented unless `--compact` is set
+pub fn to_json_string<T: serde::Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    if COMPACT_JSON.load(AtomicOrdering::Relaxed) {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
 
 // ReportConfig struct
 #[derive(Debug)]
@@ -18,6 +41,12 @@ pub struct ReportConfig {
     project_license: Option<String>,
     gist: bool,
     osi: Option<OsiFilter>,
+    summary_json: bool,
+    toml: bool,
+    ndjson: bool,
+    sort: Option<ReportSort>,
+    min_severity: Option<Severity>,
+    summary_only: bool,
 }
 
 impl ReportConfig {
@@ -45,8 +74,273 @@ impl ReportConfig {
             project_license,
             gist,
             osi,
+            summary_json: false,
+            toml: false,
+            ndjson: false,
+            sort: None,
+            min_severity: None,
+            summary_only: false,
+        }
+    }
+
+    /// Replace the normal report with a single JSON object of counts
+    pub fn with_summary_json(mut self, summary_json: bool) -> Self {
+        self.summary_json = summary_json;
+        self
+    }
+
+    /// Write the report as TOML instead of the report table
+    pub fn with_toml(mut self, toml: bool) -> Self {
+        self.toml = toml;
+        self
+    }
+
+    /// Write one JSON object per line instead of the report table
+    pub fn with_ndjson(mut self, ndjson: bool) -> Self {
+        self.ndjson = ndjson;
+        self
+    }
+
+    /// Sort the reported dependencies; `None` keeps the order they were found in
+    pub fn with_sort(mut self, sort: Option<ReportSort>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Only report dependencies at or above this severity
+    pub fn with_min_severity(mut self, min_severity: Option<Severity>) -> Self {
+        self.min_severity = min_severity;
+        self
+    }
+
+    /// Replace the report tables with the license counts and the flagged packages
+    pub fn with_summary_only(mut self, summary_only: bool) -> Self {
+        self.summary_only = summary_only;
+        self
+    }
+}
+
+/// A dependency as written to JSON and YAML reports
+#[derive(serde::Serialize)]
+struct ReportEntry<'a> {
+    #[serde(flatten)]
+    info: &'a LicenseInfo,
+    /// The license is an `OR` expression and one of its options must be picked
+    multi_license: bool,
+    /// License family, e.g. permissive or strong copyleft
+    category: LicenseCategory,
+}
+
+/// TOML report document; TOML has no top-level arrays, so entries become `[[dependency]]` tables
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TomlReport<T> {
+    dependency: Vec<T>,
+}
+
+fn report_entries(data: &[LicenseInfo]) -> Vec<ReportEntry<'_>> {
+    data.iter()
+        .map(|info| ReportEntry {
+            info,
+            multi_license: info.is_multi_license(),
+            category: info.category(),
+        })
+        .collect()
+}
+
+/// Severity of a dependency's license findings
+///
+/// Incompatible licenses and restrictive copyleft or proprietary licenses are errors;
+/// other restrictive licenses and licenses that are not OSI approved are warnings.
+pub fn severity_of(info: &LicenseInfo) -> Severity {
+    let copyleft_or_proprietary = matches!(
+        info.category(),
+        LicenseCategory::WeakCopyleft
+            | LicenseCategory::StrongCopyleft
+            | LicenseCategory::Proprietary
+    );
+
+    if info.compatibility == LicenseCompatibility::Incompatible
+        || (info.is_restrictive && copyleft_or_proprietary)
+    {
+        Severity::Error
+    } else if info.is_restrictive || info.osi_status == OsiStatus::NotApproved {
+        Severity::Warn
+    } else {
+        Severity::Info
+    }
+}
+
+/// Order two dependencies by the given key, breaking ties by name and then version
+pub fn compare_for_report(a: &LicenseInfo, b: &LicenseInfo, sort: ReportSort) -> Ordering {
+    let primary = match sort {
+        ReportSort::Name => Ordering::Equal,
+        ReportSort::License => a.get_license().cmp(&b.get_license()),
+        // Restrictive first
+        ReportSort::Restrictive => b.is_restrictive.cmp(&a.is_restrictive),
+    };
+    primary
+        .then_with(|| a.name.cmp(&b.name))
+        .then_with(|| a.version.cmp(&b.version))
+}
+
+/// Machine-readable counts emitted by `--summary-json`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LicenseSummary {
+    pub total: usize,
+    pub restrictive: usize,
+    pub incompatible: usize,
+    pub osi_approved: usize,
+    pub unknown_license: usize,
+}
+
+impl LicenseSummary {
+    pub fn from_license_info(license_info: &[LicenseInfo]) -> Self {
+        Self {
+            total: license_info.len(),
+            restrictive: license_info.iter().filter(|i| *i.is_restrictive()).count(),
+            incompatible: license_info
+                .iter()
+                .filter(|i| i.compatibility == LicenseCompatibility::Incompatible)
+                .count(),
+            osi_approved: license_info
+                .iter()
+                .filter(|i| i.osi_status == OsiStatus::Approved)
+                .count(),
+            unknown_license: license_info
+                .iter()
+                .filter(|i| i.has_unknown_license())
+                .count(),
+        }
+    }
+}
+
+/// One row of the `list-licenses` inventory
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LicenseCount {
+    pub license: String,
+    pub count: usize,
+}
+
+/// Group package names by their license, using `No License` for missing values
+fn group_by_license<'a>(
+    license_info: impl IntoIterator<Item = &'a LicenseInfo>,
+) -> HashMap<String, Vec<String>> {
+    let mut license_count: HashMap<String, Vec<String>> = HashMap::new();
+    for info in license_info {
+        license_count
+            .entry(info.get_license())
+            .or_default()
+            .push(info.name().to_string());
+    }
+    license_count
+}
+
+/// Distinct licenses with their package counts, most common first
+pub fn license_inventory(license_info: &[LicenseInfo]) -> Vec<LicenseCount> {
+    let mut inventory: Vec<LicenseCount> = group_by_license(license_info)
+        .into_iter()
+        .map(|(license, packages)| LicenseCount {
+            license,
+            count: packages.len(),
+        })
+        .collect();
+    inventory.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.license.cmp(&b.license))
+    });
+    inventory
+}
+
+pub fn print_license_inventory(license_info: &[LicenseInfo], json: bool) {
+    let inventory = license_inventory(license_info);
+    log_debug("License inventory", &inventory);
+
+    if json {
+        match to_json_string(&inventory) {
+            Ok(output) => println!("{output}"),
+            Err(err) => {
+                log_error("Failed to serialize license inventory", &err);
+                println!("Error: Failed to generate JSON output");
+            }
+        }
+        return;
+    }
+
+    let mut formatter = TableFormatter::new(vec!["License".to_string(), "Packages".to_string()]);
+    for row in &inventory {
+        formatter.add_row(&[row.license.clone(), row.count.to_string()]);
+    }
+
+    println!("\n{}", formatter.render_header().bold());
+    for row in &inventory {
+        println!(
+            "{}",
+            formatter.render_row(&[row.license.clone(), row.count.to_string()], false)
+        );
+    }
+    println!("{}", formatter.render_footer());
+    println!(
+        "\n{} distinct licenses across {} packages\n",
+        inventory.len().to_string().bold(),
+        license_info.len().to_string().bold()
+    );
+}
+
+fn stats_row(name: &str, summary: &StatsSummary) -> Vec<String> {
+    vec![
+        name.to_string(),
+        summary.dependencies.to_string(),
+        summary.distinct_licenses.to_string(),
+        format!("{:.1}%", summary.osi_approved_percent),
+        format!("{:.1}%", summary.restrictive_percent),
+        format!("{:.1}%", summary.unknown_license_percent),
+    ]
+}
+
+pub fn print_stats(stats: &Stats, json: bool) {
+    if json {
+        match to_json_string(stats) {
+            Ok(output) => println!("{output}"),
+            Err(err) => {
+                log_error("Failed to serialize license stats", &err);
+                println!("Error: Failed to generate JSON output");
+            }
         }
+        return;
     }
+
+    let mut rows: Vec<Vec<String>> = stats
+        .ecosystems
+        .iter()
+        .map(|(ecosystem, summary)| stats_row(ecosystem, summary))
+        .collect();
+    rows.push(stats_row("Total", &stats.summary));
+
+    let mut formatter = TableFormatter::new(
+        [
+            "Ecosystem",
+            "Dependencies",
+            "Licenses",
+            "OSI Approved",
+            "Restrictive",
+            "Unknown",
+        ]
+        .iter()
+        .map(|header| header.to_string())
+        .collect(),
+    );
+    for row in &rows {
+        formatter.add_row(row);
+    }
+
+    println!("\n{}", formatter.render_header().bold());
+    let (total, ecosystems) = rows.split_last().expect("total row is always present");
+    for row in ecosystems {
+        println!("{}", formatter.render_row(row, false));
+    }
+    println!("{}", formatter.render_row(total, false).bold());
+    println!("{}\n", formatter.render_footer());
 }
 
 struct TableFormatter {
@@ -119,7 +413,84 @@ impl TableFormatter {
     }
 }
 
-pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> (bool, bool) {
+/// Which kinds of license problems a report found, used for the `--fail-on-*` exit codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReportOutcome {
+    pub has_restrictive: bool,
+    pub has_incompatible: bool,
+    pub has_unknown: bool,
+    /// A dependency's license is known not to be OSI approved
+    pub has_non_osi: bool,
+    /// A dependency's OSI approval status could not be determined
+    pub has_unknown_osi: bool,
+    /// Number of dependencies analyzed
+    pub total: usize,
+    /// Number of dependencies left after the report filters
+    pub shown: usize,
+    /// Number of analyzed dependencies whose license could not be resolved
+    pub unknown: usize,
+}
+
+/// Which findings make the check command exit non-zero (`--fail-on-*` flags)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FailOn {
+    pub restrictive: bool,
+    pub incompatible: bool,
+    pub unknown: bool,
+    pub non_osi: bool,
+    pub unknown_osi: bool,
+    /// Highest percentage of unresolved licenses that still passes
+    pub unknown_ratio: Option<u8>,
+}
+
+impl ReportOutcome {
+    /// Findings among all of `data`, before any report filter applies
+    pub fn from_data(data: &[LicenseInfo]) -> Self {
+        let unknown = data
+            .iter()
+            .filter(|info| info.has_unknown_license())
+            .count();
+        Self {
+            has_restrictive: data.iter().any(|info| *info.is_restrictive()),
+            has_incompatible: data
+                .iter()
+                .any(|info| info.compatibility == LicenseCompatibility::Incompatible),
+            has_unknown: unknown > 0,
+            has_non_osi: data
+                .iter()
+                .any(|info| info.osi_status == OsiStatus::NotApproved),
+            has_unknown_osi: data
+                .iter()
+                .any(|info| info.osi_status == OsiStatus::Unknown),
+            total: data.len(),
+            shown: data.len(),
+            unknown,
+        }
+    }
+
+    /// Whether any finding is one the caller asked to fail on
+    pub fn fails(&self, fail_on: &FailOn) -> bool {
+        (fail_on.restrictive && self.has_restrictive)
+            || (fail_on.incompatible && self.has_incompatible)
+            || (fail_on.unknown && self.has_unknown)
+            || (fail_on.non_osi && self.has_non_osi)
+            || (fail_on.unknown_osi && self.has_unknown_osi)
+            || fail_on
+                .unknown_ratio
+                .is_some_and(|max| unknown_ratio(self.unknown, self.total) > f64::from(max))
+    }
+}
+
+/// Percentage of `total` dependencies whose license is unknown, 0 when there are none
+pub fn unknown_ratio(unknown: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    unknown as f64 * 100.0 / total as f64
+}
+
+/// Print the report and return which license problems were found
+pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> ReportOutcome {
     log(
         LogLevel::Info,
         &format!("Generating report with config: {config:?}"),
@@ -131,25 +502,43 @@ pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> (bool, b
         &format!("Total packages to analyze: {total_packages}"),
     );
 
-    let has_restrictive = data.iter().any(|info| *info.is_restrictive());
-    let has_incompatible = data
-        .iter()
-        .any(|info| info.compatibility == LicenseCompatibility::Incompatible);
+    let mut outcome = ReportOutcome::from_data(&data);
 
     log(
         LogLevel::Info,
-        &format!("Has restrictive licenses: {has_restrictive}"),
+        &format!("Has restrictive licenses: {}", outcome.has_restrictive),
     );
 
     log(
         LogLevel::Info,
-        &format!("Has incompatible licenses: {has_incompatible}"),
+        &format!("Has incompatible licenses: {}", outcome.has_incompatible),
     );
 
+    log(
+        LogLevel::Info,
+        &format!(
+            "Has unknown licenses: {} ({} of {total_packages}, {:.1}%)",
+            outcome.has_unknown,
+            outcome.unknown,
+            unknown_ratio(outcome.unknown, total_packages)
+        ),
+    );
+
+    log(
+        LogLevel::Info,
+        &format!("Has non-OSI-approved licenses: {}", outcome.has_non_osi),
+    );
+
+    if config.summary_json {
+        log(LogLevel::Info, "Generating JSON summary");
+        print_summary_json(&data, config.output_file.as_deref());
+        return outcome;
+    }
+
     if config.gist {
         log(LogLevel::Info, "Generating gist summary");
         print_gist_summary(&data, total_packages, config.project_license.as_deref());
-        return (has_restrictive, has_incompatible);
+        return outcome;
     }
 
     // Filter data if in restrictive or/and incompatible mode to show only restrictive or/and incompatible licenses
@@ -209,20 +598,65 @@ pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> (bool, b
         }
     }
 
+    if let Some(min_severity) = config.min_severity {
+        let before_count = filtered_data.len();
+        filtered_data.retain(|info| severity_of(info) >= min_severity);
+        log(
+            LogLevel::Info,
+            &format!(
+                "Applied minimum severity {min_severity:?}: {} of {} dependencies",
+                filtered_data.len(),
+                before_count
+            ),
+        );
+    }
+
+    if let Some(sort) = config.sort {
+        log(LogLevel::Info, &format!("Sorting report by {sort:?}"));
+        filtered_data.sort_by(|a, b| compare_for_report(a, b, sort));
+    }
+
     log(
         LogLevel::Info,
         &format!("Filtered packages count: {}", filtered_data.len()),
     );
     log_debug("Filtered license data", &filtered_data);
+    outcome.shown = filtered_data.len();
+
+    // Streams stay machine-readable: no lines at all when nothing matched the filters
+    if config.ndjson {
+        log(LogLevel::Info, "Generating NDJSON output");
+        print_ndjson(&filtered_data, config.output_file.as_deref());
+        return outcome;
+    }
+
+    // Nothing left after filtering: the restrictive and incompatible flags only cover what is shown
+    let nothing_reported = ReportOutcome {
+        has_restrictive: false,
+        has_incompatible: false,
+        ..outcome
+    };
+
+    // `--output-file` takes the human-readable report when no other format claims it
+    let table_output_file =
+        if config.ci_format.is_none() && !config.json && !config.yaml && !config.toml {
+            config.output_file.as_deref()
+        } else {
+            None
+        };
 
     if filtered_data.is_empty() {
-        println!(
-            "\n{}\n",
+        if is_quiet_mode() {
+            return nothing_reported;
+        }
+        let message = format!(
+            "\n{}\n\n",
             "🎉 All dependencies passed the license check! No restrictive or incompatible licenses found."
                 .green()
                 .bold()
         );
-        return (false, false);
+        output_table_report(&message, table_output_file);
+        return nothing_reported;
     }
 
     if let Some(format) = config.ci_format {
@@ -237,12 +671,19 @@ pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> (bool, b
                 config.output_file.as_deref(),
                 config.project_license.as_deref(),
             ),
+            CiFormat::Junit => output_junit_format(
+                &filtered_data,
+                config.output_file.as_deref(),
+                config.project_license.as_deref(),
+            ),
         }
     } else if config.json {
         // JSON output
         log(LogLevel::Info, "Generating JSON output");
-        match serde_json::to_string_pretty(&filtered_data) {
-            Ok(json_output) => println!("{json_output}"),
+        match to_json_string(&report_entries(&filtered_data)) {
+            Ok(json_output) => {
+                output_serialized_report("JSON", &json_output, config.output_file.as_deref())
+            }
             Err(err) => {
                 log_error("Failed to serialize data to JSON", &err);
                 println!("Error: Failed to generate JSON output");
@@ -251,45 +692,148 @@ pub fn generate_report(data: Vec<LicenseInfo>, config: ReportConfig) -> (bool, b
     } else if config.yaml {
         // YAML output
         log(LogLevel::Info, "Generating YAML output");
-        match serde_yaml::to_string(&filtered_data) {
-            Ok(yaml_output) => println!("{yaml_output}"),
+        match serde_yaml::to_string(&report_entries(&filtered_data)) {
+            Ok(yaml_output) => {
+                output_serialized_report("YAML", &yaml_output, config.output_file.as_deref())
+            }
             Err(err) => {
                 log_error("Failed to serialize data to YAML", &err);
                 println!("Error: Failed to generate YAML output");
             }
         }
+    } else if config.toml {
+        // TOML output
+        log(LogLevel::Info, "Generating TOML output");
+        let report = TomlReport {
+            dependency: report_entries(&filtered_data),
+        };
+        match toml::to_string(&report) {
+            Ok(toml_output) => {
+                output_serialized_report("TOML", &toml_output, config.output_file.as_deref())
+            }
+            Err(err) => {
+                log_error("Failed to serialize data to TOML", &err);
+                println!("Error: Failed to generate TOML output");
+            }
+        }
+    } else if config.summary_only {
+        log(LogLevel::Info, "Generating summary of flagged packages");
+        let summary = render_summary_only(
+            &filtered_data,
+            total_packages,
+            config.project_license.as_deref(),
+        );
+        output_table_report(&summary, table_output_file);
     } else if config.verbose {
         log(LogLevel::Info, "Generating verbose table");
-        print_verbose_table(
+        let table = render_verbose_table(
             &filtered_data,
             config.restrictive,
             config.project_license.as_deref(),
         );
+        output_table_report(&table, table_output_file);
     } else {
         log(LogLevel::Info, "Generating summary table");
-        print_summary_table(
+        let table = render_summary_table(
             &filtered_data,
             total_packages,
             config.restrictive,
             config.incompatible,
             config.project_license.as_deref(),
         );
+        output_table_report(&table, table_output_file);
     }
 
-    (has_restrictive, has_incompatible)
+    outcome
 }
 
-fn print_verbose_table(
+/// Print a JSON, YAML or TOML report, or write it to `output_path`
+fn output_serialized_report(format: &str, report: &str, output_path: Option<&str>) {
+    let Some(path) = output_path else {
+        println!("{report}");
+        return;
+    };
+
+    log(
+        LogLevel::Info,
+        &format!("Writing {format} report to file: {path}"),
+    );
+    match fs::write(path, format!("{}\n", report.trim_end())) {
+        Ok(_) => {
+            if !is_quiet_mode() {
+                println!("Report written to: {path}");
+            }
+        }
+        Err(err) => {
+            log_error(
+                &format!("Failed to write {format} report file: {path}"),
+                &err,
+            );
+            println!("Error: Failed to write report file");
+            println!("{report}");
+        }
+    }
+}
+
+/// Print a rendered table report, or write it to `output_path` without colors
+fn output_table_report(report: &str, output_path: Option<&str>) {
+    let Some(path) = output_path else {
+        print!("{report}");
+        return;
+    };
+
+    log(
+        LogLevel::Info,
+        &format!("Writing table report to file: {path}"),
+    );
+    match fs::write(path, strip_ansi(report)) {
+        Ok(_) => {
+            if !is_quiet_mode() {
+                println!("Report written to: {path}");
+            }
+        }
+        Err(err) => {
+            log_error(&format!("Failed to write report file: {path}"), &err);
+            println!("Error: Failed to write report file");
+            print!("{report}");
+        }
+    }
+}
+
+/// Remove ANSI escape sequences such as colors from rendered output
+pub(crate) fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            output.push(c);
+            continue;
+        }
+        // A control sequence runs from `ESC [` to a final character in `@`..=`~`
+        if chars.next_if_eq(&'[').is_some() {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    output
+}
+
+fn render_verbose_table(
     license_info: &[LicenseInfo],
     restrictive: bool,
     project_license: Option<&str>,
-) {
-    log(LogLevel::Info, "Printing verbose table");
+) -> String {
+    log(LogLevel::Info, "Rendering verbose table");
 
     let mut headers = vec![
         "Name".to_string(),
         "Version".to_string(),
+        "Ecosystem".to_string(),
         "License".to_string(),
+        "Multi-License".to_string(),
         "Restrictive".to_string(),
     ];
 
@@ -300,6 +844,13 @@ fn print_verbose_table(
 
     // Always add OSI status column in verbose mode
     headers.push("OSI Status".to_string());
+    headers.push("Category".to_string());
+
+    // Only show how often dependencies were reported when some appear more than once
+    let show_occurrences = license_info.iter().any(|info| info.occurrences > 1);
+    if show_occurrences {
+        headers.push("Occurrences".to_string());
+    }
 
     let mut formatter = TableFormatter::new(headers);
 
@@ -309,7 +860,9 @@ fn print_verbose_table(
             let mut row = vec![
                 info.name().to_string(),
                 info.version().to_string(),
+                info.ecosystem.clone(),
                 info.get_license(),
+                info.is_multi_license().to_string(),
                 info.is_restrictive().to_string(),
             ];
 
@@ -320,6 +873,11 @@ fn print_verbose_table(
 
             // Always add OSI status in verbose mode
             row.push(info.osi_status().to_string());
+            row.push(info.category().to_string());
+
+            if show_occurrences {
+                row.push(info.occurrences.to_string());
+            }
 
             row
         })
@@ -331,64 +889,113 @@ fn print_verbose_table(
         formatter.add_row(row);
     }
 
-    println!("\n{}", formatter.render_header());
+    let mut output = format!("\n{}\n", formatter.render_header());
 
     for (i, row) in rows.iter().enumerate() {
         let is_restrictive = *license_info[i].is_restrictive();
         let is_incompatible =
             *license_info[i].compatibility() == LicenseCompatibility::Incompatible;
 
-        println!(
-            "{}",
-            formatter.render_row(row, is_restrictive || is_incompatible)
-        );
+        output.push_str(&formatter.render_row(row, is_restrictive || is_incompatible));
+        output.push('\n');
     }
 
-    println!("{}\n", formatter.render_footer());
+    output.push_str(&format!("{}\n\n", formatter.render_footer()));
+
+    output.push_str(&render_deprecation_notes(license_info));
 
     if !restrictive {
-        print_summary_footer(license_info, project_license);
+        output.push_str(&render_summary_footer(license_info, project_license));
     }
+    output
 }
 
-fn print_summary_table(
+/// List dependencies that declare a deprecated SPDX identifier, with the suggested replacement
+fn render_deprecation_notes(license_info: &[LicenseInfo]) -> String {
+    let deprecated: Vec<&LicenseInfo> = license_info
+        .iter()
+        .filter(|info| info.license_deprecated.is_some())
+        .collect();
+    if deprecated.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("{}\n", "⚠️  Deprecated SPDX identifiers:".yellow().bold());
+    for info in deprecated {
+        output.push_str(&format!(
+            "   {} {}: {} → {}\n",
+            info.name,
+            info.version,
+            info.get_license(),
+            info.license_deprecated
+                .as_deref()
+                .unwrap_or_default()
+                .green()
+        ));
+    }
+    output.push('\n');
+    output
+}
+
+/// Print policy warnings and violations to stderr, keeping structured stdout output intact
+pub fn print_policy_outcome(outcome: &PolicyOutcome) {
+    let describe = |decision: &PolicyDecision| {
+        let rule = match &decision.rule {
+            Some(rule) => format!("matched {rule}"),
+            None => "policy default".to_string(),
+        };
+        format!(
+            "   {} {}: {} ({rule})",
+            decision.name, decision.version, decision.license
+        )
+    };
+
+    let warnings: Vec<String> = outcome.warnings().map(describe).collect();
+    if !warnings.is_empty() {
+        eprintln!("{}", "⚠️  License policy warnings:".yellow().bold());
+        for line in warnings {
+            eprintln!("{line}");
+        }
+    }
+
+    let denials: Vec<String> = outcome.denials().map(describe).collect();
+    if !denials.is_empty() {
+        eprintln!("{}", "❌ License policy violations:".red().bold());
+        for line in denials {
+            eprintln!("{}", line.red());
+        }
+    }
+}
+
+fn render_summary_table(
     license_info: &[LicenseInfo],
     total_packages: usize,
     restrictive: bool,
     incompatible: bool,
     project_license: Option<&str>,
-) {
-    log(LogLevel::Info, "Printing summary table");
+) -> String {
+    log(LogLevel::Info, "Rendering summary table");
+
+    let mut output = String::new();
 
     // Print project license if available
-    if let Some(license) = project_license {
-        println!(
-            "\n{} {}",
+    if let (Some(license), false) = (project_license, is_quiet_mode()) {
+        output.push_str(&format!(
+            "\n{} {}\n",
             "📄".bold(),
             format!("Project License: {license}").bold()
-        );
+        ));
     }
 
-    let mut license_count: HashMap<String, Vec<String>> = HashMap::new();
-    let mut restrictive_licenses: Vec<&LicenseInfo> = Vec::new();
-    let mut incompatible_licenses: Vec<&LicenseInfo> = Vec::new();
-
-    for info in license_info {
-        let license = info.get_license();
-
-        if *info.is_restrictive() {
-            restrictive_licenses.push(info);
-        } else {
-            license_count
-                .entry(license)
-                .or_default()
-                .push(info.name().to_string());
-        }
-
-        if info.compatibility == LicenseCompatibility::Incompatible {
-            incompatible_licenses.push(info);
-        }
-    }
+    let license_count = group_by_license(license_info.iter().filter(|i| !*i.is_restrictive()));
+    let restrictive_licenses: Vec<&LicenseInfo> = license_info
+        .iter()
+        .filter(|info| *info.is_restrictive())
+        .collect();
+    let incompatible_licenses: Vec<&LicenseInfo> = license_info
+        .iter()
+        .filter(|info| info.compatibility == LicenseCompatibility::Incompatible)
+        .collect();
 
     log(
         LogLevel::Info,
@@ -409,20 +1016,28 @@ fn print_summary_table(
         ),
     );
 
-    if restrictive || incompatible {
-        if restrictive && !restrictive_licenses.is_empty() {
+    // Quiet mode only prints the problem tables, so a passing run prints nothing
+    if restrictive || incompatible || is_quiet_mode() {
+        let unfiltered = !restrictive && !incompatible;
+        if (restrictive || unfiltered) && !restrictive_licenses.is_empty() {
             log(
                 LogLevel::Info,
                 "Restrictive mode enabled, showing only restrictive licenses",
             );
-            print_restrictive_licenses_table(&restrictive_licenses);
+            output.push_str(&render_restrictive_licenses_table(&restrictive_licenses));
         }
-        if incompatible && project_license.is_some() && !incompatible_licenses.is_empty() {
+        if (incompatible || unfiltered)
+            && project_license.is_some()
+            && !incompatible_licenses.is_empty()
+        {
             if let Some(license) = project_license {
-                print_incompatible_licenses_table(&incompatible_licenses, license);
+                output.push_str(&render_incompatible_licenses_table(
+                    &incompatible_licenses,
+                    license,
+                ));
             }
         }
-        return;
+        return output;
     }
 
     // License summary
@@ -439,117 +1054,144 @@ fn print_summary_table(
         formatter.add_row(row);
     }
 
-    println!(
-        "\n{} {}\n",
+    output.push_str(&format!(
+        "\n{} {}\n\n",
         "🔍".bold(),
         "License Summary".bold().underline()
-    );
+    ));
 
-    println!("{}", formatter.render_header());
+    output.push_str(&format!("{}\n", formatter.render_header()));
 
     rows.sort_by(|a, b| a[0].cmp(&b[0]));
 
     for row in &rows {
-        println!("{}", formatter.render_row(row, true));
+        output.push_str(&format!("{}\n", formatter.render_row(row, true)));
     }
 
-    println!("{}", formatter.render_footer());
+    output.push_str(&format!("{}\n", formatter.render_footer()));
 
-    println!(
-        "\n{} {}",
+    output.push_str(&format!(
+        "\n{} {}\n",
         "📦".bold(),
         format!("Total dependencies scanned: {total_packages}").bold()
-    );
+    ));
 
     if !restrictive_licenses.is_empty() {
-        print_restrictive_licenses_table(&restrictive_licenses);
+        output.push_str(&render_restrictive_licenses_table(&restrictive_licenses));
     } else {
-        println!(
-            "\n{}\n",
+        output.push_str(&format!(
+            "\n{}\n\n",
             "✅ No restrictive licenses found! 🎉".green().bold()
-        );
+        ));
     }
 
     // Print incompatible licenses if project license is available
     if project_license.is_some() && !incompatible_licenses.is_empty() {
         if let Some(license) = project_license {
-            print_incompatible_licenses_table(&incompatible_licenses, license);
+            output.push_str(&render_incompatible_licenses_table(
+                &incompatible_licenses,
+                license,
+            ));
         }
     } else if project_license.is_some() {
-        println!(
-            "\n{}\n",
+        output.push_str(&format!(
+            "\n{}\n\n",
             "✅ No incompatible licenses found! 🎉".green().bold()
-        );
+        ));
     }
+    output
 }
 
-fn print_restrictive_licenses_table(restrictive_licenses: &[&LicenseInfo]) {
-    log(
-        LogLevel::Info,
-        &format!(
-            "Printing table for {} restrictive licenses",
-            restrictive_licenses.len()
-        ),
-    );
+/// License counts and the restrictive or incompatible packages, for `--summary-only`
+fn render_summary_only(
+    license_info: &[LicenseInfo],
+    total_packages: usize,
+    project_license: Option<&str>,
+) -> String {
+    let count = |matches: fn(&LicenseInfo) -> bool| {
+        license_info.iter().filter(|info| matches(info)).count()
+    };
+    let is_incompatible =
+        |info: &LicenseInfo| info.compatibility == LicenseCompatibility::Incompatible;
 
-    println!(
+    let mut output = format!(
         "\n{} {}\n",
-        "⚠️".bold(),
-        "Warning: Restrictive licenses found!".yellow().bold()
+        "🔍".bold(),
+        format!("License Summary ({total_packages} dependencies scanned)").bold()
     );
+    if license_info.len() < total_packages {
+        output.push_str(&format!(
+            "  • {} dependencies match the filters\n",
+            license_info.len()
+        ));
+    }
+    output.push_str(&format!(
+        "  • {} {}\n",
+        count(|info| info.is_restrictive)
+            .to_string()
+            .yellow()
+            .bold(),
+        "restrictive".yellow()
+    ));
+    if let Some(license) = project_license {
+        output.push_str(&format!(
+            "  • {} {}\n",
+            count(is_incompatible).to_string().red().bold(),
+            format!("incompatible with {license}").red()
+        ));
+    }
+    output.push_str(&format!(
+        "  • {} not OSI approved\n",
+        count(|info| info.osi_status == OsiStatus::NotApproved)
+    ));
+    output.push_str(&format!(
+        "  • {} with unknown OSI status\n",
+        count(|info| info.osi_status == OsiStatus::Unknown)
+    ));
 
-    let headers = vec![
-        "Package".to_string(),
-        "Version".to_string(),
-        "License".to_string(),
-    ];
-
-    let mut formatter = TableFormatter::new(headers);
-
-    let rows: Vec<_> = restrictive_licenses
+    let flagged: Vec<&LicenseInfo> = license_info
         .iter()
-        .map(|info| {
-            vec![
-                info.name().to_string(),
-                info.version().to_string(),
-                info.get_license(),
-            ]
-        })
+        .filter(|info| info.is_restrictive || is_incompatible(info))
         .collect();
-
-    for row in &rows {
-        formatter.add_row(row);
-    }
-
-    println!("{}", formatter.render_header());
-
-    for row in &rows {
-        println!("{}", formatter.render_row(row, false));
+    if flagged.is_empty() {
+        output.push_str(&format!(
+            "\n{}\n\n",
+            "✅ No flagged packages".green().bold()
+        ));
+        return output;
     }
 
-    println!("{}\n", formatter.render_footer());
-}
-
-fn print_incompatible_licenses_table(
-    incompatible_licenses: &[&LicenseInfo],
-    project_license: &str,
-) {
-    log(
-        LogLevel::Info,
-        &format!(
-            "Printing table for {} incompatible licenses",
-            incompatible_licenses.len()
-        ),
-    );
-
-    println!(
+    output.push_str(&format!(
         "\n{} {}\n",
-        "❌".bold(),
-        format!("Warning: Licenses incompatible with {project_license} found!")
-            .red()
+        "⚠️".bold(),
+        format!("Flagged packages ({}):", flagged.len())
+            .yellow()
             .bold()
-    );
+    ));
+    for info in flagged {
+        let reasons = [
+            (info.is_restrictive, "restrictive"),
+            (is_incompatible(info), "incompatible"),
+        ]
+        .iter()
+        .filter(|(applies, _)| *applies)
+        .map(|(_, reason)| *reason)
+        .collect::<Vec<_>>()
+        .join(", ");
+        output.push_str(&format!(
+            "  {} {}@{} ({}): {reasons}\n",
+            "✗".red(),
+            info.name,
+            info.version,
+            info.get_license()
+        ));
+    }
+    output.push('\n');
+    output
+}
 
+/// Bordered `Package │ Version │ License` table of the given dependencies
+fn render_package_table(licenses: &[&LicenseInfo]) -> String {
     let headers = vec![
         "Package".to_string(),
         "Version".to_string(),
@@ -558,7 +1200,7 @@ fn print_incompatible_licenses_table(
 
     let mut formatter = TableFormatter::new(headers);
 
-    let rows: Vec<_> = incompatible_licenses
+    let rows: Vec<_> = licenses
         .iter()
         .map(|info| {
             vec![
@@ -573,17 +1215,57 @@ fn print_incompatible_licenses_table(
         formatter.add_row(row);
     }
 
-    println!("{}", formatter.render_header());
+    let mut output = format!("{}\n", formatter.render_header());
 
     for row in &rows {
-        println!("{}", formatter.render_row(row, false));
+        output.push_str(&format!("{}\n", formatter.render_row(row, false)));
     }
 
-    println!("{}\n", formatter.render_footer());
+    output.push_str(&format!("{}\n\n", formatter.render_footer()));
+    output
+}
+
+fn render_restrictive_licenses_table(restrictive_licenses: &[&LicenseInfo]) -> String {
+    log(
+        LogLevel::Info,
+        &format!(
+            "Rendering table for {} restrictive licenses",
+            restrictive_licenses.len()
+        ),
+    );
+
+    format!(
+        "\n{} {}\n\n{}",
+        "⚠️".bold(),
+        "Warning: Restrictive licenses found!".yellow().bold(),
+        render_package_table(restrictive_licenses)
+    )
+}
+
+fn render_incompatible_licenses_table(
+    incompatible_licenses: &[&LicenseInfo],
+    project_license: &str,
+) -> String {
+    log(
+        LogLevel::Info,
+        &format!(
+            "Rendering table for {} incompatible licenses",
+            incompatible_licenses.len()
+        ),
+    );
+
+    format!(
+        "\n{} {}\n\n{}",
+        "❌".bold(),
+        format!("Warning: Licenses incompatible with {project_license} found!")
+            .red()
+            .bold(),
+        render_package_table(incompatible_licenses)
+    )
 }
 
-fn print_summary_footer(license_info: &[LicenseInfo], project_license: Option<&str>) {
-    log(LogLevel::Info, "Printing summary footer");
+fn render_summary_footer(license_info: &[LicenseInfo], project_license: Option<&str>) -> String {
+    log(LogLevel::Info, "Rendering summary footer");
 
     let total = license_info.len();
     let restrictive_count = license_info.iter().filter(|i| *i.is_restrictive()).count();
@@ -609,64 +1291,148 @@ fn print_summary_footer(license_info: &[LicenseInfo], project_license: Option<&s
         (0, 0, 0)
     };
 
-    println!("{}", "🔍 License Summary:".bold());
-    println!(
-        "  • {} {}",
+    let mut output = format!("{}\n", "🔍 License Summary:".bold());
+    output.push_str(&format!(
+        "  • {} {}\n",
         permissive_count.to_string().green().bold(),
         "permissive licenses".green()
-    );
-    println!(
-        "  • {} {}",
+    ));
+    output.push_str(&format!(
+        "  • {} {}\n",
         restrictive_count.to_string().yellow().bold(),
         "restrictive licenses".yellow()
-    );
+    ));
 
     // Print compatibility info if project license is available
     if project_license.is_some() {
-        println!(
-            "  • {} {}",
+        output.push_str(&format!(
+            "  • {} {}\n",
             compatible_count.to_string().green().bold(),
             "compatible licenses".green()
-        );
-        println!(
-            "  • {} {}",
+        ));
+        output.push_str(&format!(
+            "  • {} {}\n",
             incompatible_count.to_string().red().bold(),
             "incompatible licenses".red()
-        );
-        println!(
-            "  • {} {}",
+        ));
+        output.push_str(&format!(
+            "  • {} {}\n",
             unknown_count.to_string().blue().bold(),
             "unknown compatibility".blue()
-        );
+        ));
     }
 
-    println!("  • {total} total dependencies");
+    output.push_str(&format!("  • {total} total dependencies\n"));
 
     if restrictive_count > 0 {
-        println!("\n{} {}: Review these dependencies for compliance with your project's licensing requirements.",
+        output.push_str(&format!("\n{} {}: Review these dependencies for compliance with your project's licensing requirements.\n",
             "⚠️".yellow().bold(),
             "Recommendation".yellow().bold()
-        );
+        ));
     } else {
-        println!(
-            "\n{} {}: All dependencies have permissive licenses compatible with most projects.",
+        output.push_str(&format!(
+            "\n{} {}: All dependencies have permissive licenses compatible with most projects.\n",
             "✅".green().bold(),
             "Status".green().bold()
-        );
+        ));
     }
 
     // Add compatibility recommendation if project license is available
     if let Some(license) = project_license {
         if incompatible_count > 0 {
-            println!("\n{} {}: Some dependencies have licenses that may be incompatible with your project's {} license. Review for legal compliance.",
+            output.push_str(&format!("\n{} {}: Some dependencies have licenses that may be incompatible with your project's {} license. Review for legal compliance.\n",
                 "❌".red().bold(),
                 "Warning".red().bold(),
                 license
-            );
+            ));
+        }
+    }
+
+    output.push('\n');
+    output
+}
+
+/// JUnit XML test suite with a `name@version` test case per dependency, failing for
+/// restrictive or incompatible licenses
+fn junit_report(license_info: &[LicenseInfo], project_license: Option<&str>) -> String {
+    let mut test_cases = Vec::new();
+    let mut failures = 0;
+
+    for info in license_info {
+        let name = xml_escape(&format!("{}@{}", info.name, info.version));
+        let license = info.get_license();
+
+        let mut issues = Vec::new();
+        if info.is_restrictive {
+            issues.push("restrictive".to_string());
+        }
+        if let Some(project_license) = project_license {
+            if info.compatibility == LicenseCompatibility::Incompatible {
+                issues.push(format!(
+                    "incompatible with project license {project_license}"
+                ));
+            }
+        }
+
+        if issues.is_empty() {
+            test_cases.push(format!(
+                r#"  <testcase classname="feluda.licenses" name="{name}" time="0"/>"#
+            ));
+        } else {
+            failures += 1;
+            let message = xml_escape(&format!("License {license} is {}", issues.join(" and ")));
+            test_cases.push(format!(
+                r#"  <testcase classname="feluda.licenses" name="{name}" time="0">
+    <failure message="{message}" type="license">{message}</failure>
+  </testcase>"#
+            ));
         }
     }
 
-    println!();
+    let properties = project_license
+        .map(|license| {
+            format!(
+                "  <properties>\n    <property name=\"project.license\" value=\"{}\"/>\n  </properties>\n",
+                xml_escape(license)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="Feluda License Check" tests="{}" failures="{failures}" errors="0" skipped="0" time="0">
+{properties}{}
+</testsuite>
+"#,
+        license_info.len(),
+        test_cases.join("\n")
+    )
+}
+
+fn output_junit_format(
+    license_info: &[LicenseInfo],
+    output_path: Option<&str>,
+    project_license: Option<&str>,
+) {
+    log(LogLevel::Info, "Generating JUnit XML output");
+    let junit_xml = junit_report(license_info, project_license);
+
+    if let Some(path) = output_path {
+        log(
+            LogLevel::Info,
+            &format!("Writing JUnit XML to file: {path}"),
+        );
+        match fs::write(path, &junit_xml) {
+            Ok(_) => println!("JUnit XML output written to: {path}"),
+            Err(err) => {
+                log_error(&format!("Failed to write JUnit output file: {path}"), &err);
+                println!("Error: Failed to write JUnit XML output file");
+                print!("{junit_xml}"); // Fallback to stdout
+            }
+        }
+    } else {
+        print!("{junit_xml}");
+    }
 }
 
 fn output_github_format(
@@ -933,7 +1699,61 @@ fn output_jenkins_format(
     }
 }
 
-// Add gist report function to reporter.rs
+/// Write each dependency as a single-line JSON object, one per line
+fn write_ndjson(license_info: &[LicenseInfo], mut out: impl Write) -> std::io::Result<()> {
+    for entry in report_entries(license_info) {
+        serde_json::to_writer(&mut out, &entry)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+/// Stream the report as JSON Lines to stdout or `output_path`
+pub fn print_ndjson(license_info: &[LicenseInfo], output_path: Option<&str>) {
+    let result = match output_path {
+        Some(path) => {
+            log(
+                LogLevel::Info,
+                &format!("Writing NDJSON report to file: {path}"),
+            );
+            fs::File::create(path).and_then(|file| write_ndjson(license_info, BufWriter::new(file)))
+        }
+        None => write_ndjson(license_info, BufWriter::new(std::io::stdout().lock())),
+    };
+
+    if let Err(err) = result {
+        log_error("Failed to write NDJSON output", &err);
+    }
+}
+
+/// Print a single-line JSON object with dependency counts to stdout or `output_path`
+pub fn print_summary_json(license_info: &[LicenseInfo], output_path: Option<&str>) {
+    let summary = LicenseSummary::from_license_info(license_info);
+    log_debug("License summary", &summary);
+
+    let output = match serde_json::to_string(&summary) {
+        Ok(output) => output,
+        Err(err) => {
+            log_error("Failed to serialize license summary", &err);
+            println!("Error: Failed to generate JSON summary");
+            return;
+        }
+    };
+
+    if let Some(path) = output_path {
+        log(
+            LogLevel::Info,
+            &format!("Writing JSON summary to file: {path}"),
+        );
+        if let Err(err) = fs::write(path, format!("{output}\n")) {
+            log_error(&format!("Failed to write JSON summary file: {path}"), &err);
+            println!("{output}");
+        }
+    } else {
+        println!("{output}");
+    }
+}
+
 fn print_gist_summary(
     license_info: &[LicenseInfo],
     total_packages: usize,
@@ -1002,93 +1822,391 @@ fn print_gist_summary(
 
     println!("{}", "━".repeat(50).cyan());
 
-    let overall_status = if restrictive_count > 0 || incompatible_count > 0 {
-        format!("{} {}", "⚠️".yellow(), "NEEDS ATTENTION".yellow().bold())
-    } else {
-        format!("{} {}", "✨".green(), "ALL GOOD".green().bold())
-    };
+    let overall_status = if restrictive_count > 0 || incompatible_count > 0 {
+        format!("{} {}", "⚠️".yellow(), "NEEDS ATTENTION".yellow().bold())
+    } else {
+        format!("{} {}", "✨".green(), "ALL GOOD".green().bold())
+    };
+
+    println!("│ {:30} │ {}", "Recommendation".bold(), overall_status);
+
+    println!("{}\n", "━".repeat(50).cyan());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::LicenseCompatibility;
+    use tempfile::TempDir;
+
+    /// `(has_restrictive, has_incompatible, has_unknown)` of a report
+    fn flags(outcome: ReportOutcome) -> (bool, bool, bool) {
+        (
+            outcome.has_restrictive,
+            outcome.has_incompatible,
+            outcome.has_unknown,
+        )
+    }
+
+    fn setup() -> TempDir {
+        tempfile::tempdir().unwrap()
+    }
+
+    fn get_test_data() -> Vec<LicenseInfo> {
+        vec![
+            LicenseInfo {
+                compatibility: LicenseCompatibility::Compatible,
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("crate1", "1.0.0", Some("MIT".to_string()))
+            },
+            LicenseInfo {
+                is_restrictive: true,
+                compatibility: LicenseCompatibility::Incompatible,
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("crate2", "2.0.0", Some("GPL-3.0".to_string()))
+            },
+            LicenseInfo {
+                compatibility: LicenseCompatibility::Compatible,
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("crate3", "3.0.0", Some("Apache-2.0".to_string()))
+            },
+            LicenseInfo::new("crate4", "4.0.0", Some("Unknown".to_string())),
+        ]
+    }
+
+    fn get_test_data_with_unknown_compatibility() -> Vec<LicenseInfo> {
+        vec![
+            LicenseInfo {
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("crate1", "1.0.0", Some("MIT".to_string()))
+            },
+            LicenseInfo {
+                is_restrictive: true,
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("crate2", "2.0.0", Some("GPL-3.0".to_string()))
+            },
+        ]
+    }
+
+    #[test]
+    fn test_generate_report_empty_data() {
+        let data = vec![];
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        );
+        let result = generate_report(data, config);
+        assert_eq!(flags(result), (false, false, false)); // No restrictive or incompatible licenses
+    }
+
+    #[test]
+    fn test_license_summary_counts() {
+        let summary = LicenseSummary::from_license_info(&get_test_data());
+        assert_eq!(
+            summary,
+            LicenseSummary {
+                total: 4,
+                restrictive: 1,
+                incompatible: 1,
+                osi_approved: 3,
+                unknown_license: 1,
+            }
+        );
+
+        let mut data = get_test_data();
+        data[0].license = None;
+        data[1].license = Some("No License".to_string());
+        let summary = LicenseSummary::from_license_info(&data);
+        assert_eq!(summary.unknown_license, 3);
+    }
+
+    #[test]
+    fn test_license_summary_empty() {
+        let summary = LicenseSummary::from_license_info(&[]);
+        assert_eq!(
+            serde_json::to_string(&summary).unwrap(),
+            r#"{"total":0,"restrictive":0,"incompatible":0,"osi_approved":0,"unknown_license":0}"#
+        );
+    }
+
+    #[test]
+    fn test_print_summary_json_to_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.json");
+        let data = get_test_data();
+
+        print_summary_json(&data, Some(path.to_str().unwrap()));
+
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["total"], data.len());
+    }
+
+    #[test]
+    fn test_generate_report_summary_json_keeps_exit_flags() {
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        )
+        .with_summary_json(true);
+        assert_eq!(
+            flags(generate_report(get_test_data(), config)),
+            (true, true, true)
+        );
+    }
+
+    #[test]
+    fn test_report_outcome_counts() {
+        let config = ReportConfig::new(
+            false, false, false, true, false, None, None, None, false, None,
+        );
+        let outcome = generate_report(get_test_data(), config);
+        assert_eq!(
+            outcome,
+            ReportOutcome {
+                has_restrictive: true,
+                has_incompatible: true,
+                has_unknown: true,
+                has_non_osi: false,
+                has_unknown_osi: true,
+                total: 4,
+                shown: 1,
+                unknown: 1,
+            }
+        );
+
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        );
+        let outcome = generate_report(get_test_data(), config);
+        assert_eq!((outcome.total, outcome.shown), (4, 4));
+
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        );
+        assert_eq!(
+            generate_report(Vec::new(), config),
+            ReportOutcome::default()
+        );
+    }
+
+    #[test]
+    fn test_report_outcome_fails() {
+        let outcome = ReportOutcome {
+            has_restrictive: true,
+            has_unknown_osi: true,
+            ..ReportOutcome::default()
+        };
+
+        assert!(!outcome.fails(&FailOn::default()));
+        assert!(outcome.fails(&FailOn {
+            restrictive: true,
+            ..FailOn::default()
+        }));
+        assert!(!outcome.fails(&FailOn {
+            incompatible: true,
+            unknown: true,
+            non_osi: true,
+            ..FailOn::default()
+        }));
+        assert!(outcome.fails(&FailOn {
+            non_osi: true,
+            unknown_osi: true,
+            ..FailOn::default()
+        }));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_compact_json() {
+        let data = [LicenseInfo {
+            compatibility: LicenseCompatibility::Compatible,
+            osi_status: OsiStatus::Approved,
+            ecosystem: "cargo".to_string(),
+            ..LicenseInfo::new("serde", "1.0.0", Some("MIT OR Apache-2.0".to_string()))
+        }];
+        let entries = report_entries(&data);
+
+        let pretty = to_json_string(&entries).unwrap();
+        assert!(pretty.contains("\n  "));
+
+        set_compact_json(true);
+        let compact = to_json_string(&entries);
+        set_compact_json(false);
+
+        let compact = compact.unwrap();
+        assert!(!compact.contains('\n'));
+        assert!(compact.len() < pretty.len());
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unknown_ratio_gate() {
+        assert_eq!(unknown_ratio(3, 10), 30.0);
+        assert_eq!(unknown_ratio(0, 0), 0.0);
+
+        let outcome = ReportOutcome {
+            has_unknown: true,
+            total: 10,
+            unknown: 3,
+            ..ReportOutcome::default()
+        };
+        let max = |percent| FailOn {
+            unknown_ratio: Some(percent),
+            ..FailOn::default()
+        };
+
+        // 3 of 10 unknown is 30%
+        assert!(outcome.fails(&max(25)));
+        assert!(outcome.fails(&max(10)));
+        assert!(!outcome.fails(&max(30)));
+        assert!(!outcome.fails(&FailOn::default()));
+
+        let one_unknown = ReportOutcome {
+            unknown: 1,
+            ..outcome
+        };
+        assert!(!one_unknown.fails(&max(10)));
+        assert!(one_unknown.fails(&max(5)));
+    }
+
+    #[test]
+    fn test_generate_report_flags_non_osi_licenses() {
+        let config = || {
+            ReportConfig::new(
+                false, false, false, false, false, None, None, None, false, None,
+            )
+        };
+
+        let mut approved = get_test_data();
+        approved.truncate(3);
+        let outcome = generate_report(approved.clone(), config());
+        assert!(!outcome.has_non_osi);
+        assert!(!outcome.has_unknown_osi);
 
-    println!("│ {:30} │ {}", "Recommendation".bold(), overall_status);
+        let mut data = approved;
+        data.push(LicenseInfo {
+            osi_status: OsiStatus::NotApproved,
+            ..LicenseInfo::new("vendor-sdk", "5.0.0", Some("Proprietary".to_string()))
+        });
+        let outcome = generate_report(data, config().with_summary_json(true));
+        assert!(outcome.has_non_osi);
+        assert!(!outcome.has_unknown_osi);
 
-    println!("{}\n", "━".repeat(50).cyan());
-}
+        // crate4 has an unknown OSI status
+        let outcome = generate_report(get_test_data(), config());
+        assert!(!outcome.has_non_osi);
+        assert!(outcome.has_unknown_osi);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::licenses::LicenseCompatibility;
-    use tempfile::TempDir;
+    #[test]
+    fn test_toml_report_round_trip() {
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            name: String,
+            version: String,
+            license: Option<String>,
+            is_restrictive: bool,
+            category: LicenseCategory,
+        }
 
-    fn setup() -> TempDir {
-        tempfile::tempdir().unwrap()
+        let mut data = get_test_data();
+        data[3].license = None;
+        let output = toml::to_string(&TomlReport {
+            dependency: report_entries(&data),
+        })
+        .unwrap();
+        assert!(output.contains("[[dependency]]"));
+
+        let parsed: TomlReport<Entry> = toml::from_str(&output).unwrap();
+        assert_eq!(parsed.dependency.len(), data.len());
+        for (entry, info) in parsed.dependency.iter().zip(&data) {
+            assert_eq!(entry.name, info.name);
+            assert_eq!(entry.version, info.version);
+            assert_eq!(entry.license, info.license);
+            assert_eq!(entry.is_restrictive, info.is_restrictive);
+            assert_eq!(entry.category, info.category());
+        }
     }
 
-    fn get_test_data() -> Vec<LicenseInfo> {
-        vec![
-            LicenseInfo {
-                name: "crate1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
-                compatibility: LicenseCompatibility::Compatible,
-                osi_status: crate::licenses::OsiStatus::Approved,
-            },
-            LicenseInfo {
-                name: "crate2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
-                is_restrictive: true,
-                compatibility: LicenseCompatibility::Incompatible,
-                osi_status: crate::licenses::OsiStatus::Approved,
-            },
-            LicenseInfo {
-                name: "crate3".to_string(),
-                version: "3.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
-                compatibility: LicenseCompatibility::Compatible,
-                osi_status: crate::licenses::OsiStatus::Approved,
-            },
-            LicenseInfo {
-                name: "crate4".to_string(),
-                version: "4.0.0".to_string(),
-                license: Some("Unknown".to_string()),
-                is_restrictive: false,
-                compatibility: LicenseCompatibility::Unknown,
-                osi_status: crate::licenses::OsiStatus::Unknown,
-            },
-        ]
+    #[test]
+    fn test_generate_report_toml_keeps_exit_flags() {
+        let config = ReportConfig::new(
+            false, false, false, true, false, None, None, None, false, None,
+        )
+        .with_toml(true);
+        assert_eq!(
+            flags(generate_report(get_test_data(), config)),
+            (true, true, true)
+        );
     }
 
-    fn get_test_data_with_unknown_compatibility() -> Vec<LicenseInfo> {
-        vec![
-            LicenseInfo {
-                name: "crate1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
-                compatibility: LicenseCompatibility::Unknown,
-                osi_status: crate::licenses::OsiStatus::Approved,
-            },
-            LicenseInfo {
-                name: "crate2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
-                is_restrictive: true,
-                compatibility: LicenseCompatibility::Unknown,
-                osi_status: crate::licenses::OsiStatus::Approved,
-            },
-        ]
+    #[test]
+    fn test_write_ndjson_one_object_per_line() {
+        let data = get_test_data();
+        let mut out = Vec::new();
+        write_ndjson(&data, &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.ends_with('\n'));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), data.len());
+        for (line, info) in lines.iter().zip(&data) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["name"], info.name);
+            assert_eq!(value["version"], info.version);
+        }
     }
 
     #[test]
-    fn test_generate_report_empty_data() {
-        let data = vec![];
+    fn test_generate_report_ndjson_respects_filters() {
+        let temp_dir = setup();
+        let path = temp_dir.path().join("report.ndjson");
+
         let config = ReportConfig::new(
-            false, false, false, false, false, None, None, None, false, None,
+            false,
+            false,
+            false,
+            true,
+            false,
+            None,
+            Some(path.to_str().unwrap().to_string()),
+            None,
+            false,
+            None,
+        )
+        .with_ndjson(true);
+        assert_eq!(
+            flags(generate_report(get_test_data(), config)),
+            (true, true, true)
         );
-        let result = generate_report(data, config);
-        assert_eq!(result, (false, false)); // No restrictive or incompatible licenses
+
+        let written = fs::read_to_string(&path).unwrap();
+        let names: Vec<String> = written
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["name"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(names, vec!["crate2"]);
+
+        let config = ReportConfig::new(
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            Some(path.to_str().unwrap().to_string()),
+            None,
+            false,
+            Some(OsiFilter::Approved),
+        )
+        .with_ndjson(true);
+        generate_report(get_test_data(), config);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
     }
 
     #[test]
@@ -1107,7 +2225,7 @@ mod tests {
             None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true)); // Has both restrictive and incompatible licenses
+        assert_eq!(flags(result), (true, true, true)); // Has both restrictive and incompatible licenses
     }
 
     #[test]
@@ -1126,7 +2244,7 @@ mod tests {
             None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true)); // In strict mode, still has both restrictive and incompatible
+        assert_eq!(flags(result), (true, true, true)); // In strict mode, still has both restrictive and incompatible
     }
 
     #[test]
@@ -1145,7 +2263,7 @@ mod tests {
             None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true));
+        assert_eq!(flags(result), (true, true, true));
     }
 
     #[test]
@@ -1164,7 +2282,7 @@ mod tests {
             None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true));
+        assert_eq!(flags(result), (true, true, true));
     }
 
     #[test]
@@ -1183,7 +2301,7 @@ mod tests {
             None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true));
+        assert_eq!(flags(result), (true, true, true));
     }
 
     #[test]
@@ -1193,7 +2311,7 @@ mod tests {
             false, false, false, false, false, None, None, None, false, None,
         );
         let result = generate_report(data, config);
-        assert_eq!(result, (true, false)); // Has restrictive but no incompatible since no project license
+        assert_eq!(flags(result), (true, false, false)); // Has restrictive but no incompatible since no project license
     }
 
     #[test]
@@ -1215,7 +2333,7 @@ mod tests {
         );
 
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true));
+        assert_eq!(flags(result), (true, true, true));
 
         let content = match fs::read_to_string(&output_path) {
             Ok(content) => content,
@@ -1249,7 +2367,7 @@ mod tests {
         );
 
         let result = generate_report(data, config);
-        assert_eq!(result, (true, true));
+        assert_eq!(flags(result), (true, true, true));
 
         let content = match fs::read_to_string(&output_path) {
             Ok(content) => content,
@@ -1284,7 +2402,7 @@ mod tests {
         );
 
         let result = generate_report(data, config);
-        assert_eq!(result, (true, false)); // Has restrictive but no incompatible
+        assert_eq!(flags(result), (true, false, false)); // Has restrictive but no incompatible
 
         let content = match fs::read_to_string(&output_path) {
             Ok(content) => content,
@@ -1300,6 +2418,75 @@ mod tests {
         assert!(!content.contains("Project is using"));
     }
 
+    #[test]
+    fn test_junit_report() {
+        let mut data = get_test_data();
+        data[0].name = "a<b>&c".to_string();
+        let xml = junit_report(&data, Some("MIT"));
+
+        crate::sbom::cyclonedx::assert_well_formed_xml(&xml);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains(&format!(
+            r#"<testsuite name="Feluda License Check" tests="{}""#,
+            data.len()
+        )));
+        assert!(xml.contains(r#"<property name="project.license" value="MIT"/>"#));
+        assert!(xml.contains(r#"name="a&lt;b&gt;&amp;c@1.0.0""#));
+        assert!(xml.contains(
+            r#"<failure message="License GPL-3.0 is restrictive and incompatible with project license MIT" type="license">"#
+        ));
+
+        let flagged = data
+            .iter()
+            .filter(|info| {
+                info.is_restrictive || info.compatibility == LicenseCompatibility::Incompatible
+            })
+            .count();
+        assert!(flagged > 0);
+        assert!(xml.contains(&format!(r#"failures="{flagged}""#)));
+        assert_eq!(xml.matches("<failure ").count(), flagged);
+        assert_eq!(xml.matches("<testcase ").count(), data.len());
+    }
+
+    #[test]
+    fn test_junit_report_without_project_license() {
+        let data = get_test_data_with_unknown_compatibility();
+        let xml = junit_report(&data, None);
+
+        crate::sbom::cyclonedx::assert_well_formed_xml(&xml);
+        assert!(!xml.contains("<properties>"));
+        let restrictive = data.iter().filter(|info| info.is_restrictive).count();
+        assert!(xml.contains(&format!(r#"failures="{restrictive}""#)));
+        assert!(!xml.contains("incompatible"));
+
+        let empty = junit_report(&[], None);
+        crate::sbom::cyclonedx::assert_well_formed_xml(&empty);
+        assert!(empty.contains(r#"tests="0" failures="0""#));
+    }
+
+    #[test]
+    fn test_junit_output_to_file() {
+        let temp_dir = setup();
+        let output_path = temp_dir.path().join("junit.xml");
+        let config = ReportConfig::new(
+            false,
+            false,
+            false,
+            false,
+            false,
+            Some(CiFormat::Junit),
+            Some(output_path.to_str().unwrap().to_string()),
+            Some("MIT".to_string()),
+            false,
+            None,
+        );
+
+        generate_report(get_test_data(), config);
+
+        let content = fs::read_to_string(&output_path).unwrap();
+        assert_eq!(content, junit_report(&get_test_data(), Some("MIT")));
+    }
+
     #[test]
     fn test_table_formatter() {
         let headers = vec![
@@ -1345,7 +2532,36 @@ mod tests {
     }
 
     #[test]
-    fn test_print_incompatible_licenses_table() {
+    #[serial_test::serial]
+    fn test_table_has_no_ansi_escapes_with_no_color() {
+        temp_env::with_var("NO_COLOR", Some("1"), || {
+            colored::control::set_override(crate::cli::should_use_color(
+                false,
+                std::env::var_os("NO_COLOR"),
+                true,
+            ));
+
+            let mut formatter =
+                TableFormatter::new(vec!["Name".to_string(), "License".to_string()]);
+            let row = vec!["crate2".to_string(), "GPL-3.0".to_string()];
+            formatter.add_row(&row);
+
+            let rendered = [
+                formatter.render_header(),
+                formatter.render_row(&row, true),
+                formatter.render_row(&row, false),
+                formatter.render_footer(),
+            ];
+            colored::control::unset_override();
+
+            for line in rendered {
+                assert!(!line.contains('\x1b'), "{line:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_render_incompatible_licenses_table() {
         // Create test data
         let test_data = get_test_data();
 
@@ -1356,24 +2572,146 @@ mod tests {
             .collect();
 
         assert!(!incompatible_licenses.is_empty());
-        print_incompatible_licenses_table(&incompatible_licenses, "MIT");
-        // If no panic, test passes
+        let table = render_incompatible_licenses_table(&incompatible_licenses, "MIT");
+        assert!(table.contains("Licenses incompatible with MIT found!"));
     }
 
     #[test]
-    fn test_print_summary_footer_with_compatibility() {
-        // This is primarily a visual test
+    fn test_render_summary_footer_with_compatibility() {
         let license_info = get_test_data();
-        print_summary_footer(&license_info, Some("MIT"));
-        // If no panic, test passes
+        let footer = render_summary_footer(&license_info, Some("MIT"));
+        assert!(footer.contains("compatible licenses"));
     }
 
     #[test]
-    fn test_print_summary_footer_without_compatibility() {
-        // This is primarily a visual test
+    fn test_render_summary_footer_without_compatibility() {
         let license_info = get_test_data_with_unknown_compatibility();
-        print_summary_footer(&license_info, None);
-        // If no panic, test passes
+        let footer = render_summary_footer(&license_info, None);
+        assert!(!footer.contains("unknown compatibility"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_table_report_written_to_output_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        colored::control::set_override(true);
+
+        for verbose in [false, true] {
+            let path = temp_dir.path().join(format!("report-{verbose}.txt"));
+            let config = ReportConfig::new(
+                false,
+                false,
+                verbose,
+                false,
+                false,
+                None,
+                Some(path.to_string_lossy().to_string()),
+                Some("MIT".to_string()),
+                false,
+                None,
+            );
+            generate_report(get_test_data(), config);
+
+            let report = fs::read_to_string(&path).unwrap();
+            assert!(report.contains('┌') && report.contains('└'), "{report}");
+            assert!(report.contains("crate2"), "{report}");
+            assert!(!report.contains('\u{1b}'), "{report}");
+        }
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_serialized_reports_written_to_output_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        for (format, json, yaml, toml) in [
+            ("json", true, false, false),
+            ("yaml", false, true, false),
+            ("toml", false, false, true),
+        ] {
+            let path = temp_dir.path().join(format!("report.{format}"));
+            let config = ReportConfig::new(
+                json,
+                yaml,
+                false,
+                false,
+                false,
+                None,
+                Some(path.to_string_lossy().to_string()),
+                Some("MIT".to_string()),
+                false,
+                None,
+            )
+            .with_toml(toml);
+            generate_report(get_test_data(), config);
+
+            let report = fs::read_to_string(&path).unwrap();
+            assert!(report.contains("crate2"), "{format}: {report}");
+        }
+
+        let report = fs::read_to_string(temp_dir.path().join("report.json")).unwrap();
+        let entries: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert!(entries.is_array());
+    }
+
+    #[test]
+    fn test_summary_only_lists_flagged_packages() {
+        let mut data = get_test_data();
+        data[2].osi_status = OsiStatus::NotApproved;
+        data[3].is_restrictive = true;
+
+        let summary = strip_ansi(&render_summary_only(&data, 4, Some("MIT")));
+        assert!(summary.contains("License Summary (4 dependencies scanned)"));
+        assert!(summary.contains("  • 2 restrictive\n"));
+        assert!(summary.contains("  • 1 incompatible with MIT\n"));
+        assert!(summary.contains("  • 1 not OSI approved\n"));
+        assert!(summary.contains("  • 1 with unknown OSI status\n"));
+        assert!(summary.contains("Flagged packages (2):"));
+        assert!(summary.contains("crate2@2.0.0 (GPL-3.0): restrictive, incompatible\n"));
+        assert!(summary.contains("crate4@"));
+        // Packages without findings and the table borders are left out
+        assert!(!summary.contains("crate1"));
+        assert!(!summary.contains("crate3"));
+        assert!(!summary.contains('┌'));
+
+        let clean = strip_ansi(&render_summary_only(&data[..1], 4, None));
+        assert!(clean.contains("1 dependencies match the filters"));
+        assert!(!clean.contains("incompatible"));
+        assert!(clean.contains("No flagged packages"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_summary_only_takes_precedence_over_verbose_table() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.txt");
+        let config = ReportConfig::new(
+            false,
+            false,
+            true,
+            false,
+            false,
+            None,
+            Some(path.to_string_lossy().to_string()),
+            Some("MIT".to_string()),
+            false,
+            None,
+        )
+        .with_summary_only(true);
+        let outcome = generate_report(get_test_data(), config);
+
+        let summary = fs::read_to_string(&path).unwrap();
+        assert!(summary.contains("Flagged packages (1):"));
+        assert!(!summary.contains("crate1"));
+        assert!(!summary.contains("OSI Status"));
+        assert!(outcome.has_restrictive);
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        let colored = format!("{} and {}", "red".red().bold(), "plain");
+        assert_eq!(strip_ansi(&colored), "red and plain");
+        assert_eq!(strip_ansi("│ ✅ no codes │"), "│ ✅ no codes │");
     }
 
     #[test]
@@ -1404,20 +2742,14 @@ mod tests {
     fn test_generate_report_all_permissive() {
         let data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("BSD-3-Clause".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "2.0.0", Some("BSD-3-Clause".to_string()))
             },
         ];
 
@@ -1433,30 +2765,111 @@ mod tests {
             false,
             None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            has_unknown,
+            ..
+        } = generate_report(data, config);
+
+        assert!(!has_restrictive);
+        assert!(!has_incompatible);
+        assert!(!has_unknown);
+    }
+
+    #[test]
+    fn test_generate_report_flags_missing_license_as_unknown() {
+        let data = vec![
+            LicenseInfo {
+                compatibility: LicenseCompatibility::Compatible,
+                osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("resolved", "1.0.0", Some("MIT".to_string()))
+            },
+            LicenseInfo::new("unresolved", "0.1.0", None),
+        ];
+
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        );
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            has_unknown,
+            ..
+        } = generate_report(data, config);
 
         assert!(!has_restrictive);
         assert!(!has_incompatible);
+        assert!(has_unknown);
+    }
+
+    #[test]
+    fn test_generate_report_flags_failed_lookup_as_unknown() {
+        let data = vec![LicenseInfo::new(
+            "unreachable",
+            "1.0.0",
+            Some("Unknown (failed to retrieve)".to_string()),
+        )];
+
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        );
+        let ReportOutcome { has_unknown, .. } = generate_report(data, config);
+
+        assert!(has_unknown);
+    }
+
+    fn inventory_package(name: &str, license: Option<&str>) -> LicenseInfo {
+        LicenseInfo::new(name, "1.0.0", license.map(str::to_string))
+    }
+
+    #[test]
+    fn test_license_inventory_counts_and_order() {
+        let data = vec![
+            inventory_package("a", Some("MIT")),
+            inventory_package("b", Some("Apache-2.0")),
+            inventory_package("c", Some("MIT")),
+            inventory_package("d", None),
+            inventory_package("e", Some("MIT")),
+            inventory_package("f", Some("Apache-2.0")),
+        ];
+
+        let inventory = license_inventory(&data);
+        assert_eq!(
+            inventory,
+            vec![
+                LicenseCount {
+                    license: "MIT".to_string(),
+                    count: 3
+                },
+                LicenseCount {
+                    license: "Apache-2.0".to_string(),
+                    count: 2
+                },
+                LicenseCount {
+                    license: "No License".to_string(),
+                    count: 1
+                },
+            ]
+        );
+
+        let json = serde_json::to_string(&inventory).unwrap();
+        assert!(json.starts_with(r#"[{"license":"MIT","count":3}"#));
     }
 
     #[test]
     fn test_generate_report_mixed_licenses() {
         let data = vec![
             LicenseInfo {
-                name: "good_package".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("good_package", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "bad_package".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("bad_package", "2.0.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1472,7 +2885,11 @@ mod tests {
             false,
             None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
 
         assert!(has_restrictive);
         assert!(has_incompatible);
@@ -1482,20 +2899,15 @@ mod tests {
     fn test_generate_report_strict_mode_filters() {
         let data = vec![
             LicenseInfo {
-                name: "permissive_package".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("permissive_package", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "restrictive_package".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("restrictive_package", "2.0.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1511,7 +2923,11 @@ mod tests {
             false,
             None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
 
         assert!(has_restrictive);
         assert!(has_incompatible);
@@ -1520,18 +2936,19 @@ mod tests {
     #[test]
     fn test_generate_report_json_output() {
         let data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let config = ReportConfig::new(
             true, false, false, false, false, None, None, None, false, None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
 
         assert!(!has_restrictive);
         assert!(!has_incompatible);
@@ -1540,18 +2957,19 @@ mod tests {
     #[test]
     fn test_generate_report_yaml_output() {
         let data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let config = ReportConfig::new(
             false, true, false, false, false, None, None, None, false, None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
 
         assert!(!has_restrictive);
         assert!(!has_incompatible);
@@ -1560,12 +2978,9 @@ mod tests {
     #[test]
     fn test_generate_report_verbose_output() {
         let data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let config = ReportConfig::new(
@@ -1580,7 +2995,11 @@ mod tests {
             false,
             None,
         );
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
 
         assert!(!has_restrictive);
         assert!(!has_incompatible);
@@ -1589,12 +3008,10 @@ mod tests {
     #[test]
     fn test_github_output_format_stdout() {
         let data = vec![LicenseInfo {
-            name: "restrictive_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("GPL-3.0".to_string()),
             is_restrictive: true,
             compatibility: LicenseCompatibility::Incompatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("restrictive_package", "1.0.0", Some("GPL-3.0".to_string()))
         }];
 
         let config = ReportConfig::new(
@@ -1610,7 +3027,11 @@ mod tests {
             None,
         );
 
-        let (has_restrictive, has_incompatible) = generate_report(data, config);
+        let ReportOutcome {
+            has_restrictive,
+            has_incompatible,
+            ..
+        } = generate_report(data, config);
         assert!(has_restrictive);
         assert!(has_incompatible);
     }
@@ -1618,12 +3039,9 @@ mod tests {
     #[test]
     fn test_output_github_format_file_write_error() {
         let data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         output_github_format(
@@ -1636,12 +3054,9 @@ mod tests {
     #[test]
     fn test_output_jenkins_format_file_write_error() {
         let data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         output_jenkins_format(
@@ -1652,28 +3067,25 @@ mod tests {
     }
 
     #[test]
-    fn test_print_restrictive_licenses_table() {
+    fn test_render_restrictive_licenses_table() {
         let data = [
             LicenseInfo {
-                name: "restrictive1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("restrictive1", "1.0.0", Some("GPL-3.0".to_string()))
             },
             LicenseInfo {
-                name: "restrictive2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("AGPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("restrictive2", "2.0.0", Some("AGPL-3.0".to_string()))
             },
         ];
 
         let restrictive_refs: Vec<&LicenseInfo> = data.iter().collect();
-        print_restrictive_licenses_table(&restrictive_refs);
+        let table = render_restrictive_licenses_table(&restrictive_refs);
+        assert!(table.contains("restrictive2"));
     }
 
     #[test]
@@ -1714,4 +3126,173 @@ mod tests {
         assert!(debug_str.contains("yaml: false"));
         assert!(debug_str.contains("Github"));
     }
+
+    fn sorted_names(mut data: Vec<LicenseInfo>, sort: ReportSort) -> Vec<String> {
+        data.sort_by(|a, b| compare_for_report(a, b, sort));
+        data.into_iter()
+            .map(|info| format!("{}@{}", info.name, info.version))
+            .collect()
+    }
+
+    fn sort_test_data() -> Vec<LicenseInfo> {
+        let mut data = get_test_data();
+        data.reverse();
+        // Same license and restrictiveness as crate1, so only the name breaks the tie
+        let mut duplicate = inventory_package("crate0", Some("MIT"));
+        duplicate.version = "0.1.0".to_string();
+        data.push(duplicate);
+        // Same name as crate1 with an older version
+        let mut older = inventory_package("crate1", Some("MIT"));
+        older.version = "0.9.0".to_string();
+        data.push(older);
+        data
+    }
+
+    #[test]
+    fn test_compare_for_report_by_name() {
+        assert_eq!(
+            sorted_names(sort_test_data(), ReportSort::Name),
+            vec![
+                "crate0@0.1.0",
+                "crate1@0.9.0",
+                "crate1@1.0.0",
+                "crate2@2.0.0",
+                "crate3@3.0.0",
+                "crate4@4.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_for_report_by_license() {
+        assert_eq!(
+            sorted_names(sort_test_data(), ReportSort::License),
+            vec![
+                "crate3@3.0.0", // Apache-2.0
+                "crate2@2.0.0", // GPL-3.0
+                "crate0@0.1.0", // MIT
+                "crate1@0.9.0",
+                "crate1@1.0.0",
+                "crate4@4.0.0", // Unknown
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_for_report_restrictive_first() {
+        assert_eq!(
+            sorted_names(sort_test_data(), ReportSort::Restrictive),
+            vec![
+                "crate2@2.0.0",
+                "crate0@0.1.0",
+                "crate1@0.9.0",
+                "crate1@1.0.0",
+                "crate3@3.0.0",
+                "crate4@4.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_config_with_sort() {
+        let config = ReportConfig::new(
+            true, false, false, false, false, None, None, None, false, None,
+        );
+        assert_eq!(config.sort, None);
+        let config = config.with_sort(Some(ReportSort::License));
+        assert_eq!(config.sort, Some(ReportSort::License));
+    }
+
+    #[test]
+    fn test_severity_of() {
+        let severity = |license: &str, restrictive: bool, osi: OsiStatus| {
+            let mut info = inventory_package("pkg", Some(license));
+            info.is_restrictive = restrictive;
+            info.osi_status = osi;
+            severity_of(&info)
+        };
+
+        assert_eq!(
+            severity("GPL-3.0-only", true, OsiStatus::Approved),
+            Severity::Error
+        );
+        assert_eq!(
+            severity("LGPL-2.1-only", true, OsiStatus::Approved),
+            Severity::Error
+        );
+        assert_eq!(
+            severity("BUSL-1.1", true, OsiStatus::NotApproved),
+            Severity::Error
+        );
+        // Restrictive only because a config lists it, not because it is copyleft
+        assert_eq!(
+            severity("Apache-2.0", true, OsiStatus::Approved),
+            Severity::Warn
+        );
+        assert_eq!(
+            severity("CC-BY-NC-4.0", false, OsiStatus::NotApproved),
+            Severity::Warn
+        );
+        assert_eq!(severity("MIT", false, OsiStatus::Approved), Severity::Info);
+        assert_eq!(severity("MIT", false, OsiStatus::Unknown), Severity::Info);
+        // Copyleft is fine when it is not restrictive for this project
+        assert_eq!(
+            severity("MPL-2.0", false, OsiStatus::Approved),
+            Severity::Info
+        );
+
+        let mut incompatible = inventory_package("pkg", Some("MIT"));
+        incompatible.compatibility = LicenseCompatibility::Incompatible;
+        assert_eq!(severity_of(&incompatible), Severity::Error);
+    }
+
+    #[test]
+    fn test_min_severity_filters_report() {
+        let mut data = get_test_data();
+        data[0].osi_status = OsiStatus::NotApproved;
+        let names_at = |min_severity| {
+            let mut filtered = data.clone();
+            filtered.retain(|info| severity_of(info) >= min_severity);
+            filtered
+                .into_iter()
+                .map(|info| info.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names_at(Severity::Error), ["crate2"]);
+        assert_eq!(names_at(Severity::Warn), ["crate1", "crate2"]);
+        assert_eq!(names_at(Severity::Info).len(), data.len());
+
+        let config = ReportConfig::new(
+            false, false, false, false, false, None, None, None, false, None,
+        )
+        .with_min_severity(Some(Severity::Error));
+        assert_eq!(config.min_severity, Some(Severity::Error));
+        let outcome = generate_report(data.clone(), config);
+        assert!(outcome.has_restrictive);
+    }
+
+    #[test]
+    fn test_report_entries_include_multi_license() {
+        let mut data = get_test_data();
+        data[0].license = Some("MIT OR Apache-2.0".to_string());
+
+        let json = serde_json::to_value(report_entries(&data)).unwrap();
+        assert_eq!(json[0]["name"], "crate1");
+        assert_eq!(json[0]["license"], "MIT OR Apache-2.0");
+        assert_eq!(json[0]["multi_license"], true);
+        assert_eq!(json[1]["multi_license"], false);
+    }
+
+    #[test]
+    fn test_report_entries_include_category() {
+        let mut data = get_test_data();
+        data[0].license = Some("LGPL-2.1-only".to_string());
+        data[1].license = Some("GPL-3.0-or-later".to_string());
+        data[1].is_restrictive = true;
+
+        let json = serde_json::to_value(report_entries(&data)).unwrap();
+        assert_eq!(json[0]["category"], "WeakCopyleft");
+        assert_eq!(json[1]["category"], "StrongCopyleft");
+    }
 }
@@ -0,0 +1,315 @@
+//! License changes against a base git revision for `--diff`
+//!
+//! The project is analyzed at the working tree and at the base revision, and the two results
+//! are compared like SBOMs: dependencies added, removed, or with a changed version or license.
+//! A dependency counts as introduced when its name, version and license did not appear at
+//! the base, and only introduced dependencies count toward `--fail-on-*`, so a pull request
+//! is not blamed for findings it inherited.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::debug::{FeludaError, FeludaResult};
+use crate::licenses::{LicenseCompatibility, LicenseInfo};
+use crate::reporter::{to_json_string, ReportOutcome};
+use crate::sbom::diff::{diff_packages, format_diff_table, SbomDiff, SbomPackage};
+
+/// A restrictive or incompatible dependency that is new since the base revision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+    pub restrictive: bool,
+    pub incompatible: bool,
+}
+
+/// Differences between the analyses of the base revision and the working tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RevisionDiff {
+    #[serde(flatten)]
+    pub changes: SbomDiff,
+    pub new_findings: Vec<Finding>,
+    /// Findings among the introduced dependencies only
+    #[serde(skip)]
+    pub outcome: ReportOutcome,
+}
+
+fn packages(data: &[LicenseInfo]) -> Vec<SbomPackage> {
+    data.iter()
+        .map(|info| SbomPackage {
+            name: info.name.clone(),
+            version: Some(info.version.clone()),
+            license: info.license.clone(),
+        })
+        .collect()
+}
+
+/// Where the `--manifest` file of the working tree lives in the checkout of the base revision
+///
+/// `project_path` is the analyzed directory and `base_path` its copy at the base revision; the
+/// manifest must be inside `project_path` so the same file can be read on both sides.
+pub fn rebase_manifest(
+    manifest: &Path,
+    project_path: &Path,
+    base_path: &Path,
+) -> FeludaResult<PathBuf> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let relative = canonical(manifest)
+        .strip_prefix(canonical(project_path))
+        .map(Path::to_path_buf)
+        .map_err(|_| {
+            FeludaError::InvalidData(format!(
+                "--manifest {} must be inside {} to compare it with --diff",
+                manifest.display(),
+                project_path.display()
+            ))
+        })?;
+    Ok(base_path.join(relative))
+}
+
+/// Compare the analysis of the base revision with the analysis of the working tree
+pub fn diff_analyses(base: &[LicenseInfo], head: &[LicenseInfo]) -> RevisionDiff {
+    let key = |info: &LicenseInfo| (info.name.clone(), info.version.clone(), info.get_license());
+    let base_keys: HashSet<_> = base.iter().map(key).collect();
+    let introduced: Vec<LicenseInfo> = head
+        .iter()
+        .filter(|info| !base_keys.contains(&key(info)))
+        .cloned()
+        .collect();
+
+    let new_findings = introduced
+        .iter()
+        .filter_map(|info| {
+            let incompatible = info.compatibility == LicenseCompatibility::Incompatible;
+            (info.is_restrictive || incompatible).then(|| Finding {
+                name: info.name.clone(),
+                version: info.version.clone(),
+                license: info.get_license(),
+                restrictive: info.is_restrictive,
+                incompatible,
+            })
+        })
+        .collect();
+
+    RevisionDiff {
+        changes: diff_packages(&packages(base), &packages(head)),
+        new_findings,
+        outcome: ReportOutcome {
+            total: head.len(),
+            shown: head.len(),
+            ..ReportOutcome::from_data(&introduced)
+        },
+    }
+}
+
+/// Print the dependency changes and new findings since `git_ref`, as a table or JSON
+pub fn print_revision_diff(git_ref: &str, diff: &RevisionDiff, json: bool) -> FeludaResult<()> {
+    if json {
+        let output = to_json_string(diff).map_err(|e| {
+            FeludaError::Serialization(format!("Failed to serialize revision diff: {e}"))
+        })?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!("\n{}", format!("License changes since {git_ref}").bold());
+    if diff.changes.is_empty() {
+        println!("{}", "No dependency changes".green());
+    } else {
+        print!("{}", format_diff_table(&diff.changes));
+    }
+
+    if diff.new_findings.is_empty() {
+        println!(
+            "\n{}",
+            "No new restrictive or incompatible licenses".green()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "New findings:".red().bold());
+    for finding in &diff.new_findings {
+        let kinds = [
+            (finding.restrictive, "restrictive"),
+            (finding.incompatible, "incompatible"),
+        ]
+        .iter()
+        .filter(|(applies, _)| *applies)
+        .map(|(_, kind)| *kind)
+        .collect::<Vec<_>>()
+        .join(", ");
+        println!(
+            "  {} {}@{} ({}): {kinds}",
+            "✗".red(),
+            finding.name,
+            finding.version,
+            finding.license
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FeludaConfig;
+    use crate::parser::parse_root_with_config;
+    use crate::reporter::FailOn;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// Node project whose installed packages declare the given licenses
+    fn node_fixture(root: &Path, packages: &[(&str, &str, &str)]) {
+        let dependencies = packages
+            .iter()
+            .map(|(name, version, _)| format!("\"{name}\": \"{version}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        fs::write(
+            root.join("package.json"),
+            format!(r#"{{"name": "app", "dependencies": {{{dependencies}}}}}"#),
+        )
+        .unwrap();
+
+        for (name, version, license) in packages {
+            let dir = root.join("node_modules").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("package.json"),
+                format!(r#"{{"name": "{name}", "version": "{version}", "license": "{license}"}}"#),
+            )
+            .unwrap();
+        }
+    }
+
+    fn analyze(root: &Path) -> Vec<LicenseInfo> {
+        analyze_manifest(root, None)
+    }
+
+    fn analyze_manifest(root: &Path, manifest: Option<PathBuf>) -> Vec<LicenseInfo> {
+        crate::network::set_offline_mode(true);
+        let config = FeludaConfig {
+            direct_only: true,
+            manifest,
+            ..Default::default()
+        };
+        let result = parse_root_with_config(root, None, &config, false);
+        crate::network::set_offline_mode(false);
+        result.unwrap()
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_diff_between_fixture_revisions() {
+        let base_dir = TempDir::new().unwrap();
+        let head_dir = TempDir::new().unwrap();
+        node_fixture(
+            base_dir.path(),
+            &[
+                ("left-pad", "1.3.0", "MIT"),
+                ("legacy-gpl", "1.0.0", "GPL-3.0"),
+                ("lodash", "4.17.21", "MIT"),
+            ],
+        );
+        node_fixture(
+            head_dir.path(),
+            &[
+                ("legacy-gpl", "1.0.0", "GPL-3.0"),
+                ("lodash", "5.0.0", "AGPL-3.0"),
+                ("express", "4.19.2", "MIT"),
+            ],
+        );
+
+        let diff = diff_analyses(&analyze(base_dir.path()), &analyze(head_dir.path()));
+
+        let names =
+            |packages: &[SbomPackage]| packages.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.changes.added), ["express"]);
+        assert_eq!(names(&diff.changes.removed), ["left-pad"]);
+        assert_eq!(diff.changes.changed.len(), 1);
+        assert_eq!(diff.changes.changed[0].name, "lodash");
+        assert_eq!(
+            diff.changes.changed[0].new_license.as_deref(),
+            Some("AGPL-3.0")
+        );
+
+        // The GPL dependency was already there; only the relicensed upgrade is new
+        assert_eq!(
+            diff.new_findings,
+            [Finding {
+                name: "lodash".to_string(),
+                version: "5.0.0".to_string(),
+                license: "AGPL-3.0".to_string(),
+                restrictive: true,
+                incompatible: false,
+            }]
+        );
+        let fail_on_restrictive = FailOn {
+            restrictive: true,
+            ..FailOn::default()
+        };
+        assert!(diff.outcome.fails(&fail_on_restrictive));
+        assert_eq!(diff.outcome.total, 3);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_diff_with_manifest_reads_base_revision() {
+        let base_dir = TempDir::new().unwrap();
+        let head_dir = TempDir::new().unwrap();
+        for root in [base_dir.path(), head_dir.path()] {
+            fs::create_dir(root.join("web")).unwrap();
+        }
+        node_fixture(
+            &base_dir.path().join("web"),
+            &[("left-pad", "1.3.0", "MIT")],
+        );
+        node_fixture(
+            &head_dir.path().join("web"),
+            &[("left-pad", "1.3.0", "MIT"), ("express", "4.19.2", "MIT")],
+        );
+
+        let manifest = head_dir.path().join("web/package.json");
+        let base_manifest = rebase_manifest(&manifest, head_dir.path(), base_dir.path()).unwrap();
+        assert_eq!(base_manifest, base_dir.path().join("web/package.json"));
+
+        let diff = diff_analyses(
+            &analyze_manifest(base_dir.path(), Some(base_manifest)),
+            &analyze_manifest(head_dir.path(), Some(manifest)),
+        );
+        let added: Vec<&str> = diff.changes.added.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(added, ["express"]);
+
+        let outside = base_dir.path().join("web/package.json");
+        assert!(rebase_manifest(&outside, head_dir.path(), base_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_inherited_findings_do_not_fail() {
+        let gpl = LicenseInfo {
+            is_restrictive: true,
+            compatibility: LicenseCompatibility::Incompatible,
+            osi_status: crate::licenses::OsiStatus::Approved,
+            ecosystem: "npm".to_string(),
+            ..LicenseInfo::new("legacy-gpl", "1.0.0", Some("GPL-3.0".to_string()))
+        };
+        let diff = diff_analyses(std::slice::from_ref(&gpl), std::slice::from_ref(&gpl));
+
+        assert!(diff.changes.is_empty());
+        assert!(diff.new_findings.is_empty());
+        let fail_on_all = FailOn {
+            restrictive: true,
+            incompatible: true,
+            unknown: true,
+            non_osi: true,
+            unknown_osi: true,
+            unknown_ratio: Some(0),
+        };
+        assert!(!diff.outcome.fails(&fail_on_all));
+    }
+}
@@ -0,0 +1,299 @@
+//! Convert an SBOM between SPDX and CycloneDX JSON
+//!
+//! The input format is detected from the document. Both formats are read into an
+//! [`SpdxDocument`] and written with the regular generators, so a converted SBOM looks like
+//! one Feluda produced itself. Names, versions, license expressions, purls and copyright
+//! texts carry over; fields without a counterpart get the generators' defaults, e.g.
+//! `NOASSERTION` download locations.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value as JsonValue;
+
+use crate::cli::ConvertFormat;
+use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::sbom::cyclonedx::generate_cyclonedx_output;
+use crate::sbom::diff::cyclonedx_license;
+use crate::sbom::spdx::{generate_spdx_output, SpdxDocument, SpdxPackage};
+use crate::sbom::validate::{detect_sbom_type, SbomType};
+
+fn is_placeholder(license: Option<&str>) -> bool {
+    matches!(
+        license.map(str::trim),
+        None | Some("" | "NOASSERTION" | "NONE")
+    )
+}
+
+/// Read an SPDX document, using the declared license where none was concluded
+fn spdx_document(content: &str) -> FeludaResult<SpdxDocument> {
+    let mut document: SpdxDocument = serde_json::from_str(content)
+        .map_err(|e| FeludaError::Validation(format!("Failed to parse SPDX document: {e}")))?;
+
+    for package in &mut document.packages {
+        if is_placeholder(package.license_concluded.as_deref())
+            && !is_placeholder(package.license_declared.as_deref())
+        {
+            package.license_concluded = package.license_declared.clone();
+        }
+    }
+    Ok(document)
+}
+
+fn json_str<'a>(value: &'a JsonValue, key: &str) -> Option<&'a str> {
+    value
+        .get(key)
+        .and_then(JsonValue::as_str)
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// SPDX package for a CycloneDX component
+fn component_package(component: &JsonValue) -> Option<SpdxPackage> {
+    let name = json_str(component, "name")?;
+    let mut package = SpdxPackage::new(name.to_string(), "");
+
+    if let Some(version) = json_str(component, "version") {
+        package = package.with_version(version.to_string());
+    }
+    package = package
+        .with_license(cyclonedx_license(component).unwrap_or_else(|| "NOASSERTION".to_string()));
+    if let Some(copyright) = json_str(component, "copyright") {
+        package = package.with_copyright(copyright.to_string());
+    }
+    if let Some(purl) = json_str(component, "purl") {
+        package = package.add_external_ref(
+            "PACKAGE-MANAGER".to_string(),
+            "purl".to_string(),
+            purl.to_string(),
+        );
+    }
+    Some(package)
+}
+
+/// Keep SPDX identifiers unique when a BOM lists the same name and version twice
+fn unique_id(package: &mut SpdxPackage, used_ids: &mut HashSet<String>) {
+    let base = package.spdx_id.clone();
+    let mut suffix = 1;
+    while !used_ids.insert(package.spdx_id.clone()) {
+        suffix += 1;
+        package.spdx_id = format!("{base}-{suffix}");
+    }
+}
+
+/// Read a CycloneDX BOM into an SPDX document
+///
+/// The BOM's `metadata.component` becomes the described `APPLICATION` package, which
+/// `DEPENDS_ON` every component; without it the document describes each component.
+fn cyclonedx_document(content: &str, fallback_name: &str) -> FeludaResult<SpdxDocument> {
+    let json: JsonValue = serde_json::from_str(content)
+        .map_err(|e| FeludaError::Validation(format!("Failed to parse CycloneDX BOM: {e}")))?;
+
+    let root = json
+        .get("metadata")
+        .and_then(|metadata| metadata.get("component"))
+        .and_then(component_package)
+        .map(|package| package.with_primary_package_purpose("APPLICATION"));
+
+    let project_name = root
+        .as_ref()
+        .map_or(fallback_name, |package| package.name.as_str())
+        .to_string();
+    let mut document = SpdxDocument::new(&project_name);
+    let mut used_ids = HashSet::new();
+
+    let root_id = root.map(|mut root| {
+        unique_id(&mut root, &mut used_ids);
+        let id = root.spdx_id.clone();
+        document.add_package(root);
+        id
+    });
+
+    let components = json
+        .get("components")
+        .and_then(JsonValue::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for mut package in components.iter().filter_map(component_package) {
+        unique_id(&mut package, &mut used_ids);
+        match &root_id {
+            Some(root_id) => {
+                document.add_relationship(root_id.clone(), "DEPENDS_ON", package.spdx_id.clone());
+                document.add_dependency_package(package);
+            }
+            None => document.add_package(package),
+        }
+    }
+    Ok(document)
+}
+
+/// Read an SPDX or CycloneDX JSON SBOM into an SPDX document
+pub fn load_sbom_document(content: &str, fallback_name: &str) -> FeludaResult<SpdxDocument> {
+    let document = match detect_sbom_type(content)? {
+        SbomType::Spdx => spdx_document(content)?,
+        SbomType::CycloneDx => cyclonedx_document(content, fallback_name)?,
+    };
+
+    log(
+        LogLevel::Info,
+        &format!("Loaded {} packages for conversion", document.packages.len()),
+    );
+    Ok(document)
+}
+
+pub fn handle_sbom_convert_command(
+    input: String,
+    to: ConvertFormat,
+    output: Option<String>,
+) -> FeludaResult<()> {
+    log(
+        LogLevel::Info,
+        &format!("Converting SBOM {input} to {to:?}"),
+    );
+
+    let content = fs::read_to_string(&input)
+        .map_err(|_| FeludaError::Validation(format!("Failed to read SBOM file: {input}")))?;
+    let fallback_name = Path::new(&input)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("project");
+    let document = load_sbom_document(&content, fallback_name)?;
+
+    match to {
+        ConvertFormat::Spdx => generate_spdx_output(&document, output),
+        ConvertFormat::Cyclonedx => generate_cyclonedx_output(&document, output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sbom::diff::{load_sbom_packages, SbomPackage};
+    use tempfile::TempDir;
+
+    const SPDX_FIXTURE: &str = r#"{
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "fixture",
+        "documentNamespace": "https://example.com/spdx/fixture",
+        "creationInfo": {"created": "2024-01-01T00:00:00Z", "creators": ["Tool: other"]},
+        "packages": [
+            {"name": "serde", "SPDXID": "SPDXRef-serde", "downloadLocation": "NOASSERTION",
+             "filesAnalyzed": false, "versionInfo": "1.0.200",
+             "licenseConcluded": "MIT OR Apache-2.0",
+             "externalRefs": [{"referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+                               "referenceLocator": "pkg:cargo/serde@1.0.200"}]},
+            {"name": "ring", "SPDXID": "SPDXRef-ring", "downloadLocation": "NOASSERTION",
+             "filesAnalyzed": false, "versionInfo": "0.17.8",
+             "licenseConcluded": "NOASSERTION", "licenseDeclared": "Apache-2.0 AND ISC"},
+            {"name": "mystery", "SPDXID": "SPDXRef-mystery", "downloadLocation": "NOASSERTION",
+             "filesAnalyzed": false}
+        ]
+    }"#;
+
+    fn package(name: &str, version: Option<&str>, license: Option<&str>) -> SbomPackage {
+        SbomPackage {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            license: license.map(str::to_string),
+        }
+    }
+
+    fn convert(input: &str, to: ConvertFormat, dir: &TempDir) -> Vec<SbomPackage> {
+        let input_path = dir.path().join("input.json");
+        let output_path = dir.path().join(format!("output-{to:?}.json"));
+        fs::write(&input_path, input).unwrap();
+        handle_sbom_convert_command(
+            input_path.to_string_lossy().to_string(),
+            to,
+            Some(output_path.to_string_lossy().to_string()),
+        )
+        .unwrap();
+
+        let mut packages = load_sbom_packages(&output_path.to_string_lossy()).unwrap();
+        packages.sort();
+        packages
+    }
+
+    #[test]
+    fn test_convert_spdx_to_cyclonedx() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("bom.json");
+        let input_path = dir.path().join("input.spdx.json");
+        fs::write(&input_path, SPDX_FIXTURE).unwrap();
+        handle_sbom_convert_command(
+            input_path.to_string_lossy().to_string(),
+            ConvertFormat::Cyclonedx,
+            Some(output_path.to_string_lossy().to_string()),
+        )
+        .unwrap();
+
+        let bom: JsonValue =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["components"][0]["purl"], "pkg:cargo/serde@1.0.200");
+
+        let mut packages = load_sbom_packages(&output_path.to_string_lossy()).unwrap();
+        packages.sort();
+        assert_eq!(
+            packages,
+            [
+                package("mystery", None, None),
+                package("ring", Some("0.17.8"), Some("Apache-2.0 AND ISC")),
+                package("serde", Some("1.0.200"), Some("MIT OR Apache-2.0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_preserves_packages() {
+        let dir = TempDir::new().unwrap();
+        let cyclonedx = convert(SPDX_FIXTURE, ConvertFormat::Cyclonedx, &dir);
+        let cyclonedx_json = fs::read_to_string(dir.path().join("output-Cyclonedx.json")).unwrap();
+        let spdx = convert(&cyclonedx_json, ConvertFormat::Spdx, &dir);
+
+        assert_eq!(spdx, cyclonedx);
+        assert_eq!(spdx.len(), 3);
+    }
+
+    #[test]
+    fn test_cyclonedx_metadata_component_becomes_root() {
+        let bom = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.6",
+            "metadata": {"tools": [{"name": "other"}],
+                         "component": {"type": "application", "name": "app", "version": "2.0.0",
+                                       "licenses": [{"license": {"id": "MIT"}}]}},
+            "components": [
+                {"type": "library", "name": "left-pad", "version": "1.3.0",
+                 "licenses": [{"license": {"id": "WTFPL"}}], "purl": "pkg:npm/left-pad@1.3.0"},
+                {"type": "library", "name": "left-pad", "version": "1.3.0"}
+            ]
+        }"#;
+        let document = load_sbom_document(bom, "fallback").unwrap();
+
+        assert!(document.name.starts_with("app-"));
+        assert_eq!(document.packages.len(), 3);
+        let root = &document.packages[0];
+        assert_eq!(root.primary_package_purpose.as_deref(), Some("APPLICATION"));
+        assert_eq!(root.license_declared.as_deref(), Some("MIT"));
+        assert_ne!(document.packages[1].spdx_id, document.packages[2].spdx_id);
+        assert_eq!(
+            document.packages[1].external_refs[0].reference_locator,
+            "pkg:npm/left-pad@1.3.0"
+        );
+
+        let depends_on = document
+            .relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DEPENDS_ON" && r.spdx_element_id == root.spdx_id)
+            .count();
+        assert_eq!(depends_on, 2);
+    }
+
+    #[test]
+    fn test_convert_rejects_unknown_documents() {
+        assert!(load_sbom_document(r#"{"name": "not an sbom"}"#, "x").is_err());
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::reporter::to_json_string;
 use crate::sbom::spdx::SpdxDocument;
 
 /// CycloneDX v1.5 BOM structure
@@ -30,6 +31,10 @@ pub struct CycloneDxBom {
     /// List of components (optional)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub components: Vec<CycloneDxComponent>,
+
+    /// Vulnerabilities (optional); Feluda only emits an empty list in VEX skeletons
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<Vec<serde_json::Value>>,
 }
 
 /// CycloneDX metadata structure
@@ -112,6 +117,10 @@ pub struct CycloneDxComponent {
     #[serde(rename = "type")]
     pub component_type: String, // "library", "application", "framework", etc.
 
+    /// Reference identifier unique within the BOM (optional)
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    pub bom_ref: Option<String>,
+
     /// Component name (required)
     pub name: String,
 
@@ -208,6 +217,7 @@ impl CycloneDxBom {
                 component: None,
             }),
             components: Vec::new(),
+            vulnerabilities: None,
         }
     }
 
@@ -259,15 +269,30 @@ pub fn convert_spdx_to_cyclonedx(spdx_doc: &SpdxDocument) -> CycloneDxBom {
 
     // Convert each SPDX package to CycloneDX component
     for spdx_package in &spdx_doc.packages {
+        let purl = spdx_package
+            .external_refs
+            .iter()
+            .find(|external_ref| external_ref.reference_type == "purl")
+            .map(|external_ref| external_ref.reference_locator.clone());
+
+        // The purl keeps bom-refs unique when two ecosystems share a package name
+        let bom_ref = purl
+            .clone()
+            .unwrap_or_else(|| match &spdx_package.version_info {
+                Some(version) => format!("{}@{}", spdx_package.name, version),
+                None => spdx_package.name.clone(),
+            });
+
         let mut component = CycloneDxComponent {
             component_type: "library".to_string(), // Default to library for dependencies
+            bom_ref: Some(bom_ref),
             name: spdx_package.name.clone(),
             version: spdx_package.version_info.clone(),
             description: None,
             scope: Some("required".to_string()), // Default scope
             licenses: Vec::new(),
             copyright: spdx_package.copyright_text.clone(),
-            purl: None, // Could be enhanced in the future
+            purl,
             external_references: Vec::new(),
         };
 
@@ -282,6 +307,16 @@ pub fn convert_spdx_to_cyclonedx(spdx_doc: &SpdxDocument) -> CycloneDxBom {
                 .push(convert_spdx_license_to_cyclonedx(license_declared));
         }
 
+        // The analyzed project itself describes the BOM rather than being one of its components
+        if spdx_package.primary_package_purpose.as_deref() == Some("APPLICATION") {
+            if let Some(metadata) = bom.metadata.as_mut() {
+                component.component_type = "application".to_string();
+                component.scope = None;
+                metadata.component = Some(component);
+                continue;
+            }
+        }
+
         bom.add_component(component);
     }
 
@@ -306,7 +341,7 @@ pub fn generate_cyclonedx_output(
     let cyclonedx_bom = convert_spdx_to_cyclonedx(spdx_doc);
 
     // Serialize to JSON
-    let json_output = serde_json::to_string_pretty(&cyclonedx_bom).map_err(|e| {
+    let json_output = to_json_string(&cyclonedx_bom).map_err(|e| {
         FeludaError::Serialization(format!("Failed to serialize CycloneDX BOM: {e}"))
     })?;
 
@@ -337,10 +372,305 @@ pub fn generate_cyclonedx_output(
     Ok(())
 }
 
+/// Convert an SPDX document to a CycloneDX VEX skeleton
+///
+/// The components and their `bom-ref`s are the same as in the CycloneDX SBOM, so a
+/// downstream scanner can add vulnerabilities that reference them.
+pub fn convert_spdx_to_cyclonedx_vex(spdx_doc: &SpdxDocument) -> CycloneDxBom {
+    let mut bom = convert_spdx_to_cyclonedx(spdx_doc);
+    bom.vulnerabilities = Some(Vec::new());
+    bom
+}
+
+pub fn generate_cyclonedx_vex_output(
+    spdx_doc: &SpdxDocument,
+    output_file: Option<String>,
+) -> FeludaResult<()> {
+    log(LogLevel::Info, "Generating CycloneDX VEX skeleton output");
+
+    let vex = convert_spdx_to_cyclonedx_vex(spdx_doc);
+
+    let json_output = to_json_string(&vex).map_err(|e| {
+        FeludaError::Serialization(format!("Failed to serialize CycloneDX VEX: {e}"))
+    })?;
+
+    if let Some(file_path) = output_file {
+        let vex_file = if file_path.ends_with(".json") {
+            file_path
+        } else {
+            format!("{}.vex.json", file_path.trim_end_matches(".vex"))
+        };
+
+        std::fs::write(&vex_file, &json_output)
+            .map_err(|e| FeludaError::FileWrite(format!("Failed to write VEX file: {e}")))?;
+
+        println!("🧪 CycloneDX VEX skeleton written to: {vex_file} (EXPERIMENTAL)");
+        log(
+            LogLevel::Info,
+            &format!("CycloneDX VEX skeleton written to: {vex_file}"),
+        );
+    } else {
+        println!("=== CycloneDX VEX (EXPERIMENTAL) ===");
+        println!("{json_output}");
+    }
+
+    Ok(())
+}
+
+/// Minimal well-formedness check for tests: every opened tag is closed in order
+#[cfg(test)]
+pub(crate) fn assert_well_formed_xml(xml: &str) {
+    let mut stack: Vec<String> = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let end = rest[start..].find('>').expect("unterminated tag") + start;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop().as_deref(), Some(name), "mismatched </{name}>");
+        } else if !tag.ends_with('/') {
+            let name = tag.split_whitespace().next().unwrap();
+            stack.push(name.to_string());
+        }
+    }
+    assert!(stack.is_empty(), "unclosed tags: {stack:?}");
+}
+
+/// Escape text and attribute values for XML output
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn push_xml_element(out: &mut String, indent: usize, tag: &str, value: &str) {
+    out.push_str(&format!(
+        "{}<{tag}>{}</{tag}>\n",
+        "  ".repeat(indent),
+        xml_escape(value)
+    ));
+}
+
+fn push_xml_component(out: &mut String, indent: usize, component: &CycloneDxComponent) {
+    let pad = "  ".repeat(indent);
+    match &component.bom_ref {
+        Some(bom_ref) => out.push_str(&format!(
+            "{pad}<component type=\"{}\" bom-ref=\"{}\">\n",
+            xml_escape(&component.component_type),
+            xml_escape(bom_ref)
+        )),
+        None => out.push_str(&format!(
+            "{pad}<component type=\"{}\">\n",
+            xml_escape(&component.component_type)
+        )),
+    }
+
+    push_xml_element(out, indent + 1, "name", &component.name);
+    if let Some(version) = &component.version {
+        push_xml_element(out, indent + 1, "version", version);
+    }
+    if let Some(description) = &component.description {
+        push_xml_element(out, indent + 1, "description", description);
+    }
+    if let Some(scope) = &component.scope {
+        push_xml_element(out, indent + 1, "scope", scope);
+    }
+
+    if !component.licenses.is_empty() {
+        out.push_str(&format!("{pad}  <licenses>\n"));
+        for choice in &component.licenses {
+            match choice {
+                CycloneDxLicenseChoice::License { license } => {
+                    out.push_str(&format!("{pad}    <license>\n"));
+                    if let Some(id) = &license.id {
+                        push_xml_element(out, indent + 3, "id", id);
+                    } else if let Some(name) = &license.name {
+                        push_xml_element(out, indent + 3, "name", name);
+                    }
+                    if let Some(url) = &license.url {
+                        push_xml_element(out, indent + 3, "url", url);
+                    }
+                    out.push_str(&format!("{pad}    </license>\n"));
+                }
+                CycloneDxLicenseChoice::Expression { expression } => {
+                    push_xml_element(out, indent + 2, "expression", expression);
+                }
+            }
+        }
+        out.push_str(&format!("{pad}  </licenses>\n"));
+    }
+
+    if let Some(copyright) = &component.copyright {
+        push_xml_element(out, indent + 1, "copyright", copyright);
+    }
+    if let Some(purl) = &component.purl {
+        push_xml_element(out, indent + 1, "purl", purl);
+    }
+
+    if !component.external_references.is_empty() {
+        out.push_str(&format!("{pad}  <externalReferences>\n"));
+        for reference in &component.external_references {
+            out.push_str(&format!(
+                "{pad}    <reference type=\"{}\">\n",
+                xml_escape(&reference.ref_type)
+            ));
+            push_xml_element(out, indent + 3, "url", &reference.url);
+            if let Some(comment) = &reference.comment {
+                push_xml_element(out, indent + 3, "comment", comment);
+            }
+            out.push_str(&format!("{pad}    </reference>\n"));
+        }
+        out.push_str(&format!("{pad}  </externalReferences>\n"));
+    }
+
+    out.push_str(&format!("{pad}</component>\n"));
+}
+
+/// Serialize a CycloneDX BOM using the CycloneDX 1.5 XML schema
+fn format_cyclonedx_xml(bom: &CycloneDxBom) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let mut bom_attrs = format!(
+        "xmlns=\"http://cyclonedx.org/schema/bom/{}\"",
+        xml_escape(&bom.spec_version)
+    );
+    if let Some(serial_number) = &bom.serial_number {
+        bom_attrs.push_str(&format!(" serialNumber=\"{}\"", xml_escape(serial_number)));
+    }
+    if let Some(version) = bom.version {
+        bom_attrs.push_str(&format!(" version=\"{version}\""));
+    }
+    out.push_str(&format!("<bom {bom_attrs}>\n"));
+
+    if let Some(metadata) = &bom.metadata {
+        out.push_str("  <metadata>\n");
+        if let Some(timestamp) = &metadata.timestamp {
+            push_xml_element(
+                &mut out,
+                2,
+                "timestamp",
+                &timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            );
+        }
+        if let Some(tools) = &metadata.tools {
+            out.push_str("    <tools>\n");
+            if !tools.components.is_empty() {
+                out.push_str("      <components>\n");
+                for tool in &tools.components {
+                    out.push_str(&format!(
+                        "        <component type=\"{}\">\n",
+                        xml_escape(&tool.component_type)
+                    ));
+                    push_xml_element(&mut out, 5, "name", &tool.name);
+                    if let Some(version) = &tool.version {
+                        push_xml_element(&mut out, 5, "version", version);
+                    }
+                    out.push_str("        </component>\n");
+                }
+                out.push_str("      </components>\n");
+            }
+            if !tools.services.is_empty() {
+                out.push_str("      <services>\n");
+                for service in &tools.services {
+                    out.push_str("        <service>\n");
+                    push_xml_element(&mut out, 5, "name", &service.name);
+                    if let Some(version) = &service.version {
+                        push_xml_element(&mut out, 5, "version", version);
+                    }
+                    out.push_str("        </service>\n");
+                }
+                out.push_str("      </services>\n");
+            }
+            out.push_str("    </tools>\n");
+        }
+        if !metadata.authors.is_empty() {
+            out.push_str("    <authors>\n");
+            for author in &metadata.authors {
+                out.push_str("      <author>\n");
+                if let Some(name) = &author.name {
+                    push_xml_element(&mut out, 4, "name", name);
+                }
+                if let Some(email) = &author.email {
+                    push_xml_element(&mut out, 4, "email", email);
+                }
+                out.push_str("      </author>\n");
+            }
+            out.push_str("    </authors>\n");
+        }
+        if let Some(component) = &metadata.component {
+            push_xml_component(&mut out, 2, component);
+        }
+        out.push_str("  </metadata>\n");
+    }
+
+    if !bom.components.is_empty() {
+        out.push_str("  <components>\n");
+        for component in &bom.components {
+            push_xml_component(&mut out, 2, component);
+        }
+        out.push_str("  </components>\n");
+    }
+
+    out.push_str("</bom>\n");
+    out
+}
+
+pub fn generate_cyclonedx_xml_output(
+    spdx_doc: &SpdxDocument,
+    output_file: Option<String>,
+) -> FeludaResult<()> {
+    log(LogLevel::Info, "Generating CycloneDX 1.5 XML BOM output");
+
+    let cyclonedx_bom = convert_spdx_to_cyclonedx(spdx_doc);
+    let xml_output = format_cyclonedx_xml(&cyclonedx_bom);
+
+    if let Some(file_path) = output_file {
+        let cyclonedx_file = if file_path.ends_with(".xml") {
+            file_path
+        } else {
+            format!(
+                "{}.cyclonedx.xml",
+                file_path
+                    .trim_end_matches(".json")
+                    .trim_end_matches(".cyclonedx")
+            )
+        };
+
+        std::fs::write(&cyclonedx_file, &xml_output)
+            .map_err(|e| FeludaError::FileWrite(format!("Failed to write CycloneDX file: {e}")))?;
+
+        println!("🧪 CycloneDX XML BOM written to: {cyclonedx_file} (EXPERIMENTAL)");
+        log(
+            LogLevel::Info,
+            &format!("CycloneDX XML BOM written to: {cyclonedx_file}"),
+        );
+    } else {
+        println!("=== CycloneDX XML BOM (EXPERIMENTAL) ===");
+        print!("{xml_output}");
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::sbom::spdx::{SpdxDocument, SpdxPackage};
+    use serial_test::serial;
 
     #[test]
     fn test_cyclonedx_bom_creation() {
@@ -359,6 +689,7 @@ mod tests {
         let mut bom = CycloneDxBom::new();
         let component = CycloneDxComponent {
             component_type: "library".to_string(),
+            bom_ref: None,
             name: "test-package".to_string(),
             version: Some("1.0.0".to_string()),
             description: None,
@@ -449,6 +780,7 @@ mod tests {
     fn test_cyclonedx_component_serialization() {
         let component = CycloneDxComponent {
             component_type: "library".to_string(),
+            bom_ref: None,
             name: "test-lib".to_string(),
             version: Some("2.1.0".to_string()),
             description: Some("A test library".to_string()),
@@ -582,4 +914,130 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_cyclonedx_xml_output() {
+        let mut spdx_doc = SpdxDocument::new("test-project");
+        spdx_doc.add_package(
+            SpdxPackage::new("serde".to_string(), &spdx_doc.document_namespace)
+                .with_version("1.0.0".to_string())
+                .with_license("MIT OR Apache-2.0".to_string()),
+        );
+        spdx_doc.add_package(
+            SpdxPackage::new("left-pad".to_string(), &spdx_doc.document_namespace)
+                .with_version("1.3.0".to_string())
+                .with_license("MIT".to_string()),
+        );
+        spdx_doc.add_package(
+            SpdxPackage::new("mystery".to_string(), &spdx_doc.document_namespace)
+                .with_version("0.1.0".to_string())
+                .with_license("NOASSERTION".to_string()),
+        );
+
+        let xml = format_cyclonedx_xml(&convert_spdx_to_cyclonedx(&spdx_doc));
+
+        assert_well_formed_xml(&xml);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<bom xmlns=\"http://cyclonedx.org/schema/bom/1.5\""));
+        assert_eq!(xml.matches("<component type=\"library\"").count(), 3);
+        assert!(xml.contains("<component type=\"library\" bom-ref=\"serde@1.0.0\">"));
+        assert!(xml.contains("<expression>MIT OR Apache-2.0</expression>"));
+        assert!(xml.contains("<license>\n          <id>MIT</id>"));
+        assert!(xml.contains("<name>NOASSERTION</name>"));
+    }
+
+    #[test]
+    fn test_cyclonedx_xml_escapes_values() {
+        let mut bom = CycloneDxBom::new();
+        bom.add_component(CycloneDxComponent {
+            component_type: "library".to_string(),
+            bom_ref: Some("a&b@1.0".to_string()),
+            name: "a&b <c>".to_string(),
+            version: Some("1.0".to_string()),
+            description: None,
+            scope: None,
+            licenses: Vec::new(),
+            copyright: Some("\"Quoted\" & Co".to_string()),
+            purl: None,
+            external_references: Vec::new(),
+        });
+
+        let xml = format_cyclonedx_xml(&bom);
+        assert_well_formed_xml(&xml);
+        assert!(xml.contains("bom-ref=\"a&amp;b@1.0\""));
+        assert!(xml.contains("<name>a&amp;b &lt;c&gt;</name>"));
+        assert!(xml.contains("<copyright>&quot;Quoted&quot; &amp; Co</copyright>"));
+    }
+
+    #[test]
+    fn test_cyclonedx_json_includes_bom_ref() {
+        let mut spdx_doc = SpdxDocument::new("test-project");
+        spdx_doc.add_package(
+            SpdxPackage::new("serde".to_string(), &spdx_doc.document_namespace)
+                .with_version("1.0.0".to_string()),
+        );
+
+        let json = serde_json::to_value(convert_spdx_to_cyclonedx(&spdx_doc)).unwrap();
+        assert_eq!(json["components"][0]["bom-ref"], "serde@1.0.0");
+        assert!(json.get("vulnerabilities").is_none());
+    }
+
+    #[test]
+    fn test_cyclonedx_bom_ref_uses_purl() {
+        let mut spdx_doc = SpdxDocument::new("test-project");
+        for purl in ["pkg:npm/yaml@2.4.1", "pkg:pypi/yaml@2.4.1"] {
+            spdx_doc.add_package(
+                SpdxPackage::new("yaml".to_string(), &spdx_doc.document_namespace)
+                    .with_version("2.4.1".to_string())
+                    .add_external_ref(
+                        "PACKAGE-MANAGER".to_string(),
+                        "purl".to_string(),
+                        purl.to_string(),
+                    ),
+            );
+        }
+
+        let bom = convert_spdx_to_cyclonedx(&spdx_doc);
+        let refs: Vec<_> = bom
+            .components
+            .iter()
+            .map(|c| (c.bom_ref.as_deref().unwrap(), c.purl.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                ("pkg:npm/yaml@2.4.1", "pkg:npm/yaml@2.4.1"),
+                ("pkg:pypi/yaml@2.4.1", "pkg:pypi/yaml@2.4.1"),
+            ]
+        );
+
+        let xml = format_cyclonedx_xml(&bom);
+        assert!(xml.contains("bom-ref=\"pkg:pypi/yaml@2.4.1\""));
+        assert!(xml.contains("<purl>pkg:npm/yaml@2.4.1</purl>"));
+    }
+
+    #[test]
+    fn test_cyclonedx_vex_output_to_file() {
+        let mut spdx_doc = SpdxDocument::new("test-project");
+        for (name, version) in [("serde", "1.0.0"), ("tokio", "1.40.0")] {
+            spdx_doc.add_package(
+                SpdxPackage::new(name.to_string(), &spdx_doc.document_namespace)
+                    .with_version(version.to_string())
+                    .with_license("MIT".to_string()),
+            );
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("project.vex.json");
+        generate_cyclonedx_vex_output(&spdx_doc, Some(path.to_str().unwrap().to_string())).unwrap();
+
+        let vex: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(vex["bomFormat"], "CycloneDX");
+        assert_eq!(vex["vulnerabilities"], serde_json::json!([]));
+
+        let sbom = serde_json::to_value(convert_spdx_to_cyclonedx(&spdx_doc)).unwrap();
+        assert_eq!(vex["components"], sbom["components"]);
+    }
 }
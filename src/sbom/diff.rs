@@ -0,0 +1,529 @@
+//! Compare two SBOM files
+//!
+//! Both SPDX and CycloneDX JSON documents are reduced to a common `{name, version, license}`
+//! set so that SBOMs produced in different formats can be compared with each other.
+
+use colored::*;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::reporter::strip_ansi;
+use crate::sbom::validate::{detect_sbom_type, SbomType};
+
+/// Package entry normalized from an SPDX package or a CycloneDX component
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SbomPackage {
+    pub name: String,
+    pub version: Option<String>,
+    pub license: Option<String>,
+}
+
+/// Package present in both SBOMs whose version or license changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageChange {
+    pub name: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub old_license: Option<String>,
+    pub new_license: Option<String>,
+}
+
+/// Differences between two SBOMs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SbomDiff {
+    pub added: Vec<SbomPackage>,
+    pub removed: Vec<SbomPackage>,
+    pub changed: Vec<PackageChange>,
+}
+
+impl SbomDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Treat SPDX placeholders as missing values so they compare equal across formats
+fn normalize_value(value: Option<&str>) -> Option<String> {
+    match value.map(str::trim) {
+        None | Some("") | Some("NOASSERTION") | Some("NONE") => None,
+        Some(value) => Some(value.to_string()),
+    }
+}
+
+fn spdx_packages(json: &JsonValue) -> Vec<SbomPackage> {
+    json.get("packages")
+        .and_then(|packages| packages.as_array())
+        .map(|packages| {
+            packages
+                .iter()
+                .filter_map(|package| {
+                    let name = package.get("name")?.as_str()?.to_string();
+                    let license = normalize_value(
+                        package
+                            .get("licenseConcluded")
+                            .and_then(|l| l.as_str())
+                            .filter(|l| *l != "NOASSERTION")
+                            .or_else(|| package.get("licenseDeclared").and_then(|l| l.as_str())),
+                    );
+                    Some(SbomPackage {
+                        name,
+                        version: normalize_value(
+                            package.get("versionInfo").and_then(|v| v.as_str()),
+                        ),
+                        license,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn cyclonedx_license(component: &JsonValue) -> Option<String> {
+    let licenses: Vec<String> = component
+        .get("licenses")?
+        .as_array()?
+        .iter()
+        .filter_map(|choice| {
+            if let Some(expression) = choice.get("expression").and_then(|e| e.as_str()) {
+                return normalize_value(Some(expression));
+            }
+            let license = choice.get("license")?;
+            license
+                .get("id")
+                .and_then(|id| id.as_str())
+                .or_else(|| license.get("name").and_then(|n| n.as_str()))
+                .and_then(|value| normalize_value(Some(value)))
+        })
+        .collect();
+
+    if licenses.is_empty() {
+        None
+    } else {
+        Some(licenses.join(" AND "))
+    }
+}
+
+fn cyclonedx_packages(json: &JsonValue) -> Vec<SbomPackage> {
+    json.get("components")
+        .and_then(|components| components.as_array())
+        .map(|components| {
+            components
+                .iter()
+                .filter_map(|component| {
+                    Some(SbomPackage {
+                        name: component.get("name")?.as_str()?.to_string(),
+                        version: normalize_value(component.get("version").and_then(|v| v.as_str())),
+                        license: cyclonedx_license(component),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Read an SBOM file and normalize its packages
+pub fn load_sbom_packages(path: &str) -> FeludaResult<Vec<SbomPackage>> {
+    let content = fs::read_to_string(path)
+        .map_err(|_| FeludaError::Validation(format!("Failed to read SBOM file: {path}")))?;
+    let json: JsonValue = serde_json::from_str(&content)
+        .map_err(|e| FeludaError::Validation(format!("Invalid JSON in {path}: {e}")))?;
+
+    let packages = match detect_sbom_type(&content)? {
+        SbomType::Spdx => spdx_packages(&json),
+        SbomType::CycloneDx => cyclonedx_packages(&json),
+    };
+
+    log(
+        LogLevel::Info,
+        &format!("Loaded {} packages from {path}", packages.len()),
+    );
+
+    Ok(packages)
+}
+
+/// Compare two package sets by name
+///
+/// A package whose name appears once on each side with a different version is reported as a
+/// change. When several versions of a package are present, versions that only appear on one
+/// side are reported as added or removed instead.
+pub fn diff_packages(old: &[SbomPackage], new: &[SbomPackage]) -> SbomDiff {
+    fn group(packages: &[SbomPackage]) -> BTreeMap<&str, BTreeSet<&SbomPackage>> {
+        let mut grouped: BTreeMap<&str, BTreeSet<&SbomPackage>> = BTreeMap::new();
+        for package in packages {
+            grouped.entry(&package.name).or_default().insert(package);
+        }
+        grouped
+    }
+
+    let old_grouped = group(old);
+    let new_grouped = group(new);
+    let mut diff = SbomDiff::default();
+
+    for (name, old_entries) in &old_grouped {
+        let Some(new_entries) = new_grouped.get(name) else {
+            diff.removed
+                .extend(old_entries.iter().map(|p| (*p).clone()));
+            continue;
+        };
+
+        let by_version =
+            |entries: &BTreeSet<&SbomPackage>| -> BTreeMap<Option<String>, Option<String>> {
+                entries
+                    .iter()
+                    .map(|p| (p.version.clone(), p.license.clone()))
+                    .collect()
+            };
+        let old_versions = by_version(old_entries);
+        let new_versions = by_version(new_entries);
+
+        // Same version on both sides: only the license can differ
+        for (version, old_license) in &old_versions {
+            if let Some(new_license) = new_versions.get(version) {
+                if old_license != new_license {
+                    diff.changed.push(PackageChange {
+                        name: name.to_string(),
+                        old_version: version.clone(),
+                        new_version: version.clone(),
+                        old_license: old_license.clone(),
+                        new_license: new_license.clone(),
+                    });
+                }
+            }
+        }
+
+        let old_only: Vec<_> = old_versions
+            .iter()
+            .filter(|(version, _)| !new_versions.contains_key(*version))
+            .collect();
+        let new_only: Vec<_> = new_versions
+            .iter()
+            .filter(|(version, _)| !old_versions.contains_key(*version))
+            .collect();
+
+        if let ([(old_version, old_license)], [(new_version, new_license)]) =
+            (old_only.as_slice(), new_only.as_slice())
+        {
+            diff.changed.push(PackageChange {
+                name: name.to_string(),
+                old_version: (*old_version).clone(),
+                new_version: (*new_version).clone(),
+                old_license: (*old_license).clone(),
+                new_license: (*new_license).clone(),
+            });
+            continue;
+        }
+
+        for (version, license) in old_only {
+            diff.removed.push(SbomPackage {
+                name: name.to_string(),
+                version: version.clone(),
+                license: license.clone(),
+            });
+        }
+        for (version, license) in new_only {
+            diff.added.push(SbomPackage {
+                name: name.to_string(),
+                version: version.clone(),
+                license: license.clone(),
+            });
+        }
+    }
+
+    for (name, new_entries) in &new_grouped {
+        if !old_grouped.contains_key(name) {
+            diff.added.extend(new_entries.iter().map(|p| (*p).clone()));
+        }
+    }
+
+    diff
+}
+
+fn display(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "-".to_string())
+}
+
+pub(crate) fn format_diff_table(diff: &SbomDiff) -> String {
+    if diff.is_empty() {
+        return format!("{}\n", "No differences found between the SBOMs".green());
+    }
+
+    let headers = [
+        "Change",
+        "Package",
+        "Old Version",
+        "New Version",
+        "Old License",
+        "New License",
+    ];
+    let mut rows: Vec<[String; 6]> = Vec::new();
+
+    for package in &diff.added {
+        rows.push([
+            "added".to_string(),
+            package.name.clone(),
+            "-".to_string(),
+            display(&package.version),
+            "-".to_string(),
+            display(&package.license),
+        ]);
+    }
+    for package in &diff.removed {
+        rows.push([
+            "removed".to_string(),
+            package.name.clone(),
+            display(&package.version),
+            "-".to_string(),
+            display(&package.license),
+            "-".to_string(),
+        ]);
+    }
+    for change in &diff.changed {
+        rows.push([
+            "changed".to_string(),
+            change.name.clone(),
+            display(&change.old_version),
+            display(&change.new_version),
+            display(&change.old_license),
+            display(&change.new_license),
+        ]);
+    }
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join(" │ ")
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!("{}\n", format_row(headers.to_vec()).bold().blue()));
+    output.push_str(&format!(
+        "{}\n",
+        "─".repeat(widths.iter().sum::<usize>() + 3 * (widths.len() - 1))
+    ));
+    for row in &rows {
+        let line = format_row(row.iter().map(String::as_str).collect());
+        let line = match row[0].as_str() {
+            "added" => line.green(),
+            "removed" => line.red(),
+            _ => line.yellow(),
+        };
+        output.push_str(&format!("{line}\n"));
+    }
+    output.push_str(&format!(
+        "\n{} added, {} removed, {} changed\n",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    ));
+
+    output
+}
+
+pub fn handle_sbom_diff_command(
+    old: String,
+    new: String,
+    output: Option<String>,
+    json_output: bool,
+) -> FeludaResult<()> {
+    log(LogLevel::Info, &format!("Comparing SBOMs: {old} -> {new}"));
+
+    let old_packages = load_sbom_packages(&old)?;
+    let new_packages = load_sbom_packages(&new)?;
+    let diff = diff_packages(&old_packages, &new_packages);
+
+    let output_string = if json_output {
+        serde_json::to_string_pretty(&diff)
+            .map_err(|e| FeludaError::Serialization(format!("Failed to serialize diff: {e}")))?
+    } else {
+        format_diff_table(&diff)
+    };
+
+    if let Some(path) = output {
+        fs::write(&path, strip_ansi(&output_string)).map_err(|e| {
+            FeludaError::FileWrite(format!("Failed to write diff report to {path}: {e}"))
+        })?;
+        println!("Diff report written to: {path}");
+    } else {
+        print!("{output_string}");
+        if json_output {
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn package(name: &str, version: &str, license: &str) -> SbomPackage {
+        SbomPackage {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            license: Some(license.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_license_change() {
+        let old = vec![
+            package("serde", "1.0.0", "MIT OR Apache-2.0"),
+            package("left-pad", "1.3.0", "WTFPL"),
+            package("tokio", "1.0.0", "MIT"),
+        ];
+        let new = vec![
+            package("serde", "1.0.0", "MIT OR Apache-2.0"),
+            package("tokio", "1.0.0", "GPL-3.0"),
+            package("rayon", "1.8.0", "MIT OR Apache-2.0"),
+        ];
+
+        let diff = diff_packages(&old, &new);
+
+        assert_eq!(
+            diff.added,
+            vec![package("rayon", "1.8.0", "MIT OR Apache-2.0")]
+        );
+        assert_eq!(diff.removed, vec![package("left-pad", "1.3.0", "WTFPL")]);
+        assert_eq!(
+            diff.changed,
+            vec![PackageChange {
+                name: "tokio".to_string(),
+                old_version: Some("1.0.0".to_string()),
+                new_version: Some("1.0.0".to_string()),
+                old_license: Some("MIT".to_string()),
+                new_license: Some("GPL-3.0".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_version_bump_is_a_change() {
+        let diff = diff_packages(
+            &[package("serde", "1.0.0", "MIT")],
+            &[package("serde", "1.0.1", "MIT")],
+        );
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].new_version.as_deref(), Some("1.0.1"));
+    }
+
+    #[test]
+    fn test_diff_across_spdx_and_cyclonedx() {
+        let spdx = json!({
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                {"name": "serde", "versionInfo": "1.0.0", "licenseConcluded": "MIT"},
+                {"name": "old-lib", "versionInfo": "0.1.0", "licenseConcluded": "NOASSERTION",
+                 "licenseDeclared": "NOASSERTION"}
+            ]
+        });
+        let cyclonedx = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "components": [
+                {"type": "library", "name": "serde", "version": "1.0.0",
+                 "licenses": [{"license": {"id": "MIT"}}]},
+                {"type": "library", "name": "new-lib", "version": "2.0.0",
+                 "licenses": [{"expression": "MIT OR Apache-2.0"}]}
+            ]
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let old_path = temp_dir.path().join("old.spdx.json");
+        let new_path = temp_dir.path().join("new.cyclonedx.json");
+        fs::write(&old_path, spdx.to_string()).unwrap();
+        fs::write(&new_path, cyclonedx.to_string()).unwrap();
+
+        let old = load_sbom_packages(old_path.to_str().unwrap()).unwrap();
+        let new = load_sbom_packages(new_path.to_str().unwrap()).unwrap();
+        assert_eq!(old[1].license, None);
+
+        let diff = diff_packages(&old, &new);
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "old-lib");
+        assert_eq!(
+            diff.added,
+            vec![package("new-lib", "2.0.0", "MIT OR Apache-2.0")]
+        );
+    }
+
+    #[test]
+    fn test_diff_json_report_written_to_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sbom = json!({
+            "bomFormat": "CycloneDX",
+            "components": [{"name": "serde", "version": "1.0.0"}]
+        });
+        let sbom_path = temp_dir.path().join("bom.json");
+        fs::write(&sbom_path, sbom.to_string()).unwrap();
+        let report_path = temp_dir.path().join("diff.json");
+
+        handle_sbom_diff_command(
+            sbom_path.to_string_lossy().to_string(),
+            sbom_path.to_string_lossy().to_string(),
+            Some(report_path.to_string_lossy().to_string()),
+            true,
+        )
+        .unwrap();
+
+        let report: JsonValue =
+            serde_json::from_str(&fs::read_to_string(report_path).unwrap()).unwrap();
+        assert_eq!(report["added"], json!([]));
+        assert_eq!(report["removed"], json!([]));
+        assert_eq!(report["changed"], json!([]));
+    }
+
+    #[test]
+    fn test_diff_table_report_written_without_colors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let write_sbom = |file: &str, license: &str| {
+            let sbom = json!({
+                "bomFormat": "CycloneDX",
+                "components": [{
+                    "name": "serde",
+                    "version": "1.0.0",
+                    "licenses": [{"license": {"id": license}}]
+                }]
+            });
+            let path = temp_dir.path().join(file);
+            fs::write(&path, sbom.to_string()).unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let old = write_sbom("old.json", "MIT");
+        let new = write_sbom("new.json", "GPL-3.0");
+        let report_path = temp_dir.path().join("diff.txt");
+
+        colored::control::set_override(true);
+        let result = handle_sbom_diff_command(
+            old,
+            new,
+            Some(report_path.to_string_lossy().to_string()),
+            false,
+        );
+        colored::control::unset_override();
+        result.unwrap();
+
+        let report = fs::read_to_string(report_path).unwrap();
+        assert!(report.contains("serde"), "{report}");
+        assert!(report.contains("GPL-3.0"), "{report}");
+        assert!(!report.contains('\u{1b}'), "{report}");
+    }
+}
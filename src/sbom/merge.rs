@@ -0,0 +1,310 @@
+//! Merge several SPDX documents into one
+//!
+//! Packages are deduplicated by name and version, relationships and annotations are
+//! re-pointed at the merged document, and the result gets a fresh namespace.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::sbom::spdx::{
+    generate_spdx_output, validate_and_sanitize_spdx_package, SpdxDocument, SpdxPackage,
+};
+use crate::sbom::validate::{detect_sbom_type, SbomType};
+
+fn is_concrete_license(license: &Option<String>) -> bool {
+    matches!(license.as_deref().map(str::trim), Some(l) if !l.is_empty() && l != "NOASSERTION" && l != "NONE")
+}
+
+/// Combine the license of a duplicate package, preferring a concrete value over `NOASSERTION`
+fn merge_license(existing: &mut Option<String>, incoming: &Option<String>, package: &str) {
+    if !is_concrete_license(incoming) {
+        return;
+    }
+    if !is_concrete_license(existing) {
+        *existing = incoming.clone();
+    } else if existing != incoming {
+        log(
+            LogLevel::Warn,
+            &format!(
+                "Conflicting licenses for {package}: keeping '{}' over '{}'",
+                existing.as_deref().unwrap_or_default(),
+                incoming.as_deref().unwrap_or_default()
+            ),
+        );
+    }
+}
+
+/// Read an SPDX JSON document, rejecting other SBOM formats
+pub fn load_spdx_document(path: &str) -> FeludaResult<SpdxDocument> {
+    let content = fs::read_to_string(path)
+        .map_err(|_| FeludaError::Validation(format!("Failed to read SBOM file: {path}")))?;
+
+    if detect_sbom_type(&content)? != SbomType::Spdx {
+        return Err(FeludaError::Validation(format!(
+            "{path} is not an SPDX document; only SPDX JSON files can be merged"
+        )));
+    }
+
+    serde_json::from_str(&content)
+        .map_err(|e| FeludaError::Validation(format!("Failed to parse SPDX document {path}: {e}")))
+}
+
+/// Merge SPDX documents into a single document with a fresh namespace and creation info
+pub fn merge_spdx_documents(project_name: &str, documents: &[SpdxDocument]) -> SpdxDocument {
+    let mut merged = SpdxDocument::new(project_name);
+    let mut by_name_version: HashMap<(String, Option<String>), usize> = HashMap::new();
+    let mut used_ids: HashSet<String> = HashSet::new();
+
+    let mut relationships = HashSet::new();
+    let mut annotations = HashSet::new();
+
+    for document in documents {
+        // Maps package IDs of this input document to IDs in the merged document
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        id_map.insert(document.spdx_id.clone(), merged.spdx_id.clone());
+
+        for package in &document.packages {
+            let key = (package.name.clone(), package.version_info.clone());
+            if let Some(&index) = by_name_version.get(&key) {
+                let existing = &mut merged.packages[index];
+                merge_license(
+                    &mut existing.license_concluded,
+                    &package.license_concluded,
+                    &package.name,
+                );
+                merge_license(
+                    &mut existing.license_declared,
+                    &package.license_declared,
+                    &package.name,
+                );
+                id_map.insert(package.spdx_id.clone(), existing.spdx_id.clone());
+                continue;
+            }
+
+            let mut package: SpdxPackage = package.clone();
+            let original_id = package.spdx_id.clone();
+            // A different package already uses this ID, so give this one a unique suffix
+            let mut suffix = 1;
+            while used_ids.contains(&package.spdx_id) {
+                package.spdx_id = format!("{original_id}-{suffix}");
+                suffix += 1;
+            }
+
+            used_ids.insert(package.spdx_id.clone());
+            id_map.insert(original_id, package.spdx_id.clone());
+            by_name_version.insert(key, merged.packages.len());
+            merged.packages.push(package);
+        }
+
+        for relationship in &document.relationships {
+            let (Some(from), Some(to)) = (
+                id_map.get(&relationship.spdx_element_id),
+                id_map.get(&relationship.related_spdx_element),
+            ) else {
+                continue;
+            };
+
+            let mut relationship = relationship.clone();
+            relationship.spdx_element_id = from.clone();
+            relationship.related_spdx_element = to.clone();
+            if relationships.insert((
+                relationship.spdx_element_id.clone(),
+                relationship.relationship_type.clone(),
+                relationship.related_spdx_element.clone(),
+            )) {
+                merged.relationships.push(relationship);
+            }
+        }
+
+        for annotation in &document.annotations {
+            let Some(reference) = id_map.get(&annotation.spdx_identifier_reference) else {
+                continue;
+            };
+
+            let mut annotation = annotation.clone();
+            annotation.spdx_identifier_reference = reference.clone();
+            if annotations.insert((
+                annotation.spdx_identifier_reference.clone(),
+                annotation.comment.clone(),
+            )) {
+                merged.annotations.push(annotation);
+            }
+        }
+    }
+
+    // Sanitization may regenerate invalid IDs, so keep references in sync
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    for package in &mut merged.packages {
+        let before = package.spdx_id.clone();
+        validate_and_sanitize_spdx_package(package);
+        if package.spdx_id != before {
+            renamed.insert(before, package.spdx_id.clone());
+        }
+    }
+    if !renamed.is_empty() {
+        for relationship in &mut merged.relationships {
+            if let Some(id) = renamed.get(&relationship.spdx_element_id) {
+                relationship.spdx_element_id = id.clone();
+            }
+            if let Some(id) = renamed.get(&relationship.related_spdx_element) {
+                relationship.related_spdx_element = id.clone();
+            }
+        }
+        for annotation in &mut merged.annotations {
+            if let Some(id) = renamed.get(&annotation.spdx_identifier_reference) {
+                annotation.spdx_identifier_reference = id.clone();
+            }
+        }
+    }
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Merged {} documents into {} packages",
+            documents.len(),
+            merged.packages.len()
+        ),
+    );
+
+    merged
+}
+
+pub fn handle_sbom_merge_command(files: Vec<String>, output: Option<String>) -> FeludaResult<()> {
+    log(
+        LogLevel::Info,
+        &format!("Merging {} SBOM files", files.len()),
+    );
+
+    let documents = files
+        .iter()
+        .map(|file| load_spdx_document(file))
+        .collect::<FeludaResult<Vec<_>>>()?;
+
+    let merged = merge_spdx_documents("merged", &documents);
+    generate_spdx_output(&merged, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn document(name: &str, packages: Vec<SpdxPackage>) -> SpdxDocument {
+        let mut doc = SpdxDocument::new(name);
+        for package in packages {
+            doc.add_package(package);
+        }
+        doc
+    }
+
+    fn package(name: &str, version: &str, license: &str) -> SpdxPackage {
+        SpdxPackage::new(name.to_string(), "")
+            .with_version(version.to_string())
+            .with_license(license.to_string())
+    }
+
+    #[test]
+    #[serial]
+    fn test_merge_with_overlapping_package() {
+        let mut service_a = document(
+            "service-a",
+            vec![
+                package("shared", "1.0.0", "NOASSERTION"),
+                package("only-a", "2.0.0", "MIT"),
+            ],
+        );
+        let shared_id = service_a.packages[0].spdx_id.clone();
+        let only_a_id = service_a.packages[1].spdx_id.clone();
+        service_a.add_relationship(only_a_id.clone(), "DEPENDS_ON", shared_id.clone());
+
+        let service_b = document(
+            "service-b",
+            vec![
+                package("shared", "1.0.0", "Apache-2.0"),
+                package("only-b", "3.0.0", "ISC"),
+            ],
+        );
+
+        let merged = merge_spdx_documents("merged", &[service_a.clone(), service_b.clone()]);
+
+        assert_eq!(merged.packages.len(), 3);
+        assert_ne!(merged.document_namespace, service_a.document_namespace);
+        assert_ne!(merged.document_namespace, service_b.document_namespace);
+
+        let shared = merged.packages.iter().find(|p| p.name == "shared").unwrap();
+        assert_eq!(shared.license_concluded.as_deref(), Some("Apache-2.0"));
+        assert_eq!(shared.license_declared.as_deref(), Some("Apache-2.0"));
+
+        // One DESCRIBES per unique package, all from the merged document
+        let describes: Vec<_> = merged
+            .relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DESCRIBES")
+            .collect();
+        assert_eq!(describes.len(), 3);
+        assert!(describes
+            .iter()
+            .all(|r| r.spdx_element_id == merged.spdx_id));
+
+        assert!(merged.relationships.iter().any(|r| {
+            r.relationship_type == "DEPENDS_ON"
+                && r.spdx_element_id == only_a_id
+                && r.related_spdx_element == shared_id
+        }));
+    }
+
+    #[test]
+    #[serial]
+    fn test_merge_keeps_first_concrete_license_on_conflict() {
+        let first = document("a", vec![package("lib", "1.0.0", "MIT")]);
+        let second = document("b", vec![package("lib", "1.0.0", "GPL-3.0")]);
+
+        let merged = merge_spdx_documents("merged", &[first, second]);
+
+        assert_eq!(merged.packages.len(), 1);
+        assert_eq!(merged.packages[0].license_concluded.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_merge_command_writes_valid_spdx() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let first_path = temp_dir.path().join("a.spdx.json");
+        let second_path = temp_dir.path().join("b.spdx.json");
+        let output_path = temp_dir.path().join("merged.spdx.json");
+
+        let first = document("a", vec![package("shared", "1.0.0", "MIT")]);
+        let second = document(
+            "b",
+            vec![
+                package("shared", "1.0.0", "MIT"),
+                package("other", "0.1.0", "ISC"),
+            ],
+        );
+        fs::write(&first_path, serde_json::to_string(&first).unwrap()).unwrap();
+        fs::write(&second_path, serde_json::to_string(&second).unwrap()).unwrap();
+
+        handle_sbom_merge_command(
+            vec![
+                first_path.to_string_lossy().to_string(),
+                second_path.to_string_lossy().to_string(),
+            ],
+            Some(output_path.to_string_lossy().to_string()),
+        )
+        .unwrap();
+
+        let merged = load_spdx_document(output_path.to_str().unwrap()).unwrap();
+        assert_eq!(merged.packages.len(), 2);
+        assert_eq!(merged.relationships.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_rejects_cyclonedx_input() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("bom.json");
+        fs::write(&path, r#"{"bomFormat": "CycloneDX", "specVersion": "1.5"}"#).unwrap();
+
+        assert!(load_spdx_document(path.to_str().unwrap()).is_err());
+    }
+}
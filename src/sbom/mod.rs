@@ -1,24 +1,34 @@
+pub mod convert;
 pub mod cyclonedx;
+pub mod diff;
+pub mod merge;
+pub mod purl;
 pub mod spdx;
 pub mod validate;
 
 use crate::cli::SbomFormat;
 use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
-use crate::licenses::LicenseCompatibility;
+use crate::licenses::{detect_license_from_files, detect_project_license, LicenseInfo};
 use crate::parser::parse_root;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 
-use cyclonedx::generate_cyclonedx_output;
-use spdx::{generate_spdx_output, SpdxDocument, SpdxPackage};
+use cyclonedx::{
+    generate_cyclonedx_output, generate_cyclonedx_vex_output, generate_cyclonedx_xml_output,
+};
+use spdx::{generate_spdx_output, generate_spdx_tagvalue_output, SpdxDocument, SpdxPackage};
 
 pub fn handle_sbom_command(
     path: String,
     format: &SbomFormat,
     output_file: Option<String>,
+    output_dir: Option<String>,
 ) -> FeludaResult<()> {
     log(LogLevel::Info, &format!("Generating SBOM for path: {path}"));
 
     // Parse project dependencies using existing parser
-    let analyzed_data = parse_root(&path, None, false, false)
+    let analyzed_data = parse_root(&path, None)
         .map_err(|e| FeludaError::Parser(format!("Failed to parse dependencies: {e}")))?;
 
     log(
@@ -32,8 +42,154 @@ pub fn handle_sbom_command(
         .and_then(|name| name.to_str())
         .unwrap_or("project");
 
+    let output_file = output_path(output_file, output_dir.as_deref(), project_name)?;
+    let root = project_package(&path, project_name);
+    let spdx_doc = build_spdx_document(project_name, root, analyzed_data);
+
+    // Generate output based on format
+    match format {
+        SbomFormat::Spdx => {
+            generate_spdx_output(&spdx_doc, output_file)?;
+        }
+        SbomFormat::SpdxTagValue => {
+            generate_spdx_tagvalue_output(&spdx_doc, output_file)?;
+        }
+        SbomFormat::Cyclonedx => {
+            generate_cyclonedx_output(&spdx_doc, output_file)?;
+        }
+        SbomFormat::CyclonedxXml => {
+            generate_cyclonedx_xml_output(&spdx_doc, output_file)?;
+        }
+        SbomFormat::Vex => {
+            generate_cyclonedx_vex_output(&spdx_doc, output_file)?;
+        }
+        SbomFormat::All => {
+            // Each format gets its own extension so they never overwrite each other
+            let base = output_file.as_deref().map(output_base);
+            generate_spdx_output(&spdx_doc, base.as_ref().map(|b| format!("{b}.spdx.json")))?;
+            generate_cyclonedx_output(
+                &spdx_doc,
+                base.as_ref().map(|b| format!("{b}.cyclonedx.json")),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Place the output inside `--output-dir`, creating it, named after the project by default
+fn output_path(
+    output_file: Option<String>,
+    output_dir: Option<&str>,
+    project_name: &str,
+) -> FeludaResult<Option<String>> {
+    let Some(dir) = output_dir else {
+        return Ok(output_file);
+    };
+
+    std::fs::create_dir_all(dir).map_err(|e| {
+        FeludaError::FileWrite(format!("Failed to create output directory {dir}: {e}"))
+    })?;
+
+    let name = output_file.unwrap_or_else(|| project_name.to_string());
+    Ok(Some(
+        std::path::Path::new(dir)
+            .join(name)
+            .to_string_lossy()
+            .to_string(),
+    ))
+}
+
+/// Output path without a format extension, e.g. `sbom` for `sbom.json`
+fn output_base(output_file: &str) -> &str {
+    [
+        ".spdx.json",
+        ".cyclonedx.json",
+        ".json",
+        ".spdx",
+        ".cyclonedx",
+    ]
+    .iter()
+    .find_map(|extension| output_file.strip_suffix(extension))
+    .unwrap_or(output_file)
+}
+
+/// Encode Feluda's analysis as `compatibility=<...>; restrictive=<bool>; osi=<...>`
+fn feluda_annotation_comment(dependency: &LicenseInfo) -> String {
+    format!(
+        "compatibility={}; restrictive={}; osi={}",
+        dependency.compatibility, dependency.is_restrictive, dependency.osi_status
+    )
+}
+
+/// Root package for the project itself
+///
+/// The version comes from the manifest and the license from [`detect_project_license`];
+/// license files found in the project are recorded in `licenseInfoFromFiles`.
+fn project_package(path: &str, project_name: &str) -> SpdxPackage {
+    let mut package =
+        SpdxPackage::new(project_name.to_string(), "").with_primary_package_purpose("APPLICATION");
+
+    if let Some(version) = project_version(path) {
+        package = package.with_version(version);
+    }
+
+    match detect_project_license(path) {
+        Ok(Some(license)) => package = package.with_license(license),
+        Ok(None) => {}
+        Err(err) => log(
+            LogLevel::Warn,
+            &format!("Failed to detect project license: {err}"),
+        ),
+    }
+
+    if let Some(license) = detect_license_from_files(path) {
+        package = package.with_files_analyzed(vec![license]);
+    }
+
+    package
+}
+
+/// Version declared in `package.json`, `Cargo.toml` or `pyproject.toml`
+fn project_version(path: &str) -> Option<String> {
+    let root = Path::new(path);
+
+    if let Ok(content) = fs::read_to_string(root.join("package.json")) {
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        return json["version"].as_str().map(str::to_string);
+    }
+
+    [("Cargo.toml", "package"), ("pyproject.toml", "project")]
+        .iter()
+        .find_map(|(manifest, table)| {
+            let content = fs::read_to_string(root.join(manifest)).ok()?;
+            let toml: toml::Value = toml::from_str(&content).ok()?;
+            toml.get(table)?
+                .get("version")?
+                .as_str()
+                .map(str::to_string)
+        })
+}
+
+/// Build an SPDX document from analyzed dependencies
+///
+/// The document `DESCRIBES` only the `root` package; the root `DEPENDS_ON` every package
+/// no other package in the document depends on. Feluda's own verdicts (compatibility,
+/// restrictiveness, OSI status) are kept as `REVIEW` annotations so the standard license
+/// fields stay untouched.
+fn build_spdx_document(
+    project_name: &str,
+    root: SpdxPackage,
+    analyzed_data: Vec<LicenseInfo>,
+) -> SpdxDocument {
     // Convert to SPDX-compliant format
     let mut spdx_doc = SpdxDocument::new(project_name);
+    let mut spdx_ids: HashMap<String, String> = HashMap::new();
+    let mut package_ids: Vec<String> = Vec::new();
+    let mut edges: Vec<(String, Vec<String>)> = Vec::new();
+
+    let root_id = root.spdx_id.clone();
+    spdx_doc.add_package(root);
 
     for dependency in analyzed_data {
         let mut package = SpdxPackage::new(dependency.name.clone(), &spdx_doc.document_namespace)
@@ -55,20 +211,46 @@ pub fn handle_sbom_command(
 
         package = package.with_license(license_str.to_string());
 
-        // TODO: Store Feluda-specific data as SPDX annotations
-        let _compatibility_info = format!(
-            "License compatibility: {}, Restrictive: {}",
-            match dependency.compatibility {
-                LicenseCompatibility::Compatible => "compatible",
-                LicenseCompatibility::Incompatible => "incompatible",
-                LicenseCompatibility::Unknown => "unknown",
-            },
-            dependency.is_restrictive
+        if let Some(purl) =
+            purl::package_url(&dependency.ecosystem, &dependency.name, &dependency.version)
+        {
+            package =
+                package.add_external_ref("PACKAGE-MANAGER".to_string(), "purl".to_string(), purl);
+        }
+
+        let spdx_id = package.spdx_id.clone();
+        spdx_doc.add_dependency_package(package);
+        package_ids.push(spdx_id.clone());
+        spdx_doc.add_annotation(
+            spdx_id.clone(),
+            feluda_annotation_comment(&dependency),
+            "REVIEW".to_string(),
         );
 
-        // TODO: Add dependency relationships to SPDX when LicenseInfo supports it
+        spdx_ids
+            .entry(dependency.name.clone())
+            .or_insert_with(|| spdx_id.clone());
+        if !dependency.dependencies.is_empty() {
+            edges.push((spdx_id, dependency.dependencies));
+        }
+    }
+
+    // Edges to packages that are not part of the document are skipped
+    let mut depended_on: HashSet<String> = HashSet::new();
+    for (from, dependencies) in edges {
+        for name in dependencies {
+            if let Some(to) = spdx_ids.get(&name) {
+                spdx_doc.add_relationship(from.clone(), "DEPENDS_ON", to.clone());
+                depended_on.insert(to.clone());
+            }
+        }
+    }
 
-        spdx_doc.add_package(package);
+    // Without a dependency graph every package hangs off the root directly
+    for spdx_id in package_ids {
+        if !depended_on.contains(&spdx_id) {
+            spdx_doc.add_relationship(root_id.clone(), "DEPENDS_ON", spdx_id);
+        }
     }
 
     log(
@@ -79,19 +261,439 @@ pub fn handle_sbom_command(
         ),
     );
 
-    // Generate output based on format
-    match format {
-        SbomFormat::Spdx => {
-            generate_spdx_output(&spdx_doc, output_file)?;
+    spdx_doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::{test_dependency, LicenseCompatibility, OsiStatus};
+    use serial_test::serial;
+
+    fn root() -> SpdxPackage {
+        SpdxPackage::new("demo".to_string(), "").with_primary_package_purpose("APPLICATION")
+    }
+
+    fn license_info(name: &str, compatibility: LicenseCompatibility) -> LicenseInfo {
+        LicenseInfo {
+            is_restrictive: true,
+            compatibility,
+            osi_status: OsiStatus::Approved,
+            ..test_dependency(name, "1.0.0", "GPL-3.0")
         }
-        SbomFormat::Cyclonedx => {
-            generate_cyclonedx_output(&spdx_doc, output_file)?;
+    }
+
+    #[test]
+    fn test_build_spdx_document_annotates_incompatible_package() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                license_info("copyleft-lib", LicenseCompatibility::Incompatible),
+                license_info("other-lib", LicenseCompatibility::Compatible),
+            ],
+        );
+
+        assert_eq!(doc.packages.len(), 3);
+        assert_eq!(doc.annotations.len(), 2);
+
+        let package = doc
+            .packages
+            .iter()
+            .find(|p| p.name == "copyleft-lib")
+            .unwrap();
+        let annotation = doc
+            .annotations
+            .iter()
+            .find(|a| a.spdx_identifier_reference == package.spdx_id)
+            .expect("annotation for incompatible package");
+
+        assert_eq!(annotation.annotation_type, "REVIEW");
+        assert_eq!(
+            annotation.comment,
+            "compatibility=incompatible; restrictive=true; osi=approved"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_compact_sbom_output() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                license_info("serde", LicenseCompatibility::Compatible),
+                license_info("copyleft-lib", LicenseCompatibility::Incompatible),
+            ],
+        );
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let spdx_path = temp_dir.path().join("demo.spdx.json");
+        let cyclonedx_path = temp_dir.path().join("demo.cyclonedx.json");
+
+        crate::reporter::set_compact_json(true);
+        let spdx = generate_spdx_output(&doc, Some(spdx_path.display().to_string()));
+        let cyclonedx = generate_cyclonedx_output(&doc, Some(cyclonedx_path.display().to_string()));
+        crate::reporter::set_compact_json(false);
+        spdx.unwrap();
+        cyclonedx.unwrap();
+
+        for path in [spdx_path, cyclonedx_path] {
+            let content = std::fs::read_to_string(&path).unwrap();
+            assert!(!content.contains('\n'), "{} is not compact", path.display());
+            let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+            assert!(json.is_object());
         }
-        SbomFormat::All => {
-            generate_spdx_output(&spdx_doc, output_file.clone())?;
-            generate_cyclonedx_output(&spdx_doc, output_file)?;
+    }
+
+    #[test]
+    fn test_build_spdx_document_adds_purl_external_refs() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                LicenseInfo {
+                    name: "@types/node".to_string(),
+                    version: "20.1.0".to_string(),
+                    ecosystem: "npm".to_string(),
+                    ..license_info("node-types", LicenseCompatibility::Compatible)
+                },
+                LicenseInfo {
+                    ecosystem: "cargo".to_string(),
+                    ..license_info("serde", LicenseCompatibility::Compatible)
+                },
+                license_info("no-ecosystem", LicenseCompatibility::Compatible),
+            ],
+        );
+
+        let purls: Vec<Vec<(&str, &str, &str)>> = doc
+            .packages
+            .iter()
+            .map(|package| {
+                package
+                    .external_refs
+                    .iter()
+                    .map(|r| {
+                        (
+                            r.reference_category.as_str(),
+                            r.reference_type.as_str(),
+                            r.reference_locator.as_str(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            purls,
+            vec![
+                vec![],
+                vec![("PACKAGE-MANAGER", "purl", "pkg:npm/%40types/node@20.1.0")],
+                vec![("PACKAGE-MANAGER", "purl", "pkg:cargo/serde@1.0.0")],
+                vec![],
+            ]
+        );
+
+        let bom = cyclonedx::convert_spdx_to_cyclonedx(&doc);
+        for component in &bom.components[..2] {
+            let purl = component.purl.as_deref().unwrap();
+            assert!(purl.starts_with("pkg:"), "{purl}");
+            assert_eq!(component.bom_ref.as_deref(), Some(purl));
         }
+        assert_eq!(
+            bom.components[1].purl.as_deref(),
+            Some("pkg:cargo/serde@1.0.0")
+        );
+        assert_eq!(bom.components[2].purl, None);
+        assert_eq!(
+            bom.components[2].bom_ref.as_deref(),
+            Some("no-ecosystem@1.0.0")
+        );
     }
 
-    Ok(())
+    #[test]
+    fn test_vex_skeleton_has_one_component_per_dependency() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                license_info("copyleft-lib", LicenseCompatibility::Incompatible),
+                license_info("other-lib", LicenseCompatibility::Compatible),
+            ],
+        );
+
+        let vex = serde_json::to_value(cyclonedx::convert_spdx_to_cyclonedx_vex(&doc)).unwrap();
+        assert_eq!(vex["vulnerabilities"].as_array().map(Vec::len), Some(0));
+
+        let refs: Vec<&str> = vex["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|component| component["bom-ref"].as_str().unwrap())
+            .collect();
+        assert_eq!(refs, vec!["copyleft-lib@1.0.0", "other-lib@1.0.0"]);
+    }
+
+    #[test]
+    fn test_build_spdx_document_depends_on_relationships() {
+        let with_deps = |name: &str, deps: &[&str]| LicenseInfo {
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..license_info(name, LicenseCompatibility::Compatible)
+        };
+
+        // app -> (lib-a, lib-b), lib-a -> (lib-b, missing)
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                with_deps("app", &["lib-a", "lib-b"]),
+                with_deps("lib-a", &["lib-b", "missing"]),
+                with_deps("lib-b", &[]),
+            ],
+        );
+
+        let id_of = |name: &str| {
+            doc.packages
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.spdx_id.clone())
+                .unwrap()
+        };
+        let mut depends_on: Vec<(String, String)> = doc
+            .relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DEPENDS_ON")
+            .map(|r| (r.spdx_element_id.clone(), r.related_spdx_element.clone()))
+            .collect();
+        depends_on.sort();
+
+        // Only `app` is not pulled in by another package, so it hangs off the root
+        let mut expected = vec![
+            (id_of("demo"), id_of("app")),
+            (id_of("app"), id_of("lib-a")),
+            (id_of("app"), id_of("lib-b")),
+            (id_of("lib-a"), id_of("lib-b")),
+        ];
+        expected.sort();
+
+        assert_eq!(depends_on, expected);
+
+        let describes: Vec<&str> = doc
+            .relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DESCRIBES")
+            .map(|r| r.related_spdx_element.as_str())
+            .collect();
+        assert_eq!(describes, vec![id_of("demo").as_str()]);
+    }
+
+    #[test]
+    fn test_build_spdx_document_root_depends_on_every_package_without_graph() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![
+                license_info("lib-a", LicenseCompatibility::Compatible),
+                license_info("lib-b", LicenseCompatibility::Compatible),
+            ],
+        );
+
+        let root = &doc.packages[0];
+        assert_eq!(root.name, "demo");
+        assert_eq!(root.primary_package_purpose.as_deref(), Some("APPLICATION"));
+
+        let targets: Vec<&str> = doc
+            .relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DEPENDS_ON")
+            .inspect(|r| assert_eq!(r.spdx_element_id, root.spdx_id))
+            .map(|r| r.related_spdx_element.as_str())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                doc.packages[1].spdx_id.as_str(),
+                doc.packages[2].spdx_id.as_str()
+            ]
+        );
+
+        // CycloneDX describes the project in its metadata instead of listing it as a library
+        let bom = cyclonedx::convert_spdx_to_cyclonedx(&doc);
+        let component = bom.metadata.unwrap().component.unwrap();
+        assert_eq!(component.name, "demo");
+        assert_eq!(component.component_type, "application");
+        assert_eq!(bom.components.len(), 2);
+    }
+
+    #[test]
+    fn test_build_spdx_document_annotations_serialize() {
+        let doc = build_spdx_document(
+            "demo",
+            root(),
+            vec![license_info("lib", LicenseCompatibility::Unknown)],
+        );
+
+        let json = serde_json::to_value(&doc).unwrap();
+        let annotation = &json["annotations"][0];
+        assert_eq!(annotation["annotationType"], "REVIEW");
+        assert_eq!(
+            annotation["comment"],
+            "compatibility=unknown; restrictive=true; osi=approved"
+        );
+    }
+
+    #[test]
+    fn test_output_base_strips_format_extensions() {
+        assert_eq!(output_base("sbom.json"), "sbom");
+        assert_eq!(output_base("out/sbom.spdx.json"), "out/sbom");
+        assert_eq!(output_base("sbom.cyclonedx.json"), "sbom");
+        assert_eq!(output_base("sbom"), "sbom");
+    }
+
+    #[test]
+    #[serial]
+    fn test_all_formats_write_distinct_files_in_output_dir() {
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            project.path().join("package.json"),
+            r#"{"name": "app", "version": "1.0.0", "dependencies": {"left-pad": "1.3.0"}}"#,
+        )
+        .unwrap();
+        let installed = project.path().join("node_modules").join("left-pad");
+        std::fs::create_dir_all(&installed).unwrap();
+        std::fs::write(
+            installed.join("package.json"),
+            r#"{"name": "left-pad", "version": "1.3.0", "license": "MIT"}"#,
+        )
+        .unwrap();
+
+        let out = tempfile::TempDir::new().unwrap();
+        let output_dir = out.path().join("nested").join("sboms");
+
+        crate::network::set_offline_mode(true);
+        let result = handle_sbom_command(
+            project.path().to_string_lossy().to_string(),
+            &SbomFormat::All,
+            Some("sbom.json".to_string()),
+            Some(output_dir.to_string_lossy().to_string()),
+        );
+        crate::network::set_offline_mode(false);
+        result.unwrap();
+
+        let spdx = std::fs::read_to_string(output_dir.join("sbom.spdx.json")).unwrap();
+        let cyclonedx = std::fs::read_to_string(output_dir.join("sbom.cyclonedx.json")).unwrap();
+        assert_ne!(spdx, cyclonedx);
+        assert!(spdx.contains("\"spdxVersion\""));
+        assert!(cyclonedx.contains("\"bomFormat\": \"CycloneDX\""));
+        assert!(!output_dir.join("sbom.json").exists());
+    }
+
+    #[test]
+    fn test_project_package_reads_manifest_and_license_files() {
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            project.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"2.1.0\"\nlicense = \"Apache-2.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("LICENSE"),
+            "MIT License\n\nPermission is hereby granted, free of charge, to any person",
+        )
+        .unwrap();
+
+        let package = project_package(&project.path().to_string_lossy(), "demo");
+        assert_eq!(package.version_info.as_deref(), Some("2.1.0"));
+        assert_eq!(package.license_declared.as_deref(), Some("Apache-2.0"));
+        assert!(package.files_analyzed);
+        assert_eq!(package.license_info_from_files, vec!["MIT".to_string()]);
+
+        let json = serde_json::to_value(&package).unwrap();
+        assert_eq!(json["filesAnalyzed"], true);
+        assert_eq!(json["licenseInfoFromFiles"], serde_json::json!(["MIT"]));
+        assert_eq!(json["primaryPackagePurpose"], "APPLICATION");
+
+        // Without manifest or license files the root is still there, just unanalyzed
+        let empty = tempfile::TempDir::new().unwrap();
+        let package = project_package(&empty.path().to_string_lossy(), "empty");
+        assert_eq!(package.version_info, None);
+        assert!(!package.files_analyzed);
+    }
+
+    #[test]
+    #[serial]
+    fn test_sbom_root_package_carries_detected_license() {
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            project.path().join("package.json"),
+            r#"{"name": "app", "version": "1.0.0", "dependencies": {"left-pad": "1.3.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("LICENSE"),
+            "MIT License\n\nPermission is hereby granted, free of charge, to any person",
+        )
+        .unwrap();
+        let installed = project.path().join("node_modules").join("left-pad");
+        std::fs::create_dir_all(&installed).unwrap();
+        std::fs::write(
+            installed.join("package.json"),
+            r#"{"name": "left-pad", "version": "1.3.0", "license": "MIT"}"#,
+        )
+        .unwrap();
+
+        let out = tempfile::TempDir::new().unwrap();
+        let output = out.path().join("sbom.spdx.json");
+
+        crate::network::set_offline_mode(true);
+        let result = handle_sbom_command(
+            project.path().to_string_lossy().to_string(),
+            &SbomFormat::Spdx,
+            Some(output.to_string_lossy().to_string()),
+            None,
+        );
+        crate::network::set_offline_mode(false);
+        result.unwrap();
+
+        let doc: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        let packages = doc["packages"].as_array().unwrap();
+        let project_name = project.path().file_name().unwrap().to_string_lossy();
+
+        let root = packages
+            .iter()
+            .find(|p| p["name"] == project_name.as_ref())
+            .expect("root project package");
+        assert_eq!(root["filesAnalyzed"], true);
+        assert_eq!(root["licenseInfoFromFiles"], serde_json::json!(["MIT"]));
+        assert_eq!(root["versionInfo"], "1.0.0");
+
+        let dependency = packages.iter().find(|p| p["name"] == "left-pad").unwrap();
+        assert_eq!(dependency["filesAnalyzed"], false);
+        assert!(dependency.get("licenseInfoFromFiles").is_none());
+
+        // DOCUMENT DESCRIBES root, root DEPENDS_ON left-pad
+        let relationships = doc["relationships"].as_array().unwrap();
+        let edges: Vec<(&str, &str, &str)> = relationships
+            .iter()
+            .map(|r| {
+                (
+                    r["spdxElementId"].as_str().unwrap(),
+                    r["relationshipType"].as_str().unwrap(),
+                    r["relatedSpdxElement"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        let root_id = root["SPDXID"].as_str().unwrap();
+        assert_eq!(
+            edges,
+            vec![
+                ("SPDXRef-DOCUMENT", "DESCRIBES", root_id),
+                (
+                    root_id,
+                    "DEPENDS_ON",
+                    dependency["SPDXID"].as_str().unwrap()
+                ),
+            ]
+        );
+    }
 }
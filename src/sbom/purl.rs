@@ -0,0 +1,127 @@
+//! Package URLs (purl) for SBOM packages
+//!
+//! A purl identifies a package across tools, e.g. `pkg:npm/%40types/node@20.1.0` or
+//! `pkg:cargo/serde@1.0.0`. See <https://github.com/package-url/purl-spec>.
+
+/// Purl type for an ecosystem recorded on `LicenseInfo`; `None` when there is no registry
+fn purl_type(ecosystem: &str) -> Option<&'static str> {
+    match ecosystem {
+        "cargo" => Some("cargo"),
+        "npm" => Some("npm"),
+        "pypi" => Some("pypi"),
+        "conda" => Some("conda"),
+        "go" => Some("golang"),
+        "nuget" => Some("nuget"),
+        "hex" => Some("hex"),
+        "hackage" => Some("hackage"),
+        "cpan" => Some("cpan"),
+        "r" => Some("cran"),
+        _ => None,
+    }
+}
+
+/// Percent-encode a purl segment, keeping only unreserved characters
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Build the purl of a dependency, or `None` for ecosystems without a purl type
+pub fn package_url(ecosystem: &str, name: &str, version: &str) -> Option<String> {
+    let purl_type = purl_type(ecosystem)?;
+
+    // PyPI names are case-insensitive and treat `_` like `-`
+    let name = if purl_type == "pypi" {
+        name.to_lowercase().replace('_', "-")
+    } else {
+        name.to_string()
+    };
+
+    // npm scopes and Go module paths become namespace segments
+    let path = name
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(encode)
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut purl = format!("pkg:{purl_type}/{path}");
+    if !version.is_empty() {
+        purl.push('@');
+        purl.push_str(&encode(version));
+    }
+    Some(purl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_url_per_ecosystem() {
+        let cases = [
+            ("cargo", "serde", "1.0.0", "pkg:cargo/serde@1.0.0"),
+            ("npm", "lodash", "4.17.21", "pkg:npm/lodash@4.17.21"),
+            (
+                "pypi",
+                "Typing_Extensions",
+                "4.12.2",
+                "pkg:pypi/typing-extensions@4.12.2",
+            ),
+            ("conda", "numpy", "1.26.4", "pkg:conda/numpy@1.26.4"),
+            (
+                "go",
+                "github.com/spf13/cobra",
+                "v1.8.0",
+                "pkg:golang/github.com/spf13/cobra@v1.8.0",
+            ),
+            (
+                "nuget",
+                "Newtonsoft.Json",
+                "13.0.3",
+                "pkg:nuget/Newtonsoft.Json@13.0.3",
+            ),
+            ("hex", "phoenix", "1.7.14", "pkg:hex/phoenix@1.7.14"),
+            ("hackage", "aeson", "2.2.1.0", "pkg:hackage/aeson@2.2.1.0"),
+            ("cpan", "Moose", "2.2207", "pkg:cpan/Moose@2.2207"),
+            ("r", "ggplot2", "3.5.1", "pkg:cran/ggplot2@3.5.1"),
+        ];
+
+        for (ecosystem, name, version, expected) in cases {
+            assert_eq!(
+                package_url(ecosystem, name, version).as_deref(),
+                Some(expected),
+                "{ecosystem}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_package_url_encodes_scoped_npm_names() {
+        assert_eq!(
+            package_url("npm", "@types/node", "20.1.0").as_deref(),
+            Some("pkg:npm/%40types/node@20.1.0")
+        );
+        assert_eq!(
+            package_url("cargo", "serde", "1.0.0+build.1").as_deref(),
+            Some("pkg:cargo/serde@1.0.0%2Bbuild.1")
+        );
+    }
+
+    #[test]
+    fn test_package_url_without_registry() {
+        assert_eq!(package_url("", "zlib", "1.3"), None);
+        assert_eq!(package_url("cpp", "fmt", "10.2.1"), None);
+        assert_eq!(
+            package_url("npm", "left-pad", "").as_deref(),
+            Some("pkg:npm/left-pad")
+        );
+    }
+}
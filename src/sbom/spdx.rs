@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
+use crate::reporter::to_json_string;
 
 /// Character validation for SPDX compliance
 ///
@@ -289,15 +290,15 @@ pub struct SpdxDocument {
     pub creation_info: CreationInfo,
 
     /// Packages in the document
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub packages: Vec<SpdxPackage>,
 
     /// Relationships between elements  
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub relationships: Vec<Relationship>,
 
     /// Annotations for non-standard data
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub annotations: Vec<Annotation>,
 }
 
@@ -336,6 +337,10 @@ pub struct SpdxPackage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_info: Option<String>,
 
+    /// Primary purpose, e.g. `APPLICATION` for the analyzed project (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_package_purpose: Option<String>,
+
     /// License concluded (optional but important)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license_concluded: Option<String>,
@@ -344,6 +349,10 @@ pub struct SpdxPackage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license_declared: Option<String>,
 
+    /// Licenses found in the package's own files, only when files were analyzed (optional)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub license_info_from_files: Vec<String>,
+
     /// License comments (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license_comments: Option<String>,
@@ -357,7 +366,7 @@ pub struct SpdxPackage {
     pub comment: Option<String>,
 
     /// External references (optional)
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub external_refs: Vec<ExternalReference>,
 }
 
@@ -430,8 +439,28 @@ impl SpdxDocument {
         self.relationships.push(relationship);
     }
 
-    #[allow(dead_code)]
+    /// Add a package that is only reached through `DEPENDS_ON`, not described by the document
+    pub fn add_dependency_package(&mut self, package: SpdxPackage) {
+        self.packages.push(package);
+    }
+
+    /// Add a relationship between two elements of the document
+    pub fn add_relationship(&mut self, from: String, relationship_type: &str, to: String) {
+        self.relationships.push(Relationship {
+            spdx_element_id: from,
+            relationship_type: relationship_type.to_string(),
+            related_spdx_element: to,
+            comment: None,
+        });
+    }
+
     pub fn add_annotation(&mut self, spdx_ref: String, comment: String, annotation_type: String) {
+        // Annotation comments are free text, so strip characters that would break serialization
+        let comment = comment
+            .chars()
+            .filter(|c| !spdx_charset::GLOBALLY_FORBIDDEN.contains(c))
+            .collect();
+
         let annotation = Annotation {
             annotator: format!("Tool: Feluda-{}", env!("CARGO_PKG_VERSION")),
             annotation_date: Utc::now(),
@@ -455,8 +484,10 @@ impl SpdxPackage {
             download_location: "NOASSERTION".to_string(),
             files_analyzed: false,
             version_info: None,
+            primary_package_purpose: None,
             license_concluded: None,
             license_declared: None,
+            license_info_from_files: Vec::new(),
             license_comments: None,
             copyright_text: Some("NOASSERTION".to_string()),
             comment: None,
@@ -510,6 +541,25 @@ impl SpdxPackage {
         self
     }
 
+    /// Sets the primary package purpose, e.g. `APPLICATION`
+    pub fn with_primary_package_purpose(mut self, purpose: &str) -> Self {
+        self.primary_package_purpose = Some(purpose.to_string());
+        self
+    }
+
+    /// Marks the package files as analyzed, recording the licenses found in them
+    ///
+    /// Only the project itself is analyzed this way; dependencies keep `filesAnalyzed: false`.
+    pub fn with_files_analyzed(mut self, licenses: Vec<String>) -> Self {
+        self.files_analyzed = true;
+        self.license_info_from_files = licenses
+            .iter()
+            .map(|license| convert_to_spdx_license_expression(license))
+            .filter(|license| !license.trim().is_empty())
+            .collect();
+        self
+    }
+
     /// Sets the download location with SPDX validation
     ///
     /// Per SPDX 2.3 spec, download location must be:
@@ -608,18 +658,17 @@ impl SpdxPackage {
     /// External references link a package to external sources of information.
     /// Per SPDX 2.3 spec, common reference categories include:
     /// - "SECURITY_OTHER" for security-related references
-    /// - "PACKAGE_MANAGER" for package manager records
+    /// - "PACKAGE-MANAGER" for package manager records, e.g. a `purl`
     /// - "OTHER" for miscellaneous references
     ///
     /// Example:
     /// ```ignore
     /// package.add_external_ref(
-    ///     "PACKAGE_MANAGER".to_string(),
-    ///     "npm".to_string(),
-    ///     "lodash@4.17.21".to_string()
+    ///     "PACKAGE-MANAGER".to_string(),
+    ///     "purl".to_string(),
+    ///     "pkg:npm/lodash@4.17.21".to_string()
     /// );
     /// ```
-    #[allow(dead_code)]
     pub fn add_external_ref(mut self, category: String, ref_type: String, locator: String) -> Self {
         // Validate external reference fields
         let is_valid = !spdx_charset::contains_forbidden_chars(&category)
@@ -653,7 +702,7 @@ impl Default for SpdxDocument {
     }
 }
 
-fn validate_and_sanitize_spdx_package(package: &mut SpdxPackage) -> bool {
+pub(crate) fn validate_and_sanitize_spdx_package(package: &mut SpdxPackage) -> bool {
     let mut needs_fix = false;
 
     // SPDX Identifier Validation
@@ -832,12 +881,8 @@ fn validate_and_sanitize_spdx_package(package: &mut SpdxPackage) -> bool {
     needs_fix
 }
 
-pub fn generate_spdx_output(
-    spdx_doc: &SpdxDocument,
-    output_file: Option<String>,
-) -> FeludaResult<()> {
-    log(LogLevel::Info, "Generating SPDX 2.3 compliant output");
-
+/// Clone the document and sanitize every package so it can be emitted safely
+fn sanitized_document(spdx_doc: &SpdxDocument) -> SpdxDocument {
     let mut safe_doc = spdx_doc.clone();
 
     let mut total_fixes = 0;
@@ -854,7 +899,18 @@ pub fn generate_spdx_output(
         );
     }
 
-    let json_output = serde_json::to_string_pretty(&safe_doc).map_err(|e| {
+    safe_doc
+}
+
+pub fn generate_spdx_output(
+    spdx_doc: &SpdxDocument,
+    output_file: Option<String>,
+) -> FeludaResult<()> {
+    log(LogLevel::Info, "Generating SPDX 2.3 compliant output");
+
+    let safe_doc = sanitized_document(spdx_doc);
+
+    let json_output = to_json_string(&safe_doc).map_err(|e| {
         FeludaError::Serialization(format!("Failed to serialize SPDX document: {e}"))
     })?;
 
@@ -887,6 +943,166 @@ pub fn generate_spdx_output(
     Ok(())
 }
 
+/// Wrap free-form values in `<text>` tags unless they are SPDX keywords
+fn tagvalue_text(value: &str) -> String {
+    if value == "NOASSERTION" || value == "NONE" {
+        value.to_string()
+    } else {
+        format!("<text>{value}</text>")
+    }
+}
+
+/// Serialize an SPDX document in the classic tag-value (`.spdx`) format
+fn format_spdx_tagvalue(doc: &SpdxDocument) -> String {
+    let mut lines = vec![
+        format!("SPDXVersion: {}", doc.spdx_version),
+        format!("DataLicense: {}", doc.data_license),
+        format!("SPDXID: {}", doc.spdx_id),
+        format!("DocumentName: {}", doc.name),
+        format!("DocumentNamespace: {}", doc.document_namespace),
+    ];
+
+    for creator in &doc.creation_info.creators {
+        lines.push(format!("Creator: {creator}"));
+    }
+    lines.push(format!(
+        "Created: {}",
+        doc.creation_info.created.format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+    if let Some(version) = &doc.creation_info.license_list_version {
+        lines.push(format!("LicenseListVersion: {version}"));
+    }
+
+    for package in &doc.packages {
+        lines.push(String::new());
+        lines.push(format!("##### Package: {}", package.name));
+        lines.push(String::new());
+        lines.push(format!("PackageName: {}", package.name));
+        lines.push(format!("SPDXID: {}", package.spdx_id));
+        if let Some(version) = &package.version_info {
+            lines.push(format!("PackageVersion: {version}"));
+        }
+        lines.push(format!(
+            "PackageDownloadLocation: {}",
+            package.download_location
+        ));
+        if let Some(purpose) = &package.primary_package_purpose {
+            lines.push(format!("PrimaryPackagePurpose: {purpose}"));
+        }
+        lines.push(format!("FilesAnalyzed: {}", package.files_analyzed));
+
+        let noassertion = "NOASSERTION".to_string();
+        lines.push(format!(
+            "PackageLicenseConcluded: {}",
+            package.license_concluded.as_ref().unwrap_or(&noassertion)
+        ));
+        for license in &package.license_info_from_files {
+            lines.push(format!("PackageLicenseInfoFromFiles: {license}"));
+        }
+        lines.push(format!(
+            "PackageLicenseDeclared: {}",
+            package.license_declared.as_ref().unwrap_or(&noassertion)
+        ));
+        if let Some(comments) = &package.license_comments {
+            lines.push(format!(
+                "PackageLicenseComments: {}",
+                tagvalue_text(comments)
+            ));
+        }
+        lines.push(format!(
+            "PackageCopyrightText: {}",
+            tagvalue_text(package.copyright_text.as_ref().unwrap_or(&noassertion))
+        ));
+        if let Some(comment) = &package.comment {
+            lines.push(format!("PackageComment: {}", tagvalue_text(comment)));
+        }
+        for external_ref in &package.external_refs {
+            lines.push(format!(
+                "ExternalRef: {} {} {}",
+                external_ref.reference_category,
+                external_ref.reference_type,
+                external_ref.reference_locator
+            ));
+            if let Some(comment) = &external_ref.comment {
+                lines.push(format!("ExternalRefComment: {}", tagvalue_text(comment)));
+            }
+        }
+    }
+
+    if !doc.relationships.is_empty() {
+        lines.push(String::new());
+        lines.push("##### Relationships".to_string());
+        lines.push(String::new());
+        for relationship in &doc.relationships {
+            lines.push(format!(
+                "Relationship: {} {} {}",
+                relationship.spdx_element_id,
+                relationship.relationship_type,
+                relationship.related_spdx_element
+            ));
+            if let Some(comment) = &relationship.comment {
+                lines.push(format!("RelationshipComment: {}", tagvalue_text(comment)));
+            }
+        }
+    }
+
+    for annotation in &doc.annotations {
+        lines.push(String::new());
+        lines.push(format!("Annotator: {}", annotation.annotator));
+        lines.push(format!(
+            "AnnotationDate: {}",
+            annotation.annotation_date.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+        lines.push(format!("AnnotationType: {}", annotation.annotation_type));
+        lines.push(format!("SPDXREF: {}", annotation.spdx_identifier_reference));
+        lines.push(format!(
+            "AnnotationComment: {}",
+            tagvalue_text(&annotation.comment)
+        ));
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+pub fn generate_spdx_tagvalue_output(
+    spdx_doc: &SpdxDocument,
+    output_file: Option<String>,
+) -> FeludaResult<()> {
+    log(LogLevel::Info, "Generating SPDX 2.3 tag-value output");
+
+    let safe_doc = sanitized_document(spdx_doc);
+    let tagvalue_output = format_spdx_tagvalue(&safe_doc);
+
+    if let Some(file_path) = output_file {
+        let spdx_file = if file_path.ends_with(".spdx") {
+            file_path
+        } else {
+            format!(
+                "{}.spdx",
+                file_path
+                    .trim_end_matches(".json")
+                    .trim_end_matches(".spdx")
+            )
+        };
+
+        std::fs::write(&spdx_file, &tagvalue_output)
+            .map_err(|e| FeludaError::FileWrite(format!("Failed to write SPDX file: {e}")))?;
+
+        println!("SPDX tag-value SBOM written to: {spdx_file}");
+        log(
+            LogLevel::Info,
+            &format!("SPDX tag-value SBOM written to: {spdx_file}"),
+        );
+    } else {
+        println!("=== SPDX SBOM (tag-value) ===");
+        print!("{tagvalue_output}");
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1644,6 +1860,80 @@ mod tests {
         assert_eq!(package3.comment, None);
     }
 
+    #[test]
+    #[serial]
+    fn test_spdx_tagvalue_package_tags() {
+        let mut doc = SpdxDocument::new("demo");
+        doc.add_package(
+            SpdxPackage::new("serde".to_string(), &doc.document_namespace)
+                .with_version("1.0.0".to_string())
+                .with_license("MIT OR Apache-2.0".to_string()),
+        );
+        doc.add_package(
+            SpdxPackage::new("left-pad".to_string(), &doc.document_namespace)
+                .with_version("1.3.0".to_string()),
+        );
+
+        let output = format_spdx_tagvalue(&sanitized_document(&doc));
+
+        assert!(output.starts_with("SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\n"));
+        assert!(output.contains("SPDXID: SPDXRef-DOCUMENT\n"));
+        assert!(output.contains(&format!("DocumentNamespace: {}\n", doc.document_namespace)));
+        assert!(output.contains("Creator: Tool: Feluda-"));
+
+        for package in &doc.packages {
+            assert!(output.contains(&format!("PackageName: {}\n", package.name)));
+            assert!(output.contains(&format!("SPDXID: {}\n", package.spdx_id)));
+        }
+        assert_eq!(output.matches("PackageLicenseConcluded: ").count(), 2);
+        assert_eq!(output.matches("PackageDownloadLocation: ").count(), 2);
+        assert!(output.contains("PackageLicenseConcluded: MIT OR Apache-2.0\n"));
+        assert!(output.contains("PackageLicenseConcluded: NOASSERTION\n"));
+        assert!(output.contains("PackageCopyrightText: NOASSERTION\n"));
+    }
+
+    #[test]
+    fn test_spdx_tagvalue_relationships() {
+        let mut doc = SpdxDocument::new("demo");
+        doc.add_package(
+            SpdxPackage::new("serde".to_string(), &doc.document_namespace)
+                .with_version("1.0.0".to_string()),
+        );
+
+        let output = format_spdx_tagvalue(&sanitized_document(&doc));
+        let package_id = &doc.packages[0].spdx_id;
+        assert!(package_id.starts_with("SPDXRef-Package-"));
+        assert!(output.contains(&format!(
+            "Relationship: SPDXRef-DOCUMENT DESCRIBES {package_id}\n"
+        )));
+    }
+
+    #[test]
+    fn test_spdx_tagvalue_output_file_extension() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base = temp_dir.path().join("sbom.json");
+        let doc = SpdxDocument::new("demo");
+
+        generate_spdx_tagvalue_output(&doc, Some(base.to_string_lossy().to_string())).unwrap();
+
+        let written = std::fs::read_to_string(temp_dir.path().join("sbom.spdx")).unwrap();
+        assert!(written.starts_with("SPDXVersion: SPDX-2.3"));
+    }
+
+    #[test]
+    fn test_add_annotation_strips_forbidden_chars() {
+        let mut doc = SpdxDocument::new("demo");
+        doc.add_annotation(
+            "SPDXRef-Package-test".to_string(),
+            "line one\nline \"two\"\t".to_string(),
+            "REVIEW".to_string(),
+        );
+
+        let comment = &doc.annotations[0].comment;
+        assert_eq!(comment, "line oneline two");
+        assert!(!spdx_charset::contains_forbidden_chars(comment));
+    }
+
     #[test]
     fn test_charset_validation_helpers() {
         // Test globally forbidden characters
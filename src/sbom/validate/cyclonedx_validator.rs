@@ -35,7 +35,7 @@ fn validate_required_fields(
         if !obj.contains_key(field) {
             report.add_issue(
                 ValidationIssue::error(format!("Missing required field: {field}"))
-                    .with_field(field),
+                    .with_field(format!("/{field}")),
             );
         }
     }
@@ -50,9 +50,14 @@ fn validate_bom_format(report: &mut ValidationReport, obj: &serde_json::Map<Stri
                 ValidationIssue::error(format!(
                     "Invalid bomFormat: '{format}'. Expected 'CycloneDX'"
                 ))
-                .with_field("bomFormat"),
+                .with_field("/bomFormat"),
             );
         }
+    } else if obj.contains_key("bomFormat") {
+        report.add_issue(
+            ValidationIssue::error("bomFormat must be the string 'CycloneDX'")
+                .with_field("/bomFormat"),
+        );
     }
 }
 
@@ -60,15 +65,19 @@ fn validate_spec_version(report: &mut ValidationReport, obj: &serde_json::Map<St
     let json_obj = JsonValue::Object(obj.clone());
 
     if let Some(spec_version) = parser::get_string(&json_obj, "specVersion") {
-        let valid_versions = ["1.0", "1.1", "1.2", "1.3", "1.4", "1.5"];
+        let valid_versions = ["1.0", "1.1", "1.2", "1.3", "1.4", "1.5", "1.6"];
         if !valid_versions.contains(&spec_version.as_str()) {
             report.add_issue(
-                ValidationIssue::warning(format!(
+                ValidationIssue::error(format!(
                     "Unknown or unsupported specVersion: {spec_version}"
                 ))
-                .with_field("specVersion"),
+                .with_field("/specVersion"),
             );
         }
+    } else if obj.contains_key("specVersion") {
+        report.add_issue(
+            ValidationIssue::error("specVersion must be a string").with_field("/specVersion"),
+        );
     }
 }
 
@@ -89,77 +98,97 @@ fn validate_components(report: &mut ValidationReport, obj: &serde_json::Map<Stri
 }
 
 fn validate_component(report: &mut ValidationReport, component: &JsonValue, index: usize) {
-    if let Some(comp_obj) = component.as_object() {
-        let comp_json = JsonValue::Object(comp_obj.clone());
+    let pointer = format!("/components/{index}");
 
-        let component_name =
-            parser::get_string(&comp_json, "name").unwrap_or_else(|| format!("Component[{index}]"));
+    let Some(comp_obj) = component.as_object() else {
+        report.add_issue(
+            ValidationIssue::error(format!("Component[{index}] must be a JSON object"))
+                .with_field(pointer),
+        );
+        return;
+    };
+    let comp_json = JsonValue::Object(comp_obj.clone());
 
-        if !parser::has_key(&comp_json, "type") {
+    let component_name = match parser::get_string(&comp_json, "name") {
+        Some(name) => name,
+        None => {
             report.add_issue(
-                ValidationIssue::error(format!(
-                    "Component '{component_name}': missing 'type' field"
+                ValidationIssue::error(format!("Component[{index}]: missing 'name' field"))
+                    .with_field(format!("{pointer}/name")),
+            );
+            format!("Component[{index}]")
+        }
+    };
+
+    if !parser::has_key(&comp_json, "type") {
+        report.add_issue(
+            ValidationIssue::error(format!(
+                "Component '{component_name}': missing 'type' field"
+            ))
+            .with_field(format!("{pointer}/type")),
+        );
+    } else if let Some(comp_type) = parser::get_string(&comp_json, "type") {
+        let valid_types = [
+            "application",
+            "framework",
+            "library",
+            "container",
+            "platform",
+            "operating-system",
+            "device",
+            "device-driver",
+            "firmware",
+            "file",
+            "machine-learning-model",
+            "data",
+            "cryptographic-asset",
+            "install",
+            "archive",
+            "filing-system",
+            "media",
+            "other",
+        ];
+        if !valid_types.contains(&comp_type.as_str()) {
+            report.add_issue(
+                ValidationIssue::warning(format!(
+                    "Component '{component_name}': unknown component type '{comp_type}'"
                 ))
-                .with_field("type"),
+                .with_field(format!("{pointer}/type")),
             );
-        } else if let Some(comp_type) = parser::get_string(&comp_json, "type") {
-            let valid_types = [
-                "application",
-                "framework",
-                "library",
-                "container",
-                "operating-system",
-                "device",
-                "firmware",
-                "file",
-                "install",
-                "archive",
-                "filing-system",
-                "media",
-                "other",
-            ];
-            if !valid_types.contains(&comp_type.as_str()) {
+        }
+    }
+
+    if parser::has_key(&comp_json, "version") {
+        if let Some(version) = parser::get_string(&comp_json, "version") {
+            if version.is_empty() {
                 report.add_issue(
                     ValidationIssue::warning(format!(
-                        "Component '{component_name}': unknown component type '{comp_type}'"
+                        "Component '{component_name}': version cannot be empty"
                     ))
-                    .with_field("type"),
+                    .with_field(format!("{pointer}/version")),
                 );
             }
         }
+    }
 
-        if parser::has_key(&comp_json, "version") {
-            if let Some(version) = parser::get_string(&comp_json, "version") {
-                if version.is_empty() {
+    if let Some(licenses) = parser::get_array(&comp_json, "licenses") {
+        for (license_index, license) in licenses.iter().enumerate() {
+            if let Some(license_obj) = license.as_object() {
+                let license_json = JsonValue::Object(license_obj.clone());
+                if !parser::has_key(&license_json, "license")
+                    && !parser::has_key(&license_json, "expression")
+                {
                     report.add_issue(
-                        ValidationIssue::warning(format!(
-                            "Component '{component_name}': version cannot be empty"
-                        ))
-                        .with_field("version"),
+                        ValidationIssue::warning(
+                            format!(
+                                "Component '{component_name}': license must have either 'license' or 'expression' field"
+                            ),
+                        )
+                        .with_field(format!("{pointer}/licenses/{license_index}")),
                     );
                 }
             }
         }
-
-        if let Some(licenses) = parser::get_array(&comp_json, "licenses") {
-            for license in licenses {
-                if let Some(license_obj) = license.as_object() {
-                    let license_json = JsonValue::Object(license_obj.clone());
-                    if !parser::has_key(&license_json, "license")
-                        && !parser::has_key(&license_json, "expression")
-                    {
-                        report.add_issue(
-                            ValidationIssue::warning(
-                                format!(
-                                    "Component '{component_name}': license must have either 'license' or 'expression' field"
-                                ),
-                            )
-                            .with_field("licenses"),
-                        );
-                    }
-                }
-            }
-        }
     }
 }
 
@@ -175,7 +204,7 @@ fn validate_metadata(report: &mut ValidationReport, obj: &serde_json::Map<String
                             ValidationIssue::warning(format!(
                                 "Metadata: invalid timestamp format '{timestamp}'. Expected ISO 8601 format"
                             ))
-                            .with_field("metadata.timestamp"),
+                            .with_field("/metadata/timestamp"),
                         );
                     }
                 }
@@ -183,13 +212,13 @@ fn validate_metadata(report: &mut ValidationReport, obj: &serde_json::Map<String
 
             if parser::has_key(&metadata, "tools") {
                 if let Some(tools) = parser::get_array(&metadata, "tools") {
-                    for tool in tools {
+                    for (tool_index, tool) in tools.iter().enumerate() {
                         if let Some(tool_obj) = tool.as_object() {
                             let tool_json = JsonValue::Object(tool_obj.clone());
                             if !parser::has_key(&tool_json, "name") {
                                 report.add_issue(
                                     ValidationIssue::warning("Tool entry missing 'name' field")
-                                        .with_field("metadata.tools[].name"),
+                                        .with_field(format!("/metadata/tools/{tool_index}/name")),
                                 );
                             }
                         }
@@ -199,3 +228,68 @@ fn validate_metadata(report: &mut ValidationReport, obj: &serde_json::Map<String
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::reporter::IssueSeverity;
+    use super::*;
+    use serde_json::json;
+
+    fn valid_bom() -> JsonValue {
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "components": [
+                {"type": "library", "name": "serde", "version": "1.0.0"}
+            ]
+        })
+    }
+
+    fn error_fields(report: &ValidationReport) -> Vec<String> {
+        report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .filter_map(|issue| issue.field.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_bom_has_no_errors() {
+        let report = validate(&valid_bom()).unwrap();
+        assert!(report.is_valid, "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_wrong_format_and_unknown_spec_version() {
+        let mut bom = valid_bom();
+        bom["bomFormat"] = json!("SPDX");
+        bom["specVersion"] = json!("9.9");
+
+        let report = validate(&bom).unwrap();
+        assert_eq!(error_fields(&report), vec!["/bomFormat", "/specVersion"]);
+    }
+
+    #[test]
+    fn test_components_missing_name_and_type() {
+        let mut bom = valid_bom();
+        bom["components"] = json!([
+            {"type": "library", "name": "ok"},
+            {"name": "untyped"},
+            {"type": "library"},
+            "not-an-object"
+        ]);
+
+        let report = validate(&bom).unwrap();
+        assert_eq!(
+            error_fields(&report),
+            vec!["/components/1/type", "/components/2/name", "/components/3",]
+        );
+    }
+
+    #[test]
+    fn test_missing_required_fields() {
+        let report = validate(&json!({"components": []})).unwrap();
+        assert_eq!(error_fields(&report), vec!["/bomFormat", "/specVersion"]);
+    }
+}
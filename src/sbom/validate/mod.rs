@@ -8,12 +8,12 @@ mod reporter;
 mod spdx_validator;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum SbomType {
+pub(crate) enum SbomType {
     Spdx,
     CycloneDx,
 }
 
-fn detect_sbom_type(content: &str) -> FeludaResult<SbomType> {
+pub(crate) fn detect_sbom_type(content: &str) -> FeludaResult<SbomType> {
     let json: JsonValue = serde_json::from_str(content)
         .map_err(|e| FeludaError::Validation(format!("Failed to parse JSON: {e}")))?;
 
@@ -108,7 +108,7 @@ impl ValidationReport {
     }
 
     fn format_text(&self) -> String {
-        use owo_colors::OwoColorize;
+        use colored::Colorize;
 
         let mut output = String::new();
         output.push_str(&format!("\n{}\n", "━".repeat(60)).bold().to_string());
@@ -151,7 +151,10 @@ impl ValidationReport {
                 format!("{}", self.warning_count).green().to_string()
             }
         ));
-        output.push_str(&format!("  Info:     {}\n", self.info_count.bright_blue()));
+        output.push_str(&format!(
+            "  Info:     {}\n",
+            self.info_count.to_string().bright_blue()
+        ));
 
         if !self.issues.is_empty() {
             output.push_str("\nDetailed Issues:\n");
@@ -18,8 +18,10 @@ pub fn validate(json: &JsonValue) -> FeludaResult<ValidationReport> {
 
     validate_required_fields(&mut report, obj);
     validate_spdx_version(&mut report, obj);
+    validate_document_constants(&mut report, obj);
     validate_document_name(&mut report, obj);
     validate_namespace(&mut report, obj);
+    validate_creation_info(&mut report, obj);
     validate_packages(&mut report, obj);
 
     Ok(report)
@@ -42,7 +44,7 @@ fn validate_required_fields(
         if !obj.contains_key(field) {
             report.add_issue(
                 ValidationIssue::error(format!("Missing required field: {field}"))
-                    .with_field(field),
+                    .with_field(format!("/{field}")),
             );
         }
     }
@@ -52,18 +54,57 @@ fn validate_spdx_version(report: &mut ValidationReport, obj: &serde_json::Map<St
     if let Some(version) = parser::get_string(&JsonValue::Object(obj.clone()), "spdxVersion") {
         if !version.starts_with("SPDX-") {
             report.add_issue(
-                ValidationIssue::warning(format!(
+                ValidationIssue::error(format!(
                     "Invalid SPDX version format: {version}. Expected format: SPDX-X.Y"
                 ))
-                .with_field("spdxVersion"),
+                .with_field("/spdxVersion"),
             );
+            return;
         }
 
         let supported_versions = ["SPDX-2.2", "SPDX-2.3"];
         if !supported_versions.iter().any(|v| version.starts_with(v)) {
             report.add_issue(
                 ValidationIssue::info(format!("SPDX version {version} may not be fully supported"))
-                    .with_field("spdxVersion"),
+                    .with_field("/spdxVersion"),
+            );
+        }
+    } else if obj.contains_key("spdxVersion") {
+        report.add_issue(
+            ValidationIssue::error("spdxVersion must be a string").with_field("/spdxVersion"),
+        );
+    }
+}
+
+/// Fields whose values are fixed by the SPDX 2.x specification
+fn validate_document_constants(
+    report: &mut ValidationReport,
+    obj: &serde_json::Map<String, JsonValue>,
+) {
+    let json_obj = JsonValue::Object(obj.clone());
+
+    if obj.contains_key("dataLicense") {
+        let data_license = parser::get_string(&json_obj, "dataLicense");
+        if data_license.as_deref() != Some("CC0-1.0") {
+            report.add_issue(
+                ValidationIssue::error(format!(
+                    "Invalid dataLicense: {}. SPDX documents must use 'CC0-1.0'",
+                    data_license.unwrap_or_else(|| "non-string value".to_string())
+                ))
+                .with_field("/dataLicense"),
+            );
+        }
+    }
+
+    if obj.contains_key("SPDXID") {
+        let spdx_id = parser::get_string(&json_obj, "SPDXID");
+        if spdx_id.as_deref() != Some("SPDXRef-DOCUMENT") {
+            report.add_issue(
+                ValidationIssue::error(format!(
+                    "Invalid document SPDXID: {}. Expected 'SPDXRef-DOCUMENT'",
+                    spdx_id.unwrap_or_else(|| "non-string value".to_string())
+                ))
+                .with_field("/SPDXID"),
             );
         }
     }
@@ -75,7 +116,7 @@ fn validate_document_name(report: &mut ValidationReport, obj: &serde_json::Map<S
     if let Some(name) = parser::get_string(&json_obj, "name") {
         if name.is_empty() {
             report.add_issue(
-                ValidationIssue::error("Document name cannot be empty").with_field("name"),
+                ValidationIssue::error("Document name cannot be empty").with_field("/name"),
             );
         }
     }
@@ -88,17 +129,54 @@ fn validate_namespace(report: &mut ValidationReport, obj: &serde_json::Map<Strin
         if namespace.is_empty() {
             report.add_issue(
                 ValidationIssue::error("Document namespace cannot be empty")
-                    .with_field("documentNamespace"),
+                    .with_field("/documentNamespace"),
             );
         } else if !namespace.starts_with("https://") && !namespace.starts_with("http://") {
             report.add_issue(
                 ValidationIssue::warning("Document namespace should be a valid URI")
-                    .with_field("documentNamespace"),
+                    .with_field("/documentNamespace"),
             );
         }
     }
 }
 
+fn validate_creation_info(report: &mut ValidationReport, obj: &serde_json::Map<String, JsonValue>) {
+    let Some(creation_info) = obj.get("creationInfo") else {
+        return;
+    };
+
+    if !creation_info.is_object() {
+        report.add_issue(
+            ValidationIssue::error("creationInfo must be an object").with_field("/creationInfo"),
+        );
+        return;
+    }
+
+    match parser::get_string(creation_info, "created") {
+        None => report.add_issue(
+            ValidationIssue::error("creationInfo: missing created timestamp")
+                .with_field("/creationInfo/created"),
+        ),
+        Some(created) if !parser::is_valid_iso_datetime(&created) => report.add_issue(
+            ValidationIssue::error(format!(
+                "creationInfo: invalid created timestamp '{created}'. Expected ISO 8601 format"
+            ))
+            .with_field("/creationInfo/created"),
+        ),
+        Some(_) => {}
+    }
+
+    let has_creator = parser::get_array(creation_info, "creators")
+        .map(|creators| !creators.is_empty())
+        .unwrap_or(false);
+    if !has_creator {
+        report.add_issue(
+            ValidationIssue::error("creationInfo: at least one creator is required")
+                .with_field("/creationInfo/creators"),
+        );
+    }
+}
+
 fn validate_packages(report: &mut ValidationReport, obj: &serde_json::Map<String, JsonValue>) {
     let json_obj = JsonValue::Object(obj.clone());
 
@@ -114,44 +192,159 @@ fn validate_packages(report: &mut ValidationReport, obj: &serde_json::Map<String
 }
 
 fn validate_package(report: &mut ValidationReport, package: &JsonValue, index: usize) {
-    if let Some(pkg_obj) = package.as_object() {
-        let pkg_json = JsonValue::Object(pkg_obj.clone());
+    let pointer = format!("/packages/{index}");
 
-        let package_name =
-            parser::get_string(&pkg_json, "name").unwrap_or_else(|| format!("Package[{index}]"));
+    let Some(pkg_obj) = package.as_object() else {
+        report.add_issue(
+            ValidationIssue::error(format!("Package[{index}] must be a JSON object"))
+                .with_field(pointer),
+        );
+        return;
+    };
+    let pkg_json = JsonValue::Object(pkg_obj.clone());
 
-        if !parser::has_key(&pkg_json, "SPDXID") {
+    let package_name = match parser::get_string(&pkg_json, "name") {
+        Some(name) => name,
+        None => {
             report.add_issue(
-                ValidationIssue::error(format!("Package '{package_name}': missing SPDXID"))
-                    .with_field("SPDXID"),
+                ValidationIssue::error(format!("Package[{index}]: missing name"))
+                    .with_field(format!("{pointer}/name")),
             );
-        } else if let Some(spdx_id) = parser::get_string(&pkg_json, "SPDXID") {
-            if !spdx_id.starts_with("SPDXRef-") {
-                report.add_issue(
-                    ValidationIssue::warning(format!(
-                        "Package '{package_name}': SPDXID should start with 'SPDXRef-'"
-                    ))
-                    .with_field("SPDXID"),
-                );
-            }
+            format!("Package[{index}]")
         }
+    };
 
-        if !parser::has_key(&pkg_json, "downloadLocation") {
+    if !parser::has_key(&pkg_json, "SPDXID") {
+        report.add_issue(
+            ValidationIssue::error(format!("Package '{package_name}': missing SPDXID"))
+                .with_field(format!("{pointer}/SPDXID")),
+        );
+    } else if let Some(spdx_id) = parser::get_string(&pkg_json, "SPDXID") {
+        if !spdx_id.starts_with("SPDXRef-") {
             report.add_issue(
-                ValidationIssue::error(format!(
-                    "Package '{package_name}': missing downloadLocation"
+                ValidationIssue::warning(format!(
+                    "Package '{package_name}': SPDXID should start with 'SPDXRef-'"
                 ))
-                .with_field("downloadLocation"),
+                .with_field(format!("{pointer}/SPDXID")),
             );
         }
+    }
 
-        if !parser::has_key(&pkg_json, "filesAnalyzed") {
-            report.add_issue(
-                ValidationIssue::info(format!(
-                    "Package '{package_name}': filesAnalyzed not specified"
-                ))
-                .with_field("filesAnalyzed"),
-            );
-        }
+    if !parser::has_key(&pkg_json, "downloadLocation") {
+        report.add_issue(
+            ValidationIssue::error(format!(
+                "Package '{package_name}': missing downloadLocation"
+            ))
+            .with_field(format!("{pointer}/downloadLocation")),
+        );
+    }
+
+    if !parser::has_key(&pkg_json, "licenseConcluded") {
+        report.add_issue(
+            ValidationIssue::error(format!(
+                "Package '{package_name}': missing licenseConcluded"
+            ))
+            .with_field(format!("{pointer}/licenseConcluded")),
+        );
+    }
+
+    if !parser::has_key(&pkg_json, "filesAnalyzed") {
+        report.add_issue(
+            ValidationIssue::info(format!(
+                "Package '{package_name}': filesAnalyzed not specified"
+            ))
+            .with_field(format!("{pointer}/filesAnalyzed")),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn valid_document() -> JsonValue {
+        json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": "demo",
+            "documentNamespace": "https://example.com/spdx/demo",
+            "creationInfo": {
+                "created": "2024-01-01T00:00:00Z",
+                "creators": ["Tool: Feluda"]
+            },
+            "packages": [{
+                "name": "serde",
+                "SPDXID": "SPDXRef-Package-serde",
+                "downloadLocation": "NOASSERTION",
+                "filesAnalyzed": false,
+                "licenseConcluded": "MIT"
+            }]
+        })
+    }
+
+    fn error_fields(report: &ValidationReport) -> Vec<String> {
+        report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == super::super::reporter::IssueSeverity::Error)
+            .filter_map(|issue| issue.field.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_document_has_no_errors() {
+        let report = validate(&valid_document()).unwrap();
+        assert!(report.is_valid, "{:?}", report.issues);
+        assert_eq!(report.error_count, 0);
+    }
+
+    #[test]
+    fn test_wrong_document_constants() {
+        let mut doc = valid_document();
+        doc["dataLicense"] = json!("MIT");
+        doc["SPDXID"] = json!("SPDXRef-Other");
+
+        let report = validate(&doc).unwrap();
+        assert!(!report.is_valid);
+        assert_eq!(error_fields(&report), vec!["/dataLicense", "/SPDXID"]);
+    }
+
+    #[test]
+    fn test_missing_required_fields_use_json_pointers() {
+        let mut doc = valid_document();
+        doc.as_object_mut().unwrap().remove("spdxVersion");
+        doc["creationInfo"]
+            .as_object_mut()
+            .unwrap()
+            .remove("creators");
+
+        let report = validate(&doc).unwrap();
+        let fields = error_fields(&report);
+        assert!(fields.contains(&"/spdxVersion".to_string()));
+        assert!(fields.contains(&"/creationInfo/creators".to_string()));
+    }
+
+    #[test]
+    fn test_package_missing_download_location_and_license() {
+        let mut doc = valid_document();
+        let package = doc["packages"][0].as_object_mut().unwrap();
+        package.remove("downloadLocation");
+        package.remove("licenseConcluded");
+        doc["packages"].as_array_mut().unwrap().push(
+            json!({"SPDXID": "SPDXRef-Package-x", "downloadLocation": "NONE",
+                         "licenseConcluded": "NOASSERTION"}),
+        );
+
+        let report = validate(&doc).unwrap();
+        assert_eq!(
+            error_fields(&report),
+            vec![
+                "/packages/0/downloadLocation",
+                "/packages/0/licenseConcluded",
+                "/packages/1/name",
+            ]
+        );
     }
 }
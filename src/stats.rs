@@ -0,0 +1,166 @@
+//! License health metrics for the `stats` subcommand
+//!
+//! Metrics are computed from already analyzed dependencies so they can be tested without a
+//! project on disk; see `reporter::print_stats` for the output.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use crate::licenses::{LicenseInfo, OsiStatus};
+
+/// Ecosystem name for dependencies that do not record one
+const UNKNOWN_ECOSYSTEM: &str = "unknown";
+
+/// Totals and percentages over a set of dependencies
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatsSummary {
+    pub dependencies: usize,
+    pub distinct_licenses: usize,
+    pub osi_approved_percent: f64,
+    pub restrictive_percent: f64,
+    pub unknown_license_percent: f64,
+}
+
+/// License health of a project, overall and per ecosystem
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(flatten)]
+    pub summary: StatsSummary,
+    /// Keyed by ecosystem name, e.g. `npm` or `pypi`
+    pub ecosystems: BTreeMap<String, StatsSummary>,
+}
+
+/// Compute license health metrics; an empty slice yields all zeros
+pub fn compute_stats(license_info: &[LicenseInfo]) -> Stats {
+    let mut by_ecosystem: BTreeMap<String, Vec<&LicenseInfo>> = BTreeMap::new();
+    for info in license_info {
+        let ecosystem = if info.ecosystem.is_empty() {
+            UNKNOWN_ECOSYSTEM
+        } else {
+            &info.ecosystem
+        };
+        by_ecosystem
+            .entry(ecosystem.to_string())
+            .or_default()
+            .push(info);
+    }
+
+    Stats {
+        summary: summarize(license_info.iter()),
+        ecosystems: by_ecosystem
+            .into_iter()
+            .map(|(ecosystem, deps)| (ecosystem, summarize(deps.into_iter())))
+            .collect(),
+    }
+}
+
+fn summarize<'a>(deps: impl Iterator<Item = &'a LicenseInfo>) -> StatsSummary {
+    let mut dependencies = 0;
+    let mut licenses = HashSet::new();
+    let mut osi_approved = 0;
+    let mut restrictive = 0;
+    let mut unknown = 0;
+
+    for info in deps {
+        dependencies += 1;
+        licenses.insert(info.get_license());
+        if info.osi_status == OsiStatus::Approved {
+            osi_approved += 1;
+        }
+        if info.is_restrictive {
+            restrictive += 1;
+        }
+        if info.has_unknown_license() {
+            unknown += 1;
+        }
+    }
+
+    StatsSummary {
+        dependencies,
+        distinct_licenses: licenses.len(),
+        osi_approved_percent: percent(osi_approved, dependencies),
+        restrictive_percent: percent(restrictive, dependencies),
+        unknown_license_percent: percent(unknown, dependencies),
+    }
+}
+
+/// Percentage rounded to two decimals, `0.0` when there is nothing to count
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (count as f64 * 10_000.0 / total as f64).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(
+        name: &str,
+        license: Option<&str>,
+        osi_status: OsiStatus,
+        is_restrictive: bool,
+        ecosystem: &str,
+    ) -> LicenseInfo {
+        LicenseInfo {
+            is_restrictive,
+            osi_status,
+            ecosystem: ecosystem.to_string(),
+            ..LicenseInfo::new(name, "1.0.0", license.map(str::to_string))
+        }
+    }
+
+    #[test]
+    fn test_compute_stats_mixed_dependencies() {
+        let data = vec![
+            dependency("a", Some("MIT"), OsiStatus::Approved, false, "npm"),
+            dependency("b", Some("MIT"), OsiStatus::Approved, false, "npm"),
+            dependency("c", Some("GPL-3.0-only"), OsiStatus::Approved, true, "npm"),
+            dependency("d", None, OsiStatus::Unknown, false, "pypi"),
+            dependency(
+                "e",
+                Some("Unknown license for e: 1.0.0"),
+                OsiStatus::Unknown,
+                false,
+                "pypi",
+            ),
+            dependency("f", Some("Apache-2.0"), OsiStatus::Approved, false, ""),
+        ];
+
+        let stats = compute_stats(&data);
+        assert_eq!(stats.summary.dependencies, 6);
+        assert_eq!(stats.summary.distinct_licenses, 5);
+        assert_eq!(stats.summary.osi_approved_percent, 66.67);
+        assert_eq!(stats.summary.restrictive_percent, 16.67);
+        assert_eq!(stats.summary.unknown_license_percent, 33.33);
+
+        let npm = &stats.ecosystems["npm"];
+        assert_eq!(npm.dependencies, 3);
+        assert_eq!(npm.distinct_licenses, 2);
+        assert_eq!(npm.osi_approved_percent, 100.0);
+        assert_eq!(npm.restrictive_percent, 33.33);
+
+        let pypi = &stats.ecosystems["pypi"];
+        assert_eq!(pypi.dependencies, 2);
+        assert_eq!(pypi.unknown_license_percent, 100.0);
+
+        assert_eq!(stats.ecosystems["unknown"].dependencies, 1);
+        assert_eq!(stats.ecosystems.len(), 3);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["dependencies"], 6);
+        assert_eq!(json["ecosystems"]["npm"]["restrictive_percent"], 33.33);
+    }
+
+    #[test]
+    fn test_compute_stats_empty_project() {
+        let stats = compute_stats(&[]);
+        assert_eq!(stats, Stats::default());
+        assert_eq!(stats.summary.osi_approved_percent, 0.0);
+        assert_eq!(stats.summary.restrictive_percent, 0.0);
+        assert_eq!(stats.summary.unknown_license_percent, 0.0);
+        assert!(stats.ecosystems.is_empty());
+    }
+}
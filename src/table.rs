@@ -1,28 +1,36 @@
-use crate::debug::{log, log_debug, LogLevel};
+use crate::debug::{log, log_debug, FeludaError, FeludaResult, LogLevel};
 use crate::licenses::{LicenseCompatibility, LicenseInfo};
 use color_eyre::Result;
 use ratatui::{
     crossterm::event::{self, Event, KeyCode, KeyEventKind},
     layout::{Constraint, Layout, Margin, Rect},
     style::{self, Color, Modifier, Style, Stylize},
-    text::Text,
+    text::{Line, Span, Text},
     widgets::{
         Block, BorderType, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Table, TableState,
+        ScrollbarState, Table, TableState, Wrap,
     },
     DefaultTerminal, Frame,
 };
+use std::path::Path;
+use std::time::{Duration, Instant};
 use style::palette::tailwind;
 use unicode_width::UnicodeWidthStr;
 
 const INFO_TEXT: [&str; 3] = [
     "(Esc) quit | (↑) move up | (↓) move down | (←) move left | (→) move right",
-    "(r) restrictive | (i) incompatible | (c) compatible | (a) osi-approved | (n) osi-not-approved | (u) osi-unknown | (x) clear filters | (s) sort mode",
+    "(r) restrictive | (i) incompatible | (c) compatible | (a) osi-approved | (n) osi-not-approved | (u) osi-unknown | (x) clear filters | (s) sort mode | (/) search | (e) export | (d/Enter) details",
     "(In sort mode: ←→ select column, Enter toggle sort, Esc/q exit sort)",
 ];
 
 const ITEM_HEIGHT: usize = 4;
 
+/// File the `e` key writes the current view to, relative to the working directory
+const EXPORT_FILE_NAME: &str = "feluda-report.json";
+
+/// How long a status bar message stays visible
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(3);
+
 // ============================================================================
 // KEY BINDINGS CONFIGURATION
 // ============================================================================
@@ -62,6 +70,16 @@ pub mod keybindings_normal {
 
     /// Sort mode
     pub const ENTER_SORT_MODE: char = 's';
+
+    /// Search mode
+    pub const ENTER_SEARCH_MODE: char = '/';
+
+    /// Export the current view to JSON
+    pub const EXPORT_VIEW: char = 'e';
+
+    /// Toggle the detail pane for the selected row
+    pub const TOGGLE_DETAIL: &[KeyCode] = &[KeyCode::Enter];
+    pub const TOGGLE_DETAIL_CHAR: char = 'd';
 }
 
 /// Sort mode key bindings
@@ -84,6 +102,21 @@ pub mod keybindings_sort {
     pub const EXIT_SORT_MODE_CHAR: char = 'q';
 }
 
+/// Search mode key bindings
+#[allow(dead_code)]
+pub mod keybindings_search {
+    use ratatui::crossterm::event::KeyCode;
+
+    /// Keep the search and return to normal mode
+    pub const CONFIRM_SEARCH: KeyCode = KeyCode::Enter;
+
+    /// Clear the search and return to normal mode
+    pub const CLEAR_SEARCH: KeyCode = KeyCode::Esc;
+
+    /// Delete the last character of the search
+    pub const DELETE_CHAR: KeyCode = KeyCode::Backspace;
+}
+
 const TABLE_COLOUR: tailwind::Palette = tailwind::RED;
 
 #[derive(Debug, Clone, Default)]
@@ -156,6 +189,62 @@ impl FilterState {
     }
 }
 
+/// Case-insensitive substring match of the search text against name and license
+fn matches_search(item: &LicenseInfo, search: &str) -> bool {
+    if search.is_empty() {
+        return true;
+    }
+    let search = search.to_lowercase();
+    item.name.to_lowercase().contains(&search)
+        || item.get_license().to_lowercase().contains(&search)
+}
+
+/// Indices of the items that pass both the toggle filters and the search text
+fn filter_indices(items: &[LicenseInfo], filters: &FilterState, search: &str) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| filters.matches(item) && matches_search(item, search))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Write the given rows as pretty-printed JSON, returning how many were written
+fn export_view(items: &[&LicenseInfo], path: &Path) -> FeludaResult<usize> {
+    let json = serde_json::to_string_pretty(items)
+        .map_err(|e| FeludaError::Serialization(format!("Failed to serialize view: {e}")))?;
+    std::fs::write(path, json)?;
+    Ok(items.len())
+}
+
+/// Full, untruncated description of a dependency for the detail pane
+fn format_dependency_detail(info: &LicenseInfo) -> Vec<Line<'static>> {
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{label}: "),
+                Style::new().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![
+        field("Name", info.name.clone()),
+        field("Version", info.version.clone()),
+        field("License", info.get_license()),
+        field("Restrictive", info.is_restrictive.to_string()),
+        field("Compatibility", info.compatibility.to_string()),
+        field("OSI Status", info.osi_status.to_string()),
+    ];
+
+    if !info.dependencies.is_empty() {
+        lines.push(field("Dependencies", info.dependencies.join(", ")));
+    }
+
+    lines
+}
+
 struct TableColors {
     buffer_bg: Color,
     header_bg: Color,
@@ -247,6 +336,7 @@ impl SortColumn {
 pub enum AppMode {
     Normal,
     Sorting,
+    Searching,
 }
 
 pub struct App {
@@ -257,10 +347,14 @@ pub struct App {
     colors: TableColors,
     project_license: Option<String>,
     filters: FilterState,
+    filter: String,               // Incremental search text
+    filtered_indices: Vec<usize>, // Indices into `items` of the rows currently shown
     sort_column: Option<SortColumn>,
     sort_direction: SortDirection,
     mode: AppMode,
     sort_column_selection: usize, // Index in SortColumn::all()
+    status_message: Option<(String, Instant)>,
+    show_detail: bool,
 }
 
 impl App {
@@ -278,25 +372,31 @@ impl App {
             longest_item_lens: constraint_len_calculator(&data_vec),
             scroll_state: ScrollbarState::new((data_vec.len().saturating_sub(1)) * ITEM_HEIGHT),
             colors: TableColors::new(&TABLE_COLOUR),
+            filtered_indices: (0..data_vec.len()).collect(),
             items: data_vec,
             project_license,
             filters: FilterState::default(),
+            filter: String::new(),
             sort_column: None,
             sort_direction: SortDirection::Ascending,
             mode: AppMode::Normal,
             sort_column_selection: 0,
+            status_message: None,
+            show_detail: false,
         }
     }
 
     fn get_filtered_items(&self) -> Vec<&LicenseInfo> {
-        self.items
+        self.filtered_indices
             .iter()
-            .filter(|item| self.filters.matches(item))
+            .map(|&index| &self.items[index])
             .collect()
     }
 
+    /// Recompute the visible rows after a filter, search or sort change
     fn update_scroll_state(&mut self) {
-        let filtered_count = self.get_filtered_items().len();
+        self.filtered_indices = filter_indices(&self.items, &self.filters, &self.filter);
+        let filtered_count = self.filtered_indices.len();
         self.scroll_state = ScrollbarState::new((filtered_count.saturating_sub(1)) * ITEM_HEIGHT);
     }
 
@@ -420,6 +520,94 @@ impl App {
         self.state.select(Some(0));
     }
 
+    /// Write the currently filtered and sorted rows to `path`, reporting the result in the status bar
+    pub fn export_current_view(&mut self, path: &Path) {
+        let message = match export_view(&self.get_filtered_items(), path) {
+            Ok(count) => {
+                log(
+                    LogLevel::Info,
+                    &format!("Exported {count} rows to {}", path.display()),
+                );
+                format!("Exported {count} dependencies to {}", path.display())
+            }
+            Err(err) => {
+                log(
+                    LogLevel::Error,
+                    &format!("Failed to export to {}: {err}", path.display()),
+                );
+                format!("Export failed: {err}")
+            }
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// The status bar message, if it has not expired yet
+    fn current_status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < STATUS_MESSAGE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Show or hide the detail pane for the selected row
+    pub fn toggle_detail(&mut self) {
+        self.show_detail = !self.show_detail;
+        log(
+            LogLevel::Info,
+            &format!("Detail pane: {}", self.show_detail),
+        );
+    }
+
+    /// The dependency under the cursor in the current view
+    fn selected_item(&self) -> Option<&LicenseInfo> {
+        let row = self.state.selected()?;
+        self.filtered_indices
+            .get(row)
+            .map(|&index| &self.items[index])
+    }
+
+    /// Enter search mode
+    pub fn enter_search_mode(&mut self) {
+        self.mode = AppMode::Searching;
+        log(LogLevel::Info, "Entered search mode");
+    }
+
+    /// Leave search mode, keeping the current search text
+    pub fn confirm_search(&mut self) {
+        self.mode = AppMode::Normal;
+        log(
+            LogLevel::Info,
+            &format!("Search confirmed: {:?}", self.filter),
+        );
+    }
+
+    /// Clear the search text and leave search mode
+    pub fn clear_search(&mut self) {
+        self.mode = AppMode::Normal;
+        self.set_search(String::new());
+    }
+
+    /// Append a character to the search text
+    pub fn push_search_char(&mut self, c: char) {
+        let mut search = std::mem::take(&mut self.filter);
+        search.push(c);
+        self.set_search(search);
+    }
+
+    /// Remove the last character of the search text
+    pub fn pop_search_char(&mut self) {
+        let mut search = std::mem::take(&mut self.filter);
+        search.pop();
+        self.set_search(search);
+    }
+
+    fn set_search(&mut self, search: String) {
+        self.filter = search;
+        log(LogLevel::Info, &format!("Search: {:?}", self.filter));
+        self.update_scroll_state();
+        self.state.select(Some(0));
+    }
+
     /// Enter sort mode
     pub fn enter_sort_mode(&mut self) {
         self.mode = AppMode::Sorting;
@@ -587,9 +775,8 @@ impl App {
             }
 
             // Reset selection to top when sorting
+            self.update_scroll_state();
             self.state.select(Some(0));
-            self.scroll_state =
-                ScrollbarState::new((self.items.len().saturating_sub(1)) * ITEM_HEIGHT);
         }
     }
 
@@ -604,11 +791,18 @@ impl App {
             // Render the current state
             terminal.draw(|frame| self.draw(frame))?;
 
+            // Poll so transient status messages expire without a key press
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+
             // Handle input events
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
                     match self.mode {
                         AppMode::Normal => match key.code {
+                            // Esc clears an active search before it quits
+                            KeyCode::Esc if !self.filter.is_empty() => self.clear_search(),
                             // Quit
                             KeyCode::Esc => {
                                 log(LogLevel::Info, "Quitting TUI application");
@@ -663,6 +857,29 @@ impl App {
                             KeyCode::Char(c) if c == keybindings_normal::ENTER_SORT_MODE => {
                                 self.enter_sort_mode()
                             }
+                            // Search mode
+                            KeyCode::Char(c) if c == keybindings_normal::ENTER_SEARCH_MODE => {
+                                self.enter_search_mode()
+                            }
+                            // Export
+                            KeyCode::Char(c) if c == keybindings_normal::EXPORT_VIEW => {
+                                self.export_current_view(Path::new(EXPORT_FILE_NAME))
+                            }
+                            // Detail pane
+                            KeyCode::Enter => self.toggle_detail(),
+                            KeyCode::Char(c) if c == keybindings_normal::TOGGLE_DETAIL_CHAR => {
+                                self.toggle_detail()
+                            }
+                            _ => {}
+                        },
+                        AppMode::Searching => match key.code {
+                            keybindings_search::CONFIRM_SEARCH => self.confirm_search(),
+                            keybindings_search::CLEAR_SEARCH => self.clear_search(),
+                            keybindings_search::DELETE_CHAR => self.pop_search_char(),
+                            // Arrows keep working so results can be browsed while typing
+                            KeyCode::Down => self.next_row(),
+                            KeyCode::Up => self.previous_row(),
+                            KeyCode::Char(c) => self.push_search_char(c),
                             _ => {}
                         },
                         AppMode::Sorting => match key.code {
@@ -690,9 +907,14 @@ impl App {
         }
     }
 
+    /// Whether the filter bar is shown above the table
+    fn show_filter_bar(&self) -> bool {
+        self.filters.is_any_active() || !self.filter.is_empty() || self.mode == AppMode::Searching
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
-        // Add space for filter bar if filters are active
-        let vertical = if self.filters.is_any_active() {
+        // Add space for filter bar if filters or a search are active
+        let vertical = if self.show_filter_bar() {
             Layout::vertical([
                 Constraint::Length(3),
                 Constraint::Min(5),
@@ -709,11 +931,23 @@ impl App {
 
         self.set_colors();
 
-        if self.filters.is_any_active() {
+        if self.show_filter_bar() {
             self.render_filter_bar(frame, rects[0]);
         }
-        self.render_table(frame, rects[1]);
-        self.render_scrollbar(frame, rects[1]);
+
+        // Split the list area to make room for the detail pane
+        let list_area = if self.show_detail {
+            let columns =
+                Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(rects[1]);
+            self.render_detail_pane(frame, columns[1]);
+            columns[0]
+        } else {
+            rects[1]
+        };
+
+        self.render_table(frame, list_area);
+        self.render_scrollbar(frame, list_area);
         self.render_footer(frame, rects[2]);
     }
 
@@ -865,14 +1099,26 @@ impl App {
             filter_tags.push("OSI-Unknown");
         }
 
-        let filter_text = format!("Active Filters: {}", filter_tags.join(", "));
-        let filtered_count = self.get_filtered_items().len();
-        let filter_info = format!(
-            "{} | Showing {} of {} licenses",
-            filter_text,
-            filtered_count,
-            self.items.len()
-        );
+        let filtered_count = self.filtered_indices.len();
+        let filter_info = if self.mode == AppMode::Searching || !self.filter.is_empty() {
+            let cursor = if self.mode == AppMode::Searching {
+                "_"
+            } else {
+                ""
+            };
+            let mut info = format!("Search: /{}{cursor}", self.filter);
+            if !filter_tags.is_empty() {
+                info.push_str(&format!(" | Active Filters: {}", filter_tags.join(", ")));
+            }
+            format!("{info} | matched {filtered_count} of {}", self.items.len())
+        } else {
+            format!(
+                "Active Filters: {} | Showing {} of {} licenses",
+                filter_tags.join(", "),
+                filtered_count,
+                self.items.len()
+            )
+        };
 
         let filter_paragraph = Paragraph::new(Text::from(filter_info))
             .style(
@@ -890,6 +1136,28 @@ impl App {
         frame.render_widget(filter_paragraph, area);
     }
 
+    fn render_detail_pane(&self, frame: &mut Frame, area: Rect) {
+        let lines = match self.selected_item() {
+            Some(info) => format_dependency_detail(info),
+            None => vec![Line::from("No dependency selected")],
+        };
+
+        let detail = Paragraph::new(Text::from(lines))
+            .style(
+                Style::new()
+                    .fg(self.colors.row_fg)
+                    .bg(self.colors.buffer_bg),
+            )
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title(" Details ")
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::new().fg(self.colors.footer_border_color)),
+            );
+        frame.render_widget(detail, area);
+    }
+
     fn render_scrollbar(&mut self, frame: &mut Frame, area: Rect) {
         frame.render_stateful_widget(
             Scrollbar::default()
@@ -961,7 +1229,10 @@ impl App {
                 "Project: Unknown".to_string()
             };
 
-            let footer_text = format!("{license_text} | {}{sort_indicator}", INFO_TEXT[0]);
+            let footer_text = match self.current_status_message() {
+                Some(message) => format!("{license_text} | {message}"),
+                None => format!("{license_text} | {}{sort_indicator}", INFO_TEXT[0]),
+            };
             let help_text = format!("\n{}\n{}", INFO_TEXT[1], INFO_TEXT[2]);
 
             let info_footer = Paragraph::new(Text::from(format!("{footer_text}{help_text}")))
@@ -1042,12 +1313,9 @@ mod tests {
     #[test]
     fn test_app_new() {
         let test_data = vec![LicenseInfo {
-            name: "test_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let app = App::new(test_data.clone(), Some("MIT".to_string()));
@@ -1074,28 +1342,20 @@ mod tests {
     fn test_app_navigation() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "2.0.0", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "package3".to_string(),
-                version: "3.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package3", "3.0.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1131,12 +1391,9 @@ mod tests {
     #[test]
     fn test_app_navigation_single_item() {
         let test_data = vec![LicenseInfo {
-            name: "single_package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("single_package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let mut app = App::new(test_data, None);
@@ -1168,20 +1425,19 @@ mod tests {
     fn test_constraint_len_calculator() {
         let test_data = vec![
             LicenseInfo {
-                name: "very_long_package_name_that_exceeds_normal_length".to_string(),
-                version: "1.0.0-beta.1+build.123".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new(
+                    "very_long_package_name_that_exceeds_normal_length",
+                    "1.0.0-beta.1+build.123",
+                    Some("MIT".to_string()),
+                )
             },
             LicenseInfo {
-                name: "short".to_string(),
-                version: "2.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("short", "2.0", Some("Apache-2.0".to_string()))
             },
         ];
 
@@ -1214,12 +1470,13 @@ mod tests {
     #[test]
     fn test_constraint_len_calculator_unicode() {
         let test_data = vec![LicenseInfo {
-            name: "package_with_émojis_🚀_and_ünïcödé".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new(
+                "package_with_émojis_🚀_and_ünïcödé",
+                "1.0.0",
+                Some("MIT".to_string()),
+            )
         }];
 
         let (name_len, _, _, _, _, _) = constraint_len_calculator(&test_data);
@@ -1231,29 +1488,17 @@ mod tests {
     fn test_constraint_len_calculator_all_compatibility_types() {
         let test_data = vec![
             LicenseInfo {
-                name: "compatible".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("compatible", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "incompatible".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("incompatible", "1.0.0", Some("GPL-3.0".to_string()))
             },
-            LicenseInfo {
-                name: "unknown".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("Custom".to_string()),
-                is_restrictive: false,
-                compatibility: LicenseCompatibility::Unknown,
-                osi_status: crate::licenses::OsiStatus::Unknown,
-            },
+            LicenseInfo::new("unknown", "1.0.0", Some("Custom".to_string())),
         ];
 
         let (_, _, _, _, compatibility_len, _) = constraint_len_calculator(&test_data);
@@ -1265,20 +1510,15 @@ mod tests {
     fn test_constraint_len_calculator_restrictive_values() {
         let test_data = vec![
             LicenseInfo {
-                name: "package".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: true, // true
+                is_restrictive: true,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("Apache".to_string()),
-                is_restrictive: false, // false
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "1.0.0", Some("Apache".to_string()))
             },
         ];
 
@@ -1311,20 +1551,19 @@ mod tests {
     fn test_app_longest_item_lens_calculation() {
         let test_data = vec![
             LicenseInfo {
-                name: "short".to_string(),
-                version: "1.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("short", "1.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "much_longer_name".to_string(),
-                version: "1.0.0-beta".to_string(),
-                license: Some("Apache-2.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new(
+                    "much_longer_name",
+                    "1.0.0-beta",
+                    Some("Apache-2.0".to_string()),
+                )
             },
         ];
 
@@ -1341,28 +1580,20 @@ mod tests {
     fn test_sort_by_name() {
         let test_data = vec![
             LicenseInfo {
-                name: "zebra".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("zebra", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "apple".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("apple", "2.0.0", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "banana".to_string(),
-                version: "3.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("banana", "3.0.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1383,20 +1614,14 @@ mod tests {
     fn test_sort_by_name_descending() {
         let test_data = vec![
             LicenseInfo {
-                name: "apple".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("apple", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "zebra".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("zebra", "2.0.0", Some("Apache-2.0".to_string()))
             },
         ];
 
@@ -1415,20 +1640,15 @@ mod tests {
     fn test_sort_by_restrictive() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "2.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "2.0.0", Some("Apache-2.0".to_string()))
             },
         ];
 
@@ -1449,12 +1669,9 @@ mod tests {
     #[test]
     fn test_sort_mode_navigation() {
         let test_data = vec![LicenseInfo {
-            name: "test".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test", "1.0.0", Some("MIT".to_string()))
         }];
 
         let mut app = App::new(test_data, None);
@@ -1477,12 +1694,9 @@ mod tests {
     #[test]
     fn test_sort_direction_toggle() {
         let test_data = vec![LicenseInfo {
-            name: "package".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("package", "1.0.0", Some("MIT".to_string()))
         }];
 
         let mut app = App::new(test_data, None);
@@ -1507,20 +1721,14 @@ mod tests {
     fn test_sort_column_change() {
         let test_data = vec![
             LicenseInfo {
-                name: "zebra".to_string(),
-                version: "1.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("zebra", "1.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "apple".to_string(),
-                version: "5.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("apple", "5.0.0", Some("Apache-2.0".to_string()))
             },
         ];
 
@@ -1543,12 +1751,9 @@ mod tests {
     #[test]
     fn test_initial_sort_state() {
         let test_data = vec![LicenseInfo {
-            name: "test".to_string(),
-            version: "1.0.0".to_string(),
-            license: Some("MIT".to_string()),
-            is_restrictive: false,
             compatibility: LicenseCompatibility::Compatible,
             osi_status: crate::licenses::OsiStatus::Approved,
+            ..LicenseInfo::new("test", "1.0.0", Some("MIT".to_string()))
         }];
 
         let app = App::new(test_data, None);
@@ -1563,28 +1768,20 @@ mod tests {
     fn test_sort_by_version_with_v_prefix() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "v3.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "v3.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "v1.0.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "v1.0.0", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "package3".to_string(),
-                version: "v2.5.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package3", "v2.5.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1606,28 +1803,20 @@ mod tests {
     fn test_sort_by_version_mixed_prefix() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "3.0.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "3.0.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "v1.5.0".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "v1.5.0", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "package3".to_string(),
-                version: "v2.0.0".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package3", "v2.0.0", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1647,28 +1836,20 @@ mod tests {
     fn test_sort_by_version_descending() {
         let test_data = vec![
             LicenseInfo {
-                name: "package1".to_string(),
-                version: "v10.14.0".to_string(),
-                license: Some("MIT".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package1", "v10.14.0", Some("MIT".to_string()))
             },
             LicenseInfo {
-                name: "package2".to_string(),
-                version: "0.14".to_string(),
-                license: Some("Apache-2.0".to_string()),
-                is_restrictive: false,
                 compatibility: LicenseCompatibility::Compatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package2", "0.14", Some("Apache-2.0".to_string()))
             },
             LicenseInfo {
-                name: "package3".to_string(),
-                version: "2015.7".to_string(),
-                license: Some("GPL-3.0".to_string()),
                 is_restrictive: true,
                 compatibility: LicenseCompatibility::Incompatible,
                 osi_status: crate::licenses::OsiStatus::Approved,
+                ..LicenseInfo::new("package3", "2015.7", Some("GPL-3.0".to_string()))
             },
         ];
 
@@ -1690,4 +1871,180 @@ mod tests {
         assert_eq!(app.items[2].version, "v10.14.0");
         assert_eq!(app.sort_direction, SortDirection::Descending);
     }
+
+    fn search_data() -> Vec<LicenseInfo> {
+        [
+            ("serde", Some("MIT OR Apache-2.0")),
+            ("Tokio", Some("MIT")),
+            ("readline", Some("GPL-3.0")),
+            ("mystery", None),
+        ]
+        .into_iter()
+        .map(|(name, license)| LicenseInfo {
+            is_restrictive: license == Some("GPL-3.0"),
+            ..LicenseInfo::new(
+                name.to_string(),
+                "1.0.0".to_string(),
+                license.map(str::to_string),
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_filter_indices_matches_name_case_insensitively() {
+        let items = search_data();
+        let filters = FilterState::default();
+
+        assert_eq!(filter_indices(&items, &filters, "tok"), vec![1]);
+        assert_eq!(filter_indices(&items, &filters, "SERDE"), vec![0]);
+        assert_eq!(filter_indices(&items, &filters, ""), vec![0, 1, 2, 3]);
+        assert!(filter_indices(&items, &filters, "nothing-matches").is_empty());
+    }
+
+    #[test]
+    fn test_filter_indices_matches_license_case_insensitively() {
+        let items = search_data();
+        let filters = FilterState::default();
+
+        assert_eq!(filter_indices(&items, &filters, "mit"), vec![0, 1]);
+        assert_eq!(filter_indices(&items, &filters, "apache"), vec![0]);
+        // Missing licenses are searchable by their display text
+        assert_eq!(filter_indices(&items, &filters, "no license"), vec![3]);
+    }
+
+    #[test]
+    fn test_filter_indices_combines_with_toggle_filters() {
+        let items = search_data();
+        let filters = FilterState {
+            show_restrictive_only: true,
+            ..Default::default()
+        };
+
+        assert_eq!(filter_indices(&items, &filters, "gpl"), vec![2]);
+        assert!(filter_indices(&items, &filters, "mit").is_empty());
+    }
+
+    #[test]
+    fn test_search_updates_filtered_view() {
+        let mut app = App::new(search_data(), None);
+        app.enter_search_mode();
+        assert_eq!(app.mode, AppMode::Searching);
+
+        for c in "MIT".chars() {
+            app.push_search_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+        assert_eq!(app.get_filtered_items()[1].name, "Tokio");
+
+        app.pop_search_char();
+        app.pop_search_char();
+        assert_eq!(app.filter, "M");
+
+        app.confirm_search();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.filtered_indices, vec![0, 1, 3]);
+
+        app.clear_search();
+        assert!(app.filter.is_empty());
+        assert_eq!(app.filtered_indices.len(), 4);
+    }
+
+    #[test]
+    fn test_export_view_writes_filtered_rows() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(EXPORT_FILE_NAME);
+
+        let mut app = App::new(search_data(), None);
+        for c in "mit".chars() {
+            app.push_search_char(c);
+        }
+        app.export_current_view(&path);
+
+        let exported: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0]["name"], "serde");
+        assert_eq!(exported[1]["name"], "Tokio");
+        assert_eq!(exported[1]["license"], "MIT");
+        assert!(app
+            .current_status_message()
+            .unwrap()
+            .starts_with("Exported 2"));
+    }
+
+    #[test]
+    fn test_export_view_reports_write_errors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing-dir").join(EXPORT_FILE_NAME);
+
+        let items = search_data();
+        let rows: Vec<&LicenseInfo> = items.iter().collect();
+        assert!(export_view(&rows, &path).is_err());
+
+        let mut app = App::new(items.clone(), None);
+        app.export_current_view(&path);
+        assert!(app
+            .current_status_message()
+            .unwrap()
+            .starts_with("Export failed"));
+    }
+
+    #[test]
+    fn test_format_dependency_detail() {
+        let info = LicenseInfo {
+            compatibility: LicenseCompatibility::Compatible,
+            osi_status: crate::licenses::OsiStatus::Approved,
+            dependencies: vec!["dep-a".to_string(), "dep-b".to_string()],
+            ..LicenseInfo::new(
+                "very-long-package-name",
+                "2.1.0",
+                Some("(MIT OR Apache-2.0) AND Unicode-DFS-2016".to_string()),
+            )
+        };
+
+        let lines: Vec<String> = format_dependency_detail(&info)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "Name: very-long-package-name",
+                "Version: 2.1.0",
+                "License: (MIT OR Apache-2.0) AND Unicode-DFS-2016",
+                "Restrictive: false",
+                "Compatibility: compatible",
+                "OSI Status: approved",
+                "Dependencies: dep-a, dep-b",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_dependency_detail_without_license_or_dependencies() {
+        let info = LicenseInfo {
+            is_restrictive: true,
+            ..LicenseInfo::new("mystery", "0.1.0", None)
+        };
+
+        let lines = format_dependency_detail(&info);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[2].to_string(), "License: No License");
+        assert_eq!(lines[3].to_string(), "Restrictive: true");
+    }
+
+    #[test]
+    fn test_toggle_detail_follows_filtered_selection() {
+        let mut app = App::new(search_data(), None);
+        assert!(!app.show_detail);
+        app.toggle_detail();
+        assert!(app.show_detail);
+
+        for c in "gpl".chars() {
+            app.push_search_char(c);
+        }
+        assert_eq!(app.selected_item().unwrap().name, "readline");
+    }
 }
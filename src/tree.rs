@@ -0,0 +1,323 @@
+//! Dependency tree for the `tree` subcommand and dependency paths for `--why`
+//!
+//! The tree is built from the dependency names analyzers record in
+//! `LicenseInfo::dependencies` (currently Node.js); dependencies of other ecosystems have no
+//! edges and are listed flat.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use colored::Colorize;
+
+use crate::licenses::LicenseInfo;
+
+/// Marker for a package whose dependencies were already printed higher up
+const REPEATED_MARKER: &str = " (*)";
+
+/// Most paths `--why` lists, so densely connected graphs stay readable
+const MAX_WHY_PATHS: usize = 50;
+
+/// `name@version (license)`, with restrictive licenses in red when `color` is set
+fn label(info: &LicenseInfo, color: bool) -> String {
+    let license = format!("({})", info.get_license());
+    let license = if color && info.is_restrictive {
+        license.red().to_string()
+    } else {
+        license
+    };
+    format!("{}@{} {license}", info.name, info.version)
+}
+
+/// Dependency graph over analyzed packages, looked up by name
+struct Graph<'a> {
+    packages: HashMap<&'a str, &'a LicenseInfo>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(data: &'a [LicenseInfo]) -> Self {
+        let mut packages = HashMap::new();
+        for info in data {
+            packages.entry(info.name.as_str()).or_insert(info);
+        }
+        Self { packages }
+    }
+
+    /// Dependencies of `info` that were analyzed, sorted by name
+    fn children(&self, info: &LicenseInfo) -> Vec<&'a LicenseInfo> {
+        let mut children: Vec<&LicenseInfo> = info
+            .dependencies
+            .iter()
+            .filter_map(|name| self.packages.get(name.as_str()).copied())
+            .collect();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        children.dedup_by(|a, b| a.name == b.name);
+        children
+    }
+}
+
+/// Render the packages nothing else depends on, each with its transitive dependencies
+/// below it; a package already expanded elsewhere is marked `(*)` instead of repeated
+pub fn render_tree(data: &[LicenseInfo], color: bool) -> String {
+    let graph = Graph::new(data);
+    let depended_on: HashSet<&str> = data
+        .iter()
+        .flat_map(|info| graph.children(info))
+        .map(|info| info.name.as_str())
+        .collect();
+
+    let mut roots: Vec<&LicenseInfo> = data
+        .iter()
+        .filter(|info| !depended_on.contains(info.name.as_str()))
+        .collect();
+    // Every package is part of a cycle; start from all of them
+    if roots.is_empty() {
+        roots = data.iter().collect();
+    }
+    roots.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+    let mut output = String::new();
+    let mut expanded = HashSet::new();
+    for root in roots {
+        output.push_str(&label(root, color));
+        if expanded.insert(root.name.as_str()) {
+            output.push('\n');
+            render_children(&graph, root, "", color, &mut expanded, &mut output);
+        } else {
+            output.push_str(REPEATED_MARKER);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn render_children<'a>(
+    graph: &Graph<'a>,
+    info: &LicenseInfo,
+    prefix: &str,
+    color: bool,
+    expanded: &mut HashSet<&'a str>,
+    output: &mut String,
+) {
+    let children = graph.children(info);
+    let count = children.len();
+    for (i, child) in children.into_iter().enumerate() {
+        let last = i + 1 == count;
+        output.push_str(prefix);
+        output.push_str(if last { "└── " } else { "├── " });
+        output.push_str(&label(child, color));
+
+        if expanded.insert(child.name.as_str()) {
+            output.push('\n');
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            render_children(graph, child, &prefix, color, expanded, output);
+        } else {
+            output.push_str(REPEATED_MARKER);
+            output.push('\n');
+        }
+    }
+}
+
+/// Dependency paths from a top-level package down to `target`, shortest first, or `None`
+/// when no analyzed package has that name
+///
+/// A package is top-level when nothing depends on it or its manifest declares it directly.
+/// A target that is itself top-level yields the single path `[target]`.
+pub fn why_paths(data: &[LicenseInfo], target: &str) -> Option<Vec<Vec<String>>> {
+    let graph = Graph::new(data);
+    graph.packages.get(target)?;
+
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (&name, &info) in &graph.packages {
+        for child in graph.children(info) {
+            parents.entry(child.name.as_str()).or_default().push(name);
+        }
+    }
+    for names in parents.values_mut() {
+        names.sort_unstable();
+    }
+
+    // Walk up from the target; each queued path is stored target first
+    let mut paths = Vec::new();
+    let mut queue = VecDeque::from([vec![target]]);
+    while let Some(path) = queue.pop_front() {
+        let head = path[path.len() - 1];
+        let head_parents = parents.get(head).map(Vec::as_slice).unwrap_or_default();
+        if head_parents.is_empty() || graph.packages[head].is_direct == Some(true) {
+            paths.push(path.iter().rev().map(|name| name.to_string()).collect());
+            if paths.len() == MAX_WHY_PATHS {
+                break;
+            }
+            continue;
+        }
+
+        for parent in head_parents.iter().filter(|parent| !path.contains(parent)) {
+            let mut longer = path.clone();
+            longer.push(parent);
+            queue.push_back(longer);
+        }
+    }
+    Some(paths)
+}
+
+/// Print why `target` is a dependency, one `a -> b -> target` path per line
+pub fn print_why(data: &[LicenseInfo], target: &str) {
+    let Some(paths) = why_paths(data, target) else {
+        println!("Package '{target}' is not among the analyzed dependencies");
+        return;
+    };
+
+    if data.iter().all(|info| info.dependencies.is_empty()) {
+        println!(
+            "No dependency graph is available for these ecosystems, so it is unknown what pulls in '{target}'"
+        );
+    } else if paths.is_empty() {
+        println!("No top-level dependency leads to '{target}'");
+    } else if paths == [vec![target.to_string()]] {
+        println!("'{target}' is a direct dependency");
+    } else {
+        for path in &paths {
+            println!("{}", path.join(" -> "));
+        }
+        if paths.len() == MAX_WHY_PATHS {
+            println!("(showing the first {MAX_WHY_PATHS} paths)");
+        }
+    }
+}
+
+/// Print the dependency tree, noting when no ecosystem recorded dependency edges
+pub fn print_tree(data: &[LicenseInfo]) {
+    if data.is_empty() {
+        println!("No dependencies found");
+        return;
+    }
+
+    if data.iter().all(|info| info.dependencies.is_empty()) {
+        println!(
+            "{}",
+            "No dependency graph is available for these ecosystems, listing dependencies flat"
+                .dimmed()
+        );
+    }
+    print!("{}", render_tree(data, true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::{test_dependency, OsiStatus};
+
+    fn package(name: &str, license: &str, dependencies: &[&str]) -> LicenseInfo {
+        LicenseInfo {
+            is_restrictive: license.starts_with("GPL"),
+            osi_status: OsiStatus::Approved,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            ecosystem: "npm".to_string(),
+            ..test_dependency(name, "1.0.0", license)
+        }
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let data = vec![
+            package("web", "MIT", &["router", "logger"]),
+            package("router", "MIT", &["path-utils", "logger"]),
+            package("logger", "GPL-3.0", &["path-utils"]),
+            package("path-utils", "ISC", &[]),
+            package("standalone", "Apache-2.0", &["not-installed"]),
+        ];
+
+        assert_eq!(
+            render_tree(&data, false),
+            "standalone@1.0.0 (Apache-2.0)\n\
+             web@1.0.0 (MIT)\n\
+             ├── logger@1.0.0 (GPL-3.0)\n\
+             │   └── path-utils@1.0.0 (ISC)\n\
+             └── router@1.0.0 (MIT)\n\
+             \u{20}   ├── logger@1.0.0 (GPL-3.0) (*)\n\
+             \u{20}   └── path-utils@1.0.0 (ISC) (*)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_tree_without_edges_is_flat() {
+        let data = vec![package("b", "MIT", &[]), package("a", "GPL-2.0", &[])];
+        assert_eq!(
+            render_tree(&data, false),
+            "a@1.0.0 (GPL-2.0)\nb@1.0.0 (MIT)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_tree_breaks_cycles() {
+        let data = vec![package("a", "MIT", &["b"]), package("b", "MIT", &["a"])];
+        assert_eq!(
+            render_tree(&data, false),
+            "a@1.0.0 (MIT)\n└── b@1.0.0 (MIT)\n    └── a@1.0.0 (MIT) (*)\nb@1.0.0 (MIT) (*)\n"
+        );
+    }
+
+    #[test]
+    fn test_why_paths_finds_every_path() {
+        let data = vec![
+            package("web", "MIT", &["router", "logger"]),
+            package("router", "MIT", &["path-utils", "logger"]),
+            package("logger", "GPL-3.0", &["path-utils"]),
+            package("path-utils", "ISC", &[]),
+            package("cli", "MIT", &["logger"]),
+        ];
+        let paths = |target| {
+            why_paths(&data, target)
+                .unwrap()
+                .iter()
+                .map(|path| path.join(" -> "))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            paths("logger"),
+            ["cli -> logger", "web -> logger", "web -> router -> logger"]
+        );
+        assert_eq!(
+            paths("path-utils"),
+            [
+                "cli -> logger -> path-utils",
+                "web -> logger -> path-utils",
+                "web -> router -> path-utils",
+                "web -> router -> logger -> path-utils",
+            ]
+        );
+        assert_eq!(paths("web"), ["web"]);
+        assert_eq!(why_paths(&data, "left-pad"), None);
+    }
+
+    #[test]
+    fn test_why_paths_stop_at_direct_dependencies_and_skip_cycles() {
+        let mut data = vec![
+            package("app", "MIT", &["shared"]),
+            package("shared", "MIT", &["gpl-lib", "helper"]),
+            package("helper", "MIT", &["shared"]),
+            package("gpl-lib", "GPL-3.0", &[]),
+        ];
+        assert_eq!(
+            why_paths(&data, "gpl-lib").unwrap(),
+            [["app", "shared", "gpl-lib"]]
+        );
+
+        data[1].is_direct = Some(true);
+        assert_eq!(
+            why_paths(&data, "gpl-lib").unwrap(),
+            [["shared", "gpl-lib"]]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_restrictive_license_is_colored() {
+        colored::control::set_override(true);
+        let rendered = render_tree(&[package("gpl-lib", "GPL-3.0", &[])], true);
+        let plain = render_tree(&[package("mit-lib", "MIT", &[])], true);
+        colored::control::unset_override();
+
+        assert!(rendered.contains("\u{1b}[31m(GPL-3.0)\u{1b}[0m"));
+        assert!(!plain.contains('\u{1b}'));
+    }
+}
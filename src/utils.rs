@@ -1,9 +1,46 @@
 use crate::cli::Cli;
 use crate::debug::{log, FeludaError, FeludaResult, LogLevel};
 use git2::Cred;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Upper bound for `--threads`, well above any realistic core count
+pub const MAX_THREADS: usize = 1024;
+
+/// Rayon pool builder for `--threads`, where `None` or `0` keeps rayon's default of one thread per core
+pub fn thread_pool_builder(threads: Option<usize>) -> rayon::ThreadPoolBuilder {
+    match threads {
+        Some(threads) if threads > 0 => rayon::ThreadPoolBuilder::new().num_threads(threads),
+        _ => rayon::ThreadPoolBuilder::new(),
+    }
+}
+
+/// Size the global rayon pool before any parallel work starts
+pub fn configure_thread_pool(threads: Option<usize>) -> FeludaResult<()> {
+    if let Some(threads) = threads.filter(|&threads| threads > 0) {
+        let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+        if threads > cores {
+            log(
+                LogLevel::Warn,
+                &format!("--threads {threads} exceeds the {cores} available CPU cores"),
+            );
+        }
+    }
+
+    thread_pool_builder(threads)
+        .build_global()
+        .map_err(|e| FeludaError::Config(format!("Failed to configure thread pool: {e}")))?;
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Using {} worker threads for dependency analysis",
+            rayon::current_num_threads()
+        ),
+    );
+    Ok(())
+}
+
 fn ssh_to_https_url(repo_url: &str) -> Option<String> {
     if repo_url.is_empty() || repo_url.len() < "git@github.com:a/b".len() {
         return None;
@@ -228,12 +265,111 @@ pub fn clone_repository(args: &Cli, dest_path: &Path) -> FeludaResult<()> {
     }
 }
 
+/// Write the files of `git_ref` in the repository containing `project_path` to `dest_path`
+///
+/// The working tree, index and HEAD of the repository are left untouched. Returns where
+/// `project_path` lives inside the checkout, so a subdirectory of the repository can be
+/// compared with itself at another revision.
+pub fn checkout_revision(
+    project_path: &Path,
+    git_ref: &str,
+    dest_path: &Path,
+) -> FeludaResult<PathBuf> {
+    let repo = git2::Repository::discover(project_path).map_err(|e| {
+        FeludaError::Git(format!(
+            "{} is not inside a git repository: {e}",
+            project_path.display()
+        ))
+    })?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| FeludaError::Git("Bare repositories are not supported".to_string()))?;
+
+    let tree = repo
+        .revparse_single(git_ref)
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|e| FeludaError::Git(format!("Failed to resolve revision {git_ref}: {e}")))?;
+
+    log(
+        LogLevel::Info,
+        &format!("Checking out {git_ref} into {}", dest_path.display()),
+    );
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout
+        .target_dir(dest_path)
+        .update_index(false)
+        .recreate_missing(true)
+        .force();
+    repo.checkout_tree(tree.as_object(), Some(&mut checkout))
+        .map_err(|e| FeludaError::Git(format!("Failed to check out {git_ref}: {e}")))?;
+
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let relative = canonical(project_path)
+        .strip_prefix(canonical(workdir))
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    Ok(dest_path.join(relative))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs::File;
     use tempfile::TempDir;
 
+    fn commit_all(repo: &git2::Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("feluda", "feluda@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_checkout_revision() {
+        let repo_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(repo_dir.path()).unwrap();
+        let app = repo_dir.path().join("app");
+        std::fs::create_dir(&app).unwrap();
+        std::fs::write(app.join("requirements.txt"), "requests==2.0.0\n").unwrap();
+        commit_all(&repo, "base");
+        std::fs::write(app.join("requirements.txt"), "requests==2.31.0\n").unwrap();
+        std::fs::write(app.join("new.txt"), "added later\n").unwrap();
+        commit_all(&repo, "head");
+
+        let dest = TempDir::new().unwrap();
+        let base_app = checkout_revision(&app, "HEAD~1", dest.path()).unwrap();
+
+        assert_eq!(base_app, dest.path().join("app"));
+        assert_eq!(
+            std::fs::read_to_string(base_app.join("requirements.txt")).unwrap(),
+            "requests==2.0.0\n"
+        );
+        assert!(!base_app.join("new.txt").exists());
+        // The repository itself still has the head revision checked out
+        assert_eq!(
+            std::fs::read_to_string(app.join("requirements.txt")).unwrap(),
+            "requests==2.31.0\n"
+        );
+        assert!(repo.statuses(None).unwrap().is_empty());
+
+        let err = checkout_revision(&app, "no-such-ref", dest.path()).unwrap_err();
+        assert!(err.to_string().contains("no-such-ref"));
+    }
+
     #[test]
     fn test_ssh_to_https_url_github_ssh() {
         let url = "git@github.com:anistark/feluda.git";
@@ -418,29 +554,71 @@ mod tests {
         // Create CLI args with invalid repository
         let args = Cli {
             debug: false,
+            quiet: false,
+            log_format: crate::cli::LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: None,
             path: "./".to_string(),
             repo: Some("invalid-repo-url".to_string()),
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: crate::cli::MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         let result = clone_repository(&args, temp_dir.path());
@@ -474,29 +652,71 @@ mod tests {
 
         let args = Cli {
             debug: true,
+            quiet: false,
+            log_format: crate::cli::LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: None,
             path: "./".to_string(),
             repo: Some("https://github.com/nonexistent/repo.git".to_string()),
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: crate::cli::MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         // Enable debug mode for this test
@@ -529,29 +749,71 @@ mod tests {
 
         let args = Cli {
             debug: false,
+            quiet: false,
+            log_format: crate::cli::LogFormat::Human,
+            no_color: false,
+            compact: false,
             command: None,
             path: "./".to_string(),
             repo: Some("".to_string()),
+            manifest: None,
             token: None,
             ssh_key: None,
             ssh_passphrase: None,
             github_token: None,
             json: false,
             yaml: false,
+            toml: false,
+            ndjson: false,
             verbose: false,
             restrictive: false,
             gui: false,
             language: None,
             ci_format: None,
             output_file: None,
+            summary_json: false,
             fail_on_restrictive: false,
             incompatible: false,
             fail_on_incompatible: false,
+            fail_on_unknown: false,
+            fail_on_unknown_ratio: None,
+            fail_on_non_osi: false,
+            fail_on_unknown_osi: false,
+            deny_license: Vec::new(),
+            warn_license: Vec::new(),
+            baseline: None,
+            write_baseline: false,
+            diff: None,
             project_license: None,
             gist: false,
+            summary_only: false,
             osi: None,
+            min_severity: None,
+            sort: None,
             strict: false,
             no_local: false,
+            offline: false,
+            depth: None,
+            production_only: false,
+            include_dev: false,
+            direct_only: false,
+            why: None,
+            normalize_licenses: false,
+            max_deps: None,
+            max_deps_action: crate::cli::MaxDepsAction::Error,
+            refresh_cache: false,
+            cache_ttl: None,
+            no_cache: false,
+            no_incremental: false,
+            max_concurrency: crate::network::DEFAULT_MAX_CONCURRENCY,
+            timeout: None,
+            threads: None,
+            config: None,
+            npm_registry: None,
+            pypi_index: None,
+            crates_registry: None,
+            registry_token: Vec::new(),
+            node_license_sources: None,
         };
 
         let result = clone_repository(&args, temp_dir.path());
@@ -632,4 +894,37 @@ mod tests {
         assert_eq!(ssh_to_https_url("git@github.com:user/repo@name.git"), None);
         assert_eq!(ssh_to_https_url("git@github.com:user name/repo.git"), None);
     }
+
+    #[test]
+    fn test_thread_pool_builder() {
+        let pool = thread_pool_builder(Some(1)).build().unwrap();
+        assert_eq!(pool.current_num_threads(), 1);
+
+        let default_pool = thread_pool_builder(Some(0)).build().unwrap();
+        let unset_pool = thread_pool_builder(None).build().unwrap();
+        assert_eq!(
+            default_pool.current_num_threads(),
+            unset_pool.current_num_threads()
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_single_thread_pool_analysis() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("requirements.txt"),
+            "requests==2.31.0\nflask==3.0.0\nclick==8.1.7\n",
+        )
+        .unwrap();
+
+        let pool = thread_pool_builder(Some(1)).build().unwrap();
+        crate::network::set_offline_mode(true);
+        let result = pool.install(|| crate::parser::parse_root(temp_dir.path(), None));
+        crate::network::set_offline_mode(false);
+
+        let mut names: Vec<String> = result.unwrap().into_iter().map(|info| info.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["click", "flask", "requests"]);
+    }
 }
@@ -0,0 +1,94 @@
+//! End-to-end checks for `--quiet` and redirected output, which need the real stdout of the
+//! binary
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+/// A Node.js project with one installed dependency under `license`
+fn node_project(license: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("package.json"),
+        r#"{"name": "app", "version": "1.0.0", "license": "MIT", "dependencies": {"left-pad": "1.3.0"}}"#,
+    )
+    .unwrap();
+
+    let installed = dir.path().join("node_modules").join("left-pad");
+    fs::create_dir_all(&installed).unwrap();
+    fs::write(
+        installed.join("package.json"),
+        format!(r#"{{"name": "left-pad", "version": "1.3.0", "license": "{license}"}}"#),
+    )
+    .unwrap();
+
+    dir
+}
+
+fn run_feluda(project: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_feluda"))
+        .args(["--offline", "--no-cache", "--quiet", "--path"])
+        .arg(project)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_quiet_passing_run_prints_nothing() {
+    let project = node_project("MIT");
+    let output = run_feluda(project.path(), &[]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn test_quiet_keeps_json_output() {
+    let project = node_project("MIT");
+    let output = run_feluda(project.path(), &["--json"]);
+
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report[0]["name"], "left-pad");
+}
+
+#[test]
+fn test_summary_json_for_project_without_dependencies() {
+    let project = TempDir::new().unwrap();
+    let output = run_feluda(project.path(), &["--summary-json"]);
+
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["total"], 0);
+    assert_eq!(summary["restrictive"], 0);
+}
+
+#[test]
+fn test_quiet_still_fails_on_restrictive() {
+    let project = node_project("GPL-3.0");
+    let output = run_feluda(project.path(), &["--fail-on-restrictive"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("left-pad"));
+}
+
+#[test]
+fn test_redirected_output_has_no_spinner() {
+    let project = node_project("MIT");
+    let output = Command::new(env!("CARGO_BIN_EXE_feluda"))
+        .args(["generate", "--notice", "--offline", "--no-cache", "--path"])
+        .arg(project.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("NOTICE file generated successfully"));
+    assert!(!stdout.contains('\x1B'), "control characters in {stdout:?}");
+    assert!(!stdout.contains('⠋'));
+    assert!(project.path().join("NOTICE").exists());
+}